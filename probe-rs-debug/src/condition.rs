@@ -0,0 +1,625 @@
+use probe_rs::MemoryInterface;
+
+use crate::{
+    DebugError, DebugInfo, StackFrame, VariableCache, VariableType, VariableValue,
+    expression_path::{self, evaluate_path},
+    language::parsing::read_unsigned,
+};
+
+/// An error while evaluating a condition, see [`DebugInfo::evaluate_condition`].
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    /// The condition is not a valid expression.
+    #[error("Invalid condition {condition:?} at offset {offset}: {reason}")]
+    Syntax {
+        /// The condition.
+        condition: String,
+        /// The offset in bytes of the error in the condition.
+        offset: usize,
+        /// What is wrong with the condition.
+        reason: String,
+    },
+    /// A variable of the condition does not have a number or a boolean value, e.g. because it is a
+    /// struct.
+    #[error("{path} is a {kind} of type {type_name}, not a number or a boolean")]
+    NotScalar {
+        /// The path of the variable in the condition.
+        path: String,
+        /// The kind of type of the variable, e.g. `struct`.
+        kind: String,
+        /// The name of the type of the variable.
+        type_name: String,
+    },
+    /// The value of a variable of the condition could not be read, e.g. because it was optimized
+    /// out.
+    #[error("The value of {path} is not available: {reason}")]
+    Unavailable {
+        /// The path of the variable in the condition.
+        path: String,
+        /// Why the value is not available.
+        reason: String,
+    },
+    /// Two values of the condition cannot be compared, e.g. because they are a boolean and a
+    /// floating point number.
+    #[error("Cannot apply {operator} to {left} and {right}")]
+    TypeMismatch {
+        /// The comparison operator, e.g. `<`.
+        operator: &'static str,
+        /// The kind of the left operand, e.g. `a boolean`.
+        left: &'static str,
+        /// The kind of the right operand.
+        right: &'static str,
+    },
+    /// A variable path of the condition could not be evaluated.
+    #[error(transparent)]
+    Variable(#[from] DebugError),
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// The operators, with the longer operators first, so `<=` is not parsed as `<`.
+    const ALL: [Comparison; 6] = [
+        Comparison::Equal,
+        Comparison::NotEqual,
+        Comparison::LessOrEqual,
+        Comparison::GreaterOrEqual,
+        Comparison::Less,
+        Comparison::Greater,
+    ];
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        }
+    }
+
+    /// Check if the result of the comparison is `true` for operands with this `ordering`, or
+    /// which are unordered, like a NaN.
+    fn holds(self, ordering: Option<std::cmp::Ordering>) -> bool {
+        let Some(ordering) = ordering else {
+            return self == Comparison::NotEqual;
+        };
+        match self {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::NotEqual => ordering.is_ne(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+/// A parsed condition, like `count > 10 && state == 3`.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    /// A variable path, see [`DebugInfo::evaluate_path`].
+    Path(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Comparison, Box<Expr>),
+}
+
+/// Parse a condition.
+///
+/// The operators are, from the lowest to the highest precedence, `||`, `&&`, the comparisons
+/// `==`, `!=`, `<`, `<=`, `>` and `>=`, which cannot be chained, and `!`, which applies to an
+/// operand like in C and Rust, so `!a == b` is `(!a) == b`. The operands are `true`, `false`,
+/// integer and floating point literals, variable paths, and conditions in parentheses.
+fn parse(condition: &str) -> Result<Expr, EvalError> {
+    let mut parser = Parser {
+        condition,
+        offset: 0,
+    };
+    let expr = parser.or()?;
+    parser.skip_whitespace();
+    if !parser.rest().is_empty() {
+        return Err(parser.error(format!("unexpected {:?}", parser.rest())));
+    }
+    Ok(expr)
+}
+
+struct Parser<'c> {
+    condition: &'c str,
+    offset: usize,
+}
+
+impl<'c> Parser<'c> {
+    fn rest(&self) -> &'c str {
+        &self.condition[self.offset..]
+    }
+
+    fn error(&self, reason: String) -> EvalError {
+        EvalError::Syntax {
+            condition: self.condition.to_string(),
+            offset: self.offset,
+            reason,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip `token`, if it is next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(token);
+        if found {
+            self.offset += token.len();
+        }
+        found
+    }
+
+    /// Skip the characters at the start of the rest which match `part_of_token`, and return them.
+    fn eat_while(&mut self, part_of_token: impl Fn(char) -> bool) -> &'c str {
+        let rest = self.rest();
+        let end = rest.find(|c| !part_of_token(c)).unwrap_or(rest.len());
+        self.offset += end;
+        &rest[..end]
+    }
+
+    fn or(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.and()?;
+        while self.eat("||") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.comparison()?;
+        while self.eat("&&") {
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, EvalError> {
+        let left = self.operand()?;
+        for comparison in Comparison::ALL {
+            if self.eat(comparison.symbol()) {
+                let right = self.operand()?;
+                return Ok(Expr::Compare(Box::new(left), comparison, Box::new(right)));
+            }
+        }
+        Ok(left)
+    }
+
+    fn operand(&mut self) -> Result<Expr, EvalError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+
+        if rest.starts_with('!') && !rest.starts_with("!=") {
+            self.offset += 1;
+            return Ok(Expr::Not(Box::new(self.operand()?)));
+        }
+
+        if self.eat("(") {
+            let expr = self.or()?;
+            if !self.eat(")") {
+                return Err(self.error("expected `)`".to_string()));
+            }
+            // A dereference in parentheses can be followed by the rest of a path, e.g. `(*a).b`.
+            return Ok(match expr {
+                Expr::Path(path) if starts_path_continuation(self.rest()) => {
+                    let continuation = self.path_continuation();
+                    Expr::Path(format!("({path}){continuation}"))
+                }
+                expr => expr,
+            });
+        }
+
+        let starts_number = |text: &str| text.starts_with(|c: char| c.is_ascii_digit() || c == '.');
+        if starts_number(rest) || rest.strip_prefix('-').is_some_and(starts_number) {
+            return self.number();
+        }
+
+        if rest.starts_with(|c: char| is_name_char(c) || c == '*' || c == '&')
+            && !rest.starts_with("&&")
+        {
+            return self.path();
+        }
+
+        Err(self.error(if rest.is_empty() {
+            "expected a value".to_string()
+        } else {
+            format!("expected a value before {rest:?}")
+        }))
+    }
+
+    fn number(&mut self) -> Result<Expr, EvalError> {
+        let start = self.offset;
+        let negative = self.eat("-");
+        let digits = self.eat_while(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_');
+        let is_hex = digits.starts_with("0x") || digits.starts_with("0X");
+
+        let mut text = digits.to_string();
+        // The sign of the exponent of a float, e.g. `1e-3`.
+        if !is_hex && text.ends_with(['e', 'E']) && self.rest().starts_with(['-', '+']) {
+            text.push_str(&self.rest()[..1]);
+            self.offset += 1;
+            text.push_str(self.eat_while(|c| c.is_ascii_digit()));
+        }
+
+        let invalid = || EvalError::Syntax {
+            condition: self.condition.to_string(),
+            offset: start,
+            reason: format!(
+                "{:?} is not a valid number",
+                &self.condition[start..self.offset]
+            ),
+        };
+        if !is_hex && text.contains(['.', 'e', 'E']) {
+            let value = text.parse::<f64>().map_err(|_| invalid())?;
+            Ok(Expr::Float(if negative { -value } else { value }))
+        } else {
+            let value = parse_int::parse::<i128>(&text).map_err(|_| invalid())?;
+            Ok(Expr::Int(if negative { -value } else { value }))
+        }
+    }
+
+    fn path(&mut self) -> Result<Expr, EvalError> {
+        let start = self.offset;
+        while self.rest().starts_with(['*', '&']) {
+            self.offset += 1;
+            self.skip_whitespace();
+        }
+        if self.rest().starts_with('(') {
+            // A dereference of a path in parentheses, e.g. `*(*a).b`.
+            let mut depth = 0;
+            let end = self.rest().find(|c| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            });
+            let Some(end) = end else {
+                return Err(self.error("expected `)`".to_string()));
+            };
+            self.offset += end + 1;
+        } else {
+            self.eat_while(is_name_char);
+        }
+        self.path_continuation();
+
+        let path = &self.condition[start..self.offset];
+        match path {
+            "true" => return Ok(Expr::Bool(true)),
+            "false" => return Ok(Expr::Bool(false)),
+            _ => {}
+        }
+
+        // The path is checked now, so an invalid path is reported before any variable is read.
+        if let Err(error) = expression_path::parse(path) {
+            return Err(EvalError::Syntax {
+                condition: self.condition.to_string(),
+                offset: start,
+                reason: error.to_string(),
+            });
+        }
+        Ok(Expr::Path(path.to_string()))
+    }
+
+    /// Skip the field accesses and array indices which follow the start of a path, and return them.
+    fn path_continuation(&mut self) -> &'c str {
+        let start = self.offset;
+        loop {
+            let rest = self.rest();
+            if let Some(stripped) = rest.strip_prefix('.') {
+                self.offset += rest.len() - stripped.len();
+                self.eat_while(is_name_char);
+            } else if let Some(stripped) = rest.strip_prefix("->") {
+                self.offset += rest.len() - stripped.len();
+                self.eat_while(is_name_char);
+            } else if rest.starts_with('[') {
+                self.offset += rest.find(']').map_or(rest.len(), |end| end + 1);
+            } else {
+                break;
+            }
+        }
+        &self.condition[start..self.offset]
+    }
+}
+
+/// Check if `c` can be part of a variable name, or of the namespaces which qualify it.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':' || c == '$'
+}
+
+fn starts_path_continuation(rest: &str) -> bool {
+    rest.starts_with(['.', '[']) || rest.starts_with("->")
+}
+
+/// The value of an operand of a condition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+}
+
+impl Value {
+    fn kind(self) -> &'static str {
+        match self {
+            Value::Bool(_) => "a boolean",
+            Value::Int(_) => "an integer",
+            Value::Float(_) => "a floating point number",
+        }
+    }
+
+    /// The value of an operand which is used as a condition, like in C: numbers are `true` unless
+    /// they are zero.
+    fn is_true(self) -> bool {
+        match self {
+            Value::Bool(value) => value,
+            Value::Int(value) => value != 0,
+            Value::Float(value) => value != 0.0,
+        }
+    }
+}
+
+/// Compare the values of two operands. Integers and floating point numbers can be compared with
+/// each other, and booleans can be compared for equality with each other, and with integers, as
+/// `0` and `1`.
+fn compare(left: Value, comparison: Comparison, right: Value) -> Result<bool, EvalError> {
+    let is_equality = matches!(comparison, Comparison::Equal | Comparison::NotEqual);
+    let ordering = match (left, right) {
+        (Value::Int(left), Value::Int(right)) => left.partial_cmp(&right),
+        (Value::Int(left), Value::Float(right)) => (left as f64).partial_cmp(&right),
+        (Value::Float(left), Value::Int(right)) => left.partial_cmp(&(right as f64)),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(&right),
+        (Value::Bool(left), Value::Bool(right)) if is_equality => left.partial_cmp(&right),
+        (Value::Bool(left), Value::Int(right)) if is_equality => {
+            i128::from(left).partial_cmp(&right)
+        }
+        (Value::Int(left), Value::Bool(right)) if is_equality => {
+            left.partial_cmp(&i128::from(right))
+        }
+        _ => {
+            return Err(EvalError::TypeMismatch {
+                operator: comparison.symbol(),
+                left: left.kind(),
+                right: right.kind(),
+            });
+        }
+    };
+    Ok(comparison.holds(ordering))
+}
+
+/// Evaluate `condition` in `cache`, see [`DebugInfo::evaluate_condition`].
+pub(crate) fn evaluate_condition(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    frame: &StackFrame,
+    condition: &str,
+) -> Result<bool, EvalError> {
+    let expr = parse(condition)?;
+    let mut evaluator = Evaluator {
+        debug_info,
+        memory,
+        cache,
+        frame,
+    };
+    evaluator.condition(&expr)
+}
+
+struct Evaluator<'a> {
+    debug_info: &'a DebugInfo,
+    memory: &'a mut dyn MemoryInterface,
+    cache: &'a mut VariableCache,
+    frame: &'a StackFrame,
+}
+
+impl Evaluator<'_> {
+    /// Evaluate `expr` as a condition. The right operand of `&&` and `||` is only evaluated if it
+    /// is needed, so its variables are not read otherwise.
+    fn condition(&mut self, expr: &Expr) -> Result<bool, EvalError> {
+        match expr {
+            Expr::Not(expr) => Ok(!self.condition(expr)?),
+            Expr::And(left, right) => Ok(self.condition(left)? && self.condition(right)?),
+            Expr::Or(left, right) => Ok(self.condition(left)? || self.condition(right)?),
+            Expr::Compare(left, comparison, right) => {
+                let left = self.value(left)?;
+                let right = self.value(right)?;
+                compare(left, *comparison, right)
+            }
+            operand => Ok(self.value(operand)?.is_true()),
+        }
+    }
+
+    fn value(&mut self, expr: &Expr) -> Result<Value, EvalError> {
+        match expr {
+            Expr::Bool(value) => Ok(Value::Bool(*value)),
+            Expr::Int(value) => Ok(Value::Int(*value)),
+            Expr::Float(value) => Ok(Value::Float(*value)),
+            Expr::Path(path) => self.variable_value(path),
+            condition => Ok(Value::Bool(self.condition(condition)?)),
+        }
+    }
+
+    /// The value of the variable at `path`. Pointers and C-like enums, which are shown as an
+    /// address or the name of an enumerator, are compared by the integer that is stored in them.
+    fn variable_value(&mut self, path: &str) -> Result<Value, EvalError> {
+        let variable = evaluate_path(self.debug_info, self.memory, self.cache, self.frame, path)?;
+
+        let reason = match variable.value() {
+            VariableValue::Error(reason) => Some(reason.clone()),
+            VariableValue::Unavailable(reason) => Some(reason.to_string()),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            return Err(EvalError::Unavailable {
+                path: path.to_string(),
+                reason,
+            });
+        }
+
+        let integer_size = match variable.type_name.inner() {
+            // Pointer types do not always have a size in the debug information, they have the
+            // size of an address.
+            VariableType::Pointer(_) => variable
+                .byte_size
+                .or(Some(self.frame.registers.get_address_size_bytes() as u64)),
+            VariableType::Enum(_) if !self.cache.has_children(&variable) => variable.byte_size,
+            _ => None,
+        };
+        if let Some(size @ 1..=8) = integer_size {
+            let value = read_unsigned(&variable, self.memory, size as usize)?;
+            return Ok(Value::Int(value as i128));
+        }
+
+        Ok(match *variable.value() {
+            VariableValue::Bool(value) => Value::Bool(value),
+            VariableValue::Char(value) => Value::Int(i128::from(u32::from(value))),
+            VariableValue::U64(value) => Value::Int(i128::from(value)),
+            VariableValue::I64(value) => Value::Int(i128::from(value)),
+            VariableValue::U128(value) => {
+                i128::try_from(value).map_or(Value::Float(value as f64), Value::Int)
+            }
+            VariableValue::I128(value) => Value::Int(value),
+            VariableValue::F64(value) => Value::Float(value),
+            _ => {
+                return Err(EvalError::NotScalar {
+                    path: path.to_string(),
+                    kind: variable.type_name.kind().to_string(),
+                    type_name: variable.type_name(),
+                });
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Comparison, EvalError, Expr, Value, compare, parse};
+
+    #[test]
+    fn parse_conditions() {
+        let path = |path: &str| Box::new(Expr::Path(path.to_string()));
+
+        assert_eq!(
+            parse("count > 10 && state == 3").unwrap(),
+            Expr::And(
+                Box::new(Expr::Compare(
+                    path("count"),
+                    Comparison::Greater,
+                    Box::new(Expr::Int(10))
+                )),
+                Box::new(Expr::Compare(
+                    path("state"),
+                    Comparison::Equal,
+                    Box::new(Expr::Int(3))
+                )),
+            )
+        );
+        assert_eq!(
+            parse("!ready || (*config.uart).baud <= -0x10").unwrap(),
+            Expr::Or(
+                Box::new(Expr::Not(path("ready"))),
+                Box::new(Expr::Compare(
+                    path("(*config.uart).baud"),
+                    Comparison::LessOrEqual,
+                    Box::new(Expr::Int(-16))
+                )),
+            )
+        );
+        assert_eq!(
+            parse("(a || b) && buf[2] != 1.5e-3 && !(p->x >= 2) == false").unwrap(),
+            Expr::And(
+                Box::new(Expr::And(
+                    Box::new(Expr::Or(path("a"), path("b"))),
+                    Box::new(Expr::Compare(
+                        path("buf[2]"),
+                        Comparison::NotEqual,
+                        Box::new(Expr::Float(1.5e-3))
+                    )),
+                )),
+                Box::new(Expr::Compare(
+                    Box::new(Expr::Not(Box::new(Expr::Compare(
+                        path("p->x"),
+                        Comparison::GreaterOrEqual,
+                        Box::new(Expr::Int(2))
+                    )))),
+                    Comparison::Equal,
+                    Box::new(Expr::Bool(false))
+                )),
+            )
+        );
+        // `!` applies to the operand, like in C and Rust.
+        assert_eq!(
+            parse("!a == b").unwrap(),
+            Expr::Compare(Box::new(Expr::Not(path("a"))), Comparison::Equal, path("b"))
+        );
+        assert_eq!(
+            parse("!!a != 0").unwrap(),
+            Expr::Compare(
+                Box::new(Expr::Not(Box::new(Expr::Not(path("a"))))),
+                Comparison::NotEqual,
+                Box::new(Expr::Int(0))
+            )
+        );
+
+        for (invalid, offset) in [
+            ("", 0),
+            ("a &&", 4),
+            ("(a", 2),
+            ("a < b < c", 6),
+            ("a == 1x", 5),
+            ("a.", 0),
+            ("a = 1", 2),
+        ] {
+            assert!(
+                matches!(parse(invalid), Err(EvalError::Syntax { offset: actual, .. }) if actual == offset),
+                "{invalid:?}: {:?}",
+                parse(invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn compare_values() {
+        assert!(compare(Value::Int(3), Comparison::Less, Value::Float(3.5)).unwrap());
+        assert!(compare(Value::Bool(true), Comparison::Equal, Value::Int(1)).unwrap());
+        assert!(compare(Value::Float(f64::NAN), Comparison::NotEqual, Value::Int(1)).unwrap());
+        assert!(
+            !compare(
+                Value::Float(f64::NAN),
+                Comparison::Equal,
+                Value::Float(f64::NAN)
+            )
+            .unwrap()
+        );
+        assert!(matches!(
+            compare(Value::Bool(true), Comparison::Less, Value::Int(1)),
+            Err(EvalError::TypeMismatch {
+                operator: "<",
+                left: "a boolean",
+                right: "an integer"
+            })
+        ));
+    }
+}
@@ -1,60 +1,496 @@
 use super::{
-    DebugError, DebugRegisters, StackFrame, VariableCache,
-    exception_handling::ExceptionInterface,
-    function_die::{Die, FunctionDie},
+    ColumnType, DebugError, DebugRegisters, ErrorContext, FrameKind, StackFrame, VariableCache,
+    condition::{EvalError, evaluate_condition},
+    demangle::demangle,
+    exception_handling::{ExceptionInfo, ExceptionInterface},
+    expression_path::evaluate_path,
+    function_die::{Die, FunctionDie, FunctionInfo, FunctionMatch, NameMatch},
     get_object_reference,
-    unit_info::UnitInfo,
+    path_mapping::{PathMapping, map_path},
+    section_data::{FileData, SectionData},
+    separate_debug_info::find_separate_debug_file,
+    source_file::{SourceFile, source_files},
+    source_instructions::{BreakpointCandidate, breakpoint_locations_in_range},
+    split_dwarf::SplitDwarfLoader,
+    static_variable::{StaticVariableInfo, read_static_variable, static_variables},
+    statistics::DebugInfoStatistics,
+    supplementary::SupplementaryLoader,
+    type_formatter::TypeFormatterEntry,
+    unit_info::{CompilationUnitInfo, UnitInfo},
     variable::*,
 };
-use crate::{SourceLocation, VerifiedBreakpoint, stack_frame::StackFrameInfo, unit_info::RangeExt};
+use crate::{
+    BreakpointLocation, BreakpointOptions, ResolvedSourceLocation, SourceLocation,
+    VerifiedBreakpoint,
+    language::{self, parsing::ValueExt},
+    stack_frame::{InlineFrame, StackFrameInfo},
+    unit_info::RangeExt,
+};
 use gimli::{
     BaseAddresses, DebugFrame, RunTimeEndian, UnwindContext, UnwindSection, UnwindTableRow,
     read::RegisterRule,
 };
-use object::read::{Object, ObjectSection};
+use object::read::{Object, ObjectSection, ObjectSymbol};
 use probe_rs::{
-    CoreRegister, Error, InstructionSet, MemoryInterface, RegisterDataType, RegisterRole,
+    Core, CoreRegister, Error, InstructionSet, MemoryInterface, RegisterDataType, RegisterRole,
     RegisterValue, UnwindRule,
 };
 use std::{
-    borrow, cmp::Ordering, num::NonZeroU64, ops::ControlFlow, path::Path, rc::Rc, str::from_utf8,
+    borrow::Cow,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU64,
+    ops::{ControlFlow, Range},
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
 };
 use typed_path::{TypedPath, TypedPathBuf};
 
-pub(crate) type GimliReader = gimli::EndianReader<RunTimeEndian, std::rc::Rc<[u8]>>;
+pub(crate) type GimliReader = gimli::EndianReader<RunTimeEndian, SectionData>;
 pub(crate) type GimliReaderOffset =
-    <gimli::EndianReader<RunTimeEndian, Rc<[u8]>> as gimli::Reader>::Offset;
+    <gimli::EndianReader<RunTimeEndian, SectionData> as gimli::Reader>::Offset;
 
 pub(crate) type GimliAttribute = gimli::Attribute<GimliReader>;
 
-pub(crate) type DwarfReader = gimli::read::EndianRcSlice<RunTimeEndian>;
+pub(crate) type DwarfReader = gimli::EndianReader<RunTimeEndian, SectionData>;
+
+/// The largest symbol size (in bytes) that we will consider to be a linker veneer or trampoline.
+const MAX_TRAMPOLINE_SIZE: u64 = 32;
+
+/// The largest number of `DW_AT_abstract_origin` and `DW_AT_specification` references that are followed
+/// from a single DIE. This also stops malformed references which form a cycle.
+const MAX_ORIGIN_DEPTH: usize = 8;
+
+/// The default for [`DebugInfoOptions::max_eager_array_length`].
+const DEFAULT_MAX_EAGER_ARRAY_LENGTH: usize = 1024;
+
+/// The default for [`DebugInfoOptions::max_string_length`].
+pub(crate) const DEFAULT_MAX_STRING_LENGTH: usize = 256;
+
+/// The default for [`DebugInfoOptions::max_byte_preview_length`].
+const DEFAULT_MAX_BYTE_PREVIEW_LENGTH: usize = 16;
+
+/// Controls how linker veneers and trampolines are reported during a stack unwind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrampolineHandling {
+    /// Report each trampoline as a separate [`StackFrame`], with a [`FrameKind::Trampoline`] kind.
+    #[default]
+    Label,
+    /// Omit trampolines from the unwound stack frames, and continue unwinding from the return address.
+    Collapse,
+}
+
+/// Options that control the behaviour of [`DebugInfo::unwind_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnwindOptions {
+    /// How linker veneers and trampolines are reported.
+    pub trampolines: TrampolineHandling,
+    /// Insert a [`FrameKind::ExceptionEntry`] frame for each exception that is encountered during the unwind,
+    /// which holds the register values that were stored on the stack on exception entry.
+    pub exception_entry_frames: bool,
+}
+
+/// Options that control how [`DebugInfo`] is loaded.
+#[derive(Debug, Clone, Default)]
+pub struct DebugInfoOptions {
+    /// Additional directories that are searched for split DWARF (`.dwo`) files.
+    ///
+    /// The compilation directory of each unit is always searched first, followed by the directory
+    /// of the binary when using [`DebugInfo::from_file_with_options`].
+    pub split_dwarf_search_paths: Vec<PathBuf>,
+    /// The split DWARF package (`.dwp`) for the binary.
+    ///
+    /// When not set, [`DebugInfo::from_file_with_options`] uses `<binary>.dwp` if it exists.
+    pub split_dwarf_package: Option<PathBuf>,
+    /// Global debug directories, such as `/usr/lib/debug`, which are searched for separate debug files.
+    ///
+    /// Debug files are looked up by the build ID of the binary, using the `.build-id/xx/yyyy.debug` layout,
+    /// and by the file name in the `.gnu_debuglink` section. For the latter, the directory of the binary,
+    /// and its `.debug` subdirectory are always searched first.
+    pub debug_directories: Vec<PathBuf>,
+    /// Allow loading ELF files without DWARF debug information, instead of returning [`DebugError::NoDebugInfo`].
+    ///
+    /// The loaded [`DebugInfo`] can only use the symbol table of the binary.
+    pub allow_missing_debug_info: bool,
+    /// Keep the hash at the end of demangled Rust legacy symbol names, e.g. `core::fmt::write::h6c2d4a5f2dbf3e4b`.
+    ///
+    /// By default, the hash is removed.
+    pub keep_symbol_hash: bool,
+    /// Prefer the compilation units whose compilation directory starts with this path, when more than one
+    /// unit describes an address, see [`DebugInfo::get_source_location`].
+    ///
+    /// This is usually the directory of the project being debugged, so that its own sources are shown instead
+    /// of generic instantiations from other crates.
+    pub preferred_comp_dir: Option<PathBuf>,
+    /// The supplementary object file, which holds debug information that is shared between binaries,
+    /// e.g. as created by `dwz -m`.
+    ///
+    /// When not set, the file named in the `.debug_sup` or `.gnu_debugaltlink` section of the binary is
+    /// used. Relative names are resolved against the directory of the binary, which is only known when
+    /// using [`DebugInfo::from_file_with_options`].
+    pub supplementary_file: Option<PathBuf>,
+    /// The largest array whose members are resolved together with the array itself.
+    ///
+    /// The members of longer arrays are only resolved when they are requested, with
+    /// [`DebugInfo::cache_deferred_variables`]. When not set, arrays of up to 1024 members are
+    /// resolved immediately.
+    pub max_eager_array_length: Option<usize>,
+    /// The largest number of bytes that is read for the value of a string, like a Rust `&str` or
+    /// `String`, or a C `char*`. Longer strings are truncated, and end with `...`. When not set, up
+    /// to 256 bytes are read.
+    pub max_string_length: Option<usize>,
+    /// The number of bytes that are shown in the value of a byte buffer, like a `[u8; 64]` or a
+    /// `&[u8]`, in hexadecimal and as ASCII text. The members remain available as its children.
+    /// When not set, the first 16 bytes are shown, and `0` disables the preview.
+    pub max_byte_preview_length: Option<usize>,
+    /// Omit the local variables which are not available at the program counter of their frame,
+    /// because their declaration has not been reached yet, or because their location list does not
+    /// describe the program counter. By default, they are shown with a value which says why.
+    pub hide_out_of_scope_variables: bool,
+    /// The largest number of variables in each [`VariableCache`] which is created for the local or
+    /// static variables, see [`VariableCache::set_max_len`]. When not set, there is no limit.
+    pub max_cached_variables: Option<usize>,
+}
+
+/// The rank of a line table row in [`DebugInfo::get_source_location`], where a higher rank is a better match.
+///
+/// The fields are compared in the order of their declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct LineRowRank {
+    /// The compilation directory of the unit starts with the preferred prefix.
+    preferred_unit: bool,
+    /// The row starts exactly at the address.
+    exact: bool,
+    /// The row is a recommended breakpoint location.
+    is_stmt: bool,
+}
+
+/// The line table rows of a unit that describe an address, see [`line_rows_at_address`].
+#[derive(Default)]
+struct LineRowsAtAddress {
+    /// The rows which start at the address, or if there are none, the last row before the address.
+    rows: Vec<gimli::LineRow>,
+    /// Whether the rows start exactly at the address.
+    exact: bool,
+    /// Whether the address is in code without a line, and the rows are the nearest row before it that has a line.
+    line_fallback: bool,
+    /// The address of the next row after the address, which is where the code described by the rows ends.
+    end_address: Option<u64>,
+}
+
+/// Find the line table rows of `unit` that describe `address`.
+///
+/// Compilers mark code which does not belong to a line, e.g. code they generated, with line 0. These rows are
+/// skipped in favour of the nearest row before the address that has a line.
+fn line_rows_at_address(unit: &gimli::Unit<DwarfReader>, address: u64) -> LineRowsAtAddress {
+    let Some(line_program) = unit.line_program.clone() else {
+        return LineRowsAtAddress::default();
+    };
+
+    let (program, sequences) = match line_program.sequences() {
+        Ok(value) => value,
+        Err(error) => {
+            tracing::warn!(
+                "No valid source code ranges found for address {}: {:?}",
+                address,
+                error
+            );
+            return LineRowsAtAddress::default();
+        }
+    };
+
+    let Some(sequence) = sequences
+        .iter()
+        .find(|sequence| sequence.start <= address && address < sequence.end)
+    else {
+        return LineRowsAtAddress::default();
+    };
+
+    let mut exact_rows = Vec::new();
+    let mut previous_row = None;
+    let mut previous_row_with_line = None;
+    let mut end_address = None;
+
+    let mut rows = program.resume_from(sequence);
+    while let Ok(Some((_, row))) = rows.next_row() {
+        match row.address().cmp(&address) {
+            Ordering::Less => {
+                previous_row = Some(*row);
+                if row.line().is_some() {
+                    previous_row_with_line = Some(*row);
+                }
+            }
+            Ordering::Equal => exact_rows.push(*row),
+            // The address is after the current row, so the previous row describes the address.
+            //
+            // (If we don't do this, you get the artificial effect where the debugger
+            // steps to the top of the file when it is stepping out of a function.)
+            Ordering::Greater => {
+                end_address = Some(row.address());
+                break;
+            }
+        }
+    }
+
+    let (rows, exact) = if exact_rows.is_empty() {
+        (previous_row.into_iter().collect::<Vec<_>>(), false)
+    } else {
+        (exact_rows, true)
+    };
+
+    let rows_with_line: Vec<_> = rows
+        .iter()
+        .filter(|row| row.line().is_some())
+        .copied()
+        .collect();
+    if !rows_with_line.is_empty() {
+        return LineRowsAtAddress {
+            rows: rows_with_line,
+            exact,
+            line_fallback: false,
+            end_address,
+        };
+    }
+
+    match previous_row_with_line {
+        Some(row) => LineRowsAtAddress {
+            rows: vec![row],
+            exact: false,
+            line_fallback: true,
+            end_address,
+        },
+        // Without a row with a line before the address, the rows without a line are the best match.
+        None => LineRowsAtAddress {
+            rows,
+            exact,
+            line_fallback: false,
+            end_address,
+        },
+    }
+}
+
+/// A linker generated veneer or trampoline, as identified from the symbol table.
+#[derive(Debug, Clone)]
+pub(crate) struct Trampoline {
+    pub(crate) name: String,
+    pub(crate) address_range: Range<u64>,
+}
+
+/// Returns the name of a DWARF section in an object file of the given format.
+fn section_name(format: object::BinaryFormat, id: gimli::SectionId) -> String {
+    match format {
+        // Mach-O uses `__debug_info` instead of `.debug_info`, and section names are limited to 16 characters.
+        object::BinaryFormat::MachO => {
+            let mut name = format!("__{}", id.name().trim_start_matches('.'));
+            name.truncate(16);
+            name
+        }
+        _ => id.name().to_string(),
+    }
+}
+
+/// Returns the data of an object file section, decompressing it if necessary.
+///
+/// Sections compressed with zlib or zstd can only be decompressed if the `compression` feature is enabled.
+pub(crate) fn uncompressed_section_data<'data>(
+    section: &object::Section<'data, '_>,
+) -> Result<Cow<'data, [u8]>, DebugError> {
+    section
+        .uncompressed_data()
+        .map_err(|source| DebugError::SectionDecompression {
+            section: section.name().unwrap_or("<unknown>").to_string(),
+            source,
+        })
+}
+
+/// Resolves the runtime address of a thread-local variable, see [`DebugInfo::set_tls_resolver`].
+///
+/// The arguments are the offset of the variable in the thread-local storage block, and the registers
+/// of the stack frame.
+pub type TlsResolver = dyn Fn(u64, &DebugRegisters) -> Option<u64> + Send + Sync;
 
 /// Debug information which is parsed from DWARF debugging information.
 pub struct DebugInfo {
     pub(crate) dwarf: gimli::Dwarf<DwarfReader>,
     pub(crate) frame_section: gimli::DebugFrame<DwarfReader>,
-    pub(crate) locations_section: gimli::LocationLists<DwarfReader>,
-    pub(crate) address_section: gimli::DebugAddr<DwarfReader>,
     pub(crate) debug_line_section: gimli::DebugLine<DwarfReader>,
 
     pub(crate) unit_infos: Vec<UnitInfo>,
+    /// The units of the supplementary object file, see [`DebugInfoOptions::supplementary_file`].
+    pub(crate) supplementary_unit_infos: Vec<UnitInfo>,
     pub(crate) endianness: gimli::RunTimeEndian,
 
-    pub(crate) addr2line: Option<addr2line::Loader>,
+    pub(crate) addr2line: Option<Mutex<addr2line::Loader>>,
+
+    /// Linker veneers and trampolines, which have no debug information of their own.
+    pub(crate) trampolines: Vec<Trampoline>,
+
+    /// Clears the lowest bit of the address of a Thumb function, e.g. in a function pointer, which
+    /// is not part of the address.
+    pub(crate) code_address_mask: u64,
+
+    /// The file that the debug information was loaded from.
+    pub(crate) debug_file: Option<PathBuf>,
+
+    /// The offset between the runtime addresses and the addresses in the debug information.
+    pub(crate) load_bias: i64,
+
+    /// The size of each debug section that was loaded, by section name.
+    pub(crate) section_sizes: BTreeMap<&'static str, u64>,
+
+    /// Keep the hash at the end of demangled Rust legacy symbol names.
+    pub(crate) keep_symbol_hash: bool,
+
+    /// See [`DebugInfoOptions::max_eager_array_length`].
+    pub(crate) max_eager_array_length: usize,
+
+    /// See [`DebugInfoOptions::max_string_length`].
+    pub(crate) max_string_length: usize,
+
+    /// See [`DebugInfoOptions::max_byte_preview_length`].
+    pub(crate) max_byte_preview_length: usize,
+
+    /// See [`DebugInfoOptions::hide_out_of_scope_variables`].
+    pub(crate) hide_out_of_scope_variables: bool,
+
+    /// See [`DebugInfoOptions::max_cached_variables`].
+    pub(crate) max_cached_variables: Option<usize>,
+
+    /// The normalized [`DebugInfoOptions::preferred_comp_dir`].
+    pub(crate) preferred_comp_dir: Option<TypedPathBuf>,
+
+    /// The source path mappings, see [`DebugInfo::add_path_mapping`].
+    pub(crate) path_mappings: Vec<PathMapping>,
+
+    /// The resolver for thread-local variables, see [`DebugInfo::set_tls_resolver`].
+    pub(crate) tls_resolver: Option<Box<TlsResolver>>,
+
+    /// The formatters of the standard library types, followed by the formatters of
+    /// [`DebugInfo::register_type_formatter`].
+    pub(crate) type_formatters: Vec<TypeFormatterEntry>,
 }
 
 impl DebugInfo {
     /// Read debug info directly from a ELF file.
+    ///
+    /// See [`DebugInfo::from_file_with_options`] for the requirements when the file is memory mapped.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<DebugInfo, DebugError> {
-        let data = std::fs::read(path.as_ref())?;
+        Self::from_file_with_options(path, &DebugInfoOptions::default())
+    }
+
+    /// Read debug info directly from a ELF file, using the given [`DebugInfoOptions`].
+    ///
+    /// If the `memmap2` feature is enabled, the file is memory mapped instead of read into memory, and
+    /// the DWARF sections refer to the mapped file without copying it. The file stays mapped for as long
+    /// as the returned [`DebugInfo`] exists. During that time, the file must not be modified or truncated
+    /// by another process, as this is undefined behavior. On Windows, the mapping also prevents the file
+    /// from being deleted or overwritten, so a rebuild of the binary fails until the [`DebugInfo`] is dropped.
+    pub fn from_file_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &DebugInfoOptions,
+    ) -> Result<DebugInfo, DebugError> {
+        let path = path.as_ref();
+        let mut file = FileData::read(path)?;
+
+        // Stripped binaries can refer to a separate file with the debug information. The addresses in
+        // the debug file match those of the binary, so we can use it in place of the binary.
+        let debug_file = find_separate_debug_file(path, &file, options)?;
+        if let Some(debug_file) = &debug_file {
+            tracing::debug!("Loading debug information from {}", debug_file.display());
+            file = FileData::read(debug_file)?;
+        }
+
+        let package_path = options.split_dwarf_package.clone().unwrap_or_else(|| {
+            let mut package_path = path.as_os_str().to_owned();
+            package_path.push(".dwp");
+            PathBuf::from(package_path)
+        });
+        let search_paths = path
+            .parent()
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(options.split_dwarf_search_paths.iter().cloned())
+            .collect();
+        let split_dwarf = SplitDwarfLoader::new(search_paths, Some(&package_path));
+
+        let debug_file = debug_file.unwrap_or_else(|| path.to_path_buf());
+        let supplementary = SupplementaryLoader::new(
+            options.supplementary_file.clone(),
+            debug_file.parent().map(Path::to_path_buf),
+        );
 
-        let mut this = DebugInfo::from_raw(&data)?;
-        this.addr2line = addr2line::Loader::new(path).ok();
+        let mut this = DebugInfo::from_raw_impl(
+            &file,
+            |data| file.section_data(data),
+            &split_dwarf,
+            &supplementary,
+            options.allow_missing_debug_info,
+        )?;
+        this.addr2line = addr2line::Loader::new(&debug_file).ok().map(Mutex::new);
+        this.debug_file = Some(debug_file);
+        this.apply_options(options);
         Ok(this)
     }
 
     /// Parse debug information directly from a buffer containing an ELF file.
+    ///
+    /// Returns [`DebugError::NoDebugInfo`] if the file does not contain debug information, see
+    /// [`DebugInfoOptions::allow_missing_debug_info`].
     pub fn from_raw(data: &[u8]) -> Result<Self, DebugError> {
+        Self::from_raw_with_options(data, &DebugInfoOptions::default())
+    }
+
+    /// Parse debug information directly from a buffer containing an ELF file, using the given [`DebugInfoOptions`].
+    pub fn from_raw_with_options(
+        data: &[u8],
+        options: &DebugInfoOptions,
+    ) -> Result<Self, DebugError> {
+        let split_dwarf = SplitDwarfLoader::new(
+            options.split_dwarf_search_paths.clone(),
+            options.split_dwarf_package.as_deref(),
+        );
+
+        let supplementary = SupplementaryLoader::new(options.supplementary_file.clone(), None);
+
+        let mut this = Self::from_raw_impl(
+            data,
+            |data| SectionData::from(&*data),
+            &split_dwarf,
+            &supplementary,
+            options.allow_missing_debug_info,
+        )?;
+        this.apply_options(options);
+        Ok(this)
+    }
+
+    /// Apply the options which don't affect how the debug information is loaded.
+    fn apply_options(&mut self, options: &DebugInfoOptions) {
+        self.keep_symbol_hash = options.keep_symbol_hash;
+        self.max_eager_array_length = options
+            .max_eager_array_length
+            .unwrap_or(DEFAULT_MAX_EAGER_ARRAY_LENGTH);
+        self.max_string_length = options
+            .max_string_length
+            .unwrap_or(DEFAULT_MAX_STRING_LENGTH);
+        self.max_byte_preview_length = options
+            .max_byte_preview_length
+            .unwrap_or(DEFAULT_MAX_BYTE_PREVIEW_LENGTH);
+        self.hide_out_of_scope_variables = options.hide_out_of_scope_variables;
+        self.max_cached_variables = options.max_cached_variables;
+        self.preferred_comp_dir = options
+            .preferred_comp_dir
+            .as_ref()
+            .map(|dir| TypedPath::derive(&*dir.to_string_lossy()).normalize());
+    }
+
+    fn from_raw_impl(
+        data: &[u8],
+        section_data: impl Fn(Cow<'_, [u8]>) -> SectionData,
+        split_dwarf: &SplitDwarfLoader,
+        supplementary: &SupplementaryLoader,
+        allow_missing_debug_info: bool,
+    ) -> Result<Self, DebugError> {
         let object = object::File::parse(data)?;
 
         let endianness = if object.is_little_endian() {
@@ -63,28 +499,118 @@ impl DebugInfo {
             RunTimeEndian::Big
         };
 
-        // Load a section and return as `Cow<[u8]>`.
-        let load_section = |id: gimli::SectionId| -> Result<DwarfReader, gimli::Error> {
-            let data = object
-                .section_by_name(id.name())
-                .and_then(|section| section.uncompressed_data().ok())
-                .unwrap_or_else(|| borrow::Cow::Borrowed(&[][..]));
+        let format = object.format();
 
-            Ok(gimli::read::EndianRcSlice::new(
-                Rc::from(&*data),
-                endianness,
-            ))
+        let has_debug_info = [gimli::SectionId::DebugInfo, gimli::SectionId::DebugAbbrev]
+            .into_iter()
+            .all(|id| object.section_by_name(&section_name(format, id)).is_some());
+
+        if !has_debug_info {
+            // Other formats usually contain debug information in a different format, such as CodeView,
+            // which is not supported.
+            if format != object::BinaryFormat::Elf {
+                return Err(DebugError::NoDwarf(format));
+            }
+            // ELF files without debug information are still useful for symbol based lookups, if requested.
+            if !allow_missing_debug_info {
+                return Err(DebugError::NoDebugInfo);
+            }
+        }
+
+        // Load a section and return as `SectionData`.
+        let load_section = |id: gimli::SectionId| {
+            object
+                .section_by_name(&section_name(format, id))
+                .map(|section| uncompressed_section_data(&section).map(&section_data))
+                .transpose()
+        };
+
+        let supplementary_dwarf = match supplementary.load(&object, endianness) {
+            Ok(dwarf) => dwarf,
+            Err(error) => {
+                tracing::warn!(
+                    "Unable to load the supplementary debug information, references to it cannot be resolved: {error:?}"
+                );
+                None
+            }
+        };
+
+        let mut this =
+            Self::from_sections_impl(load_section, endianness, split_dwarf, supplementary_dwarf)?;
+
+        // The `.eh_frame` section is not used for unwinding, but is reported in the statistics.
+        if let Some(eh_frame) = object.section_by_name(".eh_frame") {
+            this.section_sizes.insert(".eh_frame", eh_frame.size());
+        }
+
+        // Thumb function symbols have the lowest bit set, which is not part of the address.
+        let address_mask = if object.architecture() == object::Architecture::Arm {
+            !0b1
+        } else {
+            !0
+        };
+        this.trampolines = object
+            .symbols()
+            .filter_map(|symbol| {
+                let name = symbol.name().ok()?;
+                if !is_trampoline_symbol(name, symbol.size()) {
+                    return None;
+                }
+                let start = symbol.address() & address_mask;
+                Some(Trampoline {
+                    name: name.to_string(),
+                    address_range: start..start + symbol.size(),
+                })
+            })
+            .collect();
+        this.code_address_mask = address_mask;
+
+        Ok(this)
+    }
+
+    /// Parse debug information from the data of the individual DWARF sections.
+    ///
+    /// `sections` returns the data of the requested section, or `None` if the section is not available.
+    /// This can be used when the debug information is not available as a complete ELF file.
+    pub fn from_sections(
+        sections: impl Fn(gimli::SectionId) -> Option<Vec<u8>>,
+        endianness: RunTimeEndian,
+    ) -> Result<Self, DebugError> {
+        Self::from_sections_impl(
+            |id| Ok(sections(id).map(SectionData::from)),
+            endianness,
+            &SplitDwarfLoader::new(Vec::new(), None),
+            None,
+        )
+    }
+
+    fn from_sections_impl(
+        sections: impl Fn(gimli::SectionId) -> Result<Option<SectionData>, DebugError>,
+        endianness: RunTimeEndian,
+        split_dwarf: &SplitDwarfLoader,
+        supplementary_dwarf: Option<gimli::Dwarf<DwarfReader>>,
+    ) -> Result<Self, DebugError> {
+        let section_sizes = RefCell::new(BTreeMap::new());
+        let load_section = |id: gimli::SectionId| -> Result<DwarfReader, DebugError> {
+            let data = sections(id)?;
+            if let Some(data) = &data {
+                section_sizes
+                    .borrow_mut()
+                    .insert(id.name(), data.len() as u64);
+            }
+            let data = data.unwrap_or_else(|| SectionData::from(&[][..]));
+
+            Ok(DwarfReader::new(data, endianness))
         };
 
         // Load all of the sections.
-        let dwarf_cow = gimli::Dwarf::load(&load_section)?;
+        let mut dwarf_cow = gimli::Dwarf::load(&load_section)?;
+        if let Some(supplementary_dwarf) = supplementary_dwarf {
+            dwarf_cow.set_sup(supplementary_dwarf);
+        }
 
         use gimli::Section;
         let mut frame_section = gimli::DebugFrame::load(load_section)?;
-        let address_section = gimli::DebugAddr::load(load_section)?;
-        let debug_loc = gimli::DebugLoc::load(load_section)?;
-        let debug_loc_lists = gimli::DebugLocLists::load(load_section)?;
-        let locations_section = gimli::LocationLists::new(debug_loc, debug_loc_lists);
         let debug_line_section = gimli::DebugLine::load(load_section)?;
 
         let mut unit_infos = Vec::new();
@@ -101,28 +627,317 @@ impl DebugInfo {
                 // The frame section address size is only used for CIE versions before 4.
                 frame_section.set_address_size(unit.encoding().address_size);
 
+                // Skeleton units only contain the address ranges and the line table,
+                // the remaining debug information is stored in a separate file.
+                if unit.dwo_id.is_some() {
+                    match split_dwarf.load(&dwarf_cow, &unit) {
+                        Ok((split_unit, dwo_dwarf)) => {
+                            unit_infos.push(UnitInfo::new_split(split_unit, dwo_dwarf, unit));
+                            continue;
+                        }
+                        Err(error) => tracing::warn!(
+                            "Unable to load the split DWARF unit {:?}, only line information will be available: {error:?}",
+                            unit.dwo_id
+                        ),
+                    }
+                }
+
                 unit_infos.push(UnitInfo::new(unit, &dwarf_cow));
             };
         }
 
+        // The units of the supplementary file are only used through references from the units of the binary.
+        let mut supplementary_unit_infos = Vec::new();
+        if let Some(supplementary_dwarf) = dwarf_cow.sup() {
+            let mut iter = supplementary_dwarf.units();
+            while let Ok(Some(header)) = iter.next() {
+                if let Ok(unit) = supplementary_dwarf.unit(header) {
+                    supplementary_unit_infos
+                        .push(UnitInfo::new_supplementary(unit, supplementary_dwarf));
+                }
+            }
+        }
+
         Ok(DebugInfo {
             dwarf: dwarf_cow,
             frame_section,
-            locations_section,
-            address_section,
             debug_line_section,
             unit_infos,
+            supplementary_unit_infos,
             endianness,
             addr2line: None,
+            trampolines: Vec::new(),
+            code_address_mask: !0,
+            debug_file: None,
+            load_bias: 0,
+            section_sizes: section_sizes.into_inner(),
+            keep_symbol_hash: false,
+            max_eager_array_length: DEFAULT_MAX_EAGER_ARRAY_LENGTH,
+            max_string_length: DEFAULT_MAX_STRING_LENGTH,
+            max_byte_preview_length: DEFAULT_MAX_BYTE_PREVIEW_LENGTH,
+            hide_out_of_scope_variables: false,
+            max_cached_variables: None,
+            preferred_comp_dir: None,
+            path_mappings: Vec::new(),
+            tls_resolver: None,
+            type_formatters: language::rust::type_formatters(),
+        })
+    }
+
+    /// The file that the debug information was loaded from.
+    ///
+    /// This is either the binary itself, or a separate debug file that was found using the build ID
+    /// or the `.gnu_debuglink` section of the binary. Returns `None` if the debug information was
+    /// not loaded from a file.
+    pub fn debug_file(&self) -> Option<&Path> {
+        self.debug_file.as_deref()
+    }
+
+    /// Set the offset between the runtime addresses and the addresses in the debug information.
+    ///
+    /// This is needed when the code is executed from a different address than the one it was linked
+    /// at, for example when it is copied to RAM, or relocated by a bootloader. The `bias` is added to
+    /// the addresses in the debug information to get the runtime address.
+    pub fn set_load_bias(&mut self, bias: i64) {
+        self.load_bias = bias;
+    }
+
+    /// The offset between the runtime addresses and the addresses in the debug information.
+    ///
+    /// See [`DebugInfo::set_load_bias`].
+    pub fn load_bias(&self) -> i64 {
+        self.load_bias
+    }
+
+    /// Map source paths which start with `from_prefix` to paths starting with `to_prefix`.
+    ///
+    /// This is needed when the binary was built in a different location than the local copy of the
+    /// sources, for example on a CI server, or with the `--remap-path-prefix` option of rustc.
+    /// The mappings are applied to the paths in the debug information, e.g. in the [`SourceLocation`]s
+    /// and [`SourceFile`]s, and to the paths that are passed to [`DebugInfo::get_breakpoint_location`],
+    /// so both the local path and the path from the debug information can be used to set breakpoints.
+    ///
+    /// Prefixes only match whole path components, and both `/` and `\` are accepted as separators,
+    /// so Windows paths can be mapped on other hosts and vice versa. If more than one mapping matches
+    /// a path, the one with the longest prefix is used.
+    pub fn add_path_mapping(&mut self, from_prefix: TypedPath, to_prefix: TypedPath) {
+        self.path_mappings
+            .push(PathMapping::new(from_prefix, to_prefix));
+    }
+
+    /// Set the resolver for the addresses of thread-local variables.
+    ///
+    /// The location of a thread-local variable, e.g. a C `__thread` variable, is an offset in the
+    /// thread-local storage block of the current thread, which the resolver converts to a runtime
+    /// address. On a target with a single thread, the resolver can add the offset to the base address
+    /// of the block. Return `None` if the address cannot be resolved.
+    ///
+    /// Without a resolver, thread-local variables are shown without a value.
+    pub fn set_tls_resolver(
+        &mut self,
+        resolver: impl Fn(u64, &DebugRegisters) -> Option<u64> + Send + Sync + 'static,
+    ) {
+        self.tls_resolver = Some(Box::new(resolver));
+    }
+
+    /// Register a formatter for the values of the variables whose type name matches.
+    ///
+    /// The formatter is called after a variable is decoded, with the variable, the memory of the
+    /// target, and the cache which holds the children of the variable. It returns the text to show
+    /// as the value of the variable, or `None` to keep the decoded value. This can show the types of a program in a
+    /// meaningful way, e.g. fixed-point numbers, or timestamps which are counted in ticks. The type
+    /// name is the name which is shown for the variable, e.g. `q15_t` for a typedef.
+    ///
+    /// If `hide_children` is `true`, the children of a variable are removed when the formatter
+    /// returns a value.
+    ///
+    /// The formatters of the Rust standard library types, like `&str`, `Vec<T>` and `Option<T>`, are
+    /// registered first. All the formatters which match a type are called, in the order in which
+    /// they are registered, so a formatter can replace the value of an earlier one.
+    pub fn register_type_formatter(
+        &mut self,
+        type_name_matcher: impl Fn(&str) -> bool + Send + Sync + 'static,
+        hide_children: bool,
+        formatter: impl Fn(&Variable, &mut dyn MemoryInterface, &VariableCache) -> Option<String>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        self.type_formatters.push(TypeFormatterEntry::custom(
+            type_name_matcher,
+            hide_children,
+            formatter,
+        ));
+    }
+
+    /// Apply the [`DebugInfo::add_path_mapping`] mappings to a path.
+    pub(crate) fn map_path(&self, path: TypedPathBuf) -> TypedPathBuf {
+        map_path(&self.path_mappings, path)
+    }
+
+    /// Convert a runtime address to the corresponding address in the debug information.
+    pub(crate) fn debug_address(&self, address: u64) -> u64 {
+        address.wrapping_add_signed(self.load_bias.wrapping_neg())
+    }
+
+    /// Convert an address in the debug information to the corresponding runtime address.
+    pub(crate) fn runtime_address(&self, address: u64) -> u64 {
+        address.wrapping_add_signed(self.load_bias)
+    }
+
+    /// Get the name of the function that contains the given address.
+    ///
+    /// If the address is inside an inlined function, the name of the innermost inlined function is returned.
+    pub fn function_name(&self, address: u64) -> Option<String> {
+        let (_, functions) = self.get_function_dies(address).ok()?;
+
+        functions.last()?.function_name(self)
+    }
+
+    /// Get the chain of functions that contain the given address, in the same way as `addr2line`.
+    ///
+    /// The first frame is the innermost inlined function, and the last frame is the function which
+    /// contains the machine code. Returns an empty vector if no function contains the address.
+    ///
+    /// Unlike [`DebugInfo::unwind`], this does not need access to the target, which makes it useful to
+    /// symbolicate addresses from panic messages or logs.
+    pub fn frames_at_address(&self, address: u64) -> Vec<InlineFrame> {
+        let Ok((_, functions)) = self.get_function_dies(address) else {
+            return vec![];
+        };
+
+        let mut frames = Vec::with_capacity(functions.len());
+        let mut source_location = self.get_source_location(address);
+
+        for function in functions.iter().rev() {
+            frames.push(InlineFrame {
+                function_name: function.function_name(self),
+                source_location,
+                is_inlined: function.is_inline(),
+            });
+
+            // The location in the calling function is the call site of this function.
+            source_location = function.inline_call_location(self).map(|mut call_site| {
+                call_site.address = call_site
+                    .address
+                    .map(|address| self.runtime_address(address));
+                call_site
+            });
+        }
+
+        frames
+    }
+
+    /// Iterate over all functions with code, and the abstract instances of inlined functions,
+    /// in all compilation units.
+    ///
+    /// The address ranges include the [load bias](DebugInfo::set_load_bias).
+    pub fn functions(&self) -> impl Iterator<Item = FunctionInfo> + '_ {
+        self.unit_infos
+            .iter()
+            .flat_map(|unit_info| unit_info.functions(self))
+    }
+
+    /// Find the functions with code whose name matches `name`, for example to set a breakpoint on a function.
+    ///
+    /// Generic functions return one match for each monomorphization. The entry addresses include the
+    /// [load bias](DebugInfo::set_load_bias).
+    pub fn find_functions_by_name(&self, name: &str, match_mode: NameMatch) -> Vec<FunctionMatch> {
+        self.unit_infos
+            .iter()
+            .flat_map(|unit_info| unit_info.find_functions_by_name(self, name, match_mode))
+            .collect()
+    }
+
+    /// Collect a summary of the loaded debug information, to help diagnose missing debug information.
+    pub fn statistics(&self) -> DebugInfoStatistics {
+        DebugInfoStatistics::new(self)
+    }
+
+    /// List the metadata of all compilation units, such as the compiler and the source language.
+    ///
+    /// This only reads the root DIE of each unit.
+    pub fn units_info(&self) -> Vec<CompilationUnitInfo> {
+        self.unit_infos
+            .iter()
+            .map(|unit_info| unit_info.compilation_unit_info(self))
+            .collect()
+    }
+
+    /// List the source files which are referenced by the line programs of all compilation units.
+    ///
+    /// Each file is only listed once, even if it is used by multiple compilation units.
+    /// Files whose path cannot be read are skipped.
+    pub fn source_files(&self) -> Vec<SourceFile> {
+        source_files(self)
+    }
+
+    /// List the static variables at namespace scope of all compilation units, with their addresses.
+    ///
+    /// This only uses the debug information, and does not read the memory of the target. Variables whose
+    /// location is not a fixed address, e.g. thread-local variables, are listed with an address of `None`.
+    /// Variables which are defined in more than one unit at the same address are only listed once.
+    pub fn static_variables(&self) -> Result<Vec<StaticVariableInfo>, DebugError> {
+        static_variables(self)
+    }
+
+    /// Get the MD5 checksum of a source file, as recorded in the line tables.
+    ///
+    /// This can be used to detect if the source file was modified after the binary was built.
+    /// Returns `None` if the file is not referenced by the debug information, or if the line tables
+    /// do not contain checksums, which is always the case before DWARF 5.
+    pub fn file_checksum(&self, path: &Path) -> Option<[u8; 16]> {
+        let path = path.to_string_lossy();
+        let path = self.map_path(TypedPath::derive(&*path).to_path_buf());
+
+        self.unit_infos.iter().find_map(|unit_info| {
+            let unit = &unit_info.unit;
+            let line_program = unit.line_program.as_ref()?;
+
+            file_indices(line_program.header()).find_map(|file_index| {
+                let file_path = self.get_path(unit, file_index)?;
+                if canonical_path_eq(path.to_path(), file_path.to_path()) {
+                    file_md5(unit, file_index)
+                } else {
+                    None
+                }
+            })
         })
     }
 
     /// Try get the [`SourceLocation`] for a given address.
+    ///
+    /// More than one compilation unit can cover the same address, e.g. when the linker folded identical
+    /// functions, or with cross-crate inlining. The line table rows of all these units are considered,
+    /// and the best row is selected using these rules, in order:
+    ///
+    /// 1. Rows of units whose compilation directory starts with [`DebugInfoOptions::preferred_comp_dir`].
+    /// 2. Rows which start exactly at the address, over the last row before the address.
+    /// 3. Rows which are recommended breakpoint locations (`is_stmt`).
+    /// 4. The first row, in the order of the units in the debug information, and of the rows in the line table.
+    ///
+    /// Rows with line 0, which compilers use for code that does not belong to a line, are skipped. The location of
+    /// such code is taken from the nearest row before it with a line, and is marked with
+    /// [`SourceLocation::line_fallback`].
     pub fn get_source_location(&self, address: u64) -> Option<SourceLocation> {
+        self.resolve_source_location(address)
+            .map(|resolved| resolved.location)
+    }
+
+    /// Try get the [`SourceLocation`] for a given address, together with the details of the line table
+    /// row that describes the address.
+    ///
+    /// The row is selected in the same way as in [`DebugInfo::get_source_location`].
+    pub fn resolve_source_location(&self, address: u64) -> Option<ResolvedSourceLocation> {
+        let runtime_address = address;
+        let address = self.debug_address(address);
+
+        let mut best_match: Option<(LineRowRank, ResolvedSourceLocation)> = None;
+
         for unit_info in &self.unit_infos {
             let unit = &unit_info.unit;
 
-            let mut ranges = match self.dwarf.unit_ranges(unit) {
+            let mut ranges = match unit_info.unit_ranges(self) {
                 Ok(ranges) => ranges,
                 Err(error) => {
                     tracing::warn!(
@@ -134,83 +949,80 @@ impl DebugInfo {
                 }
             };
 
+            let mut contains_address = false;
             while let Ok(Some(range)) = ranges.next() {
-                if !(range.begin <= address && address < range.end) {
-                    continue;
+                if range.begin <= address && address < range.end {
+                    contains_address = true;
+                    break;
                 }
-                // Get the DWARF LineProgram.
-                let ilnp = unit.line_program.as_ref()?.clone();
-
-                let (program, sequences) = match ilnp.sequences() {
-                    Ok(value) => value,
-                    Err(error) => {
-                        tracing::warn!(
-                            "No valid source code ranges found for address {}: {:?}",
-                            address,
-                            error
-                        );
-                        continue;
-                    }
-                };
+            }
+            if !contains_address {
+                continue;
+            }
 
-                // Normalize the address.
-                let mut target_seq = None;
+            let preferred_unit = self.is_preferred_unit(unit);
 
-                for seq in sequences {
-                    if seq.start <= address && address < seq.end {
-                        target_seq = Some(seq);
-                        break;
-                    }
+            let LineRowsAtAddress {
+                rows,
+                exact,
+                line_fallback,
+                end_address,
+            } = line_rows_at_address(unit, address);
+
+            for row in rows {
+                let rank = LineRowRank {
+                    preferred_unit,
+                    exact,
+                    is_stmt: row.is_stmt(),
+                };
+                if best_match
+                    .as_ref()
+                    .is_some_and(|(best_rank, _)| *best_rank >= rank)
+                {
+                    continue;
                 }
 
-                let Some(target_seq) = target_seq.as_ref() else {
+                let Some(path) = self.find_file_and_directory(unit, row.file_index()) else {
                     continue;
                 };
 
-                let mut previous_row: Option<gimli::LineRow> = None;
-
-                let mut rows = program.resume_from(target_seq);
-
-                while let Ok(Some((_, row))) = rows.next_row() {
-                    match row.address().cmp(&address) {
-                        Ordering::Greater => {
-                            // The address is after the current row, so we use the previous row data.
-                            //
-                            // (If we don't do this, you get the artificial effect where the debugger
-                            // steps to the top of the file when it is stepping out of a function.)
-                            if let Some(previous_row) = previous_row
-                                && let Some(path) =
-                                    self.find_file_and_directory(unit, previous_row.file_index())
-                            {
-                                tracing::debug!("{:#010x} - {:?}", address, previous_row.isa());
-                                return Some(SourceLocation {
-                                    line: previous_row.line().map(NonZeroU64::get),
-                                    column: Some(previous_row.column().into()),
-                                    path,
-                                    address: Some(previous_row.address()),
-                                });
-                            }
-                        }
-                        Ordering::Less => {}
-                        Ordering::Equal => {
-                            if let Some(path) = self.find_file_and_directory(unit, row.file_index())
-                            {
-                                tracing::debug!("{:#010x} - {:?}", address, row.isa());
-
-                                return Some(SourceLocation {
-                                    line: row.line().map(NonZeroU64::get),
-                                    column: Some(row.column().into()),
-                                    path,
-                                    address: Some(row.address()),
-                                });
-                            }
-                        }
-                    }
-                    previous_row = Some(*row);
-                }
+                tracing::debug!("{:#010x} - {:?}", runtime_address, row.isa());
+
+                let row_address = self.runtime_address(row.address());
+                best_match = Some((
+                    rank,
+                    ResolvedSourceLocation {
+                        location: SourceLocation {
+                            line: row.line().map(NonZeroU64::get),
+                            column: Some(row.column().into()),
+                            path,
+                            address: Some(row_address),
+                            line_fallback,
+                        },
+                        address_range: end_address
+                            .map(|end_address| row_address..self.runtime_address(end_address)),
+                        is_stmt: row.is_stmt(),
+                        end_sequence: row.end_sequence(),
+                    },
+                ));
             }
         }
-        None
+
+        best_match.map(|(_, resolved)| resolved)
+    }
+
+    /// Returns `true` if the compilation directory of `unit` starts with [`DebugInfoOptions::preferred_comp_dir`].
+    fn is_preferred_unit(&self, unit: &gimli::Unit<DwarfReader>) -> bool {
+        let (Some(preferred_comp_dir), Some(comp_dir)) = (&self.preferred_comp_dir, &unit.comp_dir)
+        else {
+            return false;
+        };
+
+        let comp_dir = String::from_utf8_lossy(comp_dir);
+        TypedPath::derive(&*comp_dir)
+            .normalize()
+            .strip_prefix(preferred_comp_dir)
+            .is_ok()
     }
 
     /// We do not actually resolve the children of `[VariableName::StaticScope]` automatically,
@@ -219,7 +1031,9 @@ impl DebugInfo {
     /// This saves a lot of overhead when a user only wants to see the `[VariableName::LocalScope]` or `
     /// [VariableName::Registers]` while stepping through code (the most common use cases)
     pub fn create_static_scope_cache(&self) -> VariableCache {
-        VariableCache::new_static_cache()
+        let mut cache = VariableCache::new_static_cache();
+        cache.set_max_len(self.max_cached_variables);
+        cache
     }
 
     /// Creates the unpopulated cache for `function` variables
@@ -228,19 +1042,75 @@ impl DebugInfo {
         die_cursor_state: &FunctionDie,
         unit_info: &UnitInfo,
     ) -> Result<VariableCache, DebugError> {
-        let function_variable_cache = VariableCache::new_dwarf_cache(
+        let mut function_variable_cache = VariableCache::new_dwarf_cache(
             die_cursor_state.function_die.offset(),
             VariableName::LocalScopeRoot,
             unit_info,
         )?;
+        function_variable_cache.set_max_len(self.max_cached_variables);
 
         Ok(function_variable_cache)
     }
 
-    /// This effects the on-demand expansion of lazy/deferred load of all the 'child' `Variable`s for a given 'parent'.
-    #[tracing::instrument(level = "trace", skip_all, fields(parent_variable = ?parent_variable.variable_key()))]
-    pub fn cache_deferred_variables(
-        &self,
+    /// Read the static variable with the given name from the memory of the target, without unwinding the stack.
+    ///
+    /// The name can be qualified with the namespaces that contain the variable, e.g. `my_crate::state::CURRENT_STATE`.
+    /// The outer namespaces can be left out if the name only matches one variable.
+    ///
+    /// The variable is added to `cache` as a child of its root variable, with its children. Only the memory of the
+    /// target is read, so this also works while the core is running, if the probe can access memory while it runs.
+    pub fn read_static_variable(
+        &self,
+        core: &mut Core,
+        name: &str,
+        cache: &mut VariableCache,
+    ) -> Result<Variable, DebugError> {
+        read_static_variable(self, core, name, cache)
+    }
+
+    /// Evaluate a variable path, like `config.buffers[2].len`, and return the variable it refers to.
+    ///
+    /// The path starts with the name of a variable in `cache`, e.g. the local variables of `frame`.
+    /// It can access fields with `.field` (or `.0` for tuple fields), array elements with `[2]`, and
+    /// dereference pointers with a leading `*`, or `->field`. Fields of references are accessed without
+    /// dereferencing them first. The deferred children of the variables along the path are added to
+    /// `cache`, the other variables are not resolved.
+    ///
+    /// A leading `&` returns a pointer to the variable, with its address as value. That pointer is not
+    /// added to the cache.
+    pub fn evaluate_path(
+        &self,
+        core: &mut Core,
+        cache: &mut VariableCache,
+        frame: &StackFrame,
+        expr: &str,
+    ) -> Result<Variable, DebugError> {
+        evaluate_path(self, core, cache, frame, expr)
+    }
+
+    /// Evaluate a condition, like `count > 10 && state == 3`, e.g. of a conditional breakpoint.
+    ///
+    /// The condition combines comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`) with `&&`, `||`, `!` and
+    /// parentheses. The operands are `true`, `false`, integer and floating point literals, and variable
+    /// paths, see [`DebugInfo::evaluate_path`]. Numbers are `true` unless they are zero, pointers and
+    /// C-like enums are compared by their integer value.
+    ///
+    /// Only the variables which are needed for the result are read, e.g. `b` is not read in `a || b`
+    /// if `a` is `true`.
+    pub fn evaluate_condition(
+        &self,
+        core: &mut Core,
+        cache: &mut VariableCache,
+        frame: &StackFrame,
+        condition: &str,
+    ) -> Result<bool, EvalError> {
+        evaluate_condition(self, core, cache, frame, condition)
+    }
+
+    /// This effects the on-demand expansion of lazy/deferred load of all the 'child' `Variable`s for a given 'parent'.
+    #[tracing::instrument(level = "trace", skip_all, fields(parent_variable = ?parent_variable.variable_key()))]
+    pub fn cache_deferred_variables(
+        &self,
         cache: &mut VariableCache,
         memory: &mut dyn MemoryInterface,
         parent_variable: &mut Variable,
@@ -251,37 +1121,219 @@ impl DebugInfo {
             return Ok(());
         }
 
+        // Some of the members of a long array may already be resolved.
+        if parent_variable.variable_node_type.has_deferred_members() {
+            return self.cache_deferred_variables_paged(
+                cache,
+                memory,
+                parent_variable,
+                0,
+                usize::MAX,
+                frame_info,
+            );
+        }
+
         // Only attempt this part if we have not yet resolved the referenced children.
         if cache.has_children(parent_variable) {
             return Ok(());
         }
 
+        let result = self.resolve_deferred_children(cache, memory, parent_variable, frame_info);
+        if let Err(DebugError::VariableCacheFull { .. }) = result {
+            // Remove the children which were added before the cache was full, so the variable is
+            // expanded again when it is requested the next time.
+            cache.remove_cache_entry_children(parent_variable.variable_key)?;
+        }
+        result
+    }
+
+    /// Like [`Self::cache_deferred_variables`], but only the `count` members starting at the index
+    /// `start` are resolved, if the members of `parent_variable` are deferred, e.g. those of a long
+    /// array, see [`VariableNodeType::has_deferred_members`]. The members which are already in the
+    /// cache are kept.
+    ///
+    /// The members are retrieved with [`VariableCache::get_children_paged`], so a client only
+    /// reads the memory of the page of members it shows.
+    pub fn cache_deferred_variables_paged(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        parent_variable: &mut Variable,
+        start: usize,
+        count: usize,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !parent_variable.variable_node_type.has_deferred_members() {
+            return self.cache_deferred_variables(cache, memory, parent_variable, frame_info);
+        }
+        if !parent_variable.is_valid() {
+            return Ok(());
+        }
+
+        let member_count = cache.child_count(parent_variable.variable_key) as u64;
+        let requested = (start as u64).min(member_count)
+            ..(start.saturating_add(count) as u64).min(member_count);
+        let resolved: BTreeSet<u64> = cache
+            .get_children(parent_variable.variable_key)
+            .filter_map(|member| match member.name {
+                VariableName::Indexed(index) => Some(index),
+                _ => None,
+            })
+            .collect();
+
+        // Resolve each range of members which are not in the cache yet.
+        let mut missing_start = requested.start;
+        for index in resolved
+            .range(requested.clone())
+            .copied()
+            .chain([requested.end])
+        {
+            if missing_start < index {
+                let result = self.resolve_deferred_members(
+                    cache,
+                    memory,
+                    parent_variable,
+                    missing_start..index,
+                    frame_info,
+                );
+                if let Err(DebugError::VariableCacheFull { .. }) = result {
+                    // Only keep the members which were complete before, so the others are
+                    // resolved again when they are requested the next time.
+                    let added: Vec<_> = cache
+                        .get_children(parent_variable.variable_key)
+                        .filter_map(|member| match member.name {
+                            VariableName::Indexed(index) if resolved.contains(&index) => None,
+                            _ => Some(member.variable_key),
+                        })
+                        .collect();
+                    for key in added {
+                        cache.remove_cache_entry(key)?;
+                    }
+                }
+                result?;
+            }
+            missing_start = index + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Create the `members` of `parent_variable`, whose members are deferred, see
+    /// [`VariableNodeType::has_deferred_members`].
+    fn resolve_deferred_members(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        parent_variable: &Variable,
+        members: Range<u64>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
         match parent_variable.variable_node_type {
-            VariableNodeType::TypeOffset(header_offset, unit_offset)
-            | VariableNodeType::DirectLookup(header_offset, unit_offset) => {
-                let Some(unit_info) = self.unit_infos.iter().find(|unit_info| {
-                    unit_info
-                        .unit
-                        .header
-                        .offset()
-                        .to_debug_info_offset(&unit_info.unit)
-                        == Some(header_offset)
-                }) else {
+            VariableNodeType::ArrayMembers(header_offset, member_type_offset) => {
+                let unit_info = find_unit_info(&self.unit_infos, header_offset)?;
+                if !parent_variable.type_name.inner().is_array() {
                     return Err(DebugError::Other(
-                        "Failed to find unit info for offset lookup.".to_string(),
+                        "Deferred array members require an array type.".to_string(),
                     ));
+                }
+
+                // The members are created from a copy of the array, which keeps the type and the
+                // size of all its members.
+                let mut array_variable = parent_variable.clone();
+                let member_type_node = unit_info.unit.entry(member_type_offset)?;
+                unit_info.expand_array_members(
+                    self,
+                    &member_type_node,
+                    cache,
+                    &mut array_variable,
+                    memory,
+                    std::slice::from_ref(&members),
+                    frame_info,
+                )
+            }
+            VariableNodeType::SequenceMembers(header_offset, member_type_offset, address, _) => {
+                let unit_info = find_unit_info(&self.unit_infos, header_offset)?;
+                let member_type_node = unit_info.unit.entry(member_type_offset)?;
+                unit_info.expand_sequence_members(
+                    self,
+                    &member_type_node,
+                    cache,
+                    parent_variable,
+                    memory,
+                    address,
+                    members,
+                    frame_info,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Add the deferred children of `parent_variable` to `cache`, see [`Self::cache_deferred_variables`].
+    fn resolve_deferred_children(
+        &self,
+        cache: &mut VariableCache,
+        memory: &mut dyn MemoryInterface,
+        parent_variable: &mut Variable,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        match parent_variable.variable_node_type {
+            VariableNodeType::TypeOffset(header_offset, unit_offset)
+            | VariableNodeType::SupplementaryTypeOffset(header_offset, unit_offset)
+            | VariableNodeType::DirectLookup(header_offset, unit_offset) => {
+                let unit_infos = match parent_variable.variable_node_type {
+                    VariableNodeType::SupplementaryTypeOffset(..) => &self.supplementary_unit_infos,
+                    _ => &self.unit_infos,
                 };
+                let unit_info = find_unit_info(unit_infos, header_offset)?;
 
                 // Find the parent node
                 let mut type_tree = unit_info.unit.entries_tree(Some(unit_offset))?;
                 let parent_node = type_tree.root()?;
 
-                unit_info.process_tree(
-                    self,
-                    parent_node,
-                    parent_variable,
-                    memory,
+                if let (gimli::DW_TAG_pointer_type, Some(gimli::AttributeValue::UnitRef(target))) = (
+                    parent_node.entry().tag(),
+                    parent_node.entry().attr_value(gimli::DW_AT_type),
+                ) {
+                    // A pointer which was not followed automatically, see `StackFrameInfo::auto_deref_depth`.
+                    unit_info.extract_pointer_target(
+                        self,
+                        target,
+                        parent_variable,
+                        memory,
+                        cache,
+                        frame_info.follow_pointer(),
+                    )?;
+                } else {
+                    unit_info.process_tree(
+                        self,
+                        parent_node,
+                        parent_variable,
+                        memory,
+                        cache,
+                        frame_info,
+                    )?;
+                }
+
+                if let VariableNodeType::DirectLookup(..) = parent_variable.variable_node_type {
+                    // The local variables of a function also include the statics declared in it.
+                    unit_info.process_function_statics(
+                        self,
+                        unit_offset,
+                        parent_variable,
+                        memory,
+                        cache,
+                        frame_info,
+                    )?;
+                }
+            }
+            VariableNodeType::ArrayMembers(..) | VariableNodeType::SequenceMembers(..) => {
+                let count = cache.child_count(parent_variable.variable_key) as u64;
+                self.resolve_deferred_members(
                     cache,
+                    memory,
+                    parent_variable,
+                    0..count,
                     frame_info,
                 )?;
             }
@@ -297,7 +1349,9 @@ impl DebugInfo {
 
                     // Only process statics for this unit header.
                     // Navigate the current unit from the header down.
-                    let unit_node = entries.next_dfs()?.unwrap();
+                    let Some(unit_node) = entries.next_dfs()? else {
+                        continue;
+                    };
                     let unit_offset = unit_node.offset();
 
                     let mut type_tree = unit_info.unit.entries_tree(Some(unit_offset))?;
@@ -312,6 +1366,9 @@ impl DebugInfo {
                         frame_info,
                     )?;
                 }
+
+                // Statics which are used by more than one unit can have a DIE in each of them.
+                cache.remove_duplicate_statics(parent_variable.variable_key())?;
             }
             _ => {
                 // Do nothing. These have already been recursed to their maximum.
@@ -329,23 +1386,14 @@ impl DebugInfo {
         let Some(ref addr2line) = self.addr2line else {
             return Ok(vec![]);
         };
-        let Some(fn_name) = addr2line.find_symbol(address) else {
+        let addr2line = addr2line.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(fn_name) = addr2line.find_symbol(self.debug_address(address)) else {
             return Ok(vec![]);
         };
 
-        let mut fn_name = fn_name.to_string();
-        for lang in [
-            gimli::DW_LANG_Rust,
-            gimli::DW_LANG_C_plus_plus,
-            gimli::DW_LANG_C_plus_plus_03,
-            gimli::DW_LANG_C_plus_plus_11,
-            gimli::DW_LANG_C_plus_plus_14,
-        ] {
-            if let Some(demangle) = addr2line::demangle(&fn_name, lang) {
-                fn_name = demangle;
-                break;
-            }
-        }
+        let raw_name = fn_name.to_string();
+        let fn_name =
+            demangle(&raw_name, None, self.keep_symbol_hash).unwrap_or_else(|| raw_name.clone());
 
         Ok(vec![StackFrame {
             id: get_object_reference(),
@@ -353,16 +1401,82 @@ impl DebugInfo {
                 "{fn_name} @ {address:#0width$x}>",
                 width = (unwind_registers.get_address_size_bytes() * 2 + 2)
             ),
+            linkage_name: Some(raw_name),
             source_location: None,
             registers: unwind_registers.clone(),
             pc: RegisterValue::from(address),
             frame_base: None,
             is_inlined: false,
+            kind: FrameKind::Function,
             local_variables: None,
             canonical_frame_address: None,
         }])
     }
 
+    /// Compute the canonical frame address (CFA) of the frame with the given registers, using the
+    /// call frame information for its program counter.
+    ///
+    /// The unwinder stores the CFA in the [`StackFrameInfo`], this is the fallback for frames where it
+    /// is not available.
+    pub(crate) fn canonical_frame_address(&self, registers: &DebugRegisters) -> Option<u64> {
+        let pc: u64 = registers.get_program_counter()?.value?.try_into().ok()?;
+
+        let mut unwind_context = Box::new(UnwindContext::new());
+        let unwind_info = get_unwind_info(
+            &mut unwind_context,
+            &self.frame_section,
+            self.debug_address(pc),
+        )
+        .ok()?;
+
+        determine_cfa(registers, unwind_info).ok().flatten()
+    }
+
+    /// Recover the register values on entry to the function of the frame with the given registers,
+    /// by unwinding the frame with the call frame information.
+    ///
+    /// Registers which are not preserved across calls, such as the argument registers, usually have no
+    /// value, because the unwind cannot recover them. The program counter never has a value.
+    pub(crate) fn entry_registers(
+        &self,
+        registers: &DebugRegisters,
+        memory: &mut dyn MemoryInterface,
+    ) -> Option<DebugRegisters> {
+        let pc: u64 = registers.get_program_counter()?.value?.try_into().ok()?;
+
+        let mut unwind_context = Box::new(UnwindContext::new());
+        let unwind_info = get_unwind_info(
+            &mut unwind_context,
+            &self.frame_section,
+            self.debug_address(pc),
+        )
+        .ok()?;
+        let cfa = determine_cfa(registers, unwind_info).ok().flatten();
+
+        let mut entry_registers = registers.clone();
+        for debug_register in entry_registers.0.iter_mut() {
+            debug_register.value = if debug_register
+                .core_register
+                .register_has_role(RegisterRole::ProgramCounter)
+            {
+                None
+            } else {
+                unwind_register(
+                    debug_register,
+                    registers,
+                    unwind_info,
+                    cfa,
+                    memory,
+                    self.endianness,
+                )
+                .ok()
+                .flatten()
+            };
+        }
+
+        Some(entry_registers)
+    }
+
     /// Returns a populated (resolved) [`StackFrame`] struct.
     /// This function will also populate the `DebugInfo::VariableCache` with in scope `Variable`s for each `StackFrame`,
     /// while taking into account the appropriate strategy for lazy-loading of variables.
@@ -393,15 +1507,25 @@ impl DebugInfo {
 
         // The first function is the non-inlined function, and the rest are inlined functions.
         // The frame base only exists for the non-inlined function, so we can reuse it for all the inlined functions.
-        let frame_base = functions[0].frame_base(
-            self,
-            memory,
-            StackFrameInfo {
-                registers: unwind_registers,
-                frame_base: None,
-                canonical_frame_address: cfa,
-            },
-        )?;
+        // Without a frame base, the frame is still useful, but its variables cannot be resolved.
+        let frame_base = functions[0]
+            .frame_base(
+                self,
+                memory,
+                StackFrameInfo {
+                    registers: unwind_registers,
+                    frame_base: None,
+                    canonical_frame_address: cfa,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap_or_else(|error| {
+                tracing::warn!(
+                    "UNWIND: Unable to determine the frame base: {}",
+                    error.with_context(ErrorContext::at_address(address))
+                );
+                None
+            });
 
         let mut frames = Vec::new();
 
@@ -422,7 +1546,7 @@ impl DebugInfo {
             // Calculate the call site for this function, so that we can use it later to create an additional 'callee' `StackFrame` from that PC.
             let address_size = unit_info.unit.header.address_size() as u64;
 
-            let Some(next_function_low_pc) = next_function.low_pc() else {
+            let Some(next_function_entry_pc) = next_function.entry_pc() else {
                 tracing::warn!(
                     "UNWIND: Unknown starting address for inlined function {}.",
                     function_name
@@ -430,13 +1554,15 @@ impl DebugInfo {
                 continue;
             };
 
-            if !(next_function_low_pc > address_size && next_function_low_pc < u32::MAX as u64) {
+            if !(next_function_entry_pc > address_size && next_function_entry_pc < u32::MAX as u64)
+            {
                 tracing::warn!("UNWIND: Unknown call site for inlined function {function_name}.");
                 continue;
             }
 
             // The first instruction of the inlined function is used as the call site
-            let inlined_call_site = RegisterValue::from(next_function_low_pc);
+            let inlined_call_site =
+                RegisterValue::from(self.runtime_address(next_function_entry_pc));
 
             tracing::debug!(
                 "UNWIND: Callsite for inlined function {:?}",
@@ -460,11 +1586,13 @@ impl DebugInfo {
             frames.push(StackFrame {
                 id: get_object_reference(),
                 function_name,
+                linkage_name: function_die.linkage_name(self),
                 source_location: inlined_caller_source_location,
                 registers: unwind_registers.clone(),
                 pc: inlined_call_site,
                 frame_base,
                 is_inlined: function_die.is_inline(),
+                kind: FrameKind::Function,
                 local_variables,
                 canonical_frame_address: cfa,
             });
@@ -498,6 +1626,7 @@ impl DebugInfo {
         frames.push(StackFrame {
             id: get_object_reference(),
             function_name,
+            linkage_name: last_function.linkage_name(self),
             source_location: function_location,
             registers: unwind_registers.clone(),
             pc: match unwind_registers.get_address_size_bytes() {
@@ -507,6 +1636,7 @@ impl DebugInfo {
             },
             frame_base,
             is_inlined: last_function.is_inline(),
+            kind: FrameKind::Function,
             local_variables,
             canonical_frame_address: cfa,
         });
@@ -548,6 +1678,27 @@ impl DebugInfo {
         exception_handler: &dyn ExceptionInterface,
         instruction_set: Option<InstructionSet>,
         max_stack_frame_count: usize,
+    ) -> Result<Vec<StackFrame>, Error> {
+        self.unwind_with_options(
+            core,
+            initial_registers,
+            exception_handler,
+            instruction_set,
+            max_stack_frame_count,
+            UnwindOptions::default(),
+        )
+    }
+
+    /// Performs the logical unwind of the stack, in the same way as [`DebugInfo::unwind`],
+    /// using the provided [`UnwindOptions`].
+    pub fn unwind_with_options(
+        &self,
+        core: &mut impl MemoryInterface,
+        initial_registers: DebugRegisters,
+        exception_handler: &dyn ExceptionInterface,
+        instruction_set: Option<InstructionSet>,
+        max_stack_frame_count: usize,
+        options: UnwindOptions,
     ) -> Result<Vec<StackFrame>, Error> {
         self.unwind_impl(
             initial_registers,
@@ -555,6 +1706,7 @@ impl DebugInfo {
             exception_handler,
             instruction_set,
             max_stack_frame_count,
+            options,
         )
     }
 
@@ -565,272 +1717,197 @@ impl DebugInfo {
         exception_handler: &dyn ExceptionInterface,
         instruction_set: Option<InstructionSet>,
         max_stack_frame_count: usize,
+        options: UnwindOptions,
     ) -> Result<Vec<StackFrame>, Error> {
-        let mut stack_frames = Vec::<StackFrame>::new();
-
-        let mut unwind_context = Box::new(gimli::UnwindContext::new());
-
-        let mut unwind_registers = initial_registers;
-
-        // Unwind [StackFrame]'s for as long as we can unwind a valid PC value.
-        'unwind: while let Some(frame_pc_register_value) =
-            unwind_registers.get_program_counter().and_then(|pc| {
-                if pc.is_zero() | pc.is_max_value() {
-                    None
-                } else {
-                    pc.value
-                }
-            })
-        {
-            if stack_frames.len() >= max_stack_frame_count {
-                tracing::warn!("Stopped unwinding the stack after {max_stack_frame_count} frames");
-                break;
-            }
-            let frame_pc = frame_pc_register_value.try_into().map_err(|error| {
-                let message = format!("Cannot convert register value for program counter to a 64-bit integer value: {error:?}");
-                Error::Register(message)
-            })?;
+        unwind_images(
+            |_| self,
+            initial_registers,
+            memory,
+            exception_handler,
+            instruction_set,
+            max_stack_frame_count,
+            options,
+        )
+    }
 
-            // PART 1: Construct the `StackFrame`s for the current program counter.
-            //
-            //         Multiple stack frames can be constructed if we are inside inlined functions.
-            tracing::trace!(
-                "UNWIND: Will generate `StackFrame` for function at address (PC) {frame_pc_register_value:#}"
-            );
-            let unwind_info = get_unwind_info(&mut unwind_context, &self.frame_section, frame_pc);
+    /// Creates the synthetic [`FrameKind::ExceptionEntry`] frame for an exception,
+    /// with the source location of the instruction that was interrupted by the exception.
+    fn exception_entry_frame(
+        &self,
+        exception_info: &ExceptionInfo,
+        entry_registers: DebugRegisters,
+    ) -> StackFrame {
+        let pc = exception_info.handler_frame.pc;
 
-            // Determining the frame base may need the CFA (Canonical Frame Address) to be calculated first.
-            let cfa = unwind_info
-                .as_ref()
+        StackFrame {
+            id: get_object_reference(),
+            function_name: format!("<exception entry: {}>", exception_info.description),
+            linkage_name: None,
+            source_location: pc
+                .try_into()
                 .ok()
-                .and_then(|unwind_info| determine_cfa(&unwind_registers, unwind_info).ok())
-                .flatten();
-
-            // PART 1-a: Prepare the `StackFrame`s that holds the current frame information.
-            let cached_stack_frames =
-                match self.get_stackframe_info(memory, frame_pc, cfa, &unwind_registers) {
-                    Ok(cached_stack_frames) => cached_stack_frames,
-                    Err(e) => {
-                        tracing::error!("UNWIND: Unable to complete `StackFrame` information: {e}");
-                        // There is no point in continuing with the unwind, so let's get out of here.
-                        break;
-                    }
-                };
+                .and_then(|pc: u64| self.get_source_location(pc)),
+            registers: entry_registers,
+            pc,
+            frame_base: None,
+            is_inlined: false,
+            kind: FrameKind::ExceptionEntry,
+            local_variables: None,
+            canonical_frame_address: None,
+        }
+    }
 
-            // Add the found stackframes to the list, in reverse order. `get_stackframe_info` returns the frames in
-            // the order of the most recently called function last, but the stack frames should be
-            // in the order of the most recently called function first.
-            if !cached_stack_frames.is_empty() {
-                for frame in cached_stack_frames.into_iter().rev() {
-                    if frame.is_inlined {
-                        tracing::trace!(
-                            "UNWIND: Found inlined function - name={}, pc={}",
-                            frame.function_name,
-                            frame.pc
-                        );
-                    }
-                    stack_frames.push(frame);
-                }
-            } else {
-                // We have no valid code for the current frame, so we
-                // construct a frame, using what information we have.
-                stack_frames.push(StackFrame {
-                    id: get_object_reference(),
-                    function_name: format!(
-                        "<unknown function @ {:#0width$x}>",
-                        frame_pc,
-                        width = (unwind_registers.get_address_size_bytes() * 2 + 2)
-                    ),
-                    source_location: self.get_source_location(frame_pc),
-                    registers: unwind_registers.clone(),
-                    pc: frame_pc_register_value,
-                    frame_base: None,
-                    is_inlined: false,
-                    local_variables: None,
-                    canonical_frame_address: None,
-                });
-            };
+    /// Returns the linker veneer or trampoline that contains the given address, if any.
+    pub(crate) fn trampoline_at(&self, address: u64) -> Option<&Trampoline> {
+        let address = self.debug_address(address);
+        self.trampolines
+            .iter()
+            .find(|trampoline| trampoline.address_range.contains(&address))
+    }
 
-            // PART 2: Setup the registers for the next iteration (a.k.a. unwind previous frame, a.k.a. "callee", in the call stack).
-            tracing::trace!("UNWIND - Preparing to unwind the registers for the previous frame.");
+    /// Find the program counter where a breakpoint should be set,
+    /// given a source file, a line and optionally a column.
+    pub fn get_breakpoint_location(
+        &self,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+    ) -> Result<VerifiedBreakpoint, DebugError> {
+        self.get_breakpoint_location_with_options(path, line, column, BreakpointOptions::default())
+    }
 
-            // Because we will be updating the `unwind_registers` with previous frame unwind info,
-            // we need to keep a copy of the current frame's registers that can be used to resolve [DWARF](https://dwarfstd.org) expressions.
-            let callee_frame_registers = unwind_registers.clone();
+    /// Find the program counter where a breakpoint should be set, in the same way as
+    /// [`DebugInfo::get_breakpoint_location`], using the provided [`BreakpointOptions`].
+    // TODO: Move (and fix) this to the [`InstructionSequence::for_source_location`] method.
+    #[tracing::instrument(skip_all)]
+    pub fn get_breakpoint_location_with_options(
+        &self,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+        options: BreakpointOptions,
+    ) -> Result<VerifiedBreakpoint, DebugError> {
+        tracing::debug!(
+            "Looking for breakpoint location for {}:{}:{}",
+            path.display(),
+            line,
+            column
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "-".to_owned())
+        );
+        let path = self.map_path(path.to_path_buf());
+        let mut breakpoint =
+            VerifiedBreakpoint::for_source_location(self, path.to_path(), line, column, options)?;
+        breakpoint.address = self.runtime_address(breakpoint.address);
+        breakpoint.source_location.address = breakpoint
+            .source_location
+            .address
+            .map(|address| self.runtime_address(address));
+        Ok(breakpoint)
+    }
 
-            // PART 2-a: get the `gimli::FrameDescriptorEntry` for the program counter
-            // and then the unwind info associated with this row.
-            let unwind_info = match unwind_info {
-                Ok(unwind_info) => {
-                    tracing::trace!("UNWIND: Found unwind info for address {frame_pc:#010x}");
-                    unwind_info
-                }
-                Err(err) => {
-                    tracing::trace!(
-                        "UNWIND: Unable to find unwind info for address {frame_pc:#010x}: {err}"
-                    );
-                    if let ControlFlow::Break(error) = exception_handler.unwind_without_debuginfo(
-                        &mut unwind_registers,
-                        frame_pc,
-                        &stack_frames,
-                        instruction_set,
-                        memory,
-                    ) {
-                        if let Some(error) = error {
-                            // This is not fatal, but we cannot continue unwinding beyond the current frame.
-                            tracing::error!("{:?}", &error);
-                            if let Some(first_frame) = stack_frames.first_mut() {
-                                first_frame.function_name =
-                                    format!("{} : ERROR : {error}", first_frame.function_name);
-                            };
-                        }
-                        break 'unwind;
-                    }
-
-                    if callee_frame_registers == unwind_registers {
-                        tracing::debug!("No change, preventing infinite loop");
-                        break;
-                    }
-                    continue 'unwind;
-                }
-            };
-
-            // PART 2-b: Unwind registers for the "previous/calling" frame.
-            for debug_register in unwind_registers.0.iter_mut() {
-                // The program counter is handled later
-                if debug_register
-                    .core_register
-                    .register_has_role(RegisterRole::ProgramCounter)
-                {
-                    continue;
-                }
-
-                match unwind_register(
-                    debug_register,
-                    &callee_frame_registers,
-                    unwind_info,
-                    cfa,
-                    memory,
-                ) {
-                    Err(error) => {
-                        tracing::error!("{:?}", &error);
-                        if let Some(first_frame) = stack_frames.last_mut() {
-                            first_frame.function_name =
-                                format!("{} : ERROR: {error}", first_frame.function_name);
-                        };
-                        break 'unwind;
-                    }
-                    Ok(val) => {
-                        debug_register.value = val;
-                    }
-                };
-            }
-
-            // PART 3: Check if we entered the current frame from an exception handler.
-            // - If we are at an exception handler frame:
-            //   - Create a "handler" stackframe that can be inserted into the stack_frames list,
-            //     instead of "unknown function @ address";
-            //   - Overwrite the unwind registers with the exception context.
-            // - If for some reason we cannot determine the exception context, we silently continue with the rest of the unwind.
-            // At worst, the unwind will be able to unwind the stack to the frame of the most recent exception handler.
-            if unwind_registers
-                .get_return_address()
-                .is_some_and(|ra| ra.value.is_some())
-            {
-                match exception_handler.exception_details(memory, &unwind_registers, self) {
-                    Ok(Some(exception_info)) => {
-                        tracing::trace!(
-                            "UNWIND: Stack unwind reached an exception handler {}",
-                            exception_info.description
-                        );
-                        unwind_registers = exception_info.handler_frame.registers.clone();
-                        stack_frames.push(exception_info.handler_frame);
-                        // We have everything we need to unwind the next frame in the stack.
-                        continue 'unwind;
-                    }
-                    Ok(None) => {
-                        tracing::trace!(
-                            "UNWIND: No exception context found. Stack unwind will continue."
-                        );
-                    }
-                    Err(e) => {
-                        // TODO: Nicely print error with sources
-                        let message = format!(
-                            "UNWIND: Error while checking for exception context. The stack trace will not include the calling frames.\n{e:?}"
-                        );
-                        tracing::warn!("{message}");
-                        stack_frames.push(StackFrame {
-                            id: get_object_reference(),
-                            function_name: message,
-                            source_location: None,
-                            registers: unwind_registers.clone(),
-                            pc: frame_pc_register_value,
-                            frame_base: None,
-                            is_inlined: false,
-                            local_variables: None,
-                            canonical_frame_address: None,
-                        });
-                        break 'unwind;
-                    }
-                };
-            }
-
-            let unwound_return_address = unwind_registers
-                .get_register_by_role(&RegisterRole::ReturnAddress)
-                .ok()
-                .and_then(|reg| reg.value);
-
-            let program_counter = unwind_registers.get_program_counter_mut().unwrap();
+    /// Find all the program counters where a breakpoint should be set for a source line,
+    /// given a source file, a line and optionally a column.
+    ///
+    /// A line can have several locations, e.g. one for each monomorphization of a generic function,
+    /// or for each copy of an inlined function, and a breakpoint should be set at all of them.
+    /// [`DebugInfo::get_breakpoint_location`] returns the best of these locations.
+    pub fn get_breakpoint_locations(
+        &self,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+    ) -> Vec<BreakpointLocation> {
+        let path = self.map_path(path.to_path_buf());
+        VerifiedBreakpoint::candidates_for_source_location(
+            self,
+            path.to_path(),
+            line,
+            column,
+            BreakpointOptions::default(),
+        )
+        .into_iter()
+        .map(|candidate| candidate.into_location(self))
+        .collect()
+    }
 
-            let Ok(current_pc) =
-                callee_frame_registers.get_register_value_by_role(&RegisterRole::ProgramCounter)
-            else {
-                let error = "UNWIND: Tried to unwind return address value where current program counter is unknown.";
-                tracing::error!("{error}");
-                if let Some(first_frame) = stack_frames.last_mut() {
-                    first_frame.function_name =
-                        format!("{} : ERROR: {error}", first_frame.function_name);
-                };
-                break 'unwind;
-            };
-            // NOTE: PC = Value of the unwound LR, i.e. the first instruction after the one that called this function.
-            // If both the LR and PC registers have undefined rules, this will prevent the unwind from continuing.
-            program_counter.value = unwound_return_address.and_then(|return_address| {
-                unwind_program_counter_register(return_address, current_pc, instruction_set)
-            });
+    /// Find the program counters where a breakpoint should be set for a function, e.g. for the
+    /// `setFunctionBreakpoints` request of DAP.
+    ///
+    /// The name can be partially qualified, see [`NameMatch::Suffix`]. If no function matches, it is
+    /// matched with the demangled linkage names of the functions instead. There is one location for each
+    /// monomorphization of a generic function, and for each out-of-line copy of an inline function.
+    ///
+    /// The location is at the end of the prologue of the function, where its parameters can be read,
+    /// rather than its entry address. Functions without line information use their entry address.
+    pub fn get_function_breakpoint_locations(&self, name: &str) -> Vec<BreakpointLocation> {
+        self.get_function_breakpoint_locations_with_options(name, BreakpointOptions::default())
+    }
 
-            if callee_frame_registers == unwind_registers {
-                tracing::debug!("No change, preventing infinite loop");
+    /// Find the program counters where a breakpoint should be set for a function, like
+    /// [`DebugInfo::get_function_breakpoint_locations`], with [`BreakpointOptions`], e.g. to set the
+    /// breakpoints at the entry addresses of the functions.
+    pub fn get_function_breakpoint_locations_with_options(
+        &self,
+        name: &str,
+        options: BreakpointOptions,
+    ) -> Vec<BreakpointLocation> {
+        let mut functions = Vec::new();
+        for match_mode in [NameMatch::Suffix, NameMatch::Demangled] {
+            functions = self
+                .unit_infos
+                .iter()
+                .flat_map(|unit_info| unit_info.find_function_dies_by_name(self, name, match_mode))
+                .collect();
+            if !functions.is_empty() {
                 break;
             }
         }
 
-        Ok(stack_frames)
+        functions
+            .into_iter()
+            .filter_map(|(function_name, function)| {
+                if let Some(candidate) = BreakpointCandidate::for_function(
+                    self,
+                    &function,
+                    function_name.clone(),
+                    options,
+                ) {
+                    return Some(candidate.into_location(self));
+                }
+
+                Some(BreakpointLocation {
+                    address: self.runtime_address(function.entry_pc()?),
+                    line: None,
+                    column: None,
+                    function_name: Some(function_name),
+                    is_stmt: false,
+                })
+            })
+            .collect()
     }
 
-    /// Find the program counter where a breakpoint should be set,
-    /// given a source file, a line and optionally a column.
-    // TODO: Move (and fix) this to the [`InstructionSequence::for_source_location`] method.
-    #[tracing::instrument(skip_all)]
-    pub fn get_breakpoint_location(
+    /// List the lines of a source file from `start_line` to `end_line` where a breakpoint can be set,
+    /// with the columns of each line, e.g. for the `breakpointLocations` request of DAP.
+    ///
+    /// The lines, and the columns of each line, are sorted and distinct. Like for
+    /// [`DebugInfo::get_breakpoint_location`], the columns of the instructions which are not statements
+    /// are only listed for lines without statements.
+    pub fn breakpoint_locations_in_range(
         &self,
         path: TypedPath,
-        line: u64,
-        column: Option<u64>,
-    ) -> Result<VerifiedBreakpoint, DebugError> {
-        tracing::debug!(
-            "Looking for breakpoint location for {}:{}:{}",
-            path.display(),
-            line,
-            column
-                .map(|c| c.to_string())
-                .unwrap_or_else(|| "-".to_owned())
-        );
-        VerifiedBreakpoint::for_source_location(self, path, line, column)
+        start_line: u64,
+        end_line: u64,
+    ) -> Vec<(u64, Vec<ColumnType>)> {
+        let path = self.map_path(path.to_path_buf());
+        breakpoint_locations_in_range(self, path.to_path(), start_line..=end_line)
     }
 
     /// Get the path for an entry in a line program header, using the compilation unit's directory and file entries.
-    // TODO: Determine if it is necessary to navigate the include directories to find the file absolute path for C files.
+    ///
+    /// The `file_index` and the directory indices follow the numbering of the line program's DWARF version,
+    /// see [`file_indices`]. Relative paths are resolved against the compilation directory, and the
+    /// [`DebugInfo::add_path_mapping`] mappings are applied to the result.
     pub(crate) fn get_path(
         &self,
         unit: &gimli::read::Unit<DwarfReader>,
@@ -846,37 +1923,48 @@ impl DebugInfo {
             return None;
         };
         let file_name_attr_string = self.dwarf.attr_string(unit, file_entry.path_name()).ok()?;
-        let name_path = from_utf8(&file_name_attr_string).ok()?;
+        // Paths are not required to be valid UTF-8, e.g. when they were encoded with a legacy code page.
+        // A lossy conversion still allows the file to be used for breakpoints and source locations.
+        let name_path = String::from_utf8_lossy(&file_name_attr_string);
 
         let dir_name_attr_string = file_entry
             .directory(header)
             .and_then(|dir| self.dwarf.attr_string(unit, dir).ok());
 
-        let dir_path = dir_name_attr_string.and_then(|dir_name| {
-            from_utf8(&dir_name)
-                .ok()
-                .map(|p| TypedPath::derive(p).to_path_buf())
-        });
+        let dir_path = dir_name_attr_string
+            .map(|dir_name| TypedPath::derive(&*String::from_utf8_lossy(&dir_name)).to_path_buf());
 
         let mut combined_path = match dir_path {
-            Some(dir_path) => dir_path.join(name_path),
-            None => TypedPath::derive(name_path).to_path_buf(),
+            Some(dir_path) => dir_path.join(&*name_path),
+            None => TypedPath::derive(&*name_path).to_path_buf(),
         };
 
-        if combined_path.is_relative() {
-            let comp_dir = unit
-                .comp_dir
-                .as_ref()
-                .map(|dir| from_utf8(dir))
-                .transpose()
-                .ok()?
-                .map(TypedPath::derive);
-            if let Some(comp_dir) = comp_dir {
-                combined_path = comp_dir.join(&combined_path);
-            }
+        // In DWARF 5, directory entry 0 is the compilation directory of the line program, and the other
+        // directories are relative to it. Earlier versions have no such entry, and directory index 0
+        // refers to the `DW_AT_comp_dir` of the unit, which is also the fallback for DWARF 5.
+        let line_program_comp_dir = if header.version() >= 5 && file_entry.directory_index() != 0 {
+            header
+                .directory(0)
+                .and_then(|dir| self.dwarf.attr_string(unit, dir).ok())
+        } else {
+            None
+        };
+
+        if combined_path.is_relative()
+            && let Some(comp_dir) = line_program_comp_dir
+        {
+            let comp_dir = String::from_utf8_lossy(&comp_dir);
+            combined_path = TypedPath::derive(&*comp_dir).join(&combined_path);
         }
 
-        Some(combined_path)
+        if combined_path.is_relative()
+            && let Some(comp_dir) = unit.comp_dir.as_ref()
+        {
+            let comp_dir = String::from_utf8_lossy(comp_dir);
+            combined_path = TypedPath::derive(&*comp_dir).join(&combined_path);
+        }
+
+        Some(self.map_path(combined_path))
     }
 
     pub(crate) fn find_file_and_directory(
@@ -895,7 +1983,7 @@ impl DebugInfo {
         address: u64,
     ) -> Result<&super::unit_info::UnitInfo, DebugError> {
         for header in &self.unit_infos {
-            match self.dwarf.unit_ranges(&header.unit) {
+            match header.unit_ranges(self) {
                 Ok(mut ranges) => {
                     while let Ok(Some(range)) = ranges.next() {
                         if range.contains(address) {
@@ -921,8 +2009,9 @@ impl DebugInfo {
         &self,
         address: u64,
     ) -> Result<(&UnitInfo, Vec<FunctionDie<'_>>), DebugError> {
+        let debug_address = self.debug_address(address);
         for unit_info in &self.unit_infos {
-            let function_dies = unit_info.get_function_dies(self, address)?;
+            let function_dies = unit_info.get_function_dies(self, debug_address)?;
 
             if !function_dies.is_empty() {
                 return Ok((unit_info, function_dies));
@@ -950,6 +2039,63 @@ impl DebugInfo {
             .map(|(_, die)| die)
     }
 
+    /// Follow the `DW_AT_abstract_origin` and `DW_AT_specification` references of a DIE, e.g. from an inlined
+    /// variable to its abstract instance, or from the definition of a C++ member function to its declaration.
+    ///
+    /// Returns the referenced DIEs with their units, nearest first. The chain ends at a DIE without a
+    /// reference, at a reference which cannot be resolved, or after [`MAX_ORIGIN_DEPTH`] DIEs.
+    pub(crate) fn resolve_origin_chain<'debug_info>(
+        &'debug_info self,
+        die: &Die,
+        unit_info: &'debug_info UnitInfo,
+    ) -> Vec<(&'debug_info UnitInfo, Die)> {
+        let mut chain: Vec<(&UnitInfo, Die)> = Vec::new();
+
+        while chain.len() < MAX_ORIGIN_DEPTH {
+            let (current_unit_info, current_die) = chain
+                .last()
+                .map_or((unit_info, die), |(unit_info, die)| (*unit_info, die));
+            let Some(reference) = current_die
+                .attr(gimli::DW_AT_abstract_origin)
+                .or_else(|| current_die.attr(gimli::DW_AT_specification))
+            else {
+                break;
+            };
+
+            match self.resolve_die_reference_with_unit(reference, current_unit_info) {
+                Ok(origin) => chain.push(origin),
+                Err(error) => {
+                    tracing::debug!("Cannot resolve the origin of a DIE: {error:?}");
+                    break;
+                }
+            }
+        }
+
+        chain
+    }
+
+    /// Returns an attribute of a DIE, or if the DIE does not have it, of the nearest DIE in its chain of
+    /// `DW_AT_abstract_origin` and `DW_AT_specification` references, see [`Self::resolve_origin_chain`].
+    ///
+    /// The unit of the DIE which has the attribute is returned with it, to resolve the value of the attribute.
+    pub(crate) fn resolve_attribute<'debug_info>(
+        &'debug_info self,
+        die: &Die,
+        unit_info: &'debug_info UnitInfo,
+        attribute_name: gimli::DwAt,
+    ) -> Option<(&'debug_info UnitInfo, GimliAttribute)> {
+        if let Some(attribute) = die.attr(attribute_name) {
+            return Some((unit_info, attribute.clone()));
+        }
+
+        self.resolve_origin_chain(die, unit_info)
+            .into_iter()
+            .find_map(|(unit_info, die)| {
+                die.attr(attribute_name)
+                    .map(|attribute| (unit_info, attribute.clone()))
+            })
+    }
+
     /// The program binary's (and core's) endianness.
     pub fn endianness(&self) -> RunTimeEndian {
         self.endianness
@@ -969,129 +2115,529 @@ impl DebugInfo {
                 Ok((unit_info, unit_info.unit.entry(unit_ref)?))
             }
             gimli::AttributeValue::DebugInfoRef(offset) => {
-                for unit_info in &self.unit_infos {
-                    let Some(unit_offset) = offset.to_unit_offset(&unit_info.unit.header) else {
-                        continue;
-                    };
-
-                    let entry = unit_info.unit.entry(unit_offset).map_err(|error| {
-                        DebugError::Other(format!(
-                            "Error reading DIE at debug info offset {:#x} : {}",
-                            offset.0, error
-                        ))
-                    })?;
-                    return Ok((unit_info, entry));
-                }
-
-                Err(DebugError::Other(format!(
-                    "Unable to find unit info for debug info offset {:#x}",
-                    offset.0
-                )))
+                // A reference from a supplementary unit stays in the supplementary file.
+                let unit_infos = if unit_info.supplementary {
+                    &self.supplementary_unit_infos
+                } else {
+                    &self.unit_infos
+                };
+                Self::resolve_debug_info_offset(offset, unit_infos)
+            }
+            gimli::AttributeValue::DebugInfoRefSup(offset) => {
+                Self::resolve_debug_info_offset(offset, &self.supplementary_unit_infos)
             }
             other_attribute_value => Err(DebugError::Other(format!(
                 "Unimplemented attribute value {other_attribute_value:?}"
             ))),
         }
     }
+
+    /// Returns the UnitInfo and DIE at the given offset in `.debug_info`, from the units in `unit_infos`.
+    fn resolve_debug_info_offset(
+        offset: gimli::DebugInfoOffset,
+        unit_infos: &[UnitInfo],
+    ) -> Result<(&UnitInfo, Die), DebugError> {
+        for unit_info in unit_infos {
+            let Some(unit_offset) = offset.to_unit_offset(&unit_info.unit.header) else {
+                continue;
+            };
+
+            let entry = unit_info.unit.entry(unit_offset).map_err(|error| {
+                DebugError::Other(format!(
+                    "Error reading DIE at debug info offset {:#x} : {}",
+                    offset.0, error
+                ))
+            })?;
+            return Ok((unit_info, entry));
+        }
+
+        Err(DebugError::Other(format!(
+            "Unable to find unit info for debug info offset {:#x}",
+            offset.0
+        )))
+    }
 }
 
-/// Uses the [`TypedPathBuf::normalize`] function to normalize both paths before comparing them
-pub(crate) fn canonical_path_eq(primary_path: TypedPath, secondary_path: TypedPath) -> bool {
-    primary_path.normalize() == secondary_path.normalize()
+/// Find the unit with the given header offset, for resolving deferred variables.
+fn find_unit_info(
+    unit_infos: &[UnitInfo],
+    header_offset: gimli::DebugInfoOffset,
+) -> Result<&UnitInfo, DebugError> {
+    unit_infos
+        .iter()
+        .find(|unit_info| {
+            unit_info
+                .unit
+                .header
+                .offset()
+                .to_debug_info_offset(&unit_info.unit)
+                == Some(header_offset)
+        })
+        .ok_or_else(|| DebugError::Other("Failed to find unit info for offset lookup.".to_string()))
 }
 
-/// Get a handle to the [`gimli::UnwindTableRow`] for this call frame, so that we can reference it to unwind register values.
-pub fn get_unwind_info<'a>(
-    unwind_context: &'a mut UnwindContext<GimliReaderOffset>,
-    frame_section: &DebugFrame<DwarfReader>,
-    frame_program_counter: u64,
-) -> Result<&'a gimli::UnwindTableRow<GimliReaderOffset>, DebugError> {
-    let transform_error = |error| {
-        DebugError::Other(format!(
-            "UNWIND: Error reading FrameDescriptorEntry at PC={frame_program_counter:x} : {error}"
-        ))
-    };
+/// Identify linker generated veneers and trampolines by their symbol name and size.
+/// - GNU ld names long branch veneers `<target>_veneer` or `__<target>_veneer`,
+///   and ARM/Thumb interworking stubs `__<target>_from_thumb` or `__<target>_from_arm`.
+/// - LLVM lld names its thunks e.g. `__ThumbV7PILongThunk_<target>` or `__ARMv7ABSLongThunk_<target>`.
+fn is_trampoline_symbol(name: &str, size: u64) -> bool {
+    let has_trampoline_name = name.ends_with("_veneer")
+        || name.ends_with("_from_thumb")
+        || name.ends_with("_from_arm")
+        || (name.starts_with("__") && name.contains("Thunk_"));
+
+    has_trampoline_name && (1..=MAX_TRAMPOLINE_SIZE).contains(&size)
+}
 
-    let unwind_bases = BaseAddresses::default();
+/// The valid file indices of a line program, for use with [`DebugInfo::get_path`].
+///
+/// In DWARF 5, the file table is zero-based, and entry 0 is the primary source file of the unit.
+/// Earlier versions are one-based, and index 0 refers to the primary source file, which is not in the file table.
+pub(crate) fn file_indices(header: &gimli::LineProgramHeader<DwarfReader>) -> Range<u64> {
+    let file_count = header.file_names().len() as u64;
+    if header.version() <= 4 {
+        0..file_count + 1
+    } else {
+        0..file_count
+    }
+}
 
-    let frame_descriptor_entry = frame_section
-        .fde_for_address(
-            &unwind_bases,
-            frame_program_counter,
-            DebugFrame::cie_from_offset,
-        )
-        .map_err(transform_error)?;
+/// The MD5 checksum of a file in the line program of `unit`, if the line program records checksums.
+///
+/// Checksums are only available in DWARF 5, and are optional there.
+pub(crate) fn file_md5(unit: &gimli::Unit<DwarfReader>, file_index: u64) -> Option<[u8; 16]> {
+    let header = unit.line_program.as_ref()?.header();
+    if !header.file_has_md5() {
+        return None;
+    }
 
-    frame_descriptor_entry
-        .unwind_info_for_address(
-            frame_section,
-            &unwind_bases,
-            unwind_context,
-            frame_program_counter,
-        )
-        .map_err(transform_error)
+    header.file(file_index).map(|file| *file.md5())
 }
 
-/// Determines the CFA (canonical frame address) for the current [`gimli::UnwindTableRow`], using the current register values.
-pub fn determine_cfa<R: gimli::ReaderOffset>(
-    unwind_registers: &DebugRegisters,
-    unwind_info: &UnwindTableRow<R>,
-) -> Result<Option<u64>, Error> {
-    let gimli::CfaRule::RegisterAndOffset { register, offset } = unwind_info.cfa() else {
-        unimplemented!()
-    };
+/// Uses the [`TypedPathBuf::normalize`] function to normalize both paths before comparing them
+pub(crate) fn canonical_path_eq(primary_path: TypedPath, secondary_path: TypedPath) -> bool {
+    primary_path.normalize() == secondary_path.normalize()
+}
 
-    let reg_val = unwind_registers
-        .get_register_by_dwarf_id(register.0)
-        .and_then(|register| register.value);
+/// Performs the logical unwind of the stack, in the same way as [`DebugInfo::unwind`], using the
+/// [`DebugInfo`] returned by `image_for_address` for the program counter of each frame.
+pub(crate) fn unwind_images<'a>(
+    image_for_address: impl Fn(u64) -> &'a DebugInfo,
+    initial_registers: DebugRegisters,
+    memory: &mut impl MemoryInterface,
+    exception_handler: &dyn ExceptionInterface,
+    instruction_set: Option<InstructionSet>,
+    max_stack_frame_count: usize,
+    options: UnwindOptions,
+) -> Result<Vec<StackFrame>, Error> {
+    let mut stack_frames = Vec::<StackFrame>::new();
 
-    let cfa = match reg_val {
-        None => {
-            tracing::error!(
-                "UNWIND: `StackFrameIterator` unable to determine the unwind CFA: Missing value of register {}",
-                register.0
-            );
-            None
-        }
+    let mut unwind_context = Box::new(gimli::UnwindContext::new());
 
-        Some(reg_val) if reg_val.is_zero() => {
-            // If we encounter this rule for CFA, it implies the scenario depends on a FP/frame pointer to continue successfully.
-            // Therefore, if reg_val is zero (i.e. FP is zero), then we do not have enough information to determine the CFA by rule.
-            tracing::trace!(
-                "UNWIND: Stack unwind complete - The FP register value unwound to a value of zero."
-            );
-            None
-        }
+    let mut unwind_registers = initial_registers;
 
-        Some(reg_val) => {
-            let unwind_cfa = add_to_address(
-                reg_val.try_into()?,
-                *offset,
-                unwind_registers.get_address_size_bytes(),
-            );
+    // Unwind [StackFrame]'s for as long as we can unwind a valid PC value.
+    'unwind: while let Some(frame_pc_register_value) =
+        unwind_registers.get_program_counter().and_then(|pc| {
+            if pc.is_zero() | pc.is_max_value() {
+                None
+            } else {
+                pc.value
+            }
+        })
+    {
+        if stack_frames.len() >= max_stack_frame_count {
+            tracing::warn!("Stopped unwinding the stack after {max_stack_frame_count} frames");
+            break;
+        }
+        let frame_pc = frame_pc_register_value.try_into().map_err(|error| {
+            let message = format!("Cannot convert register value for program counter to a 64-bit integer value: {error:?}");
+            Error::Register(message)
+        })?;
+        let debug_info = image_for_address(frame_pc);
+
+        // PART 0: Linker veneers and trampolines have no debug information, and only branch to their
+        //         target without modifying the stack or the return address. We can therefore skip
+        //         straight to the return address, without losing any of the calling frames.
+        if let Some(trampoline) = debug_info.trampoline_at(frame_pc) {
             tracing::trace!(
-                "UNWIND - CFA : {:#010x}\tRule: {:?}",
-                unwind_cfa,
-                unwind_info.cfa()
+                "UNWIND: Found trampoline {} at address {frame_pc:#010x}",
+                trampoline.name
             );
-            Some(unwind_cfa)
+            if options.trampolines == TrampolineHandling::Label {
+                stack_frames.push(StackFrame {
+                    id: get_object_reference(),
+                    function_name: format!("<trampoline: {}>", trampoline.name),
+                    linkage_name: None,
+                    source_location: None,
+                    registers: unwind_registers.clone(),
+                    pc: frame_pc_register_value,
+                    frame_base: None,
+                    is_inlined: false,
+                    kind: FrameKind::Trampoline,
+                    local_variables: None,
+                    canonical_frame_address: None,
+                });
+            }
+
+            let callee_frame_registers = unwind_registers.clone();
+            let return_address = unwind_registers
+                .get_return_address()
+                .and_then(|return_address| return_address.value);
+            let Some(program_counter) = unwind_registers.get_program_counter_mut() else {
+                break;
+            };
+            program_counter.value = return_address.and_then(|return_address| {
+                unwind_program_counter_register(return_address, frame_pc, instruction_set)
+            });
+
+            if callee_frame_registers == unwind_registers {
+                tracing::debug!("No change, preventing infinite loop");
+                break;
+            }
+            continue 'unwind;
         }
-    };
 
-    Ok(cfa)
-}
+        // PART 1: Construct the `StackFrame`s for the current program counter.
+        //
+        //         Multiple stack frames can be constructed if we are inside inlined functions.
+        tracing::trace!(
+            "UNWIND: Will generate `StackFrame` for function at address (PC) {frame_pc_register_value:#}"
+        );
+        let unwind_info = get_unwind_info(
+            &mut unwind_context,
+            &debug_info.frame_section,
+            debug_info.debug_address(frame_pc),
+        );
 
-/// Unwind the program counter for the caller frame, using the LR value from the callee frame.
-pub fn unwind_pc_without_debuginfo(
-    unwind_registers: &mut DebugRegisters,
-    _frame_pc: u64,
-    instruction_set: Option<InstructionSet>,
-) -> ControlFlow<Option<DebugError>> {
-    // For non exception frames, we cannot do stack unwinding if we do not have debug info.
-    // However, there is one case where we can continue. When the frame registers have a valid
-    // return address/LR value, we can use the LR value to calculate the PC for the calling frame.
-    // The current logic will then use that PC to get the next frame's unwind info, and if that exists,
-    // we will be able to continue unwinding.
+        // Determining the frame base may need the CFA (Canonical Frame Address) to be calculated first.
+        let cfa = unwind_info
+            .as_ref()
+            .ok()
+            .and_then(|unwind_info| determine_cfa(&unwind_registers, unwind_info).ok())
+            .flatten();
+
+        // PART 1-a: Prepare the `StackFrame`s that holds the current frame information.
+        let cached_stack_frames =
+            match debug_info.get_stackframe_info(memory, frame_pc, cfa, &unwind_registers) {
+                Ok(cached_stack_frames) => cached_stack_frames,
+                Err(e) => {
+                    tracing::error!("UNWIND: Unable to complete `StackFrame` information: {e}");
+                    // There is no point in continuing with the unwind, so let's get out of here.
+                    break;
+                }
+            };
+
+        // Add the found stackframes to the list, in reverse order. `get_stackframe_info` returns the frames in
+        // the order of the most recently called function last, but the stack frames should be
+        // in the order of the most recently called function first.
+        if !cached_stack_frames.is_empty() {
+            for frame in cached_stack_frames.into_iter().rev() {
+                if frame.is_inlined {
+                    tracing::trace!(
+                        "UNWIND: Found inlined function - name={}, pc={}",
+                        frame.function_name,
+                        frame.pc
+                    );
+                }
+                stack_frames.push(frame);
+            }
+        } else {
+            // We have no valid code for the current frame, so we
+            // construct a frame, using what information we have.
+            stack_frames.push(StackFrame {
+                id: get_object_reference(),
+                function_name: format!(
+                    "<unknown function @ {:#0width$x}>",
+                    frame_pc,
+                    width = (unwind_registers.get_address_size_bytes() * 2 + 2)
+                ),
+                linkage_name: None,
+                source_location: debug_info.get_source_location(frame_pc),
+                registers: unwind_registers.clone(),
+                pc: frame_pc_register_value,
+                frame_base: None,
+                is_inlined: false,
+                kind: FrameKind::Function,
+                local_variables: None,
+                canonical_frame_address: None,
+            });
+        };
+
+        // PART 2: Setup the registers for the next iteration (a.k.a. unwind previous frame, a.k.a. "callee", in the call stack).
+        tracing::trace!("UNWIND - Preparing to unwind the registers for the previous frame.");
+
+        // Because we will be updating the `unwind_registers` with previous frame unwind info,
+        // we need to keep a copy of the current frame's registers that can be used to resolve [DWARF](https://dwarfstd.org) expressions.
+        let callee_frame_registers = unwind_registers.clone();
+
+        // PART 2-a: get the `gimli::FrameDescriptorEntry` for the program counter
+        // and then the unwind info associated with this row.
+        let unwind_info = match unwind_info {
+            Ok(unwind_info) => {
+                tracing::trace!("UNWIND: Found unwind info for address {frame_pc:#010x}");
+                unwind_info
+            }
+            Err(err) => {
+                tracing::trace!(
+                    "UNWIND: Unable to find unwind info for address {frame_pc:#010x}: {err}"
+                );
+                if let ControlFlow::Break(error) = exception_handler.unwind_without_debuginfo(
+                    &mut unwind_registers,
+                    frame_pc,
+                    &stack_frames,
+                    instruction_set,
+                    memory,
+                ) {
+                    if let Some(error) = error {
+                        // This is not fatal, but we cannot continue unwinding beyond the current frame.
+                        tracing::error!("{:?}", &error);
+                        if let Some(first_frame) = stack_frames.first_mut() {
+                            first_frame.function_name =
+                                format!("{} : ERROR : {error}", first_frame.function_name);
+                        };
+                    }
+                    break 'unwind;
+                }
+
+                if callee_frame_registers == unwind_registers {
+                    tracing::debug!("No change, preventing infinite loop");
+                    break;
+                }
+                continue 'unwind;
+            }
+        };
+
+        // PART 2-b: Unwind registers for the "previous/calling" frame.
+        for debug_register in unwind_registers.0.iter_mut() {
+            // The program counter is handled later
+            if debug_register
+                .core_register
+                .register_has_role(RegisterRole::ProgramCounter)
+            {
+                continue;
+            }
+
+            match unwind_register(
+                debug_register,
+                &callee_frame_registers,
+                unwind_info,
+                cfa,
+                memory,
+                debug_info.endianness,
+            ) {
+                Err(error) => {
+                    tracing::error!("{:?}", &error);
+                    if let Some(first_frame) = stack_frames.last_mut() {
+                        first_frame.function_name =
+                            format!("{} : ERROR: {error}", first_frame.function_name);
+                    };
+                    break 'unwind;
+                }
+                Ok(val) => {
+                    debug_register.value = val;
+                }
+            };
+        }
+
+        // PART 3: Check if we entered the current frame from an exception handler.
+        // - If we are at an exception handler frame:
+        //   - Create a "handler" stackframe that can be inserted into the stack_frames list,
+        //     instead of "unknown function @ address";
+        //   - Overwrite the unwind registers with the exception context.
+        // - If for some reason we cannot determine the exception context, we silently continue with the rest of the unwind.
+        // At worst, the unwind will be able to unwind the stack to the frame of the most recent exception handler.
+        if unwind_registers
+            .get_return_address()
+            .is_some_and(|ra| ra.value.is_some())
+        {
+            match exception_handler.exception_details(memory, &unwind_registers, debug_info) {
+                Ok(Some(exception_info)) => {
+                    tracing::trace!(
+                        "UNWIND: Stack unwind reached an exception handler {}",
+                        exception_info.description
+                    );
+                    if options.exception_entry_frames {
+                        match exception_handler.exception_entry_registers(memory, &unwind_registers)
+                        {
+                            Ok(entry_registers) => {
+                                // The interrupted instruction can be in a different image than the handler.
+                                let entry_pc: Result<u64, _> =
+                                    exception_info.handler_frame.pc.try_into();
+                                let entry_image = entry_pc.map_or(debug_info, &image_for_address);
+                                stack_frames.push(
+                                    entry_image
+                                        .exception_entry_frame(&exception_info, entry_registers),
+                                )
+                            }
+                            Err(error) => tracing::debug!(
+                                "UNWIND: Unable to determine the exception entry registers: {error}"
+                            ),
+                        }
+                    }
+                    unwind_registers = exception_info.handler_frame.registers.clone();
+                    stack_frames.push(exception_info.handler_frame);
+                    // We have everything we need to unwind the next frame in the stack.
+                    continue 'unwind;
+                }
+                Ok(None) => {
+                    tracing::trace!(
+                        "UNWIND: No exception context found. Stack unwind will continue."
+                    );
+                }
+                Err(e) => {
+                    // TODO: Nicely print error with sources
+                    let message = format!(
+                        "UNWIND: Error while checking for exception context. The stack trace will not include the calling frames.\n{e:?}"
+                    );
+                    tracing::warn!("{message}");
+                    stack_frames.push(StackFrame {
+                        id: get_object_reference(),
+                        function_name: message,
+                        linkage_name: None,
+                        source_location: None,
+                        registers: unwind_registers.clone(),
+                        pc: frame_pc_register_value,
+                        frame_base: None,
+                        is_inlined: false,
+                        kind: FrameKind::Function,
+                        local_variables: None,
+                        canonical_frame_address: None,
+                    });
+                    break 'unwind;
+                }
+            };
+        }
+
+        let unwound_return_address = unwind_registers
+            .get_register_by_role(&RegisterRole::ReturnAddress)
+            .ok()
+            .and_then(|reg| reg.value);
+
+        let program_counter = unwind_registers.get_program_counter_mut().unwrap();
+
+        let Ok(current_pc) =
+            callee_frame_registers.get_register_value_by_role(&RegisterRole::ProgramCounter)
+        else {
+            let error = "UNWIND: Tried to unwind return address value where current program counter is unknown.";
+            tracing::error!("{error}");
+            if let Some(first_frame) = stack_frames.last_mut() {
+                first_frame.function_name =
+                    format!("{} : ERROR: {error}", first_frame.function_name);
+            };
+            break 'unwind;
+        };
+        // NOTE: PC = Value of the unwound LR, i.e. the first instruction after the one that called this function.
+        // If both the LR and PC registers have undefined rules, this will prevent the unwind from continuing.
+        program_counter.value = unwound_return_address.and_then(|return_address| {
+            unwind_program_counter_register(return_address, current_pc, instruction_set)
+        });
+
+        if callee_frame_registers == unwind_registers {
+            tracing::debug!("No change, preventing infinite loop");
+            break;
+        }
+    }
+
+    Ok(stack_frames)
+}
+
+/// Get a handle to the [`gimli::UnwindTableRow`] for this call frame, so that we can reference it to unwind register values.
+pub fn get_unwind_info<'a>(
+    unwind_context: &'a mut UnwindContext<GimliReaderOffset>,
+    frame_section: &DebugFrame<DwarfReader>,
+    frame_program_counter: u64,
+) -> Result<&'a gimli::UnwindTableRow<GimliReaderOffset>, DebugError> {
+    let transform_error = |error| {
+        DebugError::Other(format!(
+            "UNWIND: Error reading FrameDescriptorEntry: {error}"
+        ))
+        .with_context(ErrorContext::at_address(frame_program_counter))
+    };
+
+    let unwind_bases = BaseAddresses::default();
+
+    let frame_descriptor_entry = frame_section
+        .fde_for_address(
+            &unwind_bases,
+            frame_program_counter,
+            DebugFrame::cie_from_offset,
+        )
+        .map_err(transform_error)?;
+
+    frame_descriptor_entry
+        .unwind_info_for_address(
+            frame_section,
+            &unwind_bases,
+            unwind_context,
+            frame_program_counter,
+        )
+        .map_err(transform_error)
+}
+
+/// Determines the CFA (canonical frame address) for the current [`gimli::UnwindTableRow`], using the current register values.
+pub fn determine_cfa<R: gimli::ReaderOffset>(
+    unwind_registers: &DebugRegisters,
+    unwind_info: &UnwindTableRow<R>,
+) -> Result<Option<u64>, Error> {
+    let gimli::CfaRule::RegisterAndOffset { register, offset } = unwind_info.cfa() else {
+        return Err(Error::Other(format!(
+            "UNWIND: CFA rule {:?} is not supported.",
+            unwind_info.cfa()
+        )));
+    };
+
+    let reg_val = unwind_registers
+        .get_register_by_dwarf_id(register.0)
+        .and_then(|register| register.value);
+
+    let cfa = match reg_val {
+        None => {
+            tracing::error!(
+                "UNWIND: `StackFrameIterator` unable to determine the unwind CFA: Missing value of register {}",
+                register.0
+            );
+            None
+        }
+
+        Some(reg_val) if reg_val.is_zero() => {
+            // If we encounter this rule for CFA, it implies the scenario depends on a FP/frame pointer to continue successfully.
+            // Therefore, if reg_val is zero (i.e. FP is zero), then we do not have enough information to determine the CFA by rule.
+            tracing::trace!(
+                "UNWIND: Stack unwind complete - The FP register value unwound to a value of zero."
+            );
+            None
+        }
+
+        Some(reg_val) => {
+            let unwind_cfa = add_to_address(
+                reg_val.try_into()?,
+                *offset,
+                unwind_registers.get_address_size_bytes(),
+            );
+            tracing::trace!(
+                "UNWIND - CFA : {:#010x}\tRule: {:?}",
+                unwind_cfa,
+                unwind_info.cfa()
+            );
+            Some(unwind_cfa)
+        }
+    };
+
+    Ok(cfa)
+}
+
+/// Unwind the program counter for the caller frame, using the LR value from the callee frame.
+pub fn unwind_pc_without_debuginfo(
+    unwind_registers: &mut DebugRegisters,
+    _frame_pc: u64,
+    instruction_set: Option<InstructionSet>,
+) -> ControlFlow<Option<DebugError>> {
+    // For non exception frames, we cannot do stack unwinding if we do not have debug info.
+    // However, there is one case where we can continue. When the frame registers have a valid
+    // return address/LR value, we can use the LR value to calculate the PC for the calling frame.
+    // The current logic will then use that PC to get the next frame's unwind info, and if that exists,
+    // we will be able to continue unwinding.
     // If the calling frame has no debug info, then the unwinding will end with that frame.
     let callee_frame_registers = unwind_registers.clone();
     let unwound_return_address: Option<RegisterValue> = unwind_registers
@@ -1127,6 +2673,7 @@ pub fn unwind_register(
     unwind_info: &gimli::UnwindTableRow<GimliReaderOffset>,
     unwind_cfa: Option<u64>,
     memory: &mut dyn MemoryInterface,
+    endianness: RunTimeEndian,
 ) -> Result<Option<RegisterValue>, Error> {
     // If we do not have unwind info, or there is no register rule, then use UnwindRule::Undefined.
     let register_rule = debug_register
@@ -1139,6 +2686,7 @@ pub fn unwind_register(
         callee_frame_registers,
         unwind_cfa,
         memory,
+        endianness,
         register_rule,
     )
 }
@@ -1148,6 +2696,7 @@ fn unwind_register_using_rule(
     callee_frame_registers: &DebugRegisters,
     unwind_cfa: Option<u64>,
     memory: &mut dyn MemoryInterface,
+    endianness: RunTimeEndian,
     register_rule: gimli::RegisterRule<usize>,
 ) -> Result<Option<RegisterValue>, Error> {
     use gimli::read::RegisterRule;
@@ -1268,13 +2817,13 @@ fn unwind_register_using_rule(
                     let mut buff = [0u8; 4];
                     memory
                         .read(previous_frame_register_address, &mut buff)
-                        .map(|_| RegisterValue::U32(u32::from_le_bytes(buff)))
+                        .map(|_| RegisterValue::U32(u32::from_target_bytes(buff, endianness)))
                 }
                 8 => {
                     let mut buff = [0u8; 8];
                     memory
                         .read(previous_frame_register_address, &mut buff)
-                        .map(|_| RegisterValue::U64(u64::from_le_bytes(buff)))
+                        .map(|_| RegisterValue::U64(u64::from_target_bytes(buff, endianness)))
                 }
                 _ => {
                     return Err(Error::Other(format!(
@@ -1303,7 +2852,11 @@ fn unwind_register_using_rule(
             }
         }
         // TODO: Implement the remainder of these `RegisterRule`s
-        _ => unimplemented!(),
+        unsupported_rule => {
+            return Err(Error::Other(format!(
+                "UNWIND: Register rule {unsupported_rule:?} for register {debug_register} is not supported."
+            )));
+        }
     };
 
     tracing::trace!(
@@ -1427,24 +2980,41 @@ fn add_to_address(address: u64, offset: i64, address_size_in_bytes: usize) -> u6
 #[cfg(test)]
 mod test {
     use crate::{
-        DebugInfo, DebugRegister, DebugRegisters,
+        BreakpointOptions, ColumnType, DebugError, DebugInfo, DebugInfoOptions, DebugRegister,
+        DebugRegisters, ErrorContext, EvalError, FrameKind, Modifier, NameMatch, NotWritableReason,
+        SectionData, StackFrame, UnavailableReason, ValueFormat, Variable, VariableCache,
+        VariableLocation, VariableName, VariableNodeType, VariableType, VariableValue,
+        VerifiedBreakpoint,
+        condition::evaluate_condition,
         exception_handling::{
             armv6m::ArmV6MExceptionHandler, armv7m::ArmV7MExceptionHandler,
             exception_handler_for_core,
         },
+        expression_path::evaluate_path,
         stack_frame::{StackFrameInfo, TestFormatter},
+        static_variable::read_static_variable,
+        test_support::{RegistersBuilder, memory_from_elf, static_fixture},
+        unit_info::{ExpressionError, ExpressionResult},
     };
 
-    use gimli::RegisterRule;
+    use gimli::{RegisterRule, RunTimeEndian};
+    use object::{Object, ObjectSection};
     use probe_rs::{
-        CoreDump, RegisterValue,
+        CoreDump, InstructionSet, MemoryInterface, RegisterRole, RegisterValue,
         architecture::arm::core::registers::cortex_m::{self, CORTEX_M_CORE_REGISTERS},
         test::MockMemory,
     };
-    use std::path::{Path, PathBuf};
+    use std::{
+        ops::Range,
+        path::{Path, PathBuf},
+    };
     use test_case::test_case;
+    use typed_path::TypedPath;
 
-    use super::unwind_register_using_rule;
+    use super::{
+        GimliReader, UnwindOptions, determine_cfa, get_unwind_info, is_trampoline_symbol,
+        section_name, unwind_register, unwind_register_using_rule,
+    };
 
     /// Get the full path to a file in the `tests` directory.
     fn get_path_for_test_files(relative_file: &str) -> PathBuf {
@@ -1463,6 +3033,29 @@ mod test {
         })
     }
 
+    /// Evaluate a DWARF expression in the first unit of the debug information.
+    fn evaluate_test_expression(
+        debug_info: &DebugInfo,
+        expression: Vec<u8>,
+        memory: &mut MockMemory,
+        registers: &DebugRegisters,
+    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, ExpressionError> {
+        debug_info.unit_infos[0].expression_to_piece(
+            debug_info,
+            memory,
+            gimli::Expression(gimli::EndianReader::new(
+                SectionData::from(expression),
+                RunTimeEndian::Little,
+            )),
+            StackFrameInfo {
+                registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                auto_deref_depth: None,
+            },
+        )
+    }
+
     #[test]
     fn unwinding_first_instruction_after_exception() {
         let debug_info = load_test_elf_as_debug_info("exceptions");
@@ -1557,6 +3150,7 @@ mod test {
                 exception_handler.as_ref(),
                 Some(probe_rs_target::InstructionSet::Thumb2),
                 500,
+                UnwindOptions::default(),
             )
             .unwrap();
 
@@ -1710,6 +3304,7 @@ mod test {
                 exception_handler.as_ref(),
                 Some(probe_rs_target::InstructionSet::Thumb2),
                 500,
+                UnwindOptions::default(),
             )
             .unwrap();
 
@@ -1744,6 +3339,70 @@ mod test {
         insta::assert_snapshot!(printed_backtrace);
     }
 
+    #[test]
+    fn unwinding_with_exception_entry_frame() {
+        let debug_info = load_test_elf_as_debug_info("exceptions");
+
+        // Same core state as `unwinding_in_exception_handler`.
+        let values: Vec<_> = [
+            0x00000001, 0x2001ff9f, 0x20000047, 0x20000047, 0x00000000, 0x00000000, 0x00000000,
+            0x2001ffc0, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x2001ffc0,
+            0x0000042f, 0x000001a4, 0x2001ffc0, 0x00000000, 0x2100000b,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(id, r)| DebugRegister {
+            dwarf_id: Some(id as u16),
+            core_register: CORTEX_M_CORE_REGISTERS.core_register(id),
+            value: Some(RegisterValue::U32(r)),
+        })
+        .collect();
+
+        let mut dummy_mem = MockMemory::new();
+        dummy_mem.add_word_range(
+            0x2001_ffc0,
+            &[
+                0x2001ffc8, 0x0000018b, 0x2001fff0, 0xfffffff9, 0x00000001, 0x2001ffcf, 0x20000044,
+                0x20000044, 0x00000000, 0x0000017f, 0x00000180, 0x21000000, 0x2001fff8, 0x00000161,
+                0x00000000, 0x0000013d,
+            ],
+        );
+
+        let frames = debug_info
+            .unwind_impl(
+                DebugRegisters(values),
+                &mut dummy_mem,
+                &ArmV6MExceptionHandler,
+                Some(probe_rs_target::InstructionSet::Thumb2),
+                500,
+                UnwindOptions {
+                    exception_entry_frames: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let entry_frames: Vec<_> = frames
+            .iter()
+            .filter(|frame| frame.kind == FrameKind::ExceptionEntry)
+            .collect();
+        assert_eq!(entry_frames.len(), 1);
+
+        let entry_frame = entry_frames[0];
+        assert!(entry_frame.function_name.starts_with("<exception entry: "));
+
+        // The stacked registers are available, the others are not.
+        let register_value = |id| {
+            entry_frame
+                .registers
+                .get_register(probe_rs::RegisterId(id))
+                .and_then(|r| r.value)
+        };
+        assert_eq!(register_value(0), Some(RegisterValue::U32(0x00000001)));
+        assert_eq!(register_value(15), Some(RegisterValue::U32(0x00000180)));
+        assert_eq!(register_value(4), None);
+    }
+
     #[test]
     fn unwinding_in_exception_trampoline() {
         let debug_info = load_test_elf_as_debug_info("exceptions");
@@ -1837,6 +3496,7 @@ mod test {
                 exception_handler.as_ref(),
                 Some(probe_rs_target::InstructionSet::Thumb2),
                 500,
+                UnwindOptions::default(),
             )
             .unwrap();
 
@@ -1930,6 +3590,7 @@ mod test {
                 exception_handler.as_ref(),
                 Some(probe_rs_target::InstructionSet::Thumb2),
                 500,
+                UnwindOptions::default(),
             )
             .unwrap();
 
@@ -2026,6 +3687,7 @@ mod test {
                         registers: &frame.registers,
                         frame_base: frame.frame_base,
                         canonical_frame_address: frame.canonical_frame_address,
+                        auto_deref_depth: None,
                     },
                 );
             }
@@ -2063,6 +3725,7 @@ mod test {
                 registers: &initial_registers,
                 frame_base: None,
                 canonical_frame_address: None,
+                auto_deref_depth: None,
             },
         );
         // Using YAML output because it is easier to read than the default snapshot output,
@@ -2070,123 +3733,3510 @@ mod test {
         insta::assert_yaml_snapshot!(snapshot_name, static_variables);
     }
 
-    fn coredump_path(base: String) -> PathBuf {
-        let possible_coredump_paths = [
-            get_path_for_test_files(format!("{base}.coredump").as_str()),
-            get_path_for_test_files(format!("{base}_coredump.elf").as_str()),
-        ];
+    #[test]
+    fn read_static_variables_by_name() {
+        let debug_info =
+            load_test_elf_as_debug_info("debug-unwind-tests/nRF52833_xxAA_full_unwind.elf");
+        let coredump_path = coredump_path("debug-unwind-tests/nRF52833_xxAA_full_unwind".into());
+        let mut adapter = CoreDump::load(&coredump_path).unwrap();
 
-        possible_coredump_paths
-            .iter()
-            .find(|path| path.exists())
-            .unwrap_or_else(|| {
-                panic!(
-                    "No coredump found for chip {base}. Expected one of: {possible_coredump_paths:?}"
-                )
-            })
-            .clone()
+        // The outer namespaces can be left out.
+        for name in ["nrf52833_pac::DEVICE_PERIPHERALS", "DEVICE_PERIPHERALS"] {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut adapter, name, &mut cache).unwrap();
+
+            assert_eq!(
+                variable.name,
+                VariableName::Named("DEVICE_PERIPHERALS".to_string())
+            );
+            assert_eq!(variable.to_string(&cache), "true");
+        }
+
+        // The children are read with the variable.
+        let mut cache = debug_info.create_static_scope_cache();
+        let exceptions = read_static_variable(
+            &debug_info,
+            &mut adapter,
+            "cortex_m_rt::__EXCEPTIONS",
+            &mut cache,
+        )
+        .unwrap();
+        assert_eq!(cache.get_children(exceptions.variable_key()).count(), 14);
+
+        let error = read_static_variable(&debug_info, &mut adapter, "NOT_A_STATIC", &mut cache)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The static variable NOT_A_STATIC was not found."
+        );
     }
 
     #[test]
-    fn unwind_same_value() {
-        let rule = gimli::RegisterRule::SameValue;
+    fn static_scope_without_duplicates() {
+        let debug_info = load_test_elf_as_debug_info("duplicate-statics/main.elf");
+        let registers = DebugRegisters::default();
+        let mut memory = MockMemory::new();
 
-        let mut callee_frame_registers = DebugRegisters::default();
-        let debug_register = CORTEX_M_CORE_REGISTERS.core_registers().next().unwrap();
+        let mut cache = debug_info.create_static_scope_cache();
+        let mut root = cache.root_variable().clone();
+        debug_info
+            .cache_deferred_variables(
+                &mut cache,
+                &mut memory,
+                &mut root,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
 
-        let expected_value = Some(RegisterValue::U32(0x1234));
+        let locations = |name: &str| {
+            cache
+                .get_children(root.variable_key())
+                .filter(|variable| variable.name == VariableName::Named(name.to_string()))
+                .map(|variable| variable.memory_location.clone())
+                .collect::<Vec<_>>()
+        };
+
+        // Both units define `shared` at the same address.
+        assert_eq!(locations("shared"), [VariableLocation::Address(0x402004)]);
+        // Each unit has its own `local`.
+        assert_eq!(
+            locations("local"),
+            [
+                VariableLocation::Address(0x402000),
+                VariableLocation::Address(0x402008)
+            ]
+        );
+
+        let static_variables = debug_info.static_variables().unwrap();
+        let names: Vec<_> = static_variables
+            .iter()
+            .map(|variable| variable.name.as_str())
+            .collect();
+        assert_eq!(names, ["shared", "local", "local"]);
+    }
+
+    #[test]
+    fn variable_cache_limit() {
+        let mut debug_info = load_test_elf_as_debug_info("duplicate-statics/main.elf");
+        debug_info.max_cached_variables = Some(2);
+        let registers = DebugRegisters::default();
+        let mut memory = MockMemory::new();
+        let frame_info = StackFrameInfo {
+            registers: &registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            auto_deref_depth: None,
+        };
+
+        let mut cache = debug_info.create_static_scope_cache();
+        assert_eq!(cache.max_len(), Some(2));
+        let mut root = cache.root_variable().clone();
+        let error = debug_info
+            .cache_deferred_variables(&mut cache, &mut memory, &mut root, frame_info)
+            .unwrap_err();
+        assert!(matches!(error, DebugError::VariableCacheFull { limit: 2 }));
+        // The children which were added before the cache was full are removed again.
+        assert_eq!(cache.len(), 1);
+        let empty_size = cache.approximate_bytes();
+
+        cache.set_max_len(None);
+        debug_info
+            .cache_deferred_variables(&mut cache, &mut memory, &mut root, frame_info)
+            .unwrap();
+        assert!(cache.len() > 2);
+        assert!(cache.approximate_bytes() > empty_size);
+    }
+
+    /// Format the variable at `path` below `variable`, where `path` is a `.` separated list of child names.
+    fn variable_at_path(cache: &VariableCache, variable: &Variable, path: &str) -> Variable {
+        let mut variable = variable.clone();
+        for name in path.split('.').filter(|name| !name.is_empty()) {
+            variable = cache
+                .get_children(variable.variable_key())
+                .find(|child| child.name.to_string() == name)
+                .unwrap_or_else(|| panic!("{} has no child {name}", variable.name))
+                .clone();
+        }
+        variable
+    }
+
+    fn value_at_path(cache: &VariableCache, variable: &Variable, path: &str) -> String {
+        variable_at_path(cache, variable, path).to_string(cache)
+    }
+
+    /// Unwind the stack of a corpus build, halted in `inner` for the Rust builds, and in
+    /// `accumulate` for the C builds.
+    fn corpus_frames(elf_file: &str) -> (DebugInfo, MockMemory, Vec<StackFrame>) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+        let mut memory = memory_from_elf(&get_path_for_test_files(elf_file));
+
+        let registers = match elf_file {
+            "corpus/debug-dwarf4.elf" | "corpus/debug-dwarf5.elf" => {
+                // The stack after `Reset` called `outer(1)`, which called `inner(2)`. Each frame
+                // has the locals, followed by the saved R7 and LR.
+                memory.add_word_range(
+                    0x2000_3fa0,
+                    &[
+                        0x00000000, 0x00000002, 0x00000002, 0x20000000, 0x00000000, 0x00000000,
+                        0x20003fd0, 0x000001af,
+                    ],
+                );
+                memory.add_word_range(
+                    0x2000_3fc0,
+                    &[
+                        0x00000000, 0x00000002, 0x00000001, 0x00000002, 0x20003ff8, 0x00000029,
+                    ],
+                );
+                // `Reset` was entered with the reset value of LR.
+                memory.add_word_range(
+                    0x2000_3fd8,
+                    &[
+                        0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x00000000, 0x000002d4,
+                        0x00000000, 0x00000000, 0x00000000, 0xffffffff,
+                    ],
+                );
+
+                // Halted in `inner`, after the prologue.
+                RegistersBuilder::cortex_m()
+                    .with_register(0, 0x2000_0000)
+                    .with_role(RegisterRole::FramePointer, 0x2000_3fb8)
+                    .with_role(RegisterRole::ProcessorStatus, 0x0100_0000)
+                    .with_role(RegisterRole::StackPointer, 0x2000_3fa0)
+                    .with_role(RegisterRole::ReturnAddress, 0x0000_01af)
+                    .with_role(RegisterRole::ProgramCounter, 0x0000_0138)
+                    .build()
+            }
+            "corpus/release.elf" => {
+                // The same calls, in the first iteration of the loop in `Reset`. `outer` and
+                // `inner` only save R7 and LR, and `Reset` has the pointers to `LIMITS` and
+                // `STOPPED` and the argument of `outer` below them.
+                memory.add_word_range(
+                    0x2000_3fd8,
+                    &[
+                        0x20003fe0, 0x00000081, 0x20003ff8, 0x00000043, 0x00000000, 0x00000084,
+                        0x00000001, 0x00000000, 0x00000000, 0xffffffff,
+                    ],
+                );
+
+                // Halted in `inner`, after the prologue.
+                RegistersBuilder::cortex_m()
+                    .with_register(0, 0x0000_0002)
+                    .with_register(1, 0x2000_0000)
+                    .with_role(RegisterRole::FramePointer, 0x2000_3fd8)
+                    .with_role(RegisterRole::ProcessorStatus, 0x0100_0000)
+                    .with_role(RegisterRole::StackPointer, 0x2000_3fd8)
+                    .with_role(RegisterRole::ReturnAddress, 0x0000_0081)
+                    .with_role(RegisterRole::ProgramCounter, 0x0000_006e)
+                    .build()
+            }
+            "corpus/c-dwarf4.elf" | "corpus/c-dwarf5.elf" | "corpus/c-big-endian.elf" => {
+                // The stack after `Reset` called `accumulate(1)`, and `scale(&origin, 20)`
+                // returned to it. Each frame has the locals, followed by the saved R7 and LR.
+                let stack = [
+                    0xffffffcb, 0x00000001, 0x20003ff8, 0x0000007b, 0x00000000, 0x00000001,
+                    0x00000000, 0xffffffff,
+                ];
+                let stack_bytes = stack
+                    .iter()
+                    .flat_map(|word: &u32| match debug_info.endianness() {
+                        RunTimeEndian::Little => word.to_le_bytes(),
+                        RunTimeEndian::Big => word.to_be_bytes(),
+                    })
+                    .collect();
+                memory.add_range(0x2000_3fe0, stack_bytes);
+
+                // Halted in `accumulate`, after `value` was stored.
+                RegistersBuilder::cortex_m()
+                    .with_register(0, 0xffff_ffcb)
+                    .with_role(RegisterRole::FramePointer, 0x2000_3fe8)
+                    .with_role(RegisterRole::ProcessorStatus, 0x0100_0000)
+                    .with_role(RegisterRole::StackPointer, 0x2000_3fe0)
+                    .with_role(RegisterRole::ReturnAddress, 0x0000_0049)
+                    .with_role(RegisterRole::ProgramCounter, 0x0000_004a)
+                    .build()
+            }
+            _ => panic!("{elf_file} is not a corpus build"),
+        };
+
+        let frames = debug_info
+            .unwind(
+                &mut memory,
+                registers,
+                &ArmV7MExceptionHandler,
+                Some(InstructionSet::Thumb2),
+                10,
+            )
+            .unwrap();
+
+        (debug_info, memory, frames)
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf", 0x138, &["inner", "outer", "Reset", "Reset"]; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf", 0x138, &["inner", "outer", "Reset", "Reset"]; "debug DWARF 5")]
+    #[test_case("corpus/release.elf", 0x6e, &["inner", "outer", "Reset", "Reset"]; "release")]
+    #[test_case("corpus/c-dwarf4.elf", 0x4a, &["accumulate", "Reset", "Reset"]; "C DWARF 4")]
+    #[test_case("corpus/c-dwarf5.elf", 0x4a, &["accumulate", "Reset", "Reset"]; "C DWARF 5")]
+    #[test_case("corpus/c-big-endian.elf", 0x4a, &["accumulate", "Reset", "Reset"]; "C big endian")]
+    /// The unwind ends with the reset exception, which is identified by the reset value of LR.
+    fn corpus_unwind(elf_file: &str, pc: u32, expected_function_names: &[&str]) {
+        let (_, _, frames) = corpus_frames(elf_file);
+
+        assert_eq!(frames[0].pc, RegisterValue::U32(pc));
+        let function_names: Vec<_> = frames
+            .iter()
+            .map(|frame| frame.function_name.as_str())
+            .collect();
+        assert_eq!(function_names, expected_function_names);
+    }
+
+    /// The variables of the unwound frames, and the static variables.
+    #[test_case("corpus/debug-dwarf4.elf", "corpus_debug_dwarf4"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf", "corpus_debug_dwarf5"; "debug DWARF 5")]
+    #[test_case("corpus/release.elf", "corpus_release"; "release")]
+    #[test_case("corpus/c-dwarf4.elf", "corpus_c_dwarf4"; "C DWARF 4")]
+    #[test_case("corpus/c-dwarf5.elf", "corpus_c_dwarf5"; "C DWARF 5")]
+    #[test_case("corpus/c-big-endian.elf", "corpus_c_big_endian"; "C big endian")]
+    fn corpus_variable_trees(elf_file: &str, snapshot_name: &str) {
+        let (debug_info, mut memory, mut frames) = corpus_frames(elf_file);
+
+        for frame in frames.iter_mut() {
+            if let Some(local_variables) = &mut frame.local_variables {
+                local_variables.recurse_deferred_variables(
+                    &debug_info,
+                    &mut memory,
+                    10,
+                    StackFrameInfo {
+                        registers: &frame.registers,
+                        frame_base: frame.frame_base,
+                        canonical_frame_address: frame.canonical_frame_address,
+                        auto_deref_depth: None,
+                    },
+                );
+            }
+        }
+        insta::assert_yaml_snapshot!(format!("{snapshot_name}_frames"), frames);
+
+        let mut static_variables = debug_info.create_static_scope_cache();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            10,
+            StackFrameInfo {
+                registers: &frames[0].registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                auto_deref_depth: None,
+            },
+        );
+        insta::assert_yaml_snapshot!(
+            format!("{snapshot_name}_static_variables"),
+            static_variables
+        );
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn evaluate_path_in_frame(elf_file: &str) {
+        let (debug_info, mut memory, mut frames) = corpus_frames(elf_file);
+        let mut frame = frames.remove(0);
+        let mut cache = frame.local_variables.take().unwrap();
+
+        let mut evaluate = |cache: &mut VariableCache, expr: &str| {
+            evaluate_path(&debug_info, &mut memory, cache, &frame, expr)
+        };
+
+        let expected = [
+            ("value", "2"),
+            ("state.count", "3"),
+            ("state->history[2]", "3"),
+            ("state.mode.Running.0", "42"),
+            ("state.name", "corpus"),
+        ];
+        for (expr, value) in expected {
+            let variable =
+                evaluate(&mut cache, expr).unwrap_or_else(|error| panic!("{expr}: {error}"));
+            assert_eq!(variable.to_string(&cache), value, "{expr}");
+        }
+
+        let state = evaluate(&mut cache, "*state").unwrap();
+        assert_eq!(state.type_name, VariableType::Struct("State".to_string()));
+        assert_eq!(
+            state.memory_location,
+            VariableLocation::Address(0x2000_0000)
+        );
+
+        let count = evaluate(&mut cache, "&state.count").unwrap();
+        assert_eq!(count.value(), &VariableValue::U64(0x2000_0010));
+
+        // The expression of a variable evaluates to the same variable.
+        for (expr, expression_path) in [
+            ("state.count", "(*state).count"),
+            ("state->history[2]", "(*state).history[2]"),
+        ] {
+            let variable = evaluate(&mut cache, expr).unwrap();
+            assert_eq!(
+                cache.expression_path(variable.variable_key()).unwrap(),
+                expression_path
+            );
+            let evaluated = evaluate(&mut cache, expression_path).unwrap();
+            assert_eq!(evaluated.variable_key(), variable.variable_key());
+        }
+
+        assert!(matches!(
+            evaluate(&mut cache, "state.cnt"),
+            Err(DebugError::PathSegmentNotFound { segment, candidates })
+                if segment == "cnt" && candidates.contains(&"count".to_string())
+        ));
+        assert!(matches!(
+            evaluate(&mut cache, "state.history[4]"),
+            Err(DebugError::PathSegmentNotFound { segment, candidates })
+                if segment == "[4]" && candidates == ["[0..4]"]
+        ));
+        assert!(matches!(
+            evaluate(&mut cache, "*value"),
+            Err(DebugError::InvalidPath { .. })
+        ));
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn evaluate_condition_in_frame(elf_file: &str) {
+        let (debug_info, mut memory, mut frames) = corpus_frames(elf_file);
+        let mut frame = frames.remove(0);
+        let mut cache = frame.local_variables.take().unwrap();
+
+        let mut evaluate = |condition: &str| {
+            evaluate_condition(&debug_info, &mut memory, &mut cache, &frame, condition)
+        };
+
+        let expected = [
+            ("value == 2 && state.count > 2", true),
+            ("!(value < 1.5) || state->history[2] != 3", true),
+            ("state.mode.Running.0 >= 0x2a && value", true),
+            ("state == 0x2000_0000", true),
+            ("(*state).count == 3 && state.count < value", false),
+            // The variables of `state.cnt` are not read, so the unknown field is not an error.
+            ("value == 3 && state.cnt == 1", false),
+            ("value != 3 || state.cnt == 1", true),
+        ];
+        for (condition, result) in expected {
+            let actual = evaluate(condition).unwrap_or_else(|error| panic!("{condition}: {error}"));
+            assert_eq!(actual, result, "{condition}");
+        }
+
+        assert!(matches!(
+            evaluate("*state == 1"),
+            Err(EvalError::NotScalar { path, .. }) if path == "*state"
+        ));
+        assert!(matches!(
+            evaluate("state.name == 1"),
+            Err(EvalError::NotScalar { path, .. }) if path == "state.name"
+        ));
+        assert!(matches!(
+            evaluate("value == 2 && state.count < true"),
+            Err(EvalError::TypeMismatch { operator: "<", .. })
+        ));
+        assert!(matches!(
+            evaluate("state.cnt == 1"),
+            Err(EvalError::Variable(DebugError::PathSegmentNotFound { .. }))
+        ));
+        assert!(matches!(
+            evaluate("value == 2 &&"),
+            Err(EvalError::Syntax { offset: 13, .. })
+        ));
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn deferred_array_members(elf_file: &str) {
+        let (mut debug_info, mut memory, mut frames) = corpus_frames(elf_file);
+        debug_info.max_eager_array_length = 3;
+        let frame = frames.remove(0);
+        let mut cache = frame.local_variables.clone().unwrap();
+
+        // `history` has 4 members, so they are only created when they are requested.
+        let history = evaluate_path(
+            &debug_info,
+            &mut memory,
+            &mut cache,
+            &frame,
+            "state.history",
+        )
+        .unwrap();
+        assert!(matches!(
+            history.variable_node_type,
+            VariableNodeType::ArrayMembers(..)
+        ));
+        assert!(!cache.has_children(&history));
+        assert_eq!(cache.child_count(history.variable_key()), 4);
+        assert_eq!(history.byte_size, Some(4));
+        assert_eq!(
+            history.type_name,
+            VariableType::Array {
+                count: 4,
+                item_type_name: Box::new(VariableType::Base("u8".to_string())),
+            }
+        );
+
+        let last = evaluate_path(
+            &debug_info,
+            &mut memory,
+            &mut cache,
+            &frame,
+            "state.history[3]",
+        )
+        .unwrap();
+        assert_eq!(last.value(), &VariableValue::U64(4));
+        assert_eq!(last.name, VariableName::Indexed(3));
+        assert_eq!(last.name.to_string(), "[3]");
+        assert!(last.is_indexed());
+        assert_eq!(last.memory_location, VariableLocation::Address(0x2000_0017));
+
+        // Only the requested members are resolved, and the others are added when they are
+        // requested.
+        let history_key = history.variable_key();
+        let member_values = |cache: &VariableCache| -> Vec<_> {
+            cache
+                .get_children_paged(history_key, 0, usize::MAX)
+                .map(|member| (member.name.to_string(), member.to_string(cache)))
+                .collect()
+        };
+        assert_eq!(
+            member_values(&cache),
+            [("[3]".to_string(), "4".to_string())]
+        );
+        assert_eq!(cache.child_count(history.variable_key()), 4);
+
+        let frame_info = StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            auto_deref_depth: None,
+        };
+        let mut history = history;
+        debug_info
+            .cache_deferred_variables_paged(
+                &mut cache,
+                &mut memory,
+                &mut history,
+                1,
+                10,
+                frame_info,
+            )
+            .unwrap();
+        assert_eq!(
+            member_values(&cache),
+            [("[1]", "2"), ("[2]", "3"), ("[3]", "4")]
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+
+        debug_info
+            .cache_deferred_variables(&mut cache, &mut memory, &mut history, frame_info)
+            .unwrap();
+        assert_eq!(cache.get_children(history_key).count(), 4);
+        assert_eq!(
+            member_values(&cache),
+            [("[0]", "1"), ("[1]", "2"), ("[2]", "3"), ("[3]", "4")]
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn auto_deref_depth(elf_file: &str) {
+        let (debug_info, mut memory, mut frames) = corpus_frames(elf_file);
+        let frame = frames.remove(0);
+        let frame_info = |auto_deref_depth| StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            auto_deref_depth,
+        };
+        let mut resolve = |cache: &mut VariableCache, variable: Option<&Variable>, depth| {
+            let mut variable = variable.unwrap_or(cache.root_variable()).clone();
+            debug_info
+                .cache_deferred_variables(cache, &mut memory, &mut variable, frame_info(depth))
+                .unwrap();
+        };
+
+        // By default, the pointer is followed, but the struct it points to is deferred.
+        let mut cache = frame.local_variables.clone().unwrap();
+        resolve(&mut cache, None, None);
+        let pointee = variable_at_path(&cache, cache.root_variable(), "state.*state");
+        assert!(pointee.variable_node_type.is_deferred());
+        assert!(!cache.has_children(&pointee));
+
+        // The pointer is only followed on request.
+        let mut cache = frame.local_variables.clone().unwrap();
+        resolve(&mut cache, None, Some(0));
+        let state = variable_at_path(&cache, cache.root_variable(), "state");
+        assert!(state.variable_node_type.is_deferred());
+        assert!(!cache.has_children(&state));
+        resolve(&mut cache, Some(&state), Some(0));
+        assert_eq!(value_at_path(&cache, &state, "*state.count"), "3");
+
+        // The pointer and the struct it points to are resolved immediately.
+        let mut cache = frame.local_variables.clone().unwrap();
+        resolve(&mut cache, None, Some(1));
+        let root = cache.root_variable().clone();
+        assert_eq!(value_at_path(&cache, &root, "state.*state.count"), "3");
+        assert_eq!(
+            value_at_path(&cache, &root, "state.*state.history.[2]"),
+            "3"
+        );
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    #[test_case("corpus/release.elf"; "release")]
+    fn corpus_static_variables(elf_file: &str) {
+        let (debug_info, mut memory, _) = static_fixture(elf_file);
+
+        let expected = [
+            ("LIMITS", "[0]", "-1"),
+            ("LIMITS", "[1]", "0"),
+            ("LIMITS", "[2]", "1"),
+            ("STOPPED", "Stopped.code", "-7"),
+            // `.bss` is zeroed.
+            ("TICKS", "", "0"),
+        ];
+        for (name, path, value) in expected {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            assert_eq!(
+                value_at_path(&cache, &variable, path),
+                value,
+                "{name}.{path}"
+            );
+        }
+    }
+
+    /// Only for the debug builds, because the release build keeps just the `count` field of `STATE`.
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn corpus_variable_tree(elf_file: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture(elf_file);
+
+        let state = read_static_variable(&debug_info, &mut memory, "STATE", &mut cache).unwrap();
+
+        let expected = [
+            ("count", "3"),
+            ("enabled", "true"),
+            ("mode.Running.__0", "42"),
+            ("history.[0]", "1"),
+            ("history.[3]", "4"),
+            ("name", "corpus"),
+        ];
+        for (path, value) in expected {
+            assert_eq!(value_at_path(&cache, &state, path), value, "STATE.{path}");
+        }
+    }
+
+    #[test_case("corpus/debug-dwarf4.elf"; "debug DWARF 4")]
+    #[test_case("corpus/debug-dwarf5.elf"; "debug DWARF 5")]
+    fn corpus_typed_values(elf_file: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture(elf_file);
+
+        let state = read_static_variable(&debug_info, &mut memory, "STATE", &mut cache).unwrap();
+
+        let expected = [
+            ("count", VariableValue::U64(3)),
+            ("enabled", VariableValue::Bool(true)),
+            ("history.[0]", VariableValue::U64(1)),
+            ("name", VariableValue::String("corpus".to_string())),
+        ];
+        for (path, value) in expected {
+            assert_eq!(
+                variable_at_path(&cache, &state, path).value(),
+                &value,
+                "STATE.{path}"
+            );
+        }
+
+        let mut cache = debug_info.create_static_scope_cache();
+        let stopped =
+            read_static_variable(&debug_info, &mut memory, "STOPPED", &mut cache).unwrap();
+        let code = variable_at_path(&cache, &stopped, "Stopped.code");
+        assert_eq!(code.value(), &VariableValue::I64(-7));
+        assert_eq!(code.value().as_i64(), Some(-7));
+        assert_eq!(code.value().as_u64(), None);
+    }
+
+    #[test]
+    fn base_type_encodings() {
+        let (debug_info, mut memory, _) = static_fixture("char-encodings/main.elf");
+
+        let expected = [
+            ("flag", "true", Some(1)),
+            ("letter", "'A'", Some(65)),
+            ("newline", "'\\n'", Some(10)),
+            ("high_byte", "'\\xff'", None),
+            ("emoji", "'😀'", Some(0x1F600)),
+            ("surrogate", "<invalid char 0xd800>", None),
+        ];
+        for (name, value, numeric) in expected {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            assert_eq!(variable.to_string(&cache), value, "{name}");
+            assert_eq!(variable.value().as_numeric(), numeric, "{name}");
+        }
+    }
+
+    #[test_case("half_values", &["1.5", "-0.00000005960464477539063", "inf", "65504.0"]; "half")]
+    #[test_case("float_values", &["0.10000000149011612", "-2.5", "NaN", "-inf"]; "float")]
+    #[test_case("double_values", &["0.1", "0.3333333333333333", "NaN", "inf"]; "double")]
+    fn float_values(name: &str, expected: &[&str]) {
+        let (debug_info, mut memory, mut cache) = static_fixture("float-encodings/main.elf");
+
+        let values = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+
+        for (index, value) in expected.iter().enumerate() {
+            assert_eq!(
+                value_at_path(&cache, &values, &format!("[{index}]")),
+                *value
+            );
+        }
+    }
+
+    #[test]
+    fn float_members_and_registers() {
+        let (debug_info, mut memory, mut cache) = static_fixture("float-encodings/main.elf");
+
+        let point = read_static_variable(&debug_info, &mut memory, "point", &mut cache).unwrap();
+        assert_eq!(value_at_path(&cache, &point, "x"), "1.25");
+        assert_eq!(value_at_path(&cache, &point, "y"), "-3.75");
+
+        // A float in an FPU register is read from the bits of the register.
+        let mut x = variable_at_path(&cache, &point, "x");
+        x.memory_location = VariableLocation::RegisterValue(RegisterValue::U32(0.5_f32.to_bits()));
+        x.set_value(VariableValue::Empty);
+        x.extract_value(&mut memory, &cache);
+        assert_eq!(x.value(), &VariableValue::F64(0.5));
+    }
+
+    #[test_case("I8_VALUES", "-128"; "i8")]
+    #[test_case("I16_VALUES", "-32768"; "i16")]
+    #[test_case("I32_VALUES", "-2147483648"; "i32")]
+    #[test_case("I64_VALUES", "-9223372036854775808"; "i64")]
+    #[test_case("I128_VALUES", "-170141183460469231731687303715884105728"; "i128")]
+    #[test_case("ISIZE_VALUES", "-2147483648"; "isize")]
+    fn signed_integer_boundaries(name: &str, min: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("integer-encodings/main.elf");
+
+        let values = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+
+        assert_eq!(value_at_path(&cache, &values, "[0]"), min);
+        assert_eq!(value_at_path(&cache, &values, "[1]"), "-1");
+    }
+
+    #[test]
+    fn integers_of_128_bits() {
+        let (debug_info, mut memory, mut cache) = static_fixture("integer-encodings/main.elf");
+
+        let signed =
+            read_static_variable(&debug_info, &mut memory, "I128_VALUES", &mut cache).unwrap();
+        let unsigned =
+            read_static_variable(&debug_info, &mut memory, "U128_VALUES", &mut cache).unwrap();
+
+        let min = variable_at_path(&cache, &signed, "[0]");
+        assert_eq!(min.value(), &VariableValue::I128(i128::MIN));
+        assert_eq!(min.value().as_i64(), None);
+        let max = variable_at_path(&cache, &unsigned, "[0]");
+        assert_eq!(max.value(), &VariableValue::U128(u128::MAX));
+        assert_eq!(
+            max.to_string(&cache),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(max.value().as_numeric(), None);
+        assert_eq!(
+            variable_at_path(&cache, &unsigned, "[1]").value().as_u128(),
+            Some(1 << 64)
+        );
+
+        // Values which do not fit in 64 bits are written back in full, in either base.
+        let written = cache
+            .update_variable_value(
+                min.variable_key(),
+                "0x7fffffffffffffffffffffffffffffff",
+                &mut memory,
+            )
+            .unwrap();
+        assert_eq!(written.value(), &VariableValue::I128(i128::MAX));
+        let written = cache
+            .update_variable_value(max.variable_key(), "18446744073709551616", &mut memory)
+            .unwrap();
+        assert_eq!(written.value(), &VariableValue::U128(1 << 64));
+    }
+
+    #[test]
+    fn signed_struct_members() {
+        let (debug_info, mut memory, mut cache) = static_fixture("integer-encodings/main.elf");
+
+        let members =
+            read_static_variable(&debug_info, &mut memory, "MEMBERS", &mut cache).unwrap();
+
+        assert_eq!(value_at_path(&cache, &members, "small"), "-2");
+        assert_eq!(value_at_path(&cache, &members, "large"), "-3");
+    }
+
+    #[test_case(ValueFormat::Auto, "-1", "-2"; "auto")]
+    #[test_case(ValueFormat::Dec, "-1", "-2"; "decimal")]
+    #[test_case(ValueFormat::Hex, "0xffff", "0xfe"; "hexadecimal")]
+    #[test_case(ValueFormat::Bin, "0b1111111111111111", "0b11111110"; "binary")]
+    fn value_formats(format: ValueFormat, minus_one: &str, small: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("integer-encodings/main.elf");
+
+        let values =
+            read_static_variable(&debug_info, &mut memory, "I16_VALUES", &mut cache).unwrap();
+        let members =
+            read_static_variable(&debug_info, &mut memory, "MEMBERS", &mut cache).unwrap();
+
+        cache.set_value_format(format);
+        assert_eq!(value_at_path(&cache, &values, "[1]"), minus_one);
+        assert_eq!(value_at_path(&cache, &members, "small"), small);
+    }
+
+    #[test]
+    fn value_format_of_a_variable() {
+        let (debug_info, mut memory, mut cache) = static_fixture("integer-encodings/main.elf");
+
+        let members =
+            read_static_variable(&debug_info, &mut memory, "MEMBERS", &mut cache).unwrap();
+        cache.set_value_format(ValueFormat::Hex);
+
+        let mut small = variable_at_path(&cache, &members, "small");
+        small.set_value_format(Some(ValueFormat::Dec));
+        cache.update_variable(&small).unwrap();
+        assert_eq!(value_at_path(&cache, &members, "small"), "-2");
+        assert_eq!(
+            value_at_path(&cache, &members, "large"),
+            "0xfffffffffffffffd"
+        );
+
+        // Values are written in whichever format they are in, regardless of the display format.
+        for new_value in ["0xfd", "0b11111101", "-3"] {
+            let written = cache
+                .update_variable_value(small.variable_key(), new_value, &mut memory)
+                .unwrap();
+            assert_eq!(written.value(), &VariableValue::I64(-3));
+            assert_eq!(written.to_string(&cache), "-3");
+        }
+
+        let mut written = cache
+            .update_variable_value(small.variable_key(), "'A'", &mut memory)
+            .unwrap();
+        assert_eq!(written.value(), &VariableValue::I64(65));
+        written.set_value_format(Some(ValueFormat::Char));
+        cache.update_variable(&written).unwrap();
+        assert_eq!(value_at_path(&cache, &members, "small"), "'A'");
+    }
+
+    #[test_case(
+        "PACKET",
+        "68 65 6c 6c 6f 00 ff 77 6f 72 6c 64 00 00 00 00 ... |hello..world.......|";
+        "truncated array"
+    )]
+    #[test_case("SHORT", "68 65 6c 6c 6f |hello|"; "short array")]
+    #[test_case(
+        "BYTES",
+        "len=18, 68 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 21 21 21 ... |hello, world!!!!...|";
+        "slice"
+    )]
+    #[test_case("WORDS", "[u16; 3] = [\n\t1,\n\t2,\n\t3]"; "not a byte array")]
+    fn byte_preview(name: &str, expected: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("byte-buffers/main.elf");
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        assert_eq!(variable.to_string(&cache), expected);
+    }
+
+    #[test]
+    fn byte_preview_keeps_the_elements() {
+        let (debug_info, mut memory, mut cache) = static_fixture("byte-buffers/main.elf");
+
+        let packet = read_static_variable(&debug_info, &mut memory, "PACKET", &mut cache).unwrap();
+        assert_eq!(cache.get_children(packet.variable_key()).count(), 64);
+        assert_eq!(value_at_path(&cache, &packet, "[4]"), "111");
+        assert_eq!(value_at_path(&cache, &packet, "[6]"), "255");
+    }
+
+    #[test_case(4, "68 65 6c 6c ... |hell...|"; "shorter preview")]
+    #[test_case(0, "[u8; 5] = [\n\t104,\n\t101,\n\t108,\n\t108,\n\t111]"; "no preview")]
+    fn byte_preview_length(max_length: usize, expected: &str) {
+        let (mut debug_info, mut memory, mut cache) = static_fixture("byte-buffers/main.elf");
+        debug_info.max_byte_preview_length = max_length;
+
+        let short = read_static_variable(&debug_info, &mut memory, "SHORT", &mut cache).unwrap();
+        assert_eq!(short.to_string(&cache), expected);
+    }
+
+    #[test_case("corpus/c-dwarf4.elf"; "DWARF 4")]
+    #[test_case("corpus/c-dwarf5.elf"; "DWARF 5")]
+    #[test_case("corpus/c-big-endian.elf"; "big endian")]
+    fn corpus_c_static_variables(elf_file: &str) {
+        let (debug_info, mut memory, _) = static_fixture(elf_file);
+
+        let expected = [
+            ("origin", "x", "-3"),
+            ("origin", "y", "7"),
+            ("origin", "flags", "16"),
+            ("counters", "[0]", "10"),
+            ("counters", "[2]", "30"),
+            ("total", "", "0"),
+        ];
+        for (name, path, value) in expected {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            assert_eq!(
+                value_at_path(&cache, &variable, path),
+                value,
+                "{name}.{path}"
+            );
+        }
+    }
+
+    /// DWARF 4 locates the bits with the legacy `DW_AT_bit_offset`, DWARF 5 with
+    /// `DW_AT_data_bit_offset`.
+    #[test_case("bitfields/dwarf4.elf"; "DWARF 4")]
+    #[test_case("bitfields/dwarf5.elf"; "DWARF 5")]
+    fn bitfields(elf_file: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture(elf_file);
+
+        let control =
+            read_static_variable(&debug_info, &mut memory, "CONTROL", &mut cache).unwrap();
+
+        let expected = [
+            ("enable", "1", 1),
+            ("mode", "5", 3),
+            ("offset", "-3", 5),
+            ("prescaler", "74565", 20),
+            ("channel", "42", 6),
+            ("ready", "true", 1),
+            ("trim", "-8", 4),
+            ("flags", "2", 2),
+            ("timestamp", "-4886718345", 40),
+        ];
+        for (path, value, bit_size) in expected {
+            let member = variable_at_path(&cache, &control, path);
+            assert_eq!(member.to_string(&cache), value, "CONTROL.{path}");
+            assert_eq!(member.bit_size(), Some(bit_size), "CONTROL.{path}");
+        }
+
+        // The members after the first word are located in the storage unit of their bits.
+        let VariableLocation::Address(address) = control.memory_location else {
+            panic!("CONTROL is not in memory: {:?}", control.memory_location);
+        };
+        let channel = variable_at_path(&cache, &control, "channel");
+        assert_eq!(
+            channel.memory_location,
+            VariableLocation::Address(address + 4)
+        );
+        assert_eq!(channel.type_name(), "unsigned int {0..6}");
+        let trim = variable_at_path(&cache, &control, "trim");
+        assert_eq!(trim.type_name(), "int {7..11}");
+        assert_eq!(
+            variable_at_path(&cache, &control, "mode").type_name(),
+            "unsigned int {1..4}"
+        );
+    }
+
+    #[test_case("enum-sizes/main.elf", "SMALL", "Small::Second"; "rust u8")]
+    #[test_case("enum-sizes/main.elf", "WIDE", "Wide::High"; "rust u16")]
+    #[test_case("enum-sizes/main.elf", "SIGNED", "Signed::Negative"; "rust i32")]
+    #[test_case("enum-sizes/main.elf", "LARGE", "Large::Max"; "rust u64")]
+    #[test_case("enum-sizes/c.elf", "LEVEL", "LOW"; "c signed")]
+    #[test_case("enum-sizes/c.elf", "COLOR", "GREEN"; "c unsigned")]
+    fn enumeration_sizes(elf_file: &str, name: &str, value: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture(elf_file);
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        assert_eq!(variable.to_string(&cache), value);
+    }
+
+    #[test_case("WIDE", &42_u16.to_le_bytes(), "Wide(<unknown: 0x2A>)"; "unsigned")]
+    #[test_case("SIGNED", &(-42_i32).to_le_bytes(), "Signed(<unknown: -0x2A>)"; "signed")]
+    fn enumeration_unknown_value(name: &str, bytes: &[u8], value: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("enum-sizes/main.elf");
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        let VariableLocation::Address(address) = variable.memory_location else {
+            panic!("{name} is not in memory: {:?}", variable.memory_location);
+        };
+        memory.write_8(address, bytes).unwrap();
+
+        let mut cache = debug_info.create_static_scope_cache();
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        assert_eq!(variable.to_string(&cache), value);
+    }
+
+    #[test_case("WIDE", None, "High"; "u16")]
+    #[test_case("SIGNED", None, "Negative"; "negative i16")]
+    #[test_case("SIGNED", Some(&0xfe_i16.to_le_bytes()), "Positive"; "positive i16")]
+    #[test_case("LARGE", None, "Max"; "i64")]
+    #[test_case("MANY", None, "V299"; "300 variants")]
+    fn variant_discriminants(name: &str, discriminant: Option<&[u8]>, variant: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("discriminants/main.elf");
+
+        if let Some(discriminant) = discriminant {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            let VariableLocation::Address(address) = variable.memory_location else {
+                panic!("{name} is not in memory: {:?}", variable.memory_location);
+            };
+            // The discriminant of a `#[repr(i16)]` enum is its first field.
+            memory.write_8(address, discriminant).unwrap();
+        }
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        let variants = cache
+            .get_children(variable.variable_key())
+            .map(|child| child.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(variants, [variant]);
+    }
+
+    #[test]
+    fn union_arms() {
+        let (debug_info, mut memory, mut cache) = static_fixture("unions/main.elf");
+
+        let word = read_static_variable(&debug_info, &mut memory, "WORD", &mut cache).unwrap();
+        assert_eq!(word.to_string(&cache), "union (3 arms, 4 bytes)");
+
+        let expected = [
+            ("word", "305419896"),
+            ("bytes", "78 56 34 12 |xV4.|"),
+            ("halves.low", "22136"),
+            ("halves.high", "4660"),
+        ];
+        for (path, value) in expected {
+            assert_eq!(value_at_path(&cache, &word, path), value, "WORD.{path}");
+        }
+    }
+
+    #[test_case("STATUS", "", "status_t", "unsigned int", "42"; "chain")]
+    #[test_case("DEVICE", "status", "status_t", "unsigned int", "2147483649"; "struct member")]
+    #[test_case("DEVICE_PTR", "*DEVICE_PTR.control", "reg_t", "unsigned int", "7"; "pointer")]
+    #[test_case("MAC", "", "mac_t", "unsigned char[6]", "02 00 5e 10 00 01 |..^...|"; "array")]
+    fn typedefs(name: &str, path: &str, type_name: &str, resolved_type_name: &str, value: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("typedefs/main.elf");
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        let variable = variable_at_path(&cache, &variable, path);
+
+        assert_eq!(variable.type_name(), type_name);
+        assert_eq!(variable.resolved_type_name(), resolved_type_name);
+        assert_eq!(variable.to_string(&cache), value);
+    }
+
+    #[test_case("STATUS", "", "volatile uint32_t", "unsigned int", "2147483649", true; "volatile")]
+    #[test_case("OFFSET", "", "volatile const int32_t", "int", "-5", true; "stacked")]
+    #[test_case("CONTROL", "", "reg_t", "unsigned int", "7", true; "typedef")]
+    #[test_case("REGS", "[2]", "volatile uint32_t", "unsigned int", "3", true; "array item")]
+    #[test_case("STATUS_PTR", "*STATUS_PTR", "volatile uint32_t", "unsigned int", "2147483649", true; "pointer target")]
+    fn type_qualifiers(
+        name: &str,
+        path: &str,
+        type_name: &str,
+        resolved_type_name: &str,
+        value: &str,
+        is_volatile: bool,
+    ) {
+        let (debug_info, mut memory, mut cache) = static_fixture("qualifiers/main.elf");
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        let variable = variable_at_path(&cache, &variable, path);
+
+        assert_eq!(variable.type_name(), type_name);
+        assert_eq!(variable.resolved_type_name(), resolved_type_name);
+        assert_eq!(variable.to_string(&cache), value);
+        assert_eq!(variable.is_volatile(), is_volatile);
+    }
+
+    #[test]
+    fn qualified_pointers_and_arrays() {
+        let (mut debug_info, mut memory, mut cache) = static_fixture("qualifiers/main.elf");
+
+        // The qualifiers of a pointer follow it, the qualifiers of its target do not apply to it.
+        let pointer =
+            read_static_variable(&debug_info, &mut memory, "STATUS_PTR", &mut cache).unwrap();
+        assert_eq!(pointer.type_name(), "volatile uint32_t* const restrict");
+        assert_eq!(
+            pointer.qualifiers().collect::<Vec<_>>(),
+            [&Modifier::Restrict, &Modifier::Const]
+        );
+        assert!(!pointer.is_volatile());
+
+        let pair = read_static_variable(&debug_info, &mut memory, "PAIR", &mut cache).unwrap();
+        assert_eq!(pair.type_name(), "volatile const uint16_t[2]");
+        assert_eq!(pair.resolved_type_name(), "short unsigned int[2]");
+        assert!(pair.is_volatile());
+        assert_eq!(value_at_path(&cache, &pair, "[1]"), "20");
+
+        // The members of a qualified array are located by the size of the array, also when they
+        // are deferred.
+        debug_info.max_eager_array_length = 2;
+        let mut cache = debug_info.create_static_scope_cache();
+        let regs = read_static_variable(&debug_info, &mut memory, "REGS", &mut cache).unwrap();
+        assert_eq!(regs.type_name(), "volatile uint32_t[4]");
+        assert!(matches!(
+            regs.variable_node_type,
+            VariableNodeType::ArrayMembers(..)
+        ));
+        let last = variable_at_path(&cache, &regs, "[3]");
+        assert_eq!(last.to_string(&cache), "4");
+        assert_eq!(last.memory_location, VariableLocation::Address(0x40302c));
+    }
+
+    #[test_case("GREETING", "", "const char*", "Hello, C!"; "pointer")]
+    #[test_case("NAME", "", "char[16]", "probe-rs"; "array")]
+    #[test_case("FULL", "", "char[4]", "abcd"; "full array")]
+    #[test_case("MISSING", "", "const char*", "const char* @ 0x00403088"; "null pointer")]
+    #[test_case("ENTRIES", "[1].label", "const char*", "second"; "struct member pointer")]
+    #[test_case("ENTRIES", "[0].tag", "char[8]", "one"; "struct member array")]
+    #[test_case("ENTRIES", "[1].mode", "mode_t", "MODE_RUN"; "enumerator")]
+    fn c_variables(name: &str, path: &str, type_name: &str, value: &str) {
+        let (debug_info, mut memory, mut cache) = static_fixture("c-variables/main.elf");
+
+        let variable = read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+        let variable = variable_at_path(&cache, &variable, path);
+
+        assert_eq!(variable.type_name(), type_name);
+        assert_eq!(variable.to_string(&cache), value);
+    }
+
+    #[test]
+    fn c_variables_are_not_rust() {
+        let (mut debug_info, mut memory, mut cache) = static_fixture("c-variables/main.elf");
+
+        // The strings are truncated at the maximum length, unless they end there.
+        debug_info.max_string_length = 4;
+        for (name, value) in [
+            ("GREETING", "Hell..."),
+            ("NAME", "prob..."),
+            ("FULL", "abcd"),
+        ] {
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            assert_eq!(variable.to_string(&cache), value, "{name}");
+        }
+
+        // A C struct named like a Rust `PhantomData` is kept, and `__0` is the name of a member.
+        let holder = read_static_variable(&debug_info, &mut memory, "HOLDER", &mut cache).unwrap();
+        assert_eq!(value_at_path(&cache, &holder, "phantom.marker"), "1");
+        let member = variable_at_path(&cache, &holder, "__0");
+        assert!(!member.is_indexed());
+        assert_eq!(member.to_string(&cache), "2");
+
+        // A C struct named like a Rust `None` has a value.
+        let none = read_static_variable(&debug_info, &mut memory, "NONE", &mut cache).unwrap();
+        assert_ne!(none.to_string(&cache), "None");
+        assert_eq!(value_at_path(&cache, &none, "value"), "3");
+    }
+
+    #[test]
+    fn cpp_classes() {
+        let (debug_info, mut memory, mut cache) = static_fixture("cpp-classes/main.elf");
+
+        // The base classes are members named after them, the member functions and the vtable
+        // pointer are left out.
+        let object = read_static_variable(&debug_info, &mut memory, "BOX", &mut cache).unwrap();
+        assert_eq!(object.type_name(), "Box");
+        let names: Vec<_> = cache
+            .get_children(object.variable_key())
+            .map(|child| child.name.to_string())
+            .collect();
+        assert_eq!(names, ["Shape", "Named", "width", "height"]);
+        assert_eq!(value_at_path(&cache, &object, "Shape.id"), "1");
+        assert_eq!(
+            cache
+                .get_children(variable_at_path(&cache, &object, "Shape").variable_key())
+                .count(),
+            1
+        );
+
+        // The second base class is at its offset in the derived class.
+        let named = variable_at_path(&cache, &object, "Named");
+        assert_eq!(named.type_name(), "Named");
+        assert_eq!(named.memory_location, VariableLocation::Address(0x403050));
+        assert_eq!(value_at_path(&cache, &object, "Named.label"), "box");
+        assert_eq!(value_at_path(&cache, &object, "height"), "4");
+
+        // A static member is defined outside of the class, and named after its declaration.
+        let count =
+            read_static_variable(&debug_info, &mut memory, "Box::count", &mut cache).unwrap();
+        assert_eq!(count.to_string(&cache), "2");
+    }
+
+    #[test]
+    fn raw_bytes_of_undecodable_types() {
+        let (debug_info, mut memory, mut cache) = static_fixture("raw-bytes/main.elf");
+
+        // The C++ reference is not decoded, so its memory, the address of `target`, is shown.
+        let holder = read_static_variable(&debug_info, &mut memory, "HOLDER", &mut cache).unwrap();
+        let raw_bytes = variable_at_path(&cache, &holder, "value.<raw bytes>");
+        assert_eq!(
+            raw_bytes.memory_location,
+            VariableLocation::Address(0x402010)
+        );
+        assert_eq!(
+            raw_bytes.to_string(&cache),
+            "00 20 40 00 00 00 00 00 |. @.....|"
+        );
+        let words: Vec<_> = cache
+            .get_children(raw_bytes.variable_key())
+            .map(|word| (word.name.to_string(), word.to_string(&cache)))
+            .collect();
+        assert_eq!(
+            words,
+            [
+                ("[0]".to_string(), "0x00402000".to_string()),
+                ("[1]".to_string(), "0x00000000".to_string())
+            ]
+        );
+
+        // The following members are decoded as usual.
+        assert_eq!(value_at_path(&cache, &holder, "tag"), "43981");
+    }
+
+    #[test]
+    fn string_types() {
+        let (mut debug_info, mut memory, mut cache) = static_fixture("string-types/main.elf");
+
+        // A string with a fixed length.
+        let fixed = read_static_variable(&debug_info, &mut memory, "FIXED", &mut cache).unwrap();
+        assert_eq!(fixed.type_name(), "string");
+        assert_eq!(fixed.to_string(&cache), "PROBE-RS");
+        let raw_bytes = variable_at_path(&cache, &fixed, "<raw bytes>");
+        assert_eq!(
+            raw_bytes.to_string(&cache),
+            "50 52 4f 42 45 2d 52 53 |PROBE-RS|"
+        );
+        assert_eq!(value_at_path(&cache, &raw_bytes, "[1]"), "0x53522d45");
+
+        // The length is stored at the location of an expression, or in another variable.
+        let name = read_static_variable(&debug_info, &mut memory, "NAME", &mut cache).unwrap();
+        assert_eq!(name.to_string(&cache), "hello");
+        assert_eq!(name.byte_size, Some(5));
+        let label = read_static_variable(&debug_info, &mut memory, "LABEL", &mut cache).unwrap();
+        assert_eq!(label.to_string(&cache), "deb");
+
+        debug_info.max_string_length = 4;
+        let fixed = read_static_variable(&debug_info, &mut memory, "FIXED", &mut cache).unwrap();
+        assert_eq!(fixed.to_string(&cache), "PROB...");
+    }
+
+    #[test]
+    fn anonymous_members() {
+        let (debug_info, mut memory, mut cache) = static_fixture("anonymous-members/main.elf");
+
+        // The members of the nested anonymous structs and unions are members of `PACKET`.
+        let packet = read_static_variable(&debug_info, &mut memory, "PACKET", &mut cache).unwrap();
+        let members: Vec<_> = cache
+            .get_children(packet.variable_key())
+            .map(|member| (member.name.to_string(), member.to_string(&cache)))
+            .collect();
+        assert_eq!(
+            members,
+            [
+                ("kind", "'\\u{1}'"),
+                ("word", "305419896"),
+                ("low", "22136"),
+                ("high", "4660"),
+                ("flags", "'\\u{2}'"),
+                ("delta", "'\\xfd'"),
+                ("raw", "'\\xfd'"),
+                ("checksum", "'\\u{4}'"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+
+        let raw = variable_at_path(&cache, &packet, "raw");
+        assert_eq!(raw.memory_location, VariableLocation::Address(0x402009));
+    }
+
+    #[test]
+    fn function_pointers() {
+        let (debug_info, mut memory, mut cache) = static_fixture("function-pointers/main.elf");
+
+        let callbacks =
+            read_static_variable(&debug_info, &mut memory, "CALLBACKS", &mut cache).unwrap();
+
+        assert_eq!(
+            value_at_path(&cache, &callbacks, "on_rx"),
+            "0x00401000 (on_rx)"
+        );
+        assert_eq!(
+            value_at_path(&cache, &callbacks, "on_error"),
+            "0x00401016 (on_error)"
+        );
+
+        // A null pointer, and a pointer to a variable, are not named after a function.
+        for (path, location) in [("on_idle", "0x00402010"), ("on_reset", "0x00402018")] {
+            let pointer = variable_at_path(&cache, &callbacks, path);
+            assert_eq!(
+                pointer.to_string(&cache),
+                format!("{} @ {location}", pointer.type_name()),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn pointer_cycle() {
+        let (debug_info, mut memory, mut cache) = static_fixture("pointer-cycles/main.elf");
+
+        let first = read_static_variable(&debug_info, &mut memory, "first", &mut cache).unwrap();
+
+        assert_eq!(value_at_path(&cache, &first, "next.*next.value"), "2");
+        assert_eq!(value_at_path(&cache, &first, "prev.*prev.value"), "2");
+
+        // `second` points back to `first`, which is not resolved again.
+        for path in ["next.*next.next.*next", "next.*next.prev.*prev"] {
+            let cycle = variable_at_path(&cache, &first, path);
+            assert_eq!(
+                cycle.to_string(&cache),
+                "<cycle: points back to 0x00402020 first>",
+                "{path}"
+            );
+            assert_eq!(cycle.variable_node_type, VariableNodeType::DoNotRecurse);
+            assert!(!cache.has_children(&cycle));
+        }
+    }
+
+    #[test]
+    fn rust_strings() {
+        let (mut debug_info, mut memory, _) = static_fixture("strings/main.elf");
+
+        // `GREETING` points to a heap buffer, with invalid UTF-8.
+        memory
+            .write_8(0x2000_0004, &0x2000_1000_u32.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x2000_0008, &9_u32.to_le_bytes()).unwrap();
+        memory.add_range(0x2000_1000, b"hi\xff there".to_vec());
+
+        let mut read = |debug_info: &DebugInfo, name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable = read_static_variable(debug_info, &mut memory, name, &mut cache).unwrap();
+            (variable, cache)
+        };
+
+        let (text, _) = read(&debug_info, "TEXT");
+        assert_eq!(text.value(), &VariableValue::String("text".to_string()));
+
+        // The pointer of an empty `String` is dangling.
+        let (empty, _) = read(&debug_info, "EMPTY");
+        assert_eq!(empty.value(), &VariableValue::String(String::new()));
+
+        let (greeting, cache) = read(&debug_info, "GREETING");
+        assert_eq!(
+            greeting.value(),
+            &VariableValue::String("hi\u{fffd} there".to_string())
+        );
+        assert_eq!(value_at_path(&cache, &greeting, "vec.len"), "9");
+
+        debug_info.max_string_length = 2;
+        let (greeting, _) = read(&debug_info, "GREETING");
+        assert_eq!(
+            greeting.value(),
+            &VariableValue::String("hi...".to_string())
+        );
+    }
+
+    #[test]
+    fn rust_sequences() {
+        let (mut debug_info, mut memory, _) = static_fixture("sequences/main.elf");
+
+        // `NUMBERS` points to a heap buffer, of which 3 out of 4 elements are used.
+        memory.write_8(0x2000_0000, &4_u32.to_le_bytes()).unwrap();
+        memory
+            .write_8(0x2000_0004, &0x2000_1000_u32.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x2000_0008, &3_u32.to_le_bytes()).unwrap();
+        memory.add_range(0x2000_1000, vec![10, 0, 20, 0, 30, 0, 0, 0]);
+
+        let mut read = |debug_info: &DebugInfo, name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable = read_static_variable(debug_info, &mut memory, name, &mut cache).unwrap();
+            (variable, cache)
+        };
+        let elements = |cache: &VariableCache, variable: &Variable| {
+            cache
+                .get_children(variable.variable_key)
+                .map(|element| (element.name.to_string(), element.to_string(cache)))
+                .collect::<Vec<_>>()
+        };
+        let expected = |values: &[&str]| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (format!("[{index}]"), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let (numbers, cache) = read(&debug_info, "NUMBERS");
+        assert_eq!(numbers.to_string(&cache), "len=3, cap=4");
+        assert_eq!(elements(&cache, &numbers), expected(&["10", "20", "30"]));
+
+        let (slice, cache) = read(&debug_info, "SLICE");
+        assert_eq!(slice.to_string(&cache), "len=3");
+        assert_eq!(elements(&cache, &slice), expected(&["1", "2", "3"]));
+
+        // The pointer of an empty slice is dangling.
+        let (empty, cache) = read(&debug_info, "EMPTY");
+        assert_eq!(empty.to_string(&cache), "len=0");
+        assert!(!cache.has_children(&empty));
+
+        // Long sequences are deferred like arrays, and read with the static variable.
+        debug_info.max_eager_array_length = 2;
+        let (slice, cache) = read(&debug_info, "SLICE");
+        assert!(matches!(
+            slice.variable_node_type,
+            VariableNodeType::SequenceMembers(_, _, 0x58, 3)
+        ));
+        assert_eq!(elements(&cache, &slice), expected(&["1", "2", "3"]));
+
+        // A `Vec` which is longer than its capacity keeps its fields.
+        memory.write_8(0x2000_0008, &5_u32.to_le_bytes()).unwrap();
+        let mut cache = debug_info.create_static_scope_cache();
+        let numbers =
+            read_static_variable(&debug_info, &mut memory, "NUMBERS", &mut cache).unwrap();
+        assert_eq!(
+            numbers.to_string(&cache),
+            "Vec<u16, alloc::alloc::Global> @ 0x20000000"
+        );
+        assert_eq!(value_at_path(&cache, &numbers, "len"), "5");
+    }
+
+    #[test]
+    fn type_formatters() {
+        let (mut debug_info, mut memory, mut cache) = static_fixture("sequences/main.elf");
+
+        // `NUMBERS` points to a heap buffer, of which 3 out of 4 elements are used.
+        memory.write_8(0x2000_0000, &4_u32.to_le_bytes()).unwrap();
+        memory
+            .write_8(0x2000_0004, &0x2000_1000_u32.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x2000_0008, &3_u32.to_le_bytes()).unwrap();
+        memory.add_range(0x2000_1000, vec![10, 0, 20, 0, 30, 0, 0, 0]);
+
+        // The formatter of a `Vec` sees the elements of the built-in formatter.
+        debug_info.register_type_formatter(
+            |name| name.starts_with("Vec<"),
+            true,
+            |variable, _, cache| {
+                let count = cache.get_children(variable.variable_key).count();
+                Some(format!("{count} numbers"))
+            },
+        );
+        // A formatter which returns `None` keeps the value.
+        debug_info.register_type_formatter(|name| name.starts_with("&["), false, |_, _, _| None);
+        debug_info.register_type_formatter(
+            |name| name == "u16",
+            false,
+            |variable, memory, _| {
+                let VariableLocation::Address(address) = variable.memory_location else {
+                    return None;
+                };
+                let value = memory.read_word_16(address).ok()?;
+                Some(format!("{value:#06x}"))
+            },
+        );
+
+        let numbers =
+            read_static_variable(&debug_info, &mut memory, "NUMBERS", &mut cache).unwrap();
+        assert_eq!(numbers.to_string(&cache), "3 numbers");
+        assert!(!cache.has_children(&numbers));
+
+        let mut cache = debug_info.create_static_scope_cache();
+        let slice = read_static_variable(&debug_info, &mut memory, "SLICE", &mut cache).unwrap();
+        assert_eq!(slice.to_string(&cache), "len=3");
+        let elements = cache
+            .get_children(slice.variable_key)
+            .map(|element| element.to_string(&cache))
+            .collect::<Vec<_>>();
+        assert_eq!(elements, ["0x0001", "0x0002", "0x0003"]);
+    }
+
+    #[test]
+    fn rust_options() {
+        let (debug_info, mut memory, _) = static_fixture("options/main.elf");
+
+        let read = |memory: &mut MockMemory, name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable = read_static_variable(&debug_info, memory, name, &mut cache).unwrap();
+            variable.to_string(&cache)
+        };
+
+        assert_eq!(read(&mut memory, "REFERENCE"), "None");
+        assert_eq!(read(&mut memory, "NON_ZERO"), "None");
+        assert_eq!(read(&mut memory, "NUMBER"), "Some(7)");
+        assert_eq!(read(&mut memory, "RESULT"), "Err(true)");
+        assert_eq!(read(&mut memory, "BOOL"), "None");
+
+        // Niche optimized layouts have no separate discriminant, any other value is the payload.
+        memory
+            .write_8(0x2000_0014, &0x2000_0008_u32.to_le_bytes())
+            .unwrap();
+        assert_eq!(read(&mut memory, "REFERENCE"), "Some(&u32 @ 0x20000014)");
+        memory.write_8(0x2000_0010, &5_u32.to_le_bytes()).unwrap();
+        assert_eq!(
+            read(&mut memory, "NON_ZERO"),
+            "Some(NonZero<u32> @ 0x20000010)"
+        );
+        memory.write_8(0x2000_0000, &[1]).unwrap();
+        assert_eq!(read(&mut memory, "BOOL"), "Some(true)");
+
+        memory.write_8(0x2000_0004, &0_u32.to_le_bytes()).unwrap();
+        assert_eq!(read(&mut memory, "NUMBER"), "None");
+        memory.write_8(0x2000_000C, &[0, 0, 42, 0]).unwrap();
+        assert_eq!(read(&mut memory, "RESULT"), "Ok(42)");
+    }
+
+    #[test]
+    fn rust_closures_and_coroutines() {
+        let (debug_info, mut memory, _) = static_fixture("closures/main.elf");
+
+        let read = |memory: &mut MockMemory, name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable = read_static_variable(&debug_info, memory, name, &mut cache).unwrap();
+            // The value of the `ManuallyDrop` is wrapped in a `MaybeDangling`, which is deferred.
+            let mut wrapper = variable_at_path(&cache, &variable, "value.value");
+            let registers = DebugRegisters::default();
+            debug_info
+                .cache_deferred_variables(
+                    &mut cache,
+                    memory,
+                    &mut wrapper,
+                    StackFrameInfo {
+                        registers: &registers,
+                        frame_base: None,
+                        canonical_frame_address: None,
+                        auto_deref_depth: None,
+                    },
+                )
+                .unwrap();
+            let value = variable_at_path(&cache, &wrapper, "__0");
+            (value, cache)
+        };
+        let names = |cache: &VariableCache, variable: &Variable| {
+            cache
+                .get_children(variable.variable_key())
+                .map(|child| child.name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // `callback` captures `scale`, `offset` and `limit` by value, and `check` captures
+        // `*threshold` by reference. Both point to `SCALE`, which is 2.
+        memory
+            .write_8(0x20_3918, &0x20_0200_u64.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x20_3920, &1_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x20_3924, &100_u32.to_le_bytes()).unwrap();
+        memory
+            .write_8(0x20_3910, &0x20_0200_u64.to_le_bytes())
+            .unwrap();
+
+        let (callback, cache) = read(&mut memory, "CALLBACK");
+        assert_eq!(
+            callback.to_string(&cache),
+            "closure capturing scale, offset, limit"
+        );
+        assert_eq!(value_at_path(&cache, &callback, "offset"), "1");
+
+        let (check, cache) = read(&mut memory, "CHECK");
+        assert_eq!(check.to_string(&cache), "closure capturing threshold");
+        assert_eq!(value_at_path(&cache, &check, "threshold.*threshold"), "2");
+
+        // `blink(7, 3)` has not been polled yet.
+        memory.write_8(0x20_38F8, &3_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x20_3904, &[7]).unwrap();
+        let (blink, cache) = read(&mut memory, "BLINK");
+        assert_eq!(blink.to_string(&cache), "Unresumed");
+        assert_eq!(names(&cache, &blink), ["led", "times"]);
+        assert_eq!(value_at_path(&cache, &blink, "times"), "3");
+
+        // Suspended at the first `.await`, in the first iteration of the loop. The arguments are
+        // only listed once.
+        memory.write_8(0x20_38FC, &3_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x20_3900, &1_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x20_3905, &[7, 3]).unwrap();
+        let (blink, cache) = read(&mut memory, "BLINK");
+        assert_eq!(blink.to_string(&cache), "Suspend0 at main.rs:38");
+        assert_eq!(
+            names(&cache, &blink),
+            ["led", "times", "count", "__awaitee"]
+        );
+        assert_eq!(value_at_path(&cache, &blink, "led"), "7");
+        assert_eq!(value_at_path(&cache, &blink, "count"), "1");
+    }
+
+    #[test_case("function-statics/c.elf", 0x401007, "count", "5"; "C")]
+    #[test_case("function-statics/rust.elf", 0x201479, "COUNTER", "7"; "Rust")]
+    fn function_statics_in_local_scope(
+        elf_file: &str,
+        address: u32,
+        static_name: &str,
+        static_value: &str,
+    ) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+        let mut memory = memory_from_elf(&get_path_for_test_files(elf_file));
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(address)),
+        });
+
+        let (unit_info, functions) = debug_info.get_function_dies(address.into()).unwrap();
+        let mut cache = debug_info
+            .create_function_scope_cache(&functions[0], unit_info)
+            .unwrap();
+        let mut root = cache.root_variable().clone();
+        debug_info
+            .cache_deferred_variables(
+                &mut cache,
+                &mut memory,
+                &mut root,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: Some(0x1000),
+                    canonical_frame_address: Some(0x1000),
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+
+        // The static is listed with the parameter of the function, and is read from its address.
+        let root = cache.root_variable().clone();
+        let counter = variable_at_path(&cache, &root, static_name);
+        assert!(counter.is_static);
+        assert_eq!(counter.to_string(&cache), static_value);
+
+        let step = variable_at_path(&cache, &root, "step");
+        assert!(!step.is_static);
+    }
+
+    // `work` declares `first` on line 5, and `second` on line 7. The addresses are the first
+    // instruction of each line.
+    //
+    // With a load bias, the runtime address of the program counter is converted to the address in
+    // the debug information.
+    #[test_case("variable-scopes/main.elf", 0x401000, 0, &[]; "location lists, line 5")]
+    #[test_case("variable-scopes/main.elf", 0x401003, 0, &["first"]; "location lists, line 6")]
+    #[test_case("variable-scopes/main.elf", 0x401009, 0, &["first"]; "location lists, line 7")]
+    #[test_case("variable-scopes/main.elf", 0x401011, 0, &["first", "second"]; "location lists, line 8")]
+    #[test_case("variable-scopes/main.elf", 0x401003, 0x1000_0000, &["first"]; "location lists with a load bias, line 6")]
+    #[test_case("variable-scopes/main.elf", 0x401011, -0x40_0000, &["first", "second"]; "location lists with a load bias, line 8")]
+    #[test_case("variable-scopes/start-scope.elf", 0x401013, 0, &["first"]; "start scope, line 6")]
+    #[test_case("variable-scopes/start-scope.elf", 0x40101c, 0, &["first", "second"]; "start scope, line 7")]
+    #[test_case("variable-scopes/start-scope.elf", 0x401013, 0x1000_0000, &["first"]; "start scope with a load bias, line 6")]
+    fn variables_in_scope(elf_file: &str, address: u32, bias: i64, in_scope: &[&str]) {
+        let mut memory = memory_from_elf(&get_path_for_test_files(elf_file));
+        let address = u64::from(address).wrapping_add_signed(bias);
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U64(address)),
+        });
+
+        for hide_out_of_scope_variables in [false, true] {
+            let options = DebugInfoOptions {
+                hide_out_of_scope_variables,
+                ..Default::default()
+            };
+            let mut debug_info =
+                DebugInfo::from_file_with_options(get_path_for_test_files(elf_file), &options)
+                    .unwrap();
+            debug_info.set_load_bias(bias);
+
+            let (unit_info, functions) = debug_info.get_function_dies(address).unwrap();
+            let mut cache = debug_info
+                .create_function_scope_cache(&functions[0], unit_info)
+                .unwrap();
+            let mut root = cache.root_variable().clone();
+            debug_info
+                .cache_deferred_variables(
+                    &mut cache,
+                    &mut memory,
+                    &mut root,
+                    StackFrameInfo {
+                        registers: &registers,
+                        frame_base: Some(0x1000),
+                        canonical_frame_address: Some(0x1000),
+                        auto_deref_depth: None,
+                    },
+                )
+                .unwrap();
+
+            let root = cache.root_variable().clone();
+            for name in ["first", "second"] {
+                let variable = cache
+                    .get_children(root.variable_key())
+                    .find(|child| child.name.to_string() == name);
+                let not_yet_in_scope =
+                    variable.map(|variable| variable.to_string(&cache) == "< not yet in scope >");
+
+                let expected = if in_scope.contains(&name) {
+                    Some(false)
+                } else if hide_out_of_scope_variables {
+                    None
+                } else {
+                    Some(true)
+                };
+                assert_eq!(not_yet_in_scope, expected, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn function_parameters() {
+        let elf_file = "function-statics/c.elf";
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+        let mut memory = memory_from_elf(&get_path_for_test_files(elf_file));
+        let address = 0x401007_u32;
+
+        // `step` and `total` are on the stack, below the canonical frame address, which is the frame
+        // base of the function.
+        let canonical_frame_address = 0x1000;
+        memory.add_range(canonical_frame_address - 36, 2_i32.to_le_bytes().to_vec());
+        memory.add_range(canonical_frame_address - 20, 7_i32.to_le_bytes().to_vec());
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(address)),
+        });
+
+        let (unit_info, functions) = debug_info.get_function_dies(address.into()).unwrap();
+        let mut frame = StackFrame {
+            id: crate::get_object_reference(),
+            function_name: functions[0].function_name(&debug_info).unwrap(),
+            linkage_name: None,
+            source_location: None,
+            registers: registers.clone(),
+            pc: RegisterValue::U32(address),
+            frame_base: Some(canonical_frame_address),
+            is_inlined: false,
+            kind: FrameKind::Function,
+            local_variables: Some(
+                debug_info
+                    .create_function_scope_cache(&functions[0], unit_info)
+                    .unwrap(),
+            ),
+            canonical_frame_address: Some(canonical_frame_address),
+        };
+        assert_eq!(frame.signature(), "next_sample");
+
+        let cache = frame.local_variables.as_mut().unwrap();
+        let mut root = cache.root_variable().clone();
+        debug_info
+            .cache_deferred_variables(
+                cache,
+                &mut memory,
+                &mut root,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: Some(canonical_frame_address),
+                    canonical_frame_address: Some(canonical_frame_address),
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+
+        let root = cache.root_variable().clone();
+        let parameters = cache
+            .get_children(root.variable_key())
+            .map(|variable| (variable.name.to_string(), variable.is_parameter))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parameters,
+            [
+                ("step".to_string(), true),
+                ("count".to_string(), false),
+                ("total".to_string(), false),
+            ]
+        );
+        assert_eq!(frame.signature(), "next_sample(step=2)");
+    }
+
+    #[test]
+    fn rust_smart_pointers() {
+        let (debug_info, mut memory, _) = static_fixture("smart-pointers/main.elf");
+
+        // Nothing was allocated, so the heap values are added here.
+        memory.add_range(0x2000_1000, vec![0xff, 0xff, 2, 0]);
+        memory
+            .write_8(0x2000_0020, &0x2000_1000_u32.to_le_bytes())
+            .unwrap();
+        memory.add_range(0x2000_1010, vec![5, 0, 6, 0]);
+        memory
+            .write_8(0x2000_0018, &0x2000_1010_u32.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x2000_001C, &2_u32.to_le_bytes()).unwrap();
+        memory.add_word_range(0x2000_1020, &[1, 1, 42]);
+        memory
+            .write_8(0x2000_0028, &0x2000_1020_u32.to_le_bytes())
+            .unwrap();
+        memory.add_word_range(0x2000_1030, &[2, 1, 7]);
+        memory
+            .write_8(0x2000_0024, &0x2000_1030_u32.to_le_bytes())
+            .unwrap();
+
+        let mut read = |name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            (variable, cache)
+        };
+        let children = |cache: &VariableCache, variable: &Variable| {
+            cache
+                .get_children(variable.variable_key)
+                .map(|child| (child.name.to_string(), child.to_string(cache)))
+                .collect::<Vec<_>>()
+        };
+        let named = |children: &[(&str, &str)]| {
+            children
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let (boxed, cache) = read("BOXED");
+        assert_eq!(value_at_path(&cache, &boxed, "Some.__0.*__0.x"), "-1");
+        assert_eq!(value_at_path(&cache, &boxed, "Some.__0.*__0.y"), "2");
+
+        let (boxed_slice, cache) = read("BOXED_SLICE");
+        assert_eq!(boxed_slice.to_string(&cache), "Some(len=2)");
+        let slice = variable_at_path(&cache, &boxed_slice, "Some.__0");
+        assert_eq!(
+            slice.type_name(),
+            "alloc::boxed::Box<[u16], alloc::alloc::Global>"
+        );
+        assert_eq!(
+            children(&cache, &slice),
+            named(&[("[0]", "5"), ("[1]", "6")])
+        );
+
+        // The reference counts follow the value, and the type is not replaced.
+        let (shared, cache) = read("SHARED");
+        assert_eq!(shared.to_string(&cache), "Some(42)");
+        let rc = variable_at_path(&cache, &shared, "Some.__0");
+        assert_eq!(rc.type_name(), "Rc<u32, alloc::alloc::Global>");
+        assert_eq!(
+            children(&cache, &rc),
+            named(&[("*__0", "42"), ("strong", "1"), ("weak", "1")])
+        );
+
+        let (atomic, cache) = read("ATOMIC");
+        assert_eq!(atomic.to_string(&cache), "Some(7)");
+        let arc = variable_at_path(&cache, &atomic, "Some.__0");
+        assert_eq!(
+            children(&cache, &arc),
+            named(&[("*__0", "7"), ("strong", "2"), ("weak", "1")])
+        );
+
+        let (borrowed, cache) = read("BORROWED");
+        assert_eq!(borrowed.to_string(&cache), "cow");
+        assert_eq!(children(&cache, &borrowed), named(&[("*BORROWED", "cow")]));
+
+        let (cell, cache) = read("CELL");
+        assert_eq!(cell.to_string(&cache), "3");
+        assert_eq!(cell.type_name(), "Cell<u32>");
+        assert_eq!(children(&cache, &cell), named(&[("value", "3")]));
+
+        let (ref_cell, cache) = read("REF_CELL");
+        assert_eq!(ref_cell.to_string(&cache), "9");
+        assert_eq!(
+            children(&cache, &ref_cell),
+            named(&[("value", "9"), ("borrow", "0")])
+        );
+    }
+
+    #[test]
+    fn rust_atomics() {
+        let (debug_info, mut memory, _) = static_fixture("atomics/main.elf");
+
+        let mut read = |name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            (variable, cache)
+        };
+
+        // The value is shown on the atomic, which keeps its type and has no fields.
+        let (counter, cache) = read("COUNTER");
+        assert_eq!(counter.value(), &VariableValue::U64(42));
+        assert_eq!(counter.type_name(), "AtomicU32");
+        assert!(!cache.has_children(&counter));
+
+        let (offset, _) = read("OFFSET");
+        assert_eq!(offset.value(), &VariableValue::I64(-3));
+        let (ready, _) = read("READY");
+        assert_eq!(ready.value(), &VariableValue::Bool(true));
+
+        // An `AtomicPtr<T>` is not a number, so it keeps its fields.
+        let (target, cache) = read("TARGET");
+        assert!(cache.has_children(&target));
+
+        let (registers, cache) = read("REGISTERS");
+        let status = variable_at_path(&cache, &registers, "status");
+        assert_eq!(status.to_string(&cache), "4660");
+        assert_eq!(status.type_name(), "VolatileCell<u32>");
+        assert_eq!(value_at_path(&cache, &registers, "control"), "7");
+    }
+
+    #[test]
+    fn rust_collections() {
+        let (debug_info, mut memory, _) = static_fixture("heapless/main.elf");
+
+        // `NUMBERS` uses 3 out of its 4 elements.
+        memory
+            .write_8(0x2000_0038, &[1, 0, 2, 0, 3, 0, 0, 0])
+            .unwrap();
+        memory.write_8(0x2000_0040, &3_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x2000_0010, b"probe\0\0\0").unwrap();
+        memory.write_8(0x2000_0018, &5_u32.to_le_bytes()).unwrap();
+
+        // `DEQUE` starts in its last slot, and wraps around to its first two.
+        memory.write_8(0x2000_001C, &[20, 30, 0, 10]).unwrap();
+        memory.write_8(0x2000_0020, &3_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x2000_0024, &2_u32.to_le_bytes()).unwrap();
+
+        // `QUEUE` starts in its third slot, and wraps around to its first one.
+        memory.write_8(0x2000_002C, &2_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x2000_0030, &1_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x2000_0034, &[7, 0, 5, 6]).unwrap();
+
+        // `RING` points to a heap buffer, which starts in its last slot.
+        memory.write_8(0x2000_0000, &4_u32.to_le_bytes()).unwrap();
+        memory
+            .write_8(0x2000_0004, &0x2000_1000_u32.to_le_bytes())
+            .unwrap();
+        memory.write_8(0x2000_0008, &3_u32.to_le_bytes()).unwrap();
+        memory.write_8(0x2000_000C, &2_u32.to_le_bytes()).unwrap();
+        memory.add_range(0x2000_1000, vec![200, 0, 0, 0, 0, 0, 100, 0]);
+
+        let mut read = |name: &str| {
+            let mut cache = debug_info.create_static_scope_cache();
+            let variable =
+                read_static_variable(&debug_info, &mut memory, name, &mut cache).unwrap();
+            (variable, cache)
+        };
+        let elements = |cache: &VariableCache, variable: &Variable| {
+            cache
+                .get_children(variable.variable_key)
+                .map(|element| (element.name.to_string(), element.to_string(cache)))
+                .collect::<Vec<_>>()
+        };
+        let expected = |values: &[&str]| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (format!("[{index}]"), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let (numbers, cache) = read("NUMBERS");
+        assert_eq!(numbers.to_string(&cache), "len=3, cap=4");
+        assert_eq!(elements(&cache, &numbers), expected(&["1", "2", "3"]));
+
+        let (name, _) = read("NAME");
+        assert_eq!(name.value(), &VariableValue::String("probe".to_string()));
+
+        // The elements are shown in the order they are dequeued.
+        let (deque, cache) = read("DEQUE");
+        assert_eq!(deque.to_string(&cache), "len=3, cap=4");
+        assert_eq!(elements(&cache, &deque), expected(&["10", "20", "30"]));
+
+        // One slot of a `heapless::spsc::Queue` is always empty.
+        let (queue, cache) = read("QUEUE");
+        assert_eq!(queue.to_string(&cache), "len=3, cap=3");
+        assert_eq!(elements(&cache, &queue), expected(&["5", "6", "7"]));
+
+        let (ring, cache) = read("RING");
+        assert_eq!(ring.to_string(&cache), "len=2, cap=4");
+        assert_eq!(elements(&cache, &ring), expected(&["100", "200"]));
+    }
+
+    #[test]
+    fn update_variable_value() {
+        let (debug_info, mut memory, mut cache) = static_fixture("corpus/debug-dwarf4.elf");
+
+        let state = read_static_variable(&debug_info, &mut memory, "STATE", &mut cache).unwrap();
+        let field = |cache: &VariableCache, name: &str| {
+            cache
+                .get_children(state.variable_key())
+                .find(|child| child.name == VariableName::Named(name.to_string()))
+                .unwrap()
+                .variable_key()
+        };
+
+        // Integers can be written in hexadecimal.
+        let count = cache
+            .update_variable_value(field(&cache, "count"), "0x10", &mut memory)
+            .unwrap();
+        assert_eq!(count.to_string(&cache), "16");
+        assert_eq!(memory.read_word_32(0x2000_0010).unwrap(), 16);
+
+        let enabled = cache
+            .update_variable_value(field(&cache, "enabled"), "false", &mut memory)
+            .unwrap();
+        assert_eq!(enabled.to_string(&cache), "false");
+
+        // The value is parsed according to the type.
+        let error = cache
+            .update_variable_value(field(&cache, "count"), "0x1_0000_0000", &mut memory)
+            .unwrap_err();
+        assert!(matches!(error, DebugError::WarnAndContinue { .. }));
+        assert_eq!(memory.read_word_32(0x2000_0010).unwrap(), 16);
+
+        let error = cache
+            .update_variable_value(field(&cache, "history"), "0", &mut memory)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DebugError::VariableNotWritable {
+                reason: NotWritableReason::NotBaseType(_),
+                ..
+            }
+        ));
+
+        // Values that are not in memory cannot be written.
+        for (location, reason) in [
+            (
+                VariableLocation::Implicit(vec![1, 0, 0, 0]),
+                NotWritableReason::Synthetic,
+            ),
+            (
+                VariableLocation::RegisterValue(RegisterValue::U32(1)),
+                NotWritableReason::Register,
+            ),
+            (VariableLocation::Unavailable, NotWritableReason::NoLocation),
+        ] {
+            let mut variable = cache.create_variable(state.variable_key(), None).unwrap();
+            variable.name = VariableName::Named("synthetic".to_string());
+            variable.type_name = VariableType::Base("u32".to_string());
+            variable.byte_size = Some(4);
+            variable.memory_location = location;
+            cache.update_variable(&variable).unwrap();
+
+            let error = cache
+                .update_variable_value(variable.variable_key(), "2", &mut memory)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("Cannot change the value of synthetic: {reason}")
+            );
+        }
+    }
+
+    /// The contents of a register are the value of the variable, not the address of the variable.
+    #[test]
+    fn register_variable_has_no_address() {
+        let (debug_info, mut memory, mut cache) = static_fixture("corpus/debug-dwarf4.elf");
+
+        let state = read_static_variable(&debug_info, &mut memory, "STATE", &mut cache).unwrap();
+
+        let mut variable = cache.create_variable(state.variable_key(), None).unwrap();
+        variable.name = VariableName::Named("in_register".to_string());
+        variable.type_name = VariableType::Base("u32".to_string());
+        variable.byte_size = Some(4);
+        variable.memory_location = VariableLocation::RegisterValue(RegisterValue::U32(0x2000_0000));
+        cache.update_variable(&variable).unwrap();
+
+        assert!(variable.memory_location.memory_address().is_err());
+
+        let before = memory.read_word_32(0x2000_0000).unwrap();
+        let error = variable
+            .update_value(&mut memory, &mut cache, "5".to_string())
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            DebugError::VariableNotWritable {
+                reason: NotWritableReason::Register,
+                ..
+            }
+        ));
+        assert_eq!(memory.read_word_32(0x2000_0000).unwrap(), before);
+    }
+
+    fn coredump_path(base: String) -> PathBuf {
+        let possible_coredump_paths = [
+            get_path_for_test_files(format!("{base}.coredump").as_str()),
+            get_path_for_test_files(format!("{base}_coredump.elf").as_str()),
+        ];
+
+        possible_coredump_paths
+            .iter()
+            .find(|path| path.exists())
+            .unwrap_or_else(|| {
+                panic!(
+                    "No coredump found for chip {base}. Expected one of: {possible_coredump_paths:?}"
+                )
+            })
+            .clone()
+    }
+
+    #[test]
+    fn unwind_same_value() {
+        let rule = gimli::RegisterRule::SameValue;
+
+        let mut callee_frame_registers = DebugRegisters::default();
+        let debug_register = CORTEX_M_CORE_REGISTERS.core_registers().next().unwrap();
+
+        let expected_value = Some(RegisterValue::U32(0x1234));
+
+        callee_frame_registers.0.push(DebugRegister {
+            core_register: debug_register,
+            dwarf_id: Some(0),
+            value: expected_value,
+        });
+
+        let mut memory = MockMemory::new();
+
+        let value = unwind_register_using_rule(
+            debug_register,
+            &callee_frame_registers,
+            None,
+            &mut memory,
+            RunTimeEndian::Little,
+            rule,
+        )
+        .unwrap();
+
+        assert_eq!(value, expected_value);
+    }
+
+    #[test_case(RunTimeEndian::Little, vec![0xfe, 0xca, 0x00, 0x00]; "little endian")]
+    #[test_case(RunTimeEndian::Big, vec![0x00, 0x00, 0xca, 0xfe]; "big endian")]
+    fn unwind_offset(endianness: RunTimeEndian, stacked_bytes: Vec<u8>) {
+        let cfa = 0x1000;
+        let offset = 4;
+        let rule = gimli::RegisterRule::Offset(offset as i64);
+        let expected_value = 0xcafe;
+
+        let expected_register_value = Some(RegisterValue::U32(expected_value));
+
+        let mut memory = MockMemory::new();
+        memory.add_range(cfa + offset, stacked_bytes);
+
+        let mut callee_frame_registers = DebugRegisters::default();
+        let debug_register = CORTEX_M_CORE_REGISTERS.core_registers().next().unwrap();
+
+        callee_frame_registers.0.push(DebugRegister {
+            core_register: debug_register,
+            dwarf_id: Some(0),
+            value: None,
+        });
+
+        // This is necessary for the unwind code to determine the address size of the system
+        callee_frame_registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(15),
+            value: Some(RegisterValue::U32(0x0)),
+        });
+
+        let value = unwind_register_using_rule(
+            debug_register,
+            &callee_frame_registers,
+            Some(cfa),
+            &mut memory,
+            endianness,
+            rule,
+        )
+        .unwrap();
+
+        assert_eq!(value, expected_register_value);
+    }
+
+    #[test]
+    fn unwind_undefined_for_frame_pointer() {
+        let mut callee_frame_registers = DebugRegisters::default();
+        callee_frame_registers.0.push(DebugRegister {
+            core_register: &cortex_m::FP,
+            dwarf_id: Some(7),
+            value: Some(RegisterValue::U32(0x100)),
+        });
+
+        // This is necessary for the unwind code to determine the address size of the system
+        callee_frame_registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(15),
+            value: Some(RegisterValue::U32(0x0)),
+        });
+
+        let cfa = 0x200;
+
+        let mut memory = MockMemory::new();
+
+        let value = unwind_register_using_rule(
+            &cortex_m::FP,
+            &callee_frame_registers,
+            Some(cfa),
+            &mut memory,
+            RunTimeEndian::Little,
+            RegisterRule::Undefined,
+        )
+        .unwrap();
+
+        // If there is no rule defined for the frame pointer,
+        // we assume that it is the same as the canonical frame address.
+        assert_eq!(value, Some(RegisterValue::U32(0x200)));
+    }
+
+    #[test_case(0x401000, "add"; "function in the first subprogram")]
+    #[test_case(0x401024, "main"; "function in the second subprogram")]
+    fn dwarf5_function_names(address: u64, expected_name: &str) {
+        // The fixture uses `DW_FORM_strx1` for names and `DW_FORM_addrx` for addresses.
+        let debug_info = load_test_elf_as_debug_info("dwarf5/main.elf");
+
+        let (_, function_dies) = debug_info.get_function_dies(address).unwrap();
+        let function_name = function_dies
+            .last()
+            .and_then(|die| die.function_name(&debug_info));
+
+        assert_eq!(function_name.as_deref(), Some(expected_name));
+    }
+
+    #[test_case(object::BinaryFormat::Elf, gimli::SectionId::DebugInfo, ".debug_info"; "elf")]
+    #[test_case(object::BinaryFormat::Coff, gimli::SectionId::DebugStrOffsets, ".debug_str_offsets"; "coff")]
+    #[test_case(object::BinaryFormat::MachO, gimli::SectionId::DebugInfo, "__debug_info"; "mach-o")]
+    #[test_case(object::BinaryFormat::MachO, gimli::SectionId::DebugStrOffsets, "__debug_str_offs"; "mach-o truncated")]
+    fn section_names(format: object::BinaryFormat, id: gimli::SectionId, expected: &str) {
+        assert_eq!(section_name(format, id), expected);
+    }
+
+    #[test]
+    fn macho_source_location() {
+        let debug_info = load_test_elf_as_debug_info("macho/main.o");
+
+        let location = debug_info.get_source_location(0xa).unwrap();
+        assert_eq!(location.line, Some(4));
+        assert_eq!(location.column, Some(ColumnType::Column(12)));
+    }
+
+    #[test]
+    #[expect(clippy::single_range_in_vec_init)]
+    fn units_info() {
+        let mut debug_info =
+            DebugInfo::from_file(get_path_for_test_files("source-files/main.elf")).unwrap();
+        debug_info.set_load_bias(0x1000);
+
+        let units = debug_info.units_info();
+        assert_eq!(units.len(), 2);
+
+        let producer = "GNU C17 12.2.0 -mtune=generic -march=x86-64 -g -O1 -fno-pie -fno-asynchronous-unwind-tables";
+        for (unit, name, ranges) in [
+            (&units[0], "main.c", vec![0x402000..0x40201b]),
+            (&units[1], "util.c", vec![0x40201b..0x402030]),
+        ] {
+            assert_eq!(unit.name.as_deref(), Some(name));
+            assert_eq!(unit.producer.as_deref(), Some(producer));
+            assert_eq!(unit.language, Some(gimli::DW_LANG_C11));
+            assert_eq!(
+                unit.comp_dir,
+                Some(TypedPath::derive("/tmp/source-files").to_path_buf())
+            );
+            assert_eq!(unit.ranges, ranges);
+            assert_eq!(unit.dwarf_version, 5);
+        }
+    }
+
+    #[test]
+    #[expect(clippy::single_range_in_vec_init)]
+    fn functions() {
+        let mut debug_info =
+            DebugInfo::from_file(get_path_for_test_files("functions/main.elf")).unwrap();
+        debug_info.set_load_bias(0x1000);
+
+        let functions: Vec<_> = debug_info
+            .functions()
+            .map(|function| {
+                (
+                    function.name.unwrap(),
+                    function.ranges,
+                    function.is_inline_abstract,
+                    function.source.and_then(|source| source.line),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            functions,
+            vec![
+                (
+                    "_start".to_string(),
+                    vec![0x402020..0x402043],
+                    false,
+                    Some(20)
+                ),
+                (
+                    "checked".to_string(),
+                    vec![0x402010..0x40201e, 0x402002..0x40200d],
+                    false,
+                    Some(12)
+                ),
+                ("square".to_string(), vec![], true, Some(8)),
+                ("halt".to_string(), vec![0x402000..0x402002], false, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    #[expect(clippy::single_range_in_vec_init)]
+    fn out_of_line_instance_names() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("implicit-values/main.elf")).unwrap();
+
+        // The out of line copy of `compute` refers to its abstract instance for the name and the location.
+        let functions: Vec<_> = debug_info
+            .functions()
+            .filter(|function| function.name.as_deref() == Some("compute"))
+            .map(|function| {
+                (
+                    function.ranges,
+                    function.is_inline_abstract,
+                    function.source.and_then(|source| source.line),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            functions,
+            vec![
+                (vec![], true, Some(12)),
+                (vec![0x401010..0x401039], false, Some(12))
+            ]
+        );
+    }
+
+    #[test]
+    #[expect(clippy::single_range_in_vec_init)]
+    fn specification_attributes() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("specification/main.elf")).unwrap();
+
+        // The definition of the member function has its own line, and refers to the declaration in the
+        // class for the name and the file.
+        let function = debug_info
+            .functions()
+            .find(|function| function.ranges == [0x401000..0x401024])
+            .unwrap();
+        let source = function.source.unwrap();
+        assert_eq!(function.name.as_deref(), Some("increment"));
+        assert_eq!(
+            source.path,
+            TypedPath::derive("/tmp/specification/main.cpp").to_path_buf()
+        );
+        assert_eq!(source.line, Some(14));
+
+        // The definition of the static member only has a location, and refers to the declaration for the name.
+        let unit_info = &debug_info.unit_infos[0];
+        let definition = unit_info.unit.entry(gimli::UnitOffset(0x99)).unwrap();
+        assert_eq!(
+            crate::unit_info::extract_name(&debug_info, unit_info, &definition).unwrap(),
+            Some("instances".to_string())
+        );
+    }
+
+    #[test]
+    fn functions_with_disjoint_ranges() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("hot-cold/main.elf")).unwrap();
+
+        // `check` is split into a hot part at 0x401020, and a cold part at 0x401000 where `report` is inlined.
+        let (_, functions) = debug_info.get_function_dies(0x401008).unwrap();
+        let names: Vec<_> = functions
+            .iter()
+            .map(|function| function.function_name(&debug_info))
+            .collect();
+        assert_eq!(
+            names,
+            [Some("check".to_string()), Some("report".to_string())]
+        );
+
+        let check = &functions[0];
+        assert_eq!(check.entry_pc(), Some(0x401020));
+        assert_eq!(
+            check.range_containing(0x401008),
+            Some(&(0x401000..0x40101d))
+        );
+        assert_eq!(
+            check.range_containing(0x401030),
+            Some(&(0x401020..0x401034))
+        );
+        assert_eq!(check.range_containing(0x40101d), None);
+        assert_eq!(functions[1].entry_pc(), Some(0x401005));
+
+        let addresses: Vec<_> = debug_info
+            .find_functions_by_name("check", NameMatch::Exact)
+            .into_iter()
+            .map(|function| function.address)
+            .collect();
+        assert_eq!(addresses, [0x401020]);
+    }
+
+    #[test]
+    fn frames_at_inlined_address() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("functions/main.elf")).unwrap();
+
+        let frames: Vec<_> = debug_info
+            .frames_at_address(0x40101b)
+            .into_iter()
+            .map(|frame| {
+                let source_location = frame.source_location.unwrap();
+                (
+                    frame.function_name.unwrap(),
+                    frame.is_inlined,
+                    source_location.line,
+                    source_location.column,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            frames,
+            vec![
+                (
+                    "square".to_string(),
+                    true,
+                    Some(9),
+                    Some(ColumnType::Column(14))
+                ),
+                (
+                    "checked".to_string(),
+                    false,
+                    Some(17),
+                    Some(ColumnType::Column(12))
+                ),
+            ]
+        );
+
+        assert!(debug_info.frames_at_address(0x500000).is_empty());
+    }
+
+    #[test_case("transfer", NameMatch::Suffix, &[("main::spi::transfer<u16>", 0x201290), ("main::spi::transfer<u8>", 0x2012a0), ("main::spi::Spi::transfer", 0x201280)]; "suffix")]
+    #[test_case("spi::transfer", NameMatch::Suffix, &[("main::spi::transfer<u16>", 0x201290), ("main::spi::transfer<u8>", 0x2012a0)]; "partially qualified suffix")]
+    #[test_case("main::spi::transfer", NameMatch::Exact, &[("main::spi::transfer<u16>", 0x201290), ("main::spi::transfer<u8>", 0x2012a0)]; "exact monomorphizations")]
+    #[test_case("main::spi::transfer<u8>", NameMatch::Exact, &[("main::spi::transfer<u8>", 0x2012a0)]; "exact with generic arguments")]
+    #[test_case("spi::transfer", NameMatch::Exact, &[]; "exact requires qualified name")]
+    fn find_functions_by_name(name: &str, match_mode: NameMatch, expected: &[(&str, u64)]) {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("function-names/main.elf")).unwrap();
+
+        let matches: Vec<_> = debug_info
+            .find_functions_by_name(name, match_mode)
+            .into_iter()
+            .map(|function| (function.name, function.address))
+            .collect();
+
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|(name, address)| (name.to_string(), *address))
+            .collect();
+        assert_eq!(matches, expected);
+    }
+
+    /// Matching the demangled linkage name needs a demangler.
+    #[cfg(feature = "rustc-demangle")]
+    #[test]
+    fn find_functions_by_demangled_name() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("function-names/main.elf")).unwrap();
+
+        let matches: Vec<_> = debug_info
+            .find_functions_by_name("main::spi::Spi::transfer", NameMatch::Demangled)
+            .into_iter()
+            .map(|function| (function.name, function.address))
+            .collect();
+        assert_eq!(
+            matches,
+            [("main::spi::Spi::transfer".to_string(), 0x201280)]
+        );
+    }
+
+    #[test]
+    fn function_breakpoint_locations() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("function-names/main.elf")).unwrap();
+
+        // One location for each monomorphization, after the prologue.
+        let locations: Vec<_> = debug_info
+            .get_function_breakpoint_locations("spi::transfer")
+            .into_iter()
+            .map(|location| (location.function_name.unwrap(), location.address))
+            .collect();
+        assert_eq!(
+            locations,
+            [
+                ("main::spi::transfer<u16>".to_string(), 0x201290),
+                ("main::spi::transfer<u8>".to_string(), 0x2012a0),
+            ]
+        );
+
+        let locations = debug_info.get_function_breakpoint_locations("_start");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].address, 0x2012b1);
+        assert_eq!(locations[0].line, Some(31));
+        assert!(locations[0].is_stmt);
+
+        // The entry address, before the prologue.
+        let options = BreakpointOptions::default().with_raw_entry(true);
+        let locations =
+            debug_info.get_function_breakpoint_locations_with_options("_start", options);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].address, 0x2012b0);
+        assert_eq!(locations[0].line, Some(30));
+
+        assert!(
+            debug_info
+                .get_function_breakpoint_locations("receive")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn function_breakpoint_locations_without_prologue_end() {
+        let debug_info =
+            DebugInfo::from_file(get_path_for_test_files("breakpoint-locations/main.elf")).unwrap();
+
+        // Without `prologue_end`, the prologue ends at the first statement of the next line.
+        let locations: Vec<_> = ["first", "second", "_start"]
+            .into_iter()
+            .flat_map(|name| debug_info.get_function_breakpoint_locations(name))
+            .map(|location| (location.address, location.line))
+            .collect();
+        assert_eq!(
+            locations,
+            [
+                (0x401000, Some(10)),
+                (0x401010, Some(15)),
+                (0x40102f, Some(21))
+            ]
+        );
+
+        let options = BreakpointOptions::default().with_raw_entry(true);
+        let locations =
+            debug_info.get_function_breakpoint_locations_with_options("second", options);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].address, 0x401010);
+        assert_eq!(locations[0].line, Some(14));
+    }
+
+    #[test_case(false; "strict")]
+    #[test_case(true; "permissive")]
+    fn stripped_binary(allow_missing_debug_info: bool) {
+        let options = DebugInfoOptions {
+            allow_missing_debug_info,
+            ..Default::default()
+        };
+        let result = DebugInfo::from_file_with_options(
+            get_path_for_test_files("stripped/main.elf"),
+            &options,
+        );
+
+        if allow_missing_debug_info {
+            let debug_info = result.unwrap();
+            assert!(debug_info.get_source_location(0x40100a).is_none());
+        } else {
+            assert!(matches!(result, Err(crate::DebugError::NoDebugInfo)));
+        }
+    }
+
+    #[test]
+    fn coff_without_dwarf() {
+        let result = DebugInfo::from_file(get_path_for_test_files("coff/codeview.obj"));
+
+        assert!(matches!(
+            result,
+            Err(crate::DebugError::NoDwarf(object::BinaryFormat::Coff))
+        ));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn zstd_compressed_sections() {
+        let debug_info = load_test_elf_as_debug_info("compressed/zstd.elf");
+
+        let location = debug_info.get_source_location(0x401008).unwrap();
+        assert_eq!(location.line, Some(12));
+    }
+
+    #[test]
+    fn corrupt_compressed_section() {
+        let error = DebugInfo::from_file(get_path_for_test_files("compressed/corrupt.elf"))
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(error, crate::DebugError::SectionDecompression { ref section, .. } if section == ".debug_info"),
+            "Unexpected error: {error:?}"
+        );
+    }
+
+    #[test_case(0; "no bias")]
+    #[test_case(0x2000_0000; "positive bias")]
+    #[test_case(-0x40_0000; "negative bias")]
+    fn load_bias(bias: i64) {
+        let mut debug_info = load_test_elf_as_debug_info("dwarf5/main.elf");
+        debug_info.set_load_bias(bias);
+        let runtime_address = |address: u64| address.wrapping_add_signed(bias);
+
+        assert_eq!(
+            debug_info.function_name(runtime_address(0x401000)),
+            Some("add".to_string())
+        );
+        assert_eq!(
+            debug_info.function_name(runtime_address(0x401024)),
+            Some("main".to_string())
+        );
+
+        let location = debug_info
+            .get_source_location(runtime_address(0x40100a))
+            .unwrap();
+        assert_eq!(location.line, Some(4));
+        assert_eq!(location.address, Some(runtime_address(0x40100a)));
+
+        let breakpoint = debug_info
+            .get_breakpoint_location(TypedPath::derive("/tmp/dwarf5/main.c"), 8, Some(15))
+            .unwrap();
+        assert_eq!(breakpoint.address, runtime_address(0x401024));
+        assert_eq!(
+            breakpoint.source_location.address,
+            Some(runtime_address(0x401024))
+        );
+    }
+
+    // `i` is declared in a lexical block of `_start`, and is `0` in the first entry of its location
+    // list. The ranges of both are addresses in the debug information, which the program counter is
+    // converted to.
+    #[test_case(0; "no bias")]
+    #[test_case(0x2000_0000; "positive bias")]
+    #[test_case(-0x40_0000; "negative bias")]
+    fn block_variables_with_load_bias(bias: i64) {
+        let mut debug_info = load_test_elf_as_debug_info("hot-cold/main.elf");
+        debug_info.set_load_bias(bias);
+        let address = 0x401041_u64.wrapping_add_signed(bias);
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U64(address)),
+        });
+
+        let (unit_info, functions) = debug_info.get_function_dies(address).unwrap();
+        let mut cache = debug_info
+            .create_function_scope_cache(&functions[0], unit_info)
+            .unwrap();
+        let mut root = cache.root_variable().clone();
+        debug_info
+            .cache_deferred_variables(
+                &mut cache,
+                &mut MockMemory::new(),
+                &mut root,
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+
+        let root = cache.root_variable().clone();
+        assert_eq!(value_at_path(&cache, &root, "i"), "0");
+    }
+
+    #[test_case("line-tables/main-v3.elf"; "dwarf 3")]
+    #[test_case("line-tables/main-v4.elf"; "dwarf 4")]
+    #[test_case("line-tables/main-v5.elf"; "dwarf 5")]
+    fn line_table_paths(elf_file: &str) {
+        let debug_info = load_test_elf_as_debug_info(elf_file);
+
+        let header_path = TypedPath::derive("/tmp/line-tables/include/util.h");
+        let source_path = TypedPath::derive("/tmp/line-tables/src/main.c");
+
+        let paths: Vec<_> = debug_info
+            .source_files()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![header_path.to_path_buf(), source_path.to_path_buf()]
+        );
+
+        let location = debug_info.get_source_location(0x401007).unwrap();
+        assert_eq!(location.path, header_path.to_path_buf());
+        assert_eq!(location.line, Some(2));
+
+        let breakpoint = debug_info
+            .get_breakpoint_location(source_path, 7, None)
+            .unwrap();
+        assert!((0x401013..0x401028).contains(&breakpoint.address));
+        assert_eq!(breakpoint.source_location.path, source_path.to_path_buf());
+    }
+
+    #[test]
+    fn breakpoint_locations_of_inlined_function() {
+        let debug_info = load_test_elf_as_debug_info("breakpoint-locations/main.elf");
+        let path = TypedPath::derive("/tmp/breakpoint-locations/main.c");
+
+        // `bump` is inlined once into `first`, and twice into `second`.
+        let locations = debug_info.get_breakpoint_locations(path, 5, None);
+        let addresses: Vec<_> = locations.iter().map(|location| location.address).collect();
+        assert_eq!(addresses, [0x401000, 0x401010, 0x40101f]);
+        for location in &locations {
+            assert_eq!(location.function_name.as_deref(), Some("bump"));
+            assert_eq!(location.line, Some(5));
+            assert!(location.is_stmt);
+        }
+
+        let breakpoint = debug_info.get_breakpoint_location(path, 5, None).unwrap();
+        assert_eq!(breakpoint.address, 0x401000);
+
+        // Line 11 has no statement, only the return instruction of `first`.
+        let locations = debug_info.get_breakpoint_locations(path, 11, None);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].address, 0x40100f);
+        assert_eq!(locations[0].function_name.as_deref(), Some("first"));
+        assert!(!locations[0].is_stmt);
+
+        let locations = debug_info.get_breakpoint_locations(path, 21, None);
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].address, 0x40102f);
+        assert_eq!(locations[0].function_name.as_deref(), Some("_start"));
+
+        assert!(
+            debug_info
+                .get_breakpoint_locations(path, 2, None)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn breakpoint_locations_in_range() {
+        let debug_info = load_test_elf_as_debug_info("breakpoint-locations/main.elf");
+        let path = TypedPath::derive("/tmp/breakpoint-locations/main.c");
+
+        // Line 5 also has instructions at column 13 which are not statements, and line 9 is the
+        // prologue of `first`.
+        assert_eq!(
+            debug_info.breakpoint_locations_in_range(path, 4, 12),
+            [
+                (5, vec![ColumnType::Column(5)]),
+                (10, vec![ColumnType::Column(5)]),
+                (11, vec![ColumnType::Column(1)]),
+            ]
+        );
+        assert!(
+            debug_info
+                .breakpoint_locations_in_range(path, 6, 9)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn breakpoint_location_on_next_line() {
+        let debug_info = load_test_elf_as_debug_info("breakpoint-locations/main.elf");
+        let path = TypedPath::derive("/tmp/breakpoint-locations/main.c");
+
+        // Line 6 is the end of `bump`, without code, and line 9 is the prologue of `first`.
+        assert!(debug_info.get_breakpoint_location(path, 6, None).is_err());
+
+        let options = BreakpointOptions::default().with_next_line_window(20);
+        let breakpoint = debug_info
+            .get_breakpoint_location_with_options(path, 6, None, options)
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x401000);
+        assert_eq!(breakpoint.source_location.line, Some(10));
+
+        // The requested line is used if it has code.
+        let breakpoint = debug_info
+            .get_breakpoint_location_with_options(path, 16, None, options)
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x40101f);
+        assert_eq!(breakpoint.source_location.line, Some(16));
+
+        let options = BreakpointOptions::default().with_next_line_window(3);
+        assert!(
+            debug_info
+                .get_breakpoint_location_with_options(path, 6, None, options)
+                .is_err()
+        );
+    }
+
+    #[test_case(0x4000e8, None, "/tmp/multi-unit/library/generic.c", 1; "first unit")]
+    #[test_case(0x4000e8, Some("/tmp/multi-unit/app"), "/tmp/multi-unit/app/main.c", 1; "preferred unit")]
+    #[test_case(0x4000ec, None, "/tmp/multi-unit/app/main.c", 4; "exact row")]
+    #[test_case(0x4000ef, None, "/tmp/multi-unit/app/main.c", 5; "statement row")]
+    #[test_case(0x4000ef, Some("/tmp/multi-unit/library"), "/tmp/multi-unit/library/generic.c", 5; "preferred unit over statement row")]
+    fn source_location_in_folded_function(
+        address: u64,
+        preferred_comp_dir: Option<&str>,
+        expected_path: &str,
+        expected_line: u64,
+    ) {
+        let options = DebugInfoOptions {
+            preferred_comp_dir: preferred_comp_dir.map(PathBuf::from),
+            ..Default::default()
+        };
+        let debug_info = DebugInfo::from_file_with_options(
+            get_path_for_test_files("multi-unit/main.elf"),
+            &options,
+        )
+        .unwrap();
+
+        let location = debug_info.get_source_location(address).unwrap();
+        assert_eq!(
+            location.path,
+            TypedPath::derive(expected_path).to_path_buf()
+        );
+        assert_eq!(location.line, Some(expected_line));
+    }
+
+    #[test_case(0x4000ec, 4, 0x4000ec..0x4000ef, false; "exact row")]
+    #[test_case(0x4000ed, 3, 0x4000e8..0x4000ef, false; "previous row")]
+    #[test_case(0x4000ef, 5, 0x4000ef..0x4000f0, true; "statement row")]
+    fn resolved_source_location(
+        address: u64,
+        expected_line: u64,
+        expected_range: Range<u64>,
+        expected_is_stmt: bool,
+    ) {
+        let debug_info = load_test_elf_as_debug_info("multi-unit/main.elf");
+
+        let resolved = debug_info.resolve_source_location(address).unwrap();
+        assert_eq!(resolved.location.line, Some(expected_line));
+        assert_eq!(resolved.location.address, Some(expected_range.start));
+        assert_eq!(resolved.address_range, Some(expected_range));
+        assert_eq!(resolved.is_stmt, expected_is_stmt);
+        assert!(!resolved.end_sequence);
+    }
+
+    #[test_case(0x401014, 0x40100e, false; "row with a line")]
+    #[test_case(0x401018, 0x40100e, true; "row with line zero")]
+    #[test_case(0x40101c, 0x40100e, true; "inside row with line zero")]
+    fn source_location_skips_line_zero(address: u64, expected_address: u64, line_fallback: bool) {
+        let debug_info = load_test_elf_as_debug_info("line-zero/main.elf");
+
+        let location = debug_info.get_source_location(address).unwrap();
+        assert_eq!(location.line, Some(5));
+        assert_eq!(location.address, Some(expected_address));
+        assert_eq!(location.line_fallback, line_fallback);
+    }
+
+    #[test]
+    fn breakpoints_skip_line_zero() {
+        let debug_info = load_test_elf_as_debug_info("line-zero/main.elf");
+
+        // The code without a line is not a halt location, so the next line is used.
+        let breakpoint = VerifiedBreakpoint::for_address(&debug_info, 0x401018).unwrap();
+        assert_eq!(breakpoint.address, 0x401020);
+        assert_eq!(breakpoint.source_location.line, Some(6));
+        assert!(!breakpoint.source_location.line_fallback);
+    }
+
+    #[test]
+    fn file_checksums() {
+        let debug_info = load_test_elf_as_debug_info("checksums/main.elf");
+        let checksum = [
+            0xc3, 0x0d, 0x0e, 0x3d, 0xac, 0x34, 0x74, 0x9d, 0x23, 0xb6, 0x16, 0xdf, 0x65, 0xda,
+            0xea, 0x68,
+        ];
 
-        callee_frame_registers.0.push(DebugRegister {
-            core_register: debug_register,
-            dwarf_id: Some(0),
-            value: expected_value,
-        });
+        let source_files = debug_info.source_files();
+        assert_eq!(source_files.len(), 1);
+        assert_eq!(source_files[0].checksum, Some(checksum));
 
-        let mut memory = MockMemory::new();
+        assert_eq!(
+            debug_info.file_checksum(Path::new("/tmp/checksums/main.c")),
+            Some(checksum)
+        );
+        assert_eq!(
+            debug_info.file_checksum(Path::new("/tmp/checksums/other.c")),
+            None
+        );
 
-        let value = unwind_register_using_rule(
-            debug_register,
-            &callee_frame_registers,
-            None,
-            &mut memory,
-            rule,
-        )
-        .unwrap();
+        let breakpoint = debug_info
+            .get_breakpoint_location(TypedPath::derive("/tmp/checksums/main.c"), 8, Some(15))
+            .unwrap();
+        assert_eq!(breakpoint.checksum, Some(checksum));
+    }
 
-        assert_eq!(value, expected_value);
+    #[test]
+    fn file_checksums_missing() {
+        let debug_info = load_test_elf_as_debug_info("dwarf5/main.elf");
+
+        assert_eq!(
+            debug_info.file_checksum(Path::new("/tmp/dwarf5/main.c")),
+            None
+        );
+
+        let breakpoint = debug_info
+            .get_breakpoint_location(TypedPath::derive("/tmp/dwarf5/main.c"), 8, Some(15))
+            .unwrap();
+        assert_eq!(breakpoint.checksum, None);
     }
 
     #[test]
-    fn unwind_offset() {
-        let cfa = 0x1000;
-        let offset = 4;
-        let rule = gimli::RegisterRule::Offset(offset as i64);
-        let expected_value = 0xcafe;
+    fn non_utf8_paths() {
+        let debug_info = load_test_elf_as_debug_info("non-utf8/main.elf");
 
-        let expected_register_value = Some(RegisterValue::U32(expected_value));
+        // The compilation directory is Latin-1 encoded, and the invalid byte is replaced.
+        let path = TypedPath::derive("/tmp/non-utf8/caf\u{FFFD}/main.c");
+
+        let location = debug_info.get_source_location(0x401008).unwrap();
+        assert_eq!(location.path, path.to_path_buf());
+        assert_eq!(location.line, Some(4));
+
+        let breakpoint = debug_info.get_breakpoint_location(path, 4, None).unwrap();
+        assert!((0x401000..0x40100f).contains(&breakpoint.address));
+        assert_eq!(breakpoint.source_location.line, Some(4));
+    }
+
+    #[test]
+    fn path_mapping_windows_build() {
+        let mut debug_info = load_test_elf_as_debug_info("windows-paths/main.elf");
+        debug_info.add_path_mapping(
+            TypedPath::derive("C:/build/agent"),
+            TypedPath::derive("/home/dev/agent"),
+        );
+
+        let local_path = TypedPath::derive("/home/dev/agent/main.c");
+
+        let location = debug_info.get_source_location(0x401008).unwrap();
+        assert_eq!(location.path, local_path.to_path_buf());
+        assert_eq!(location.line, Some(4));
+
+        // Both the local path and the path in the debug information can be used.
+        for path in [local_path, TypedPath::derive("C:\\build\\agent\\main.c")] {
+            let breakpoint = debug_info.get_breakpoint_location(path, 4, None).unwrap();
+            assert!((0x401000..0x40100f).contains(&breakpoint.address));
+            assert_eq!(breakpoint.source_location.path, local_path.to_path_buf());
+        }
+    }
+
+    #[test]
+    fn path_mapping_rustc_remapped_paths() {
+        let mut debug_info = load_test_elf_as_debug_info("function-names/main.elf");
+        debug_info.add_path_mapping(
+            TypedPath::derive("/rustc/59807616e1fa2540724bfbac14d7976d7e4a3860"),
+            TypedPath::derive("/home/dev/rust"),
+        );
+        debug_info.add_path_mapping(
+            TypedPath::derive("/tmp"),
+            TypedPath::derive("/home/dev/tmp"),
+        );
+        // The longest prefix is used, regardless of the order of the mappings.
+        debug_info.add_path_mapping(
+            TypedPath::derive("/tmp/function-names"),
+            TypedPath::derive("/home/dev/function-names"),
+        );
+
+        let local_path = TypedPath::derive("/home/dev/function-names/main.rs");
+
+        let location = debug_info.get_source_location(0x201238).unwrap();
+        assert_eq!(
+            location.path,
+            TypedPath::derive("/home/dev/rust/library/core/src/hint.rs").to_path_buf()
+        );
+        assert_eq!(location.line, Some(491));
+
+        for path in [local_path, TypedPath::derive("/tmp/function-names/main.rs")] {
+            let breakpoint = debug_info.get_breakpoint_location(path, 12, None).unwrap();
+            assert!((0x201280..0x201287).contains(&breakpoint.address));
+            assert_eq!(breakpoint.source_location.path, local_path.to_path_buf());
+        }
+    }
+
+    #[test]
+    fn error_context() {
+        let debug_info = load_test_elf_as_debug_info("source-files/main.elf");
+        let unit_info = &debug_info.unit_infos[1];
+        let entry = unit_info.unit.entry(gimli::UnitOffset(0xc)).unwrap();
+
+        let error = DebugError::Other("Unsupported type".to_string())
+            .with_context(
+                unit_info
+                    .error_context(&entry)
+                    .with_attribute(gimli::DW_AT_type),
+            )
+            .with_context(ErrorContext::at_address(0x401000));
+
+        assert_eq!(
+            error.context(),
+            Some(&ErrorContext {
+                unit_offset: Some(0xbf),
+                die_offset: Some(0xcb),
+                attribute: Some(gimli::DW_AT_type),
+                address: Some(0x401000),
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "Unsupported type (unit at 0xbf, DIE at 0xcb, attribute DW_AT_type, address 0x00401000)"
+        );
 
+        // Errors which don't interrupt processing are not wrapped, so they can still be matched.
+        let warning = DebugError::WarnAndContinue {
+            message: "Value optimized away".to_string(),
+        }
+        .with_context(ErrorContext::at_address(0x401000));
+        assert!(matches!(warning, DebugError::WarnAndContinue { .. }));
+    }
+
+    #[test_case("malformed/empty-unit.elf"; "empty unit")]
+    #[test_case("malformed/unit-length.elf"; "unit length")]
+    #[test_case("malformed/unknown-abbreviation.elf"; "unknown abbreviation")]
+    #[test_case("malformed/invalid-form.elf"; "invalid form")]
+    #[test_case("malformed/line-program-version.elf"; "line program version")]
+    #[test_case("malformed/cfa-expression.elf"; "cfa expression")]
+    #[test_case("malformed/fuzz-1.elf"; "fuzz 1")]
+    #[test_case("malformed/fuzz-2.elf"; "fuzz 2")]
+    #[test_case("malformed/fuzz-3.elf"; "fuzz 3")]
+    #[test_case("malformed/fuzz-4.elf"; "fuzz 4")]
+    #[test_case("malformed/fuzz-5.elf"; "fuzz 5")]
+    #[test_case("malformed/fuzz-6.elf"; "fuzz 6")]
+    #[test_case("malformed/fuzz-7.elf"; "fuzz 7")]
+    #[test_case("malformed/fuzz-8.elf"; "fuzz 8")]
+    fn malformed_debug_info(elf_file: &str) {
+        // Malformed debug information may fail to load, or result in missing information,
+        // but it must not panic.
+        let Ok(debug_info) = DebugInfo::from_file(get_path_for_test_files(elf_file)) else {
+            return;
+        };
+        let registers = DebugRegisters::default();
         let mut memory = MockMemory::new();
-        memory.add_word_range(cfa + offset, &[expected_value]);
 
-        let mut callee_frame_registers = DebugRegisters::default();
-        let debug_register = CORTEX_M_CORE_REGISTERS.core_registers().next().unwrap();
+        debug_info.units_info();
+        debug_info.source_files();
+        let _ = debug_info.static_variables();
+        debug_info.functions().for_each(drop);
+        debug_info.find_functions_by_name("main", NameMatch::Suffix);
+        debug_info.statistics();
+
+        for address in 0x401000..0x401040 {
+            debug_info.get_source_location(address);
+            debug_info.frames_at_address(address);
+            let _ = debug_info.get_stackframe_info(&mut memory, address, None, &registers);
+        }
 
-        callee_frame_registers.0.push(DebugRegister {
-            core_register: debug_register,
-            dwarf_id: Some(0),
-            value: None,
-        });
+        for line in 1..10 {
+            let _ = debug_info.get_breakpoint_location(
+                TypedPath::derive("/tmp/source-files/main.c"),
+                line,
+                None,
+            );
+        }
 
-        // This is necessary for the unwind code to determine the address size of the system
-        callee_frame_registers.0.push(DebugRegister {
+        let mut static_variables = debug_info.create_static_scope_cache();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
+            &mut memory,
+            10,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                auto_deref_depth: None,
+            },
+        );
+    }
+
+    #[test]
+    fn unsupported_unwind_rules() {
+        let debug_info = load_test_elf_as_debug_info("malformed/cfa-expression.elf");
+
+        let mut unwind_context = Box::new(gimli::UnwindContext::new());
+        let unwind_info =
+            get_unwind_info(&mut unwind_context, &debug_info.frame_section, 0x401000).unwrap();
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
             core_register: &cortex_m::PC,
             dwarf_id: Some(15),
-            value: Some(RegisterValue::U32(0x0)),
+            value: Some(RegisterValue::U32(0x401000)),
         });
+        let return_address = DebugRegister {
+            core_register: &cortex_m::RA,
+            dwarf_id: Some(16),
+            value: None,
+        };
 
-        let value = unwind_register_using_rule(
-            debug_register,
-            &callee_frame_registers,
-            Some(cfa),
+        // The CFA expression and the value expression of the return address are not supported,
+        // so the frame cannot be unwound.
+        assert!(determine_cfa(&registers, unwind_info).is_err());
+        assert!(
+            unwind_register(
+                &return_address,
+                &registers,
+                unwind_info,
+                None,
+                &mut MockMemory::new(),
+                RunTimeEndian::Little,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn frame_base_from_call_frame_cfa() {
+        let debug_info = load_test_elf_as_debug_info("source-files/main.elf");
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(0x401004)),
+        });
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::SP,
+            dwarf_id: Some(7),
+            value: Some(RegisterValue::U32(0x1000)),
+        });
+
+        // The frame base of `_start` is `DW_OP_call_frame_cfa`, and the CFA at this address is
+        // `RSP+16`.
+        let (_, functions) = debug_info.get_function_dies(0x401004).unwrap();
+        let frame_base = functions[0].frame_base(
+            &debug_info,
+            &mut MockMemory::new(),
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                auto_deref_depth: None,
+            },
+        );
+
+        assert_eq!(frame_base.unwrap(), Some(0x1010));
+    }
+
+    #[test_case(&[0x06], gimli::Value::Generic(0x0123_4567_89ab_cdef); "deref")]
+    #[test_case(&[0x94, 0x02], gimli::Value::Generic(0xcdef); "deref_size")]
+    #[test_case(&[0x94, 0x03], gimli::Value::Generic(0xab_cdef); "deref_size with odd size")]
+    #[test_case(&[0xa6, 0x04, 0x4e], gimli::Value::I32(0x89ab_cdef_u32 as i32); "deref_type")]
+    fn expression_memory_reads(deref: &[u8], expected: gimli::Value) {
+        let debug_info = load_test_elf_as_debug_info("source-files/main.elf");
+
+        // `DW_OP_addr 0x2000`, the dereference, and `DW_OP_stack_value`.
+        let mut expression = vec![0x03];
+        expression.extend_from_slice(&0x2000_u64.to_le_bytes());
+        expression.extend_from_slice(deref);
+        expression.push(0x9f);
+
+        let mut memory = MockMemory::new();
+        memory.add_range(0x2000, 0x0123_4567_89ab_cdef_u64.to_le_bytes().to_vec());
+
+        let pieces = evaluate_test_expression(
+            &debug_info,
+            expression,
             &mut memory,
-            rule,
+            &DebugRegisters::default(),
         )
         .unwrap();
 
-        assert_eq!(value, expected_register_value);
+        assert_eq!(
+            pieces[0].location,
+            gimli::Location::Value { value: expected }
+        );
     }
 
-    #[test]
-    fn unwind_undefined_for_frame_pointer() {
-        let mut callee_frame_registers = DebugRegisters::default();
-        callee_frame_registers.0.push(DebugRegister {
+    #[test_case(0x43, RegisterValue::U64(0xffff_fffe), Some(gimli::Value::I32(-2)); "int")]
+    #[test_case(0x5f, RegisterValue::U128(0x0123_4567_89ab_cdef), Some(gimli::Value::U64(0x0123_4567_89ab_cdef)); "unsigned long long")]
+    #[test_case(0x7b, RegisterValue::U32(1.5_f32.to_bits()), Some(gimli::Value::F32(1.5)); "float")]
+    #[test_case(0x97, RegisterValue::U64(2.25_f64.to_bits()), Some(gimli::Value::F64(2.25)); "double")]
+    #[test_case(0x97, RegisterValue::U32(0), None; "double from 32-bit register")]
+    fn expression_typed_register_reads(
+        base_type: u8,
+        value: RegisterValue,
+        expected: Option<gimli::Value>,
+    ) {
+        let debug_info = load_test_elf_as_debug_info("base-types/main.elf");
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
             core_register: &cortex_m::FP,
+            dwarf_id: Some(17),
+            value: Some(value),
+        });
+
+        // `DW_OP_regval_type 17 <base_type>` and `DW_OP_stack_value`. The offset of the base type
+        // is ULEB128 encoded.
+        let expression = if base_type < 0x80 {
+            vec![0xa5, 17, base_type, 0x9f]
+        } else {
+            vec![0xa5, 17, base_type, 0x01, 0x9f]
+        };
+        let pieces =
+            evaluate_test_expression(&debug_info, expression, &mut MockMemory::new(), &registers);
+
+        match expected {
+            Some(value) => assert_eq!(
+                pieces.unwrap()[0].location,
+                gimli::Location::Value { value }
+            ),
+            None => assert!(pieces.is_err()),
+        }
+    }
+
+    #[test]
+    fn expression_entry_values() {
+        let debug_info = load_test_elf_as_debug_info("source-files/main.elf");
+
+        // At this address in `_start`, the CFA is `RSP+16`, which is the stack pointer on entry.
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(0x401004)),
+        });
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::SP,
             dwarf_id: Some(7),
-            value: Some(RegisterValue::U32(0x100)),
+            value: Some(RegisterValue::U32(0x1000)),
         });
 
-        // This is necessary for the unwind code to determine the address size of the system
-        callee_frame_registers.0.push(DebugRegister {
+        // `DW_OP_entry_value(DW_OP_breg7 8)` and `DW_OP_stack_value`.
+        let pieces = evaluate_test_expression(
+            &debug_info,
+            vec![0xa3, 0x02, 0x77, 0x08, 0x9f],
+            &mut MockMemory::new(),
+            &registers,
+        )
+        .unwrap();
+        assert_eq!(
+            pieces[0].location,
+            gimli::Location::Value {
+                value: gimli::Value::Generic(0x1018)
+            }
+        );
+
+        // `DW_OP_entry_value(DW_OP_reg5)` and `DW_OP_stack_value`. The unwind does not recover a value
+        // for the register, so its value on entry is not available.
+        let result = debug_info.unit_infos[0]
+            .evaluate_expression(
+                &debug_info,
+                &mut MockMemory::new(),
+                gimli::Expression(gimli::EndianReader::new(
+                    SectionData::from(vec![0xa3, 0x01, 0x55, 0x9f]),
+                    RunTimeEndian::Little,
+                )),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            result,
+            ExpressionResult::Value(VariableValue::Unavailable(UnavailableReason::EntryValue))
+        ));
+    }
+
+    #[test]
+    fn expression_implicit_values() {
+        let debug_info = load_test_elf_as_debug_info("implicit-values/main.elf");
+        let registers = DebugRegisters::default();
+
+        // `DW_OP_implicit_value` with the 8 bytes of the `double` 2.5.
+        let mut expression = vec![0x9e, 0x08];
+        expression.extend(2.5f64.to_le_bytes());
+        let result = debug_info.unit_infos[0]
+            .evaluate_expression(
+                &debug_info,
+                &mut MockMemory::new(),
+                gimli::Expression(gimli::EndianReader::new(
+                    SectionData::from(expression),
+                    RunTimeEndian::Little,
+                )),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            result,
+            ExpressionResult::Location(VariableLocation::Implicit(bytes))
+                if bytes == 2.5f64.to_le_bytes()
+        ));
+    }
+
+    // In the out of line `compute`, `scale` is 2.5 and then 4.0, and `answer` has no location after
+    // its last use.
+    #[test_case(0x1e3, 0x401012, Some(2.5f64.to_le_bytes().to_vec()); "first entry")]
+    #[test_case(0x1e3, 0x401032, Some(4.0f64.to_le_bytes().to_vec()); "second entry")]
+    #[test_case(0x1d6, 0x401037, None; "no entry")]
+    fn location_list_entries(offset: usize, program_counter: u32, expected: Option<Vec<u8>>) {
+        let debug_info = load_test_elf_as_debug_info("implicit-values/main.elf");
+        let unit_info = &debug_info.unit_infos[0];
+        let entry = unit_info.unit.entry(gimli::UnitOffset(offset)).unwrap();
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
             core_register: &cortex_m::PC,
-            dwarf_id: Some(15),
-            value: Some(RegisterValue::U32(0x0)),
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(program_counter)),
         });
 
-        let cfa = 0x200;
+        let result = unit_info
+            .extract_location(
+                &debug_info,
+                &entry,
+                &VariableLocation::Unknown,
+                &mut MockMemory::new(),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+        match (expected, result) {
+            (Some(bytes), ExpressionResult::Location(location)) => {
+                assert_eq!(location, VariableLocation::Implicit(bytes))
+            }
+            (None, ExpressionResult::Value(value)) => assert_eq!(
+                value,
+                VariableValue::Unavailable(UnavailableReason::NoLocationAtProgramCounter)
+            ),
+            (_, result) => panic!("Unexpected result {result:?}"),
+        }
+    }
+
+    // The pointer of the inlined `deref` refers to `answer` of `compute`, which is `42` at these
+    // addresses. In the out of line `compute`, the target is the variable of the abstract instance.
+    #[test_case(0x401012, 0x123; "abstract target")]
+    #[test_case(0x401040, 0x73; "concrete target")]
+    fn expression_implicit_pointers(program_counter: u32, target: u32) {
+        let debug_info = load_test_elf_as_debug_info("implicit-values/main.elf");
+
+        let mut registers = DebugRegisters::default();
+        registers.0.push(DebugRegister {
+            core_register: &cortex_m::PC,
+            dwarf_id: Some(16),
+            value: Some(RegisterValue::U32(program_counter)),
+        });
+
+        // `DW_OP_implicit_pointer` to the variable at `target`, without an offset.
+        let mut expression = vec![0xa0];
+        expression.extend(target.to_le_bytes());
+        expression.push(0x00);
+        let result = debug_info.unit_infos[0]
+            .evaluate_expression(
+                &debug_info,
+                &mut MockMemory::new(),
+                gimli::Expression(gimli::EndianReader::new(
+                    SectionData::from(expression),
+                    RunTimeEndian::Little,
+                )),
+                StackFrameInfo {
+                    registers: &registers,
+                    frame_base: None,
+                    canonical_frame_address: None,
+                    auto_deref_depth: None,
+                },
+            )
+            .unwrap();
+        assert!(matches!(
+            result,
+            ExpressionResult::Location(VariableLocation::ImplicitPointer(target))
+                if *target == VariableLocation::Implicit(42i32.to_le_bytes().to_vec())
+        ));
+    }
+
+    #[test]
+    fn expression_thread_local_addresses() {
+        let mut debug_info = load_test_elf_as_debug_info("source-files/main.elf");
+        let registers = DebugRegisters::default();
+
+        // `DW_OP_const4u 0x10` and `DW_OP_form_tls_address`.
+        let expression = [0x0c, 0x10, 0x00, 0x00, 0x00, 0x9b];
+        let evaluate = |debug_info: &DebugInfo| {
+            debug_info.unit_infos[0]
+                .evaluate_expression(
+                    debug_info,
+                    &mut MockMemory::new(),
+                    gimli::Expression(gimli::EndianReader::new(
+                        SectionData::from(expression.to_vec()),
+                        RunTimeEndian::Little,
+                    )),
+                    StackFrameInfo {
+                        registers: &registers,
+                        frame_base: None,
+                        canonical_frame_address: None,
+                        auto_deref_depth: None,
+                    },
+                )
+                .unwrap()
+        };
+
+        assert!(matches!(
+            evaluate(&debug_info),
+            ExpressionResult::Value(VariableValue::Unavailable(
+                UnavailableReason::TlsResolverNotConfigured
+            ))
+        ));
+
+        debug_info.set_tls_resolver(|offset, _| Some(0x2000_0000 + offset));
+        assert!(matches!(
+            evaluate(&debug_info),
+            ExpressionResult::Location(VariableLocation::Address(0x2000_0010))
+        ));
+    }
+
+    #[test_case(false; "named by the binary")]
+    #[test_case(true; "from the options")]
+    fn supplementary_file_references(from_options: bool) {
+        // The names and types of the variables are in `main.sup`, which is named by the `.debug_sup`
+        // section of the binary.
+        let debug_info = if from_options {
+            let data = std::fs::read(get_path_for_test_files("supplementary/main.elf")).unwrap();
+            let options = DebugInfoOptions {
+                supplementary_file: Some(get_path_for_test_files("supplementary/main.sup")),
+                ..Default::default()
+            };
+            DebugInfo::from_raw_with_options(&data, &options).unwrap()
+        } else {
+            load_test_elf_as_debug_info("supplementary/main.elf")
+        };
 
         let mut memory = MockMemory::new();
+        memory.add_range(
+            0x402000,
+            [1i32, 2, 3]
+                .into_iter()
+                .flat_map(i32::to_le_bytes)
+                .collect(),
+        );
+        let registers = DebugRegisters::default();
 
-        let value = unwind_register_using_rule(
-            &cortex_m::FP,
-            &callee_frame_registers,
-            Some(cfa),
+        let mut static_variables = debug_info.create_static_scope_cache();
+        static_variables.recurse_deferred_variables(
+            &debug_info,
             &mut memory,
-            RegisterRule::Undefined,
+            10,
+            StackFrameInfo {
+                registers: &registers,
+                frame_base: None,
+                canonical_frame_address: None,
+                auto_deref_depth: None,
+            },
+        );
+
+        let origin = static_variables
+            .get_variable_by_name(&VariableName::Named("origin".to_string()))
+            .unwrap();
+        assert_eq!(origin.type_name(), "point");
+        for (member, value) in [("x", "1"), ("y", "2")] {
+            let member = static_variables
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named(member.to_string()),
+                    origin.variable_key(),
+                )
+                .unwrap();
+            assert_eq!(member.type_name(), "int");
+            assert_eq!(member.to_string(&static_variables), value);
+        }
+
+        let count = static_variables
+            .get_variable_by_name(&VariableName::Named("count".to_string()))
+            .unwrap();
+        assert_eq!(count.type_name(), "int");
+        assert_eq!(count.to_string(&static_variables), "3");
+    }
+
+    #[test]
+    fn debug_info_is_send_and_sync() {
+        fn assert_send<T: Send + Sync>() {}
+
+        assert_send::<DebugInfo>();
+        assert_send::<crate::DebugInfoSet>();
+    }
+
+    #[test]
+    fn from_sections_matches_from_raw() {
+        let data = std::fs::read(get_path_for_test_files("dwarf5/main.elf")).unwrap();
+        let object = object::File::parse(&*data).unwrap();
+
+        let debug_info = DebugInfo::from_sections(
+            |id| {
+                object
+                    .section_by_name(id.name())
+                    .and_then(|section| section.data().ok())
+                    .map(<[u8]>::to_vec)
+            },
+            RunTimeEndian::Little,
         )
         .unwrap();
 
-        // If there is no rule defined for the frame pointer,
-        // we assume that it is the same as the canonical frame address.
-        assert_eq!(value, Some(RegisterValue::U32(0x200)));
+        let expected = DebugInfo::from_raw(&data).unwrap();
+        assert_eq!(
+            debug_info.get_source_location(0x40100a),
+            expected.get_source_location(0x40100a)
+        );
+        assert!(debug_info.get_source_location(0x40100a).is_some());
+    }
+
+    #[test]
+    fn split_dwarf_from_dwo_file() {
+        // The `.dwo` file is found in the directory of the binary.
+        let debug_info = load_test_elf_as_debug_info("split-dwarf/main.elf");
+
+        let (_, function_dies) = debug_info.get_function_dies(0x401000).unwrap();
+        let function_name = function_dies
+            .last()
+            .and_then(|die| die.function_name(&debug_info));
+        assert_eq!(function_name.as_deref(), Some("add"));
+
+        let location = debug_info.get_source_location(0x40100a).unwrap();
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test]
+    fn split_dwarf_missing_dwo_file() {
+        // Without a path, the `.dwo` file cannot be found, but the line information
+        // from the skeleton unit is still available.
+        let data = std::fs::read(get_path_for_test_files("split-dwarf/main.elf")).unwrap();
+        let debug_info = DebugInfo::from_raw(&data).unwrap();
+
+        assert!(debug_info.get_function_dies(0x401000).is_err());
+
+        let location = debug_info.get_source_location(0x40100a).unwrap();
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test_case("__ThumbV7PILongThunk_main", 12, true; "lld thumb thunk")]
+    #[test_case("__ARMv7ABSLongThunk_some_function", 16, true; "lld arm thunk")]
+    #[test_case("__flash_function_veneer", 8, true; "gnu ld veneer")]
+    #[test_case("__main_from_thumb", 8, true; "gnu ld interworking stub")]
+    #[test_case("__ThumbV7PILongThunk_main", 0, false; "unknown size")]
+    #[test_case("my_large_veneer", 1024, false; "too large")]
+    #[test_case("main", 12, false; "regular function")]
+    fn trampoline_symbols(name: &str, size: u64, expected: bool) {
+        assert_eq!(is_trampoline_symbol(name, size), expected);
     }
 }
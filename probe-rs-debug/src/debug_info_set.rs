@@ -0,0 +1,315 @@
+use std::ops::Range;
+
+use gimli::RunTimeEndian;
+use probe_rs::{Error, InstructionSet, MemoryInterface};
+use typed_path::TypedPath;
+
+use crate::{
+    BreakpointLocation, DebugError, DebugInfo, DebugRegisters, SourceLocation, StackFrame,
+    UnwindOptions, VerifiedBreakpoint, debug_info::unwind_images,
+    exception_handling::ExceptionInterface,
+};
+
+/// Identifies an image that was added to a [`DebugInfoSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageId(u32);
+
+/// A loaded image, together with the runtime address ranges that it covers.
+struct Image {
+    id: ImageId,
+    debug_info: DebugInfo,
+    address_ranges: Vec<Range<u64>>,
+}
+
+/// The debug information for multiple images that are resident on the target at the same time,
+/// for example a bootloader and an application.
+///
+/// Address based lookups are dispatched to the image that covers the address. The address ranges
+/// of the images must not overlap. Images can be added and removed at any time, for example after
+/// flashing a new application.
+#[derive(Default)]
+pub struct DebugInfoSet {
+    images: Vec<Image>,
+    next_id: u32,
+}
+
+impl DebugInfoSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an image to the set.
+    ///
+    /// The address ranges are determined from the compilation units of the debug information,
+    /// including the [load bias](DebugInfo::set_load_bias), which should be set before the image is added.
+    ///
+    /// Returns an error if the image overlaps with an image that is already part of the set.
+    pub fn insert(&mut self, debug_info: DebugInfo) -> Result<ImageId, DebugError> {
+        let address_ranges = address_ranges(&debug_info);
+
+        for range in &address_ranges {
+            if let Some(image) = self.images.iter().find(|image| {
+                image
+                    .address_ranges
+                    .iter()
+                    .any(|other| range.start < other.end && other.start < range.end)
+            }) {
+                return Err(DebugError::Other(format!(
+                    "The address range {:#010x}..{:#010x} overlaps with the image {:?}.",
+                    range.start, range.end, image.id
+                )));
+            }
+        }
+
+        let id = ImageId(self.next_id);
+        self.next_id += 1;
+
+        self.images.push(Image {
+            id,
+            debug_info,
+            address_ranges,
+        });
+
+        Ok(id)
+    }
+
+    /// Remove an image from the set, and return its debug information.
+    pub fn remove(&mut self, id: ImageId) -> Option<DebugInfo> {
+        let index = self.images.iter().position(|image| image.id == id)?;
+
+        Some(self.images.remove(index).debug_info)
+    }
+
+    /// Get the debug information of an image in the set.
+    pub fn get(&self, id: ImageId) -> Option<&DebugInfo> {
+        self.images
+            .iter()
+            .find(|image| image.id == id)
+            .map(|image| &image.debug_info)
+    }
+
+    /// Iterate over the images in the set, in the order they were added.
+    pub fn images(&self) -> impl Iterator<Item = (ImageId, &DebugInfo)> {
+        self.images
+            .iter()
+            .map(|image| (image.id, &image.debug_info))
+    }
+
+    /// Get the debug information of the image that covers the given address.
+    pub fn image_for_address(&self, address: u64) -> Option<&DebugInfo> {
+        self.images
+            .iter()
+            .find(|image| {
+                image
+                    .address_ranges
+                    .iter()
+                    .any(|range| range.contains(&address))
+            })
+            .map(|image| &image.debug_info)
+    }
+
+    /// Get the name of the function that contains the given address.
+    ///
+    /// See [`DebugInfo::function_name`].
+    pub fn function_name(&self, address: u64) -> Option<String> {
+        self.image_for_address(address)?.function_name(address)
+    }
+
+    /// Try get the [`SourceLocation`] for a given address.
+    ///
+    /// See [`DebugInfo::get_source_location`].
+    pub fn get_source_location(&self, address: u64) -> Option<SourceLocation> {
+        self.image_for_address(address)?
+            .get_source_location(address)
+    }
+
+    /// Find the program counter where a breakpoint should be set,
+    /// given a source file, a line and optionally a column.
+    ///
+    /// The images are searched in the order they were added, and the first match is returned.
+    pub fn get_breakpoint_location(
+        &self,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+    ) -> Result<VerifiedBreakpoint, DebugError> {
+        let mut last_error = None;
+
+        for image in &self.images {
+            match image.debug_info.get_breakpoint_location(path, line, column) {
+                Ok(breakpoint) => return Ok(breakpoint),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| DebugError::Other("No debug information is loaded.".to_string())))
+    }
+
+    /// Find all the program counters where a breakpoint should be set for a source line, in all
+    /// the images.
+    ///
+    /// See [`DebugInfo::get_breakpoint_locations`].
+    pub fn get_breakpoint_locations(
+        &self,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+    ) -> Vec<BreakpointLocation> {
+        self.images
+            .iter()
+            .flat_map(|image| {
+                image
+                    .debug_info
+                    .get_breakpoint_locations(path, line, column)
+            })
+            .collect()
+    }
+
+    /// Performs the logical unwind of the stack, in the same way as [`DebugInfo::unwind`],
+    /// using the image that covers the program counter of each frame.
+    pub fn unwind(
+        &self,
+        core: &mut impl MemoryInterface,
+        initial_registers: DebugRegisters,
+        exception_handler: &dyn ExceptionInterface,
+        instruction_set: Option<InstructionSet>,
+        max_stack_frame_count: usize,
+    ) -> Result<Vec<StackFrame>, Error> {
+        self.unwind_with_options(
+            core,
+            initial_registers,
+            exception_handler,
+            instruction_set,
+            max_stack_frame_count,
+            UnwindOptions::default(),
+        )
+    }
+
+    /// Performs the logical unwind of the stack, in the same way as [`DebugInfoSet::unwind`],
+    /// using the provided [`UnwindOptions`].
+    pub fn unwind_with_options(
+        &self,
+        core: &mut impl MemoryInterface,
+        initial_registers: DebugRegisters,
+        exception_handler: &dyn ExceptionInterface,
+        instruction_set: Option<InstructionSet>,
+        max_stack_frame_count: usize,
+        options: UnwindOptions,
+    ) -> Result<Vec<StackFrame>, Error> {
+        // Frames outside of all images are unwound without debug information.
+        let endianness = self
+            .images
+            .first()
+            .map_or(RunTimeEndian::Little, |image| image.debug_info.endianness());
+        let no_debug_info = DebugInfo::from_sections(|_| None, endianness).map_err(|error| {
+            Error::Other(format!("Unable to create empty debug information: {error}"))
+        })?;
+
+        unwind_images(
+            |address| self.image_for_address(address).unwrap_or(&no_debug_info),
+            initial_registers,
+            core,
+            exception_handler,
+            instruction_set,
+            max_stack_frame_count,
+            options,
+        )
+    }
+}
+
+/// The runtime address ranges covered by the compilation units of the debug information.
+fn address_ranges(debug_info: &DebugInfo) -> Vec<Range<u64>> {
+    let mut address_ranges = Vec::new();
+
+    for unit_info in &debug_info.unit_infos {
+        let Ok(mut ranges) = unit_info.unit_ranges(debug_info) else {
+            continue;
+        };
+
+        while let Ok(Some(range)) = ranges.next() {
+            if range.begin < range.end {
+                address_ranges.push(
+                    debug_info.runtime_address(range.begin)..debug_info.runtime_address(range.end),
+                );
+            }
+        }
+    }
+
+    address_ranges
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use typed_path::TypedPath;
+
+    use super::DebugInfoSet;
+    use crate::DebugInfo;
+
+    /// The offset of the application image.
+    const APPLICATION_BIAS: i64 = 0x1000_0000;
+
+    fn load_test_elf(relative_file: &str) -> DebugInfo {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests");
+        path.push(relative_file);
+        DebugInfo::from_file(&path).expect("Failed to load debug info")
+    }
+
+    /// A set with an application image, and a bootloader image at a lower address.
+    fn bootloader_and_application() -> DebugInfoSet {
+        let mut application = load_test_elf("compressed/zstd.elf");
+        application.set_load_bias(APPLICATION_BIAS);
+
+        let mut set = DebugInfoSet::new();
+        set.insert(application).unwrap();
+        set.insert(load_test_elf("dwarf5/main.elf")).unwrap();
+        set
+    }
+
+    #[test]
+    fn lookups_use_the_image_covering_the_address() {
+        let set = bootloader_and_application();
+
+        assert_eq!(set.function_name(0x401000), Some("add".to_string()));
+        assert_eq!(set.function_name(0x1040_1000), Some("width".to_string()));
+        assert_eq!(set.function_name(0x2000_0000), None);
+
+        let location = set.get_source_location(0x1040_1008).unwrap();
+        assert_eq!(location.line, Some(12));
+        assert_eq!(location.address, Some(0x1040_1008));
+
+        let location = set.get_source_location(0x40100a).unwrap();
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test]
+    fn breakpoint_in_second_image() {
+        let set = bootloader_and_application();
+
+        let breakpoint = set
+            .get_breakpoint_location(TypedPath::derive("/tmp/dwarf5/main.c"), 8, Some(15))
+            .unwrap();
+        assert_eq!(breakpoint.address, 0x401024);
+    }
+
+    #[test]
+    fn overlapping_images_are_rejected() {
+        let mut set = DebugInfoSet::new();
+        let bootloader = set.insert(load_test_elf("dwarf5/main.elf")).unwrap();
+
+        assert!(set.insert(load_test_elf("compressed/zstd.elf")).is_err());
+
+        // After removing the image, the address range is available again.
+        assert!(set.remove(bootloader).is_some());
+        let application = set.insert(load_test_elf("compressed/zstd.elf")).unwrap();
+
+        assert_eq!(set.images().count(), 1);
+        assert!(set.get(bootloader).is_none());
+        assert!(set.get(application).is_some());
+        assert_eq!(set.function_name(0x401000), Some("width".to_string()));
+    }
+}
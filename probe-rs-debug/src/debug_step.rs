@@ -276,10 +276,9 @@ impl SteppingMode {
                     // to access the 'innermost' functions first.
                     if let Some(function) = function_dies.iter().next_back() {
                         tracing::trace!(
-                            "Step Out target: Evaluating function {:?}, low_pc={:?}, high_pc={:?}",
+                            "Step Out target: Evaluating function {:?}, ranges={:#010x?}",
                             function.function_name(debug_info),
-                            function.low_pc(),
-                            function.high_pc()
+                            function.ranges
                         );
 
                         if function
@@ -293,14 +292,12 @@ impl SteppingMode {
                                     .as_deref()
                                     .unwrap_or("<unknown>")
                             )));
-                        } else if function.range_contains(program_counter) {
+                        } else if let Some(range) = function.range_containing(program_counter) {
                             if function.is_inline() {
                                 // Step_out_address for inlined functions, is the first available breakpoint address after the last statement in the inline function.
-                                let (_, next_instruction_address) = run_to_address(
-                                    program_counter,
-                                    function.high_pc().unwrap(), //unwrap is OK because `range_contains` is true.
-                                    core,
-                                )?;
+                                // The ranges of the function can be disjoint, so only the range of the current instruction applies.
+                                let (_, next_instruction_address) =
+                                    run_to_address(program_counter, range.end, core)?;
                                 return SteppingMode::BreakPoint.get_halt_location(
                                     core,
                                     debug_info,
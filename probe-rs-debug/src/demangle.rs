@@ -0,0 +1,95 @@
+/// Demangle a symbol name, using the demangling scheme for the given language.
+///
+/// If the language is not known, the Rust and C++ schemes are tried, in that order. The hash at the end of
+/// Rust legacy symbols, e.g. `core::fmt::write::h6c2d4a5f2dbf3e4b`, is removed unless `keep_hash` is set.
+///
+/// Returns `None` if the name is not mangled, or the demangler for the scheme is not enabled.
+pub(crate) fn demangle(
+    name: &str,
+    language: Option<gimli::DwLang>,
+    keep_hash: bool,
+) -> Option<String> {
+    match language {
+        Some(gimli::DW_LANG_Rust) => demangle_rust(name, keep_hash),
+        Some(
+            gimli::DW_LANG_C_plus_plus
+            | gimli::DW_LANG_C_plus_plus_03
+            | gimli::DW_LANG_C_plus_plus_11
+            | gimli::DW_LANG_C_plus_plus_14
+            | gimli::DW_LANG_C_plus_plus_17
+            | gimli::DW_LANG_C_plus_plus_20,
+        ) => demangle_cpp(name),
+        Some(_) => None,
+        None => demangle_rust(name, keep_hash).or_else(|| demangle_cpp(name)),
+    }
+}
+
+#[cfg(feature = "rustc-demangle")]
+fn demangle_rust(name: &str, keep_hash: bool) -> Option<String> {
+    let demangled = rustc_demangle::try_demangle(name).ok()?;
+
+    // The alternate format omits the hash.
+    if keep_hash {
+        Some(demangled.to_string())
+    } else {
+        Some(format!("{demangled:#}"))
+    }
+}
+
+#[cfg(not(feature = "rustc-demangle"))]
+fn demangle_rust(_name: &str, _keep_hash: bool) -> Option<String> {
+    None
+}
+
+#[cfg(feature = "cpp_demangle")]
+fn demangle_cpp(name: &str) -> Option<String> {
+    cpp_demangle::Symbol::new(name)
+        .ok()?
+        .demangle_with_options(&Default::default())
+        .ok()
+}
+
+#[cfg(not(feature = "cpp_demangle"))]
+fn demangle_cpp(_name: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(any(feature = "rustc-demangle", feature = "cpp_demangle"))]
+    use test_case::test_case;
+
+    use super::demangle;
+
+    #[cfg(feature = "rustc-demangle")]
+    #[test_case("_ZN4core3fmt5write17h6c2d4a5f2dbf3e4bE", false, "core::fmt::write"; "legacy")]
+    #[test_case("_ZN4core3fmt5write17h6c2d4a5f2dbf3e4bE", true, "core::fmt::write::h6c2d4a5f2dbf3e4b"; "legacy with hash")]
+    #[test_case("_RNvNtCs1234_7mycrate3spi8transfer", false, "mycrate::spi::transfer"; "v0")]
+    fn rust_symbols(name: &str, keep_hash: bool, expected: &str) {
+        assert_eq!(
+            demangle(name, Some(gimli::DW_LANG_Rust), keep_hash).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(demangle(name, None, keep_hash).as_deref(), Some(expected));
+    }
+
+    #[cfg(feature = "cpp_demangle")]
+    #[test_case("_ZN3spi8transferEh", "spi::transfer(unsigned char)"; "function")]
+    #[test_case("_ZN3spi3Spi8transferEv", "spi::Spi::transfer()"; "method")]
+    fn cpp_symbols(name: &str, expected: &str) {
+        assert_eq!(
+            demangle(name, Some(gimli::DW_LANG_C_plus_plus_11), false).as_deref(),
+            Some(expected)
+        );
+        assert_eq!(demangle(name, None, false).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn unmangled_symbols() {
+        assert_eq!(demangle("main", None, false), None);
+        assert_eq!(
+            demangle("_ZN3spi8transferEh", Some(gimli::DW_LANG_C11), false),
+            None
+        );
+    }
+}
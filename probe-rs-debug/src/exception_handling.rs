@@ -84,6 +84,17 @@ pub trait ExceptionInterface {
         Err(DebugError::NotImplemented("calling frame registers"))
     }
 
+    /// Using the `stackframe_registers` for a "called frame", retrieve the register values that were
+    /// pushed onto the stack by the processor on exception entry, exactly as they were stacked.
+    /// Registers that are not part of the exception stack frame will not have a value.
+    fn exception_entry_registers(
+        &self,
+        _memory: &mut dyn MemoryInterface,
+        _stackframe_registers: &crate::DebugRegisters,
+    ) -> Result<crate::DebugRegisters, DebugError> {
+        Err(DebugError::NotImplemented("exception entry registers"))
+    }
+
     /// Retrieve the architecture specific exception number.
     fn raw_exception(
         &self,
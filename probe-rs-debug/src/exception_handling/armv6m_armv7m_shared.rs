@@ -1,4 +1,4 @@
-use crate::{DebugError, DebugRegisters, StackFrame, get_object_reference};
+use crate::{DebugError, DebugRegisters, FrameKind, StackFrame, get_object_reference};
 use bitfield::bitfield;
 use probe_rs::{Error, MemoryInterface, RegisterRole, RegisterValue};
 
@@ -93,12 +93,14 @@ pub(crate) fn exception_details(
             handler_frame: StackFrame {
                 id: get_object_reference(),
                 function_name: "Reset".to_string(),
+                linkage_name: None,
                 source_location: None,
                 registers: stackframe_registers.clone(),
                 // The PC value is not 0, this should be the address of the reset vector
                 pc: RegisterValue::U32(0),
                 frame_base: None,
                 is_inlined: false,
+                kind: FrameKind::Function,
                 local_variables: None,
                 canonical_frame_address: None,
             },
@@ -123,11 +125,13 @@ pub(crate) fn exception_details(
     let mut handler_frame = StackFrame {
         id: get_object_reference(),
         function_name: description.clone(),
+        linkage_name: None,
         source_location: None,
         registers,
         pc: pc_value,
         frame_base: None,
         is_inlined: false,
+        kind: FrameKind::Function,
         local_variables: None,
         canonical_frame_address: None,
     };
@@ -228,3 +232,34 @@ pub(crate) fn calling_frame_registers(
 
     Ok(calling_frame_registers)
 }
+
+/// Read the registers that were pushed onto the stack on exception entry, without any of the
+/// adjustments that are applied to unwind the calling frame.
+/// All registers that are not part of `EXCEPTION_STACK_REGISTERS` are returned without a value.
+pub(crate) fn exception_entry_registers(
+    memory: &mut dyn MemoryInterface,
+    stackframe_registers: &DebugRegisters,
+) -> Result<DebugRegisters, DebugError> {
+    if raw_exception(stackframe_registers)? == 1 {
+        return Err(DebugError::Other(
+            "No registers are stored on the stack for a Reset exception.".to_string(),
+        ));
+    }
+
+    let entry_registers = calling_frame_registers(memory, stackframe_registers)?;
+
+    Ok(only_stacked_registers(entry_registers))
+}
+
+/// Clear the values of all registers that are not stored on the stack on exception entry.
+pub(crate) fn only_stacked_registers(mut registers: DebugRegisters) -> DebugRegisters {
+    for register in &mut registers.0 {
+        if !EXCEPTION_STACK_REGISTERS
+            .iter()
+            .any(|role| register.core_register.register_has_role(*role))
+        {
+            register.value = None;
+        }
+    }
+    registers
+}
@@ -360,6 +360,14 @@ impl ExceptionInterface for ArmV7MExceptionHandler {
         Ok(updated_registers)
     }
 
+    fn exception_entry_registers(
+        &self,
+        memory_interface: &mut dyn MemoryInterface,
+        stackframe_registers: &crate::DebugRegisters,
+    ) -> Result<crate::DebugRegisters, DebugError> {
+        armv6m_armv7m_shared::exception_entry_registers(memory_interface, stackframe_registers)
+    }
+
     fn raw_exception(
         &self,
         stackframe_registers: &crate::DebugRegisters,
@@ -1,4 +1,4 @@
-use crate::{DebugError, DebugInfo, DebugRegisters, StackFrame, get_object_reference};
+use crate::{DebugError, DebugInfo, DebugRegisters, FrameKind, StackFrame, get_object_reference};
 use bitfield::bitfield;
 use probe_rs::{
     Error, MemoryInterface, MemoryMappedRegister, RegisterRole, RegisterValue,
@@ -7,7 +7,7 @@ use probe_rs::{
 
 use super::{
     ExceptionInfo, ExceptionInterface,
-    armv6m_armv7m_shared::{EXCEPTION_STACK_REGISTERS, Xpsr},
+    armv6m_armv7m_shared::{EXCEPTION_STACK_REGISTERS, Xpsr, only_stacked_registers},
 };
 
 bitfield! {
@@ -497,6 +497,21 @@ impl ExceptionInterface for ArmV8MExceptionHandler {
         Ok(calling_frame_registers)
     }
 
+    fn exception_entry_registers(
+        &self,
+        memory_interface: &mut dyn MemoryInterface,
+        stackframe_registers: &DebugRegisters,
+    ) -> Result<DebugRegisters, DebugError> {
+        // The calling frame registers are not adjusted on this architecture, so they match the stacked values.
+        let entry_registers = self.calling_frame_registers(
+            memory_interface,
+            stackframe_registers,
+            self.raw_exception(stackframe_registers)?,
+        )?;
+
+        Ok(only_stacked_registers(entry_registers))
+    }
+
     fn raw_exception(&self, stackframe_registers: &DebugRegisters) -> Result<u32, DebugError> {
         // Load the provided xPSR register as a bitfield.
         let exception_number = Xpsr(
@@ -539,11 +554,13 @@ impl ExceptionInterface for ArmV8MExceptionHandler {
             let handler_frame = StackFrame {
                 id: get_object_reference(),
                 function_name: description.clone(),
+                linkage_name: None,
                 source_location: None,
                 registers,
                 pc: RegisterValue::U32(exception_frame_pc as u32),
                 frame_base: None,
                 is_inlined: false,
+                kind: FrameKind::Function,
                 local_variables: None,
                 canonical_frame_address: None,
             };
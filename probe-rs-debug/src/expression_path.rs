@@ -0,0 +1,402 @@
+use probe_rs::MemoryInterface;
+
+use crate::{
+    DebugError, DebugInfo, ObjectRef, StackFrame, Variable, VariableCache, VariableLocation,
+    VariableName, VariableNodeType, VariableType, VariableValue, stack_frame::StackFrameInfo,
+};
+
+/// A step from one variable to the next in a variable path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// A child with the given name, e.g. a variable, the field of a struct, or a namespace.
+    Field(String),
+    /// An element of an array, or of the array a pointer or slice points to.
+    Index(u64),
+    /// The value a pointer points to.
+    Deref,
+}
+
+impl std::fmt::Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Segment::Field(name) => name.fmt(f),
+            Segment::Index(index) => write!(f, "[{index}]"),
+            Segment::Deref => "*".fmt(f),
+        }
+    }
+}
+
+/// A parsed variable path, like `&config.buffers[2].len`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExpressionPath {
+    /// The path starts with `&`, so the result is the address of the variable.
+    address_of: bool,
+    segments: Vec<Segment>,
+}
+
+/// Parse a variable path.
+///
+/// A path starts with the name of a variable, which can be qualified with namespaces, e.g.
+/// `my_crate::STATE`. It is followed by field accesses (`.field`, or `.0` for tuple fields),
+/// array indices (`[2]`), and field accesses through a pointer (`->field`). Leading `*` dereference
+/// the result, like in C and Rust, and a leading `&` takes its address. A dereference in
+/// parentheses can be followed by field accesses and indices, e.g. `(*config.uart).baud`.
+pub(crate) fn parse(expr: &str) -> Result<ExpressionPath, DebugError> {
+    let invalid = |reason: String| DebugError::InvalidPath {
+        path: expr.to_string(),
+        reason,
+    };
+
+    let mut rest = expr.trim();
+    let address_of = if let Some(stripped) = rest.strip_prefix('&') {
+        rest = stripped.trim_start();
+        true
+    } else {
+        false
+    };
+
+    let (segments, rest) = parse_segments(rest, &invalid)?;
+    if !rest.is_empty() {
+        return Err(invalid(format!("unexpected {rest:?}")));
+    }
+
+    Ok(ExpressionPath {
+        address_of,
+        segments,
+    })
+}
+
+/// Parse the segments of a path, or of a path in parentheses, up to the closing parenthesis. The
+/// rest of `expr` after them is returned with the segments.
+fn parse_segments<'e>(
+    expr: &'e str,
+    invalid: &impl Fn(String) -> DebugError,
+) -> Result<(Vec<Segment>, &'e str), DebugError> {
+    let mut rest = expr.trim_start();
+    let mut derefs = 0;
+    while let Some(stripped) = rest.strip_prefix('*') {
+        rest = stripped.trim_start();
+        derefs += 1;
+    }
+
+    let mut segments = if let Some(stripped) = rest.strip_prefix('(') {
+        let (segments, stripped) = parse_segments(stripped, invalid)?;
+        rest = stripped
+            .strip_prefix(')')
+            .ok_or_else(|| invalid("expected `)`".to_string()))?;
+        segments
+    } else {
+        let (name, stripped) = split_name(rest);
+        if name.is_empty() {
+            return Err(invalid("expected a variable name".to_string()));
+        }
+        let segments: Vec<_> = name
+            .split("::")
+            .map(|name| Segment::Field(name.to_string()))
+            .collect();
+        if segments.contains(&Segment::Field(String::new())) {
+            return Err(invalid(format!("{name} is not a valid name")));
+        }
+        rest = stripped;
+        segments
+    };
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() || rest.starts_with(')') {
+            break;
+        }
+
+        let field = if let Some(stripped) = rest.strip_prefix('.') {
+            stripped
+        } else if let Some(stripped) = rest.strip_prefix("->") {
+            segments.push(Segment::Deref);
+            stripped
+        } else if let Some(stripped) = rest.strip_prefix('[') {
+            let Some((index, stripped)) = stripped.split_once(']') else {
+                return Err(invalid("expected `]`".to_string()));
+            };
+            let index = parse_int::parse::<u64>(index.trim())
+                .map_err(|_| invalid(format!("{} is not a valid array index", index.trim())))?;
+            segments.push(Segment::Index(index));
+            rest = stripped;
+            continue;
+        } else {
+            return Err(invalid(format!("unexpected {rest:?}")));
+        };
+
+        let (name, stripped) = split_name(field.trim_start());
+        if name.is_empty() || name.contains(':') {
+            return Err(invalid(format!("expected a field name before {field:?}")));
+        }
+        segments.push(Segment::Field(name.to_string()));
+        rest = stripped;
+    }
+
+    segments.extend(std::iter::repeat_n(Segment::Deref, derefs));
+
+    Ok((segments, rest))
+}
+
+/// Split the name at the start of `rest` from the rest of the path.
+fn split_name(rest: &str) -> (&str, &str) {
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':' || c == '$'))
+        .unwrap_or(rest.len());
+    rest.split_at(end)
+}
+
+/// Evaluate the variable path `expr` in `cache`, see [`DebugInfo::evaluate_path`].
+pub(crate) fn evaluate_path(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    frame: &StackFrame,
+    expr: &str,
+) -> Result<Variable, DebugError> {
+    let path = parse(expr)?;
+    let mut evaluator = Evaluator {
+        debug_info,
+        memory,
+        cache,
+        frame_info: StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            auto_deref_depth: None,
+        },
+        expr,
+    };
+
+    let mut variable = evaluator.cache.root_variable().clone();
+    for segment in &path.segments {
+        variable = match segment {
+            Segment::Field(name) => evaluator.field(&variable, name)?,
+            Segment::Index(index) => evaluator.index(&variable, *index)?,
+            Segment::Deref => evaluator.deref(&variable)?,
+        };
+    }
+
+    if path.address_of {
+        address_of(&variable, expr)
+    } else {
+        Ok(variable)
+    }
+}
+
+struct Evaluator<'a> {
+    debug_info: &'a DebugInfo,
+    memory: &'a mut dyn MemoryInterface,
+    cache: &'a mut VariableCache,
+    frame_info: StackFrameInfo<'a>,
+    expr: &'a str,
+}
+
+impl Evaluator<'_> {
+    /// The children of `variable`, which are resolved first if they are deferred.
+    fn children(&mut self, variable: &Variable) -> Result<Vec<Variable>, DebugError> {
+        let mut parent = variable.clone();
+        self.debug_info.cache_deferred_variables(
+            self.cache,
+            self.memory,
+            &mut parent,
+            self.frame_info,
+        )?;
+
+        Ok(self
+            .cache
+            .get_children(parent.variable_key())
+            .cloned()
+            .collect())
+    }
+
+    fn invalid(&self, reason: String) -> DebugError {
+        DebugError::InvalidPath {
+            path: self.expr.to_string(),
+            reason,
+        }
+    }
+
+    fn field(&mut self, variable: &Variable, name: &str) -> Result<Variable, DebugError> {
+        // Like in Rust, fields are accessed through references without an explicit dereference.
+        let variable = if matches!(variable.type_name.inner(), VariableType::Pointer(_)) {
+            self.deref(variable)?
+        } else {
+            variable.clone()
+        };
+
+        let children = self.children(&variable)?;
+
+        // Tuple fields are named `__0`, `__1`, and so on, but are accessed as `.0`, `.1`.
+        let tuple_field = name
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+            .then(|| format!("__{name}"));
+
+        children
+            .iter()
+            .find(|child| {
+                let child_name = child.name.to_string();
+                child_name == name || Some(&child_name) == tuple_field.as_ref()
+            })
+            .cloned()
+            .ok_or_else(|| DebugError::PathSegmentNotFound {
+                segment: name.to_string(),
+                candidates: children
+                    .iter()
+                    .filter(|child| {
+                        matches!(
+                            child.name,
+                            VariableName::Named(_)
+                                | VariableName::Namespace(_)
+                                | VariableName::AnonymousNamespace
+                        )
+                    })
+                    .map(|child| child.name.to_string())
+                    .collect(),
+            })
+    }
+
+    fn index(&mut self, variable: &Variable, index: u64) -> Result<Variable, DebugError> {
+        let array = match variable.type_name.inner() {
+            VariableType::Array { .. } => variable.clone(),
+            VariableType::Pointer(_) => self.deref(variable)?,
+            // Slices are structs, which hold the array they point to.
+            _ => self
+                .children(variable)?
+                .into_iter()
+                .find(|child| child.type_name.inner().is_array())
+                .ok_or_else(|| self.invalid(format!("{} is not an array", variable.name)))?,
+        };
+
+        // Only the indexed member of a long array is resolved.
+        let mut array = array;
+        self.debug_info.cache_deferred_variables_paged(
+            self.cache,
+            self.memory,
+            &mut array,
+            usize::try_from(index).unwrap_or(usize::MAX),
+            1,
+            self.frame_info,
+        )?;
+
+        self.cache
+            .get_children(array.variable_key())
+            .find(|child| child.name == VariableName::Indexed(index))
+            .cloned()
+            .ok_or_else(|| DebugError::PathSegmentNotFound {
+                segment: Segment::Index(index).to_string(),
+                candidates: match self.cache.child_count(array.variable_key()) {
+                    0 => vec![],
+                    length => vec![format!("[0..{length}]")],
+                },
+            })
+    }
+
+    fn deref(&mut self, variable: &Variable) -> Result<Variable, DebugError> {
+        if !matches!(variable.type_name.inner(), VariableType::Pointer(_)) {
+            return Err(self.invalid(format!("{} is not a pointer", variable.name)));
+        }
+
+        self.children(variable)?.into_iter().next().ok_or_else(|| {
+            self.invalid(format!(
+                "the value {} points to is not known",
+                variable.name
+            ))
+        })
+    }
+}
+
+/// A pointer to `variable`, which is not added to the cache.
+fn address_of(variable: &Variable, expr: &str) -> Result<Variable, DebugError> {
+    let VariableLocation::Address(address) = variable.memory_location else {
+        return Err(DebugError::InvalidPath {
+            path: expr.to_string(),
+            reason: format!("{} does not have a memory address", variable.name),
+        });
+    };
+
+    let type_name = if variable.language == gimli::DW_LANG_Rust {
+        format!("&{}", variable.type_name())
+    } else {
+        format!("{}*", variable.type_name())
+    };
+
+    let mut pointer = variable.clone();
+    pointer.variable_key = ObjectRef::Invalid;
+    pointer.parent_key = ObjectRef::Invalid;
+    pointer.name = VariableName::Named(format!("&{}", variable.name));
+    pointer.type_name = VariableType::Pointer(Some(type_name));
+    pointer.type_node_offset = None;
+    pointer.byte_size = None;
+    pointer.variable_node_type = VariableNodeType::DoNotRecurse;
+    pointer.memory_location = VariableLocation::Value;
+    pointer.value = VariableValue::U64(address);
+
+    Ok(pointer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExpressionPath, Segment, parse};
+
+    #[test]
+    fn parse_paths() {
+        let field = |name: &str| Segment::Field(name.to_string());
+
+        assert_eq!(
+            parse("config.buffers[2].len").unwrap(),
+            ExpressionPath {
+                address_of: false,
+                segments: vec![
+                    field("config"),
+                    field("buffers"),
+                    Segment::Index(2),
+                    field("len")
+                ],
+            }
+        );
+        assert_eq!(
+            parse("&*my_crate::STATE->next[0x10].0").unwrap(),
+            ExpressionPath {
+                address_of: true,
+                segments: vec![
+                    field("my_crate"),
+                    field("STATE"),
+                    Segment::Deref,
+                    field("next"),
+                    Segment::Index(16),
+                    field("0"),
+                    Segment::Deref,
+                ],
+            }
+        );
+
+        assert_eq!(
+            parse("(*config.uart).buffers[1]").unwrap(),
+            ExpressionPath {
+                address_of: false,
+                segments: vec![
+                    field("config"),
+                    field("uart"),
+                    Segment::Deref,
+                    field("buffers"),
+                    Segment::Index(1),
+                ],
+            }
+        );
+        assert_eq!(
+            parse("*(*a).b").unwrap(),
+            ExpressionPath {
+                address_of: false,
+                segments: vec![field("a"), Segment::Deref, field("b"), Segment::Deref],
+            }
+        );
+
+        for invalid in [
+            "", "&", "a.", "a[1", "a[x]", "a b", "a::", "a.b::c", "(*a", "(a))", "()",
+        ] {
+            assert!(parse(invalid).is_err(), "{invalid:?}");
+        }
+    }
+}
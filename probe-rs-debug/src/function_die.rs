@@ -1,15 +1,88 @@
 use gimli::{Dwarf, UnitOffset};
 use std::ops::Range;
 
-use crate::{GimliReader, MemoryInterface, stack_frame::StackFrameInfo};
+use crate::{GimliReader, MemoryInterface, demangle::demangle, stack_frame::StackFrameInfo};
 
 use super::{
     ColumnType, DebugError, DebugInfo, SourceLocation, VariableLocation, debug_info, extract_file,
-    unit_info::{ExpressionResult, UnitInfo},
+    unit_info::{ExpressionResult, UnitInfo, extract_name},
 };
 
 pub(crate) type Die = gimli::DebuggingInformationEntry<debug_info::GimliReader, usize>;
 
+/// A function from the debug information, see [`DebugInfo::functions`].
+///
+/// Unlike the internal function DIE references, this does not borrow the debug information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionInfo {
+    /// The name of the function, if it is known.
+    pub name: Option<String>,
+    /// The address ranges of the machine code of the function.
+    ///
+    /// These are empty for the abstract instance of an inlined function. The code of the inlined copies
+    /// is part of the functions they are inlined into.
+    pub ranges: Vec<Range<u64>>,
+    /// Whether this is the abstract instance of an inlined function (`DW_AT_inline`), which describes the
+    /// function, but has no code of its own.
+    pub is_inline_abstract: bool,
+    /// The location where the function is declared.
+    pub source: Option<SourceLocation>,
+}
+
+/// How the name of a function is matched in [`DebugInfo::find_functions_by_name`].
+///
+/// Generic arguments are ignored unless the name contains them, so `transfer` matches both
+/// `transfer<u8>` and `transfer<u16>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatch {
+    /// The name must be equal to the qualified name of the function, e.g. `my_crate::spi::transfer`.
+    Exact,
+    /// The name must be equal to the end of the qualified name of the function, starting at a `::`
+    /// separator, e.g. `transfer` or `spi::transfer` for `my_crate::spi::transfer`.
+    Suffix,
+    /// The name must be equal to the demangled linkage name of the function (`DW_AT_linkage_name`),
+    /// without the hash of Rust legacy symbols, or the parameter list of C++ symbols.
+    Demangled,
+}
+
+impl NameMatch {
+    /// Returns the name of the function, if it matches `name`.
+    ///
+    /// The qualified name is returned, unless the function was matched by its demangled name.
+    pub(crate) fn matches(
+        self,
+        name: &str,
+        function: &FunctionDie,
+        debug_info: &DebugInfo,
+    ) -> Option<String> {
+        let candidate = match self {
+            NameMatch::Exact | NameMatch::Suffix => function.qualified_name(debug_info)?,
+            NameMatch::Demangled => function.demangled_linkage_name(debug_info)?,
+        };
+
+        let is_match = |candidate: &str| match self {
+            NameMatch::Exact | NameMatch::Demangled => candidate == name,
+            NameMatch::Suffix => candidate
+                .strip_suffix(name)
+                .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with("::")),
+        };
+
+        (is_match(&candidate) || is_match(&strip_generic_arguments(&candidate)))
+            .then_some(candidate)
+    }
+}
+
+/// A function that was found by [`DebugInfo::find_functions_by_name`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionMatch {
+    /// The name of the function that matched, including generic arguments.
+    pub name: String,
+    /// The entry address of the function.
+    pub address: u64,
+    /// The location where the function is declared, with the entry address.
+    pub source_location: Option<SourceLocation>,
+}
+
 /// Reference to a DIE for a function
 #[derive(Clone)]
 pub(crate) struct FunctionDie<'data> {
@@ -29,7 +102,12 @@ pub(crate) struct FunctionDie<'data> {
     /// to the declaration of the function.
     pub(crate) abstract_die: Option<Die>,
     /// The address ranges for which this function is valid.
+    ///
+    /// The ranges can be disjoint, e.g. when the compiler moves rarely executed code of the function
+    /// to a separate section.
     pub(crate) ranges: Vec<Range<u64>>,
+    /// The address of the first instruction that is executed when the function is called.
+    pub(crate) entry_pc: Option<u64>,
 }
 
 impl<'a> FunctionDie<'a> {
@@ -69,23 +147,41 @@ impl<'a> FunctionDie<'a> {
         debug_info: &'a DebugInfo,
         address: u64,
     ) -> Result<Option<Self>, DebugError> {
+        let Some(die_ranges) =
+            Self::function_ranges(&function_die, unit_info, unit_info.dwarf(debug_info))?
+        else {
+            return Ok(None);
+        };
+        if !die_ranges.iter().any(|range| range.contains(&address)) {
+            return Ok(None);
+        }
+
+        Ok(Self::with_ranges(
+            function_die,
+            unit_info,
+            debug_info,
+            die_ranges,
+        ))
+    }
+
+    /// Create a new function DIE reference, for a function DIE with the given address ranges.
+    ///
+    /// Returns `None` if the DIE is not a function, or if the abstract origin of an inlined function cannot be found.
+    pub(crate) fn with_ranges(
+        function_die: Die,
+        unit_info: &'a UnitInfo,
+        debug_info: &'a DebugInfo,
+        ranges: Vec<Range<u64>>,
+    ) -> Option<Self> {
         let is_inlined_function = match function_die.tag() {
             gimli::DW_TAG_subprogram => false,
             gimli::DW_TAG_inlined_subroutine => true,
             _ => {
                 // We only need DIEs for functions, so we can ignore all other DIEs.
-                return Ok(None);
+                return None;
             }
         };
 
-        let Some(die_ranges) = Self::function_ranges(&function_die, unit_info, &debug_info.dwarf)?
-        else {
-            return Ok(None);
-        };
-        if !die_ranges.iter().any(|range| range.contains(&address)) {
-            return Ok(None);
-        }
-
         let specification_die;
 
         // For inlined functions, we also need to find the abstract origin.
@@ -96,7 +192,7 @@ impl<'a> FunctionDie<'a> {
                 unit_info,
             ) else {
                 tracing::debug!("No abstract origin found for inlined function");
-                return Ok(None);
+                return None;
             };
             specification_die = debug_info.resolve_die_reference(
                 gimli::DW_AT_specification,
@@ -113,32 +209,42 @@ impl<'a> FunctionDie<'a> {
             None
         };
 
-        Ok(Some(Self {
+        // The entry of a function with several ranges is not necessarily at the lowest address,
+        // so `DW_AT_entry_pc` and `DW_AT_low_pc` are preferred over the first range.
+        let dwarf = unit_info.dwarf(debug_info);
+        let entry_pc = [gimli::DW_AT_entry_pc, gimli::DW_AT_low_pc]
+            .into_iter()
+            .find_map(|attribute| {
+                let value = function_die.attr_value(attribute)?;
+                dwarf.attr_address(&unit_info.unit, value).ok().flatten()
+            })
+            .or_else(|| ranges.first().map(|range| range.start));
+
+        Some(Self {
             unit_info,
             function_die,
             specification_die,
             abstract_die,
-            ranges: die_ranges,
-        }))
-    }
-
-    /// Test whether the given address is contained in the address ranges of this function.
-    /// Use this, instead of checking for values between `low_pc()` and `high_pc()`, because
-    /// the address ranges can be disjointed.
-    pub(crate) fn range_contains(&self, address: u64) -> bool {
-        self.ranges.iter().any(|range| range.contains(&address))
+            ranges,
+            entry_pc,
+        })
     }
 
-    /// Returns the lowest valid address for which this function DIE is valid.
-    /// Please use `range_contains()` to check whether an address is contained in the range.
-    pub(crate) fn low_pc(&self) -> Option<u64> {
-        self.ranges.first().map(|range| range.start)
+    /// Returns the address range of this function which contains the given address.
+    ///
+    /// Use this, instead of checking for values between the start and end of the function, because
+    /// the address ranges can be disjoint.
+    pub(crate) fn range_containing(&self, address: u64) -> Option<&Range<u64>> {
+        self.ranges.iter().find(|range| range.contains(&address))
     }
 
-    /// Returns the highest valid address for which this function DIE is valid.
-    /// Please use `range_contains()` to check whether an address is contained in the range.
-    pub(crate) fn high_pc(&self) -> Option<u64> {
-        self.ranges.last().map(|range| range.end)
+    /// Returns the entry address of the function, which is the first instruction that is executed
+    /// when the function is called.
+    ///
+    /// This is the `DW_AT_entry_pc` or `DW_AT_low_pc` of the function DIE, or the start of its first
+    /// address range. It is not necessarily the lowest address of the function.
+    pub(crate) fn entry_pc(&self) -> Option<u64> {
+        self.entry_pc
     }
 
     /// Returns whether this is an inlined function DIE reference.
@@ -147,17 +253,27 @@ impl<'a> FunctionDie<'a> {
     }
 
     /// Returns the function name described by the die.
+    ///
+    /// If the DIE has no `DW_AT_name` attribute, the demangled linkage name is used instead.
     pub(crate) fn function_name(&self, debug_info: &super::DebugInfo) -> Option<String> {
-        let Some(fn_name_attr) = self.attribute(debug_info, gimli::DW_AT_name) else {
-            tracing::debug!("DW_AT_name attribute not found, unable to retrieve function name");
-            return None;
-        };
-        let value = fn_name_attr.value();
-        let gimli::AttributeValue::DebugStrRef(fn_name_ref) = value else {
-            tracing::debug!("Unexpected attribute value for DW_AT_name: {:?}", value);
-            return None;
+        let Some((name_unit_info, fn_name_attr)) =
+            debug_info.resolve_attribute(&self.function_die, self.unit_info, gimli::DW_AT_name)
+        else {
+            tracing::debug!("DW_AT_name attribute not found, using the linkage name instead");
+            let linkage_name = self.linkage_name(debug_info)?;
+            return Some(
+                demangle(
+                    &linkage_name,
+                    Some(self.unit_info.get_language()),
+                    debug_info.keep_symbol_hash,
+                )
+                .unwrap_or(linkage_name),
+            );
         };
-        match debug_info.dwarf.string(fn_name_ref) {
+        match name_unit_info
+            .dwarf(debug_info)
+            .attr_string(&name_unit_info.unit, fn_name_attr.value())
+        {
             Ok(fn_name_raw) => {
                 let function_name = String::from_utf8_lossy(&fn_name_raw);
 
@@ -172,6 +288,68 @@ impl<'a> FunctionDie<'a> {
         }
     }
 
+    /// Returns the function name, prefixed with the names of the namespaces that contain it,
+    /// e.g. `my_crate::spi::transfer`.
+    pub(crate) fn qualified_name(&self, debug_info: &super::DebugInfo) -> Option<String> {
+        let mut name = self.function_name(debug_info)?;
+
+        let mut parent_offset = self.parent_offset();
+        while let Some(offset) = parent_offset {
+            let Ok(parent) = self.unit_info.unit.entry(offset) else {
+                break;
+            };
+            if parent.tag() == gimli::DW_TAG_namespace
+                && let Ok(Some(namespace)) = extract_name(debug_info, self.unit_info, &parent)
+            {
+                name = format!("{namespace}::{name}");
+            }
+            parent_offset = self.unit_info.parent_offset(offset);
+        }
+
+        Some(name)
+    }
+
+    /// Returns the `DW_AT_linkage_name` of the function, which is the mangled symbol name.
+    pub(crate) fn linkage_name(&self, debug_info: &super::DebugInfo) -> Option<String> {
+        let (linkage_name_unit_info, linkage_name_attr) = debug_info
+            .resolve_attribute(
+                &self.function_die,
+                self.unit_info,
+                gimli::DW_AT_linkage_name,
+            )
+            .or_else(|| {
+                debug_info.resolve_attribute(
+                    &self.function_die,
+                    self.unit_info,
+                    gimli::DW_AT_MIPS_linkage_name,
+                )
+            })?;
+        let linkage_name = linkage_name_unit_info
+            .dwarf(debug_info)
+            .attr_string(&linkage_name_unit_info.unit, linkage_name_attr.value())
+            .ok()?;
+
+        Some(String::from_utf8_lossy(&linkage_name).into_owned())
+    }
+
+    /// Returns the demangled `DW_AT_linkage_name` of the function.
+    ///
+    /// The hash of Rust legacy symbols, and the parameter list of C++ symbols is removed.
+    pub(crate) fn demangled_linkage_name(&self, debug_info: &super::DebugInfo) -> Option<String> {
+        let linkage_name = self.linkage_name(debug_info)?;
+
+        let language = self.unit_info.get_language();
+        let Some(demangled) = demangle(&linkage_name, Some(language), false) else {
+            return Some(linkage_name);
+        };
+
+        if language == gimli::DW_LANG_Rust {
+            Some(demangled)
+        } else {
+            Some(strip_parameter_list(&demangled).to_string())
+        }
+    }
+
     /// Get the call site of an inlined function.
     ///
     /// If this function is not inlined (`is_inline()` returns false),
@@ -198,52 +376,86 @@ impl<'a> FunctionDie<'a> {
                     Some(c) => ColumnType::Column(c),
                 });
 
-        let address = self.low_pc();
+        let address = self.entry_pc();
 
         Some(SourceLocation {
             line,
             column,
             path,
             address,
+            line_fallback: false,
         })
     }
 
-    /// Resolve an attribute by looking through both the specification and die, or abstract specification and die, entries.
-    pub(crate) fn attribute(
+    /// Get the location where the function is declared, from the `DW_AT_decl_*` attributes.
+    ///
+    /// The address of the location is the start of the first address range of the function, if it has any.
+    pub(crate) fn declaration_location(
         &self,
         debug_info: &super::DebugInfo,
-        attribute_name: gimli::DwAt,
-    ) -> Option<debug_info::GimliAttribute> {
-        let attribute = collapsed_attribute(
+    ) -> Option<SourceLocation> {
+        let (file_unit_info, file_name_attr) = debug_info.resolve_attribute(
             &self.function_die,
-            self.specification_die.as_ref(),
-            attribute_name,
-        );
+            self.unit_info,
+            gimli::DW_AT_decl_file,
+        )?;
 
-        if attribute.is_some() {
-            return attribute.cloned();
-        }
+        let path = extract_file(debug_info, &file_unit_info.unit, file_name_attr.value())?;
+        let line = self
+            .attribute(debug_info, gimli::DW_AT_decl_line)
+            .and_then(|line| line.udata_value());
 
-        // For inlined function, the *abstract instance* has to be checked if we cannot find the
-        // attribute on the *concrete instance*. The abstract instance my also be a reference to a specification.
-        if let Some(abstract_die) = &self.abstract_die {
-            let inlined_specification_die = debug_info.resolve_die_reference(
-                gimli::DW_AT_specification,
-                abstract_die,
-                self.unit_info,
-            );
-            let inline_attribute = collapsed_attribute(
-                abstract_die,
-                inlined_specification_die.as_ref(),
-                attribute_name,
-            );
+        // According to the DWARF standard, a value of 0 means no column is specified.
+        let column = self
+            .attribute(debug_info, gimli::DW_AT_decl_column)
+            .and_then(|column| column.udata_value())
+            .filter(|column| *column != 0)
+            .map(ColumnType::Column);
 
-            if inline_attribute.is_some() {
-                return inline_attribute.cloned();
-            }
+        let address = self.entry_pc();
+
+        Some(SourceLocation {
+            line,
+            column,
+            path,
+            address,
+            line_fallback: false,
+        })
+    }
+
+    /// Convert this reference into a [`FunctionInfo`], which does not borrow the debug information.
+    pub(crate) fn function_info(&self, debug_info: &super::DebugInfo) -> FunctionInfo {
+        let mut source = self.declaration_location(debug_info);
+        if let Some(source) = &mut source {
+            source.address = source
+                .address
+                .map(|address| debug_info.runtime_address(address));
         }
 
-        None
+        FunctionInfo {
+            name: self.function_name(debug_info),
+            ranges: self
+                .ranges
+                .iter()
+                .map(|range| {
+                    debug_info.runtime_address(range.start)..debug_info.runtime_address(range.end)
+                })
+                .collect(),
+            is_inline_abstract: self.function_die.attr(gimli::DW_AT_inline).is_some(),
+            source,
+        }
+    }
+
+    /// Resolve an attribute of the function DIE, or of the DIEs it refers to with `DW_AT_abstract_origin`
+    /// and `DW_AT_specification`, if the function DIE does not have it.
+    pub(crate) fn attribute(
+        &self,
+        debug_info: &super::DebugInfo,
+        attribute_name: gimli::DwAt,
+    ) -> Option<debug_info::GimliAttribute> {
+        debug_info
+            .resolve_attribute(&self.function_die, self.unit_info, attribute_name)
+            .map(|(_, attribute)| attribute)
     }
 
     /// Try to retrieve the frame base for this function
@@ -280,14 +492,44 @@ impl<'a> FunctionDie<'a> {
     }
 }
 
-// Try to retrieve the attribute from the specification or the function DIE.
-fn collapsed_attribute<'a>(
-    function_die: &'a Die,
-    specification_die: Option<&'a Die>,
-    attribute_name: gimli::DwAt,
-) -> Option<&'a debug_info::GimliAttribute> {
-    specification_die
-        .as_ref()
-        .and_then(|specification_die| specification_die.attr(attribute_name))
-        .or_else(|| function_die.attr(attribute_name))
+/// Remove all generic arguments from a name, e.g. `Spi<u8>::transfer<u16>` becomes `Spi::transfer`.
+fn strip_generic_arguments(name: &str) -> String {
+    let mut depth = 0usize;
+    name.chars()
+        .filter(|c| match c {
+            '<' => {
+                depth += 1;
+                false
+            }
+            '>' if depth > 0 => {
+                depth -= 1;
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect()
+}
+
+/// Remove the parameter list and qualifiers from a demangled C++ function name,
+/// e.g. `(anonymous namespace)::transfer(unsigned char) const` becomes `(anonymous namespace)::transfer`.
+fn strip_parameter_list(name: &str) -> &str {
+    let Some(end) = name.rfind(')') else {
+        return name;
+    };
+
+    let mut depth = 0usize;
+    for (index, c) in name[..=end].char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &name[..index];
+                }
+            }
+            _ => {}
+        }
+    }
+
+    name
 }
@@ -7,30 +7,36 @@ use crate::{
     unit_info::UnitInfo,
 };
 
-/// C, C89, C99, C11, ...
+/// C, C89, C99, C11, ..., and C++
 pub mod c;
 /// Rust
 pub mod rust;
 
-mod parsing;
-mod value;
+pub(crate) mod parsing;
+pub(crate) mod value;
 
 pub fn from_dwarf(language: DwLang) -> Box<dyn ProgrammingLanguage> {
     match language {
-        // Handle all C-like languages the same now.
-        // We may have to split it later if this is not good enough.
+        // Handle all C-like languages the same now, including C++, whose classes are described
+        // like structs. We may have to split it later if this is not good enough.
         gimli::DW_LANG_C
         | gimli::DW_LANG_C89
         | gimli::DW_LANG_C99
         | gimli::DW_LANG_C11
-        | gimli::DW_LANG_C17 => Box::new(c::C),
+        | gimli::DW_LANG_C17
+        | gimli::DW_LANG_C_plus_plus
+        | gimli::DW_LANG_C_plus_plus_03
+        | gimli::DW_LANG_C_plus_plus_11
+        | gimli::DW_LANG_C_plus_plus_14
+        | gimli::DW_LANG_C_plus_plus_17
+        | gimli::DW_LANG_C_plus_plus_20 => Box::new(c::C),
         gimli::DW_LANG_Rust => Box::new(rust::Rust),
         other => Box::new(UnknownLanguage(other)),
     }
 }
 
 /// Programming language specific operations.
-pub trait ProgrammingLanguage {
+pub trait ProgrammingLanguage: Send + Sync {
     fn read_variable_value(
         &self,
         _variable: &Variable,
@@ -45,6 +51,10 @@ pub trait ProgrammingLanguage {
         _new_value: &str,
     ) -> Result<(), DebugError>;
 
+    /// Convert `new_value` into the bytes of a value with the base type of `variable`, in the byte
+    /// order of the target.
+    fn value_to_bytes(&self, variable: &Variable, new_value: &str) -> Result<Vec<u8>, DebugError>;
+
     fn format_enum_value(&self, type_name: &VariableType, value: &VariableName) -> VariableValue;
 
     fn format_array_type(&self, item_type: &str, length: usize) -> String;
@@ -66,6 +76,12 @@ pub trait ProgrammingLanguage {
         function_name.to_string()
     }
 
+    /// The name of a member of a struct of the type `struct_type`, which is named `name` in the
+    /// debug information.
+    fn format_member_name(&self, _struct_type: &VariableType, name: String) -> String {
+        name
+    }
+
     fn process_tag_with_no_type(&self, _variable: &Variable, tag: gimli::DwTag) -> VariableValue {
         VariableValue::Error(format!("Error: Failed to decode {tag} type reference"))
     }
@@ -74,6 +90,11 @@ pub trait ProgrammingLanguage {
         false
     }
 
+    /// `true` if arrays of `char`, and pointers to `char`, hold NUL terminated strings, like in C.
+    fn has_c_strings(&self) -> bool {
+        false
+    }
+
     fn modified_type_name(&self, modifier: &Modifier, name: &str) -> String {
         match modifier {
             Modifier::Const => format!("const {name}"),
@@ -84,6 +105,18 @@ pub trait ProgrammingLanguage {
         }
     }
 
+    /// The name of a pointer with a type qualifier. The qualifier follows the pointer, like in
+    /// `int* const`, because it applies to the pointer itself and not to the type it points to.
+    fn qualified_pointer_type_name(&self, modifier: &Modifier, pointer: &str) -> String {
+        match modifier {
+            Modifier::Const => format!("{pointer} const"),
+            Modifier::Volatile => format!("{pointer} volatile"),
+            Modifier::Restrict => format!("{pointer} restrict"),
+            Modifier::Atomic => format!("{pointer} _Atomic"),
+            Modifier::Typedef(ty) => ty.to_string(),
+        }
+    }
+
     // Post-process raw type representations for more user-friendly output.
     #[expect(clippy::too_many_arguments)]
     fn process_struct(
@@ -128,6 +161,17 @@ impl ProgrammingLanguage for UnknownLanguage {
         )))
     }
 
+    fn value_to_bytes(
+        &self,
+        _variable: &Variable,
+        _new_value: &str,
+    ) -> Result<Vec<u8>, DebugError> {
+        Err(DebugError::Other(format!(
+            "Updating variables for language {} is not supported.",
+            self.0
+        )))
+    }
+
     fn format_enum_value(&self, type_name: &VariableType, value: &VariableName) -> VariableValue {
         VariableValue::Valid(format!("{}::{}", type_name.display_name(self), value))
     }
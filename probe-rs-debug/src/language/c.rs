@@ -5,10 +5,13 @@ use crate::{
     VariableValue,
     language::{
         ProgrammingLanguage,
-        value::{Value, format_float},
+        parsing::{
+            ValueExt, parse_integer, read_unsigned, sign_extend, u128_from_target_bytes,
+            u128_to_target_bytes,
+        },
+        value::{Value, c_char_value, unquote_char},
     },
 };
-use std::fmt::{Display, Write};
 
 #[derive(Debug, Clone)]
 pub struct C;
@@ -25,39 +28,25 @@ impl ProgrammingLanguage for C {
 
             VariableType::Base(name) => match name.as_str() {
                 "_Bool" => UnsignedInt::get_value(variable, None, memory, variable_cache).into(),
-                "char" => CChar::get_value(variable, memory, variable_cache).into(),
+                name if is_char(variable, name) => {
+                    CChar::get_value(variable, memory, variable_cache).into()
+                }
 
-                "unsigned char"
-                | "unsigned int"
-                | "short unsigned int"
-                | "long unsigned int"
-                | "long long unsigned int" => {
+                name if is_unsigned_int(variable, name) => {
                     UnsignedInt::get_value(variable, None, memory, variable_cache).into()
                 }
-                "signed char"
-                | "int"
-                | "short int"
-                | "long int"
-                | "long long int"
-                | "signed int"
-                | "short signed int"
-                | "long signed int"
-                | "long long signed int" => {
+                name if is_signed_int(variable, name) => {
                     SignedInt::get_value(variable, None, memory, variable_cache).into()
                 }
 
                 "float" => match variable.byte_size {
-                    Some(4) | None => f32::get_value(variable, memory, variable_cache)
-                        .map(|f| format_float(f as f64))
-                        .into(),
+                    Some(4) | None => f32::get_value(variable, memory, variable_cache).into(),
                     Some(size) => {
                         VariableValue::Error(format!("Invalid byte size for float: {size}"))
                     }
                 },
                 "double" => match variable.byte_size {
-                    Some(8) | None => f64::get_value(variable, memory, variable_cache)
-                        .map(format_float)
-                        .into(),
+                    Some(8) | None => f64::get_value(variable, memory, variable_cache).into(),
                     Some(size) => {
                         VariableValue::Error(format!("Invalid byte size for double: {size}"))
                     }
@@ -92,12 +81,11 @@ impl ProgrammingLanguage for C {
         match variable.type_name.inner() {
             VariableType::Base(name) => match name.as_str() {
                 "_Bool" => UnsignedInt::update_value(variable, None, memory, new_value),
-                "char" => CChar::update_value(variable, memory, new_value),
-                "unsigned char" | "unsigned int" | "short unsigned int" | "long unsigned int" => {
+                name if is_char(variable, name) => CChar::update_value(variable, memory, new_value),
+                name if is_unsigned_int(variable, name) => {
                     UnsignedInt::update_value(variable, None, memory, new_value)
                 }
-                "signed char" | "int" | "short int" | "long int" | "signed int"
-                | "short signed int" | "long signed int" => {
+                name if is_signed_int(variable, name) => {
                     SignedInt::update_value(variable, None, memory, new_value)
                 }
                 "float" => f32::update_value(variable, memory, new_value),
@@ -130,6 +118,37 @@ impl ProgrammingLanguage for C {
         }
     }
 
+    fn value_to_bytes(&self, variable: &Variable, new_value: &str) -> Result<Vec<u8>, DebugError> {
+        let VariableType::Base(name) = variable.type_name.inner() else {
+            return Err(DebugError::WarnAndContinue {
+                message: format!(
+                    "Updating {} variables is not yet supported.",
+                    variable.type_name.kind()
+                ),
+            });
+        };
+
+        let bytes = variable.byte_size.unwrap_or(1).min(16) as usize;
+        let bits = bytes as u32 * 8;
+
+        let value = match name.as_str() {
+            name if is_char(variable, name) => CChar::parse(new_value)? as u128,
+            name if name == "_Bool" || is_unsigned_int(variable, name) => {
+                parse_integer(new_value, bits, false)?
+            }
+            name if is_signed_int(variable, name) => parse_integer(new_value, bits, true)?,
+            "float" => return Ok(f32::parse_to_bytes(new_value, variable.endianness)?.to_vec()),
+            "double" => return Ok(f64::parse_to_bytes(new_value, variable.endianness)?.to_vec()),
+            other => {
+                return Err(DebugError::WarnAndContinue {
+                    message: format!("Updating {other} variables is not yet supported."),
+                });
+            }
+        };
+
+        Ok(u128_to_target_bytes(value, bytes, variable.endianness))
+    }
+
     fn format_array_type(&self, item_type: &str, length: usize) -> String {
         format!("{item_type}[{length}]")
     }
@@ -142,9 +161,14 @@ impl ProgrammingLanguage for C {
         format!("{}*", pointee.unwrap_or("void"))
     }
 
+    fn has_c_strings(&self) -> bool {
+        true
+    }
+
     fn process_tag_with_no_type(&self, variable: &Variable, tag: gimli::DwTag) -> VariableValue {
         match tag {
             gimli::DW_TAG_const_type => VariableValue::Valid("const void".to_string()),
+            gimli::DW_TAG_volatile_type => VariableValue::Valid("volatile void".to_string()),
             gimli::DW_TAG_pointer_type => {
                 let name = if let VariableLocation::Address(addr) = variable.memory_location {
                     format!("void* @ {addr:X}")
@@ -159,16 +183,61 @@ impl ProgrammingLanguage for C {
     }
 }
 
+/// The names of the unsigned integer types, for base types without a `DW_AT_encoding`.
+const UNSIGNED_INT_TYPES: &[&str] = &[
+    "unsigned char",
+    "unsigned int",
+    "short unsigned int",
+    "long unsigned int",
+    "long long unsigned int",
+    "__int128 unsigned",
+];
+
+/// The names of the signed integer types, for base types without a `DW_AT_encoding`.
+const SIGNED_INT_TYPES: &[&str] = &[
+    "signed char",
+    "int",
+    "short int",
+    "long int",
+    "long long int",
+    "signed int",
+    "short signed int",
+    "long signed int",
+    "long long signed int",
+    "__int128",
+];
+
+/// Whether `variable`, of the base type `name`, is a character.
+fn is_char(variable: &Variable, name: &str) -> bool {
+    match variable.encoding {
+        Some(encoding) => {
+            [gimli::DW_ATE_signed_char, gimli::DW_ATE_unsigned_char].contains(&encoding)
+        }
+        None => name == "char",
+    }
+}
+
+/// Whether `variable`, of the base type `name`, is an unsigned integer of up to 16 bytes.
+fn is_unsigned_int(variable: &Variable, name: &str) -> bool {
+    match variable.encoding {
+        Some(encoding) => encoding == gimli::DW_ATE_unsigned,
+        None => UNSIGNED_INT_TYPES.contains(&name),
+    }
+}
+
+/// Whether `variable`, of the base type `name`, is a signed integer of up to 16 bytes.
+fn is_signed_int(variable: &Variable, name: &str) -> bool {
+    match variable.encoding {
+        Some(encoding) => encoding == gimli::DW_ATE_signed,
+        None => SIGNED_INT_TYPES.contains(&name),
+    }
+}
+
 struct CChar(u8);
 
-impl Display for CChar {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let c = self.0;
-        if c.is_ascii() {
-            f.write_char(c as char)
-        } else {
-            f.write_fmt(format_args!("\\x{c:02x}"))
-        }
+impl From<CChar> for VariableValue {
+    fn from(value: CChar) -> Self {
+        c_char_value(value.0)
     }
 }
 
@@ -191,6 +260,17 @@ impl Value for CChar {
         memory: &mut dyn MemoryInterface,
         new_value: &str,
     ) -> Result<(), DebugError> {
+        let new_value = Self::parse(new_value)?;
+        memory.write_word_8(variable.memory_location.memory_address()?, new_value)?;
+
+        Ok(())
+    }
+}
+
+impl CChar {
+    /// Parse a single ASCII character, or an escaped byte like `\x7f`, with or without the quotes
+    /// it is shown with.
+    fn parse(new_value: &str) -> Result<u8, DebugError> {
         fn input_error(value: &str) -> DebugError {
             DebugError::WarnAndContinue {
                 message: format!(
@@ -201,28 +281,22 @@ impl Value for CChar {
 
         // TODO: what do we want to support here exactly? This is now symmetrical with get_value
         // but we could be somewhat smarter, too.
+        let new_value = unquote_char(new_value);
         let new_value = if new_value.len() == 1 && new_value.is_ascii() {
             new_value.as_bytes()[0]
         } else if new_value.starts_with("\\x") && [3, 4].contains(&new_value.len()) {
             u8::from_str_radix(&new_value[2..], 16).map_err(|_| input_error(new_value))?
         } else {
-            return Err(input_error(new_value));
+            // The numeric value, as it is shown with `ValueFormat::Dec`.
+            parse_integer(new_value, 8, false).map_err(|_| input_error(new_value))? as u8
         };
 
-        memory.write_word_8(variable.memory_location.memory_address()?, new_value)?;
-
-        Ok(())
+        Ok(new_value)
     }
 }
 
 struct UnsignedInt(u128);
 
-impl Display for UnsignedInt {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.0))
-    }
-}
-
 impl UnsignedInt {
     fn get_value(
         variable: &Variable,
@@ -234,22 +308,8 @@ impl UnsignedInt {
         Self: Sized,
     {
         // Read the bits. The actual count is encoded in the variable type.
-        let mut buff = [0u8; 16];
         let bytes = variable.byte_size.unwrap_or(1).min(16) as usize;
-        if let VariableLocation::RegisterValue(value) = variable.memory_location {
-            // The value is in a register, we just need to extract the bytes.
-            let reg_bytes = TryInto::<u128>::try_into(value)?.to_le_bytes();
-
-            buff[..bytes].copy_from_slice(&reg_bytes[..bytes]);
-        } else {
-            // We only have an address, we need to read the value from memory.
-            memory.read(
-                variable.memory_location.memory_address()?,
-                &mut buff[..bytes],
-            )?;
-        }
-
-        let value = u128::from_le_bytes(buff);
+        let value = read_unsigned(variable, memory, bytes)?;
 
         // Extract bitfield bits
         let value = match bitfield {
@@ -266,12 +326,8 @@ impl UnsignedInt {
         memory: &mut dyn MemoryInterface,
         new_value: &str,
     ) -> Result<(), DebugError> {
-        match parse_int::parse::<u128>(new_value) {
-            Ok(value) => write_unsigned_bytes(variable, bitfield, memory, value),
-            Err(e) => Err(DebugError::WarnAndContinue {
-                message: format!("Invalid data conversion from value: {new_value:?}. {e:?}"),
-            }),
-        }
+        let value = parse_integer(new_value, integer_bits(variable, bitfield), false)?;
+        write_unsigned_bytes(variable, bitfield, memory, value)
     }
 }
 
@@ -282,12 +338,12 @@ fn write_unsigned_bytes(
     unsigned: u128,
 ) -> Result<(), DebugError> {
     // TODO: check that value actually fits into `bytes` number of bytes
-    let bytes = variable.byte_size.unwrap_or(1) as usize;
+    let bytes = variable.byte_size.unwrap_or(1).min(16) as usize;
 
     // Figure out the bitfield offset & bit count
     let Some(bitfield) = bitfield else {
-        let buff = unsigned.to_le_bytes();
-        memory.write_8(variable.memory_location.memory_address()?, &buff[..bytes])?;
+        let buff = u128_to_target_bytes(unsigned, bytes, variable.endianness);
+        memory.write_8(variable.memory_location.memory_address()?, &buff)?;
         return Ok(());
     };
 
@@ -299,25 +355,27 @@ fn write_unsigned_bytes(
         variable.memory_location.memory_address()?,
         &mut buff[..bytes],
     )?;
-    let read_value = u128::from_le_bytes(buff);
+    let read_value = u128_from_target_bytes(&buff[..bytes], variable.endianness);
 
     let new_value = bitfield.insert(read_value, unsigned);
 
     // Write the new value
-    let buff = new_value.to_le_bytes();
-    memory.write_8(variable.memory_location.memory_address()?, &buff[..bytes])?;
+    let buff = u128_to_target_bytes(new_value, bytes, variable.endianness);
+    memory.write_8(variable.memory_location.memory_address()?, &buff)?;
 
     Ok(())
 }
 
-struct SignedInt(i128);
-
-impl Display for SignedInt {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.0))
+/// The number of bits of an integer, or of a bitfield.
+fn integer_bits(variable: &Variable, bitfield: Option<Bitfield>) -> u32 {
+    match bitfield {
+        Some(bitfield) => bitfield.length() as u32,
+        None => variable.byte_size.unwrap_or(1).min(16) as u32 * 8,
     }
 }
 
+struct SignedInt(i128);
+
 impl SignedInt {
     fn get_value(
         variable: &Variable,
@@ -330,22 +388,11 @@ impl SignedInt {
     {
         // We read the number as Unsigned first, to avoid duplicating the bitfield handling.
         let unsigned = UnsignedInt::get_value(variable, bitfield, memory, variable_cache)?.0;
-        let bytes = variable.byte_size.unwrap_or(1).min(16) as usize;
-
-        // Sign extend
-        let sign_bit_shift = match bitfield {
-            Some(bitfield) => bitfield.length() as usize - 1,
-            None => (bytes * 8) - 1,
-        };
-
-        let negative = unsigned & (1 << sign_bit_shift) != 0;
-        let value = if negative {
-            (unsigned | (!0 << sign_bit_shift)) as i128
-        } else {
-            unsigned as i128
-        };
 
-        Ok(Self(value))
+        Ok(Self(sign_extend(
+            unsigned,
+            integer_bits(variable, bitfield),
+        )))
     }
 
     fn update_value(
@@ -354,12 +401,8 @@ impl SignedInt {
         memory: &mut dyn MemoryInterface,
         new_value: &str,
     ) -> Result<(), DebugError> {
-        match parse_int::parse::<i128>(new_value) {
-            Ok(value) => write_unsigned_bytes(variable, bitfield, memory, value as u128),
-            Err(e) => Err(DebugError::WarnAndContinue {
-                message: format!("Invalid data conversion from value: {new_value:?}. {e:?}"),
-            }),
-        }
+        let value = parse_integer(new_value, integer_bits(variable, bitfield), true)?;
+        write_unsigned_bytes(variable, bitfield, memory, value)
     }
 }
 
@@ -367,7 +410,7 @@ impl From<Result<UnsignedInt, DebugError>> for VariableValue {
     fn from(val: Result<UnsignedInt, DebugError>) -> Self {
         val.map_or_else(
             |err| VariableValue::Error(format!("{err:?}")),
-            |value| VariableValue::Valid(value.to_string()),
+            |value| value.0.into(),
         )
     }
 }
@@ -376,7 +419,7 @@ impl From<Result<SignedInt, DebugError>> for VariableValue {
     fn from(val: Result<SignedInt, DebugError>) -> Self {
         val.map_or_else(
             |err| VariableValue::Error(format!("{err:?}")),
-            |value| VariableValue::Valid(value.to_string()),
+            |value| value.0.into(),
         )
     }
 }
@@ -1,3 +1,4 @@
+use gimli::RunTimeEndian;
 use probe_rs::MemoryInterface;
 
 use crate::{DebugError, Variable, VariableLocation};
@@ -6,8 +7,14 @@ use crate::{DebugError, Variable, VariableLocation};
 pub trait ValueExt: Sized {
     type Out;
 
-    /// Parse the value from a string into a number of bytes.
-    fn parse_to_bytes(s: &str) -> Result<Self::Out, DebugError>;
+    /// Parse the value from a string into a number of bytes, in the byte order of the target.
+    fn parse_to_bytes(s: &str, endianness: RunTimeEndian) -> Result<Self::Out, DebugError>;
+
+    /// Convert the bytes of a value, as stored in target memory, into the value.
+    fn from_target_bytes(bytes: Self::Out, endianness: RunTimeEndian) -> Self;
+
+    /// Convert the value into the bytes that represent it in target memory.
+    fn to_target_bytes(self, endianness: RunTimeEndian) -> Self::Out;
 
     /// Read the value from a [`VariableLocation`].
     fn read_from_location(
@@ -16,17 +23,184 @@ pub trait ValueExt: Sized {
     ) -> Result<Self, DebugError>;
 }
 
+/// Read the bytes of a value from its [`VariableLocation`], which is either an address in memory, or
+/// the bytes of an implicit value.
+pub(crate) fn read_location_bytes(
+    location: &VariableLocation,
+    memory: &mut dyn MemoryInterface,
+    buff: &mut [u8],
+) -> Result<(), DebugError> {
+    if let VariableLocation::Implicit(bytes) = location {
+        let Some(bytes) = bytes.get(..buff.len()) else {
+            return Err(DebugError::WarnAndContinue {
+                message: format!(
+                    "The implicit value has {} bytes, but {} bytes are required.",
+                    bytes.len(),
+                    buff.len()
+                ),
+            });
+        };
+        buff.copy_from_slice(bytes);
+    } else {
+        memory.read(location.memory_address()?, buff)?;
+    }
+
+    Ok(())
+}
+
+/// Read the `size` bytes of an unsigned integer of up to 16 bytes from the location of `variable`.
+pub(crate) fn read_unsigned(
+    variable: &Variable,
+    memory: &mut dyn MemoryInterface,
+    size: usize,
+) -> Result<u128, DebugError> {
+    if let VariableLocation::RegisterValue(value) = variable.memory_location {
+        // The value is in a register, we just need to mask off the unused bytes.
+        let value = TryInto::<u128>::try_into(value)?;
+        return Ok(if size < 16 {
+            value & ((1 << (size * 8)) - 1)
+        } else {
+            value
+        });
+    }
+
+    let mut buff = [0u8; 16];
+    read_location_bytes(&variable.memory_location, memory, &mut buff[..size])?;
+    Ok(u128_from_target_bytes(&buff[..size], variable.endianness))
+}
+
+/// Sign extend the two's complement integer in the `bits` least significant bits of `value`.
+pub(crate) fn sign_extend(value: u128, bits: u32) -> i128 {
+    let shift = 128 - bits.clamp(1, 128);
+    ((value << shift) as i128) >> shift
+}
+
+/// Convert the bytes of an unsigned integer of up to 16 bytes, as stored in target memory, into an `u128`.
+pub(crate) fn u128_from_target_bytes(bytes: &[u8], endianness: RunTimeEndian) -> u128 {
+    let mut buff = [0u8; 16];
+    match endianness {
+        RunTimeEndian::Little => {
+            buff[..bytes.len()].copy_from_slice(bytes);
+            u128::from_le_bytes(buff)
+        }
+        RunTimeEndian::Big => {
+            buff[16 - bytes.len()..].copy_from_slice(bytes);
+            u128::from_be_bytes(buff)
+        }
+    }
+}
+
+/// Convert the `size` least significant bytes of `value` into the bytes that represent it in target memory.
+pub(crate) fn u128_to_target_bytes(value: u128, size: usize, endianness: RunTimeEndian) -> Vec<u8> {
+    match endianness {
+        RunTimeEndian::Little => value.to_le_bytes()[..size].to_vec(),
+        RunTimeEndian::Big => value.to_be_bytes()[16 - size..].to_vec(),
+    }
+}
+
+fn conversion_error(s: &str, error: impl std::fmt::Debug) -> DebugError {
+    DebugError::WarnAndContinue {
+        message: format!("Invalid data conversion from value: {s:?}. {error:?}"),
+    }
+}
+
+/// Parse an integer of `bits` bits, in any of the formats it can be displayed in, see
+/// [`crate::ValueFormat`].
+///
+/// The value can be decimal, hexadecimal (`0x2a`), binary (`0b101010`), octal (`0o52`), or a
+/// quoted character (`'*'`). Hexadecimal, binary and octal values of signed integers are the two's
+/// complement bits of the value, e.g. `0xff` is `-1` for an `i8`. The bits of the value are returned.
+pub(crate) fn parse_integer(s: &str, bits: u32, signed: bool) -> Result<u128, DebugError> {
+    let s = s.trim();
+    let mask = if bits < 128 {
+        (1 << bits) - 1
+    } else {
+        u128::MAX
+    };
+    let out_of_range = || DebugError::WarnAndContinue {
+        message: format!("The value {s} does not fit in {bits} bits."),
+    };
+
+    let value = if s.len() > 2 && s.starts_with('\'') && s.ends_with('\'') {
+        let mut chars = s[1..s.len() - 1].chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => u128::from(u32::from(c)),
+            _ => return Err(conversion_error(s, "Not a single character")),
+        }
+    } else if ["0x", "0X", "0b", "0B", "0o", "0O"]
+        .iter()
+        .any(|prefix| s.starts_with(prefix))
+    {
+        // The bits of the value, regardless of the signedness.
+        ::parse_int::parse::<u128>(s).map_err(|error| conversion_error(s, error))?
+    } else if signed {
+        let value = ::parse_int::parse::<i128>(s).map_err(|error| conversion_error(s, error))?;
+        if sign_extend(value as u128 & mask, bits) != value {
+            return Err(out_of_range());
+        }
+        return Ok(value as u128 & mask);
+    } else if s.starts_with('-') {
+        // `parse_int` can't parse negative numbers as an unsigned type.
+        return Err(out_of_range());
+    } else {
+        ::parse_int::parse::<u128>(s).map_err(|error| conversion_error(s, error))?
+    };
+
+    if value & !mask != 0 {
+        return Err(out_of_range());
+    }
+
+    Ok(value)
+}
+
+/// Parse a boolean, either as `true` or `false`, or as the numeric value `1` or `0`.
+pub(crate) fn parse_bool(s: &str) -> Result<bool, DebugError> {
+    match s.trim() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => match parse_integer(other, 8, false) {
+            Ok(0) => Ok(false),
+            Ok(1) => Ok(true),
+            _ => Err(conversion_error(s, "Not a boolean")),
+        },
+    }
+}
+
+/// Parse a character, either quoted or not, or its numeric value, e.g. `65` for `'A'`.
+pub(crate) fn parse_char(s: &str) -> Result<char, DebugError> {
+    let unquoted = crate::language::value::unquote_char(s);
+    let mut chars = unquoted.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(c);
+    }
+
+    parse_integer(s, 32, false)
+        .ok()
+        .and_then(|value| char::from_u32(value as u32))
+        .ok_or_else(|| conversion_error(s, "Not a character"))
+}
+
 macro_rules! impl_extensions {
-    ($t:ty, $bytes:expr) => {
+    ($t:ty, $bytes:expr, $parse:expr) => {
         impl ValueExt for $t {
             type Out = [u8; $bytes];
 
-            fn parse_to_bytes(s: &str) -> Result<Self::Out, DebugError> {
-                match ::parse_int::parse::<$t>(s) {
-                    Ok(value) => Ok(<$t>::to_le_bytes(value)),
-                    Err(e) => Err(DebugError::WarnAndContinue {
-                        message: format!("Invalid data conversion from value: {s:?}. {e:?}"),
-                    }),
+            fn parse_to_bytes(s: &str, endianness: RunTimeEndian) -> Result<Self::Out, DebugError> {
+                let parse: fn(&str) -> Result<$t, DebugError> = $parse;
+                parse(s).map(|value| value.to_target_bytes(endianness))
+            }
+
+            fn from_target_bytes(bytes: Self::Out, endianness: RunTimeEndian) -> Self {
+                match endianness {
+                    RunTimeEndian::Little => <$t>::from_le_bytes(bytes),
+                    RunTimeEndian::Big => <$t>::from_be_bytes(bytes),
+                }
+            }
+
+            fn to_target_bytes(self, endianness: RunTimeEndian) -> Self::Out {
+                match endianness {
+                    RunTimeEndian::Little => <$t>::to_le_bytes(self),
+                    RunTimeEndian::Big => <$t>::to_be_bytes(self),
                 }
             }
 
@@ -36,31 +210,106 @@ macro_rules! impl_extensions {
             ) -> Result<Self, DebugError> {
                 let mut buff: Self::Out = [0u8; $bytes];
                 if let VariableLocation::RegisterValue(value) = variable.memory_location {
-                    // The value is in a register, we just need to extract the bytes.
+                    // The value is in a register, we just need to extract the least significant bytes.
                     let reg_bytes = TryInto::<u128>::try_into(value)?.to_le_bytes();
 
                     buff.copy_from_slice(&reg_bytes[..$bytes]);
+                    Ok(<$t>::from_le_bytes(buff))
                 } else {
-                    // We only have an address, we need to read the value from memory.
-                    memory.read(variable.memory_location.memory_address()?, &mut buff)?;
+                    read_location_bytes(&variable.memory_location, memory, &mut buff)?;
+                    Ok(<$t>::from_target_bytes(buff, variable.endianness))
                 }
-
-                Ok(<$t>::from_le_bytes(buff))
             }
         }
     };
 }
 
-impl_extensions!(u8, 1);
-impl_extensions!(i8, 1);
-impl_extensions!(u16, 2);
-impl_extensions!(i16, 2);
-impl_extensions!(u32, 4);
-impl_extensions!(i32, 4);
-impl_extensions!(u64, 8);
-impl_extensions!(i64, 8);
-impl_extensions!(u128, 16);
-impl_extensions!(i128, 16);
-
-impl_extensions!(f32, 4);
-impl_extensions!(f64, 8);
+impl_extensions!(u8, 1, |s| parse_integer(s, 8, false).map(|v| v as u8));
+impl_extensions!(i8, 1, |s| parse_integer(s, 8, true).map(|v| v as i8));
+impl_extensions!(u16, 2, |s| parse_integer(s, 16, false).map(|v| v as u16));
+impl_extensions!(i16, 2, |s| parse_integer(s, 16, true).map(|v| v as i16));
+impl_extensions!(u32, 4, |s| parse_integer(s, 32, false).map(|v| v as u32));
+impl_extensions!(i32, 4, |s| parse_integer(s, 32, true).map(|v| v as i32));
+impl_extensions!(u64, 8, |s| parse_integer(s, 64, false).map(|v| v as u64));
+impl_extensions!(i64, 8, |s| parse_integer(s, 64, true).map(|v| v as i64));
+impl_extensions!(u128, 16, |s| parse_integer(s, 128, false));
+impl_extensions!(i128, 16, |s| parse_integer(s, 128, true).map(|v| v as i128));
+
+impl_extensions!(f32, 4, |s| ::parse_int::parse::<f32>(s)
+    .map_err(|error| conversion_error(s, error)));
+impl_extensions!(f64, 8, |s| ::parse_int::parse::<f64>(s)
+    .map_err(|error| conversion_error(s, error)));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use gimli::RunTimeEndian;
+    use test_case::test_case;
+
+    #[test_case(RunTimeEndian::Little, [0x78, 0x56, 0x34, 0x12]; "little endian")]
+    #[test_case(RunTimeEndian::Big, [0x12, 0x34, 0x56, 0x78]; "big endian")]
+    fn target_bytes_round_trip(endianness: RunTimeEndian, bytes: [u8; 4]) {
+        assert_eq!(u32::from_target_bytes(bytes, endianness), 0x1234_5678);
+        assert_eq!(0x1234_5678u32.to_target_bytes(endianness), bytes);
+        assert_eq!(
+            u32::parse_to_bytes("0x12345678", endianness).unwrap(),
+            bytes
+        );
+    }
+
+    #[test_case(RunTimeEndian::Little, &[0x34, 0x12, 0x00]; "little endian")]
+    #[test_case(RunTimeEndian::Big, &[0x00, 0x12, 0x34]; "big endian")]
+    fn partial_integer_round_trip(endianness: RunTimeEndian, bytes: &[u8]) {
+        assert_eq!(u128_from_target_bytes(bytes, endianness), 0x1234);
+        assert_eq!(u128_to_target_bytes(0x1234, bytes.len(), endianness), bytes);
+    }
+
+    #[test_case(0x80, 8, i128::from(i8::MIN); "i8 min")]
+    #[test_case(0xff, 8, -1; "i8 minus one")]
+    #[test_case(0x7f, 8, 127; "i8 max")]
+    #[test_case(0x8000, 16, i128::from(i16::MIN); "i16 min")]
+    #[test_case(0xffff, 16, -1; "i16 minus one")]
+    #[test_case(0x8000_0000, 32, i128::from(i32::MIN); "i32 min")]
+    #[test_case(0xffff_ffff, 32, -1; "i32 minus one")]
+    #[test_case(1 << 63, 64, i128::from(i64::MIN); "i64 min")]
+    #[test_case(u64::MAX as u128, 64, -1; "i64 minus one")]
+    #[test_case(1 << 127, 128, i128::MIN; "i128 min")]
+    #[test_case(u128::MAX, 128, -1; "i128 minus one")]
+    #[test_case(0b100, 3, -4; "3 bit bitfield")]
+    fn sign_extend_twos_complement(value: u128, bits: u32, expected: i128) {
+        assert_eq!(sign_extend(value, bits), expected);
+    }
+
+    #[test_case("42", 8, false, 42; "decimal")]
+    #[test_case("0x2a", 8, false, 42; "hexadecimal")]
+    #[test_case("0b101010", 8, false, 42; "binary")]
+    #[test_case("0o52", 8, false, 42; "octal")]
+    #[test_case("'*'", 8, false, 42; "character")]
+    #[test_case("-1", 8, true, 0xff; "negative decimal")]
+    #[test_case("0xff", 8, true, 0xff; "twos complement hexadecimal")]
+    #[test_case("0b11111110", 8, true, 0xfe; "twos complement binary")]
+    #[test_case("-1", 128, true, u128::MAX; "negative 128 bits")]
+    fn parse_integer_in_any_format(s: &str, bits: u32, signed: bool, expected: u128) {
+        assert_eq!(parse_integer(s, bits, signed).unwrap(), expected);
+    }
+
+    #[test_case("256", 8, false; "unsigned too large")]
+    #[test_case("0x100", 8, true; "hexadecimal too large")]
+    #[test_case("128", 8, true; "signed too large")]
+    #[test_case("-129", 8, true; "signed too small")]
+    #[test_case("-1", 8, false; "negative unsigned")]
+    #[test_case("'ab'", 8, false; "more than one character")]
+    fn parse_integer_out_of_range(s: &str, bits: u32, signed: bool) {
+        assert!(parse_integer(s, bits, signed).is_err());
+    }
+
+    #[test]
+    fn parse_bool_and_char_numerically() {
+        assert!(parse_bool("1").unwrap());
+        assert!(!parse_bool("0x0").unwrap());
+        assert!(parse_bool("2").is_err());
+        assert_eq!(parse_char("'A'").unwrap(), 'A');
+        assert_eq!(parse_char("65").unwrap(), 'A');
+        assert_eq!(parse_char("0x1f600").unwrap(), '😀');
+    }
+}
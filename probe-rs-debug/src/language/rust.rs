@@ -1,17 +1,21 @@
 use crate::{
     DebugError, DebugInfo, GimliReader, ObjectRef, Variable, VariableCache, VariableLocation,
-    VariableName, VariableNodeType, VariableType, VariableValue,
+    VariableName, VariableNodeType, VariableType, VariableValue, VariantRole, extract_byte_size,
     function_die::Die,
     language::{
         ProgrammingLanguage,
-        value::{Value, format_float},
+        parsing::{ValueExt, parse_bool, parse_char},
+        value::{Value, read_string, read_text},
     },
     stack_frame::StackFrameInfo,
-    unit_info::UnitInfo,
+    type_formatter::{FormatContext, TypeFormatterEntry},
+    unit_info::{UnitInfo, extract_name},
 };
 
-use gimli::DebuggingInformationEntry;
+use gimli::{DebuggingInformationEntry, UnitOffset};
+use itertools::Itertools;
 use probe_rs::MemoryInterface;
+use std::collections::HashSet;
 
 struct Slice<'a> {
     length: u64,
@@ -22,20 +26,12 @@ struct Slice<'a> {
 pub struct Rust;
 impl Rust {
     fn try_get_slice<'a>(variable: &'a Variable, cache: &'a VariableCache) -> Option<Slice<'a>> {
-        fn is_field(var: &Variable, name: &str) -> bool {
-            matches!(var.name, VariableName::Named(ref var_name) if var_name == name)
-        }
-
         Some(Slice {
             // Do we have a length?
             length: cache
                 .get_children(variable.variable_key)
                 .find(|c| is_field(c, "length"))
-                .and_then(|field| match &field.value {
-                    VariableValue::Valid(length_value) => Some(length_value),
-                    _ => None,
-                })
-                .and_then(|length_str| length_str.parse().ok())?,
+                .and_then(|field| field.value.as_u64())?,
 
             // Do we have a data pointer?
             data_ptr: cache
@@ -44,7 +40,8 @@ impl Rust {
         })
     }
 
-    /// Replaces *const data pointer with *const [data; len] in slices.
+    /// Replaces the length and data pointer of a slice with its elements, and shows its length as
+    /// its value.
     ///
     /// This function may return `Ok(())` even if it does not modify the variable.
     #[expect(clippy::too_many_arguments)]
@@ -61,57 +58,1410 @@ impl Rust {
         let Some(slice) = Self::try_get_slice(variable, cache) else {
             return Ok(());
         };
-
-        // Turn the data pointer into an array.
-        let pointer_key = slice.data_ptr.variable_key;
         let length = slice.length;
+        if length == 0 {
+            // The pointer of an empty slice may be dangling, and there are no elements to show.
+            cache.remove_cache_entry_children(variable.variable_key)?;
+            variable.variable_node_type = VariableNodeType::DoNotRecurse;
+            variable.value = VariableValue::Valid("len=0".to_string());
+            return Ok(());
+        }
 
-        let Some(mut pointee) = cache.get_children(pointer_key).next().cloned() else {
+        let Some(pointee) = cache.get_children(slice.data_ptr.variable_key).next() else {
             return Ok(());
         };
 
-        // Do we know the type of the data?
-        let Some(type_node_offset) = pointee.type_node_offset else {
+        // Do we know the type and the location of the data? If not, the fields are kept.
+        let (Some(type_node_offset), VariableLocation::Address(address)) =
+            (pointee.type_node_offset, &pointee.memory_location)
+        else {
             return Ok(());
         };
+        let address = *address;
+        let element_type_node = unit_info.unit.entry(type_node_offset)?;
+        if address == 0 || !elements_are_readable(memory, &element_type_node, address, length) {
+            tracing::debug!(
+                "Not expanding the elements of {}, with the length {length} and the address {address:#010x}.",
+                variable.name
+            );
+            return Ok(());
+        }
 
-        // Let's just remove the pointer. While it may be interesting where the data is, the
-        // address can be read using the debugger, and is otherwise just noise on the UI.
-        cache.remove_cache_entry(pointer_key)?;
-
-        // Replace the pointee type with an array of known length. We don't have to modify the
-        // memory location, as the pointer is already pointing to the first element of the array.
-        pointee.parent_key = ObjectRef::Invalid;
-        pointee.variable_key = ObjectRef::Invalid;
-        pointee.value = VariableValue::Empty;
-        pointee.type_name = VariableType::Array {
-            item_type_name: { Box::new(pointee.type_name) },
-            count: length as usize,
-        };
-        pointee.variable_node_type = VariableNodeType::RecurseToBaseType;
+        self.replace_with_elements(
+            unit_info,
+            debug_info,
+            variable,
+            memory,
+            cache,
+            type_node_offset,
+            address,
+            length,
+            frame_info,
+        )?;
+        variable.value = VariableValue::Valid(
+            match unit_info.byte_preview(debug_info, type_node_offset, address, length, memory) {
+                Some(preview) => format!("len={length}, {preview}"),
+                None => format!("len={length}"),
+            },
+        );
 
-        cache.add_variable(variable.variable_key, &mut pointee)?;
+        Ok(())
+    }
+
+    /// Replaces the children of a sequence, like a slice or a `Vec<T>`, with its `length`
+    /// elements, which are stored at `address`.
+    #[expect(clippy::too_many_arguments)]
+    fn replace_with_elements(
+        &self,
+        unit_info: &UnitInfo,
+        debug_info: &DebugInfo,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        element_type_offset: UnitOffset,
+        address: u64,
+        length: u64,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let element_type_node = unit_info.unit.entry(element_type_offset)?;
 
-        let array_member_type_node = unit_info
-            .unit
-            .entry(type_node_offset)
-            .expect("Failed to get array member type node. This is a bug, please report it!");
+        // The fields are just noise on the UI, once the elements are known.
+        cache.remove_cache_entry_children(variable.variable_key)?;
 
-        let member_range = 0..length;
-        unit_info.expand_array_members(
+        unit_info.extract_sequence_members(
             debug_info,
-            &array_member_type_node,
+            &element_type_node,
             cache,
-            &mut pointee,
+            variable,
             memory,
-            &[member_range],
+            address,
+            length,
+            frame_info,
+        )
+    }
+
+    /// Set the value of a closure to the names of the variables it captures, which are its fields.
+    fn format_closure(
+        &self,
+        debug_info: &DebugInfo,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        cache.update_variable(variable)?;
+        let captures = resolve_children(debug_info, memory, cache, variable, frame_info);
+        if captures.is_empty() && variable.byte_size != Some(0) {
+            // The captures could not be resolved.
+            return Ok(());
+        }
+
+        variable.value = VariableValue::Valid(if captures.is_empty() {
+            "closure without captures".to_string()
+        } else {
+            format!(
+                "closure capturing {}",
+                captures.iter().map(|capture| &capture.name).join(", ")
+            )
+        });
+
+        Ok(())
+    }
+
+    /// Set the value of the state machine of an `async fn` or an `async` block to its state, e.g.
+    /// `Suspend0 at main.rs:36` while it waits at its first `.await`, and replace its fields with
+    /// the variables which are kept alive in that state.
+    ///
+    /// The state is selected from the `DW_TAG_variant_part` of the type, see
+    /// `UnitInfo::process_tree`, so this only formats the single state which is left as a child.
+    fn format_coroutine(
+        &self,
+        debug_info: &DebugInfo,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        cache.update_variable(variable)?;
+        let states = resolve_children(debug_info, memory, cache, variable, frame_info);
+        let [state] = states.as_slice() else {
+            return Ok(());
+        };
+
+        let state_name = unqualified_type_name(&state.type_name()).to_string();
+        let live_variables = resolve_children(debug_info, memory, cache, state, frame_info);
+        cache.adopt_grand_children(variable, state)?;
+
+        // The arguments of the function are listed again in the states which keep them alive.
+        let mut names = HashSet::new();
+        for live_variable in live_variables {
+            if !names.insert(live_variable.name) {
+                cache.remove_cache_entry(live_variable.variable_key)?;
+            }
+        }
+
+        // The declaration of a suspended state is the `.await` it waits at.
+        let await_point = match &state.source_location {
+            Some(location) if state_name.starts_with("Suspend") => {
+                location.file_name().zip(location.line)
+            }
+            _ => None,
+        };
+        variable.value = VariableValue::Valid(match await_point {
+            Some((file_name, line)) => format!("{state_name} at {file_name}:{line}"),
+            None => state_name,
+        });
+
+        Ok(())
+    }
+
+    /// Replaces the fields of a smart pointer or a cell, like `Rc<T>` or `RefCell<T>`, with the
+    /// value it contains, followed by its bookkeeping fields, e.g. the reference counts of an
+    /// `Rc<T>`, or the borrow flag of a `RefCell<T>`.
+    ///
+    /// The type of the variable is kept, and a scalar contained value is also shown as its value.
+    /// This function may return `Ok(())` even if it does not modify the variable.
+    #[expect(clippy::too_many_arguments)]
+    fn unwrap_container(
+        &self,
+        unit_info: &UnitInfo,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+        container: Container,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        // The fields look up their parents, e.g. to find cycles of pointers.
+        cache.update_variable(variable)?;
+        let fields = resolve_children(debug_info, memory, cache, variable, frame_info);
+
+        // A `Box<[T]>` is shown like a slice.
+        if container == Container::Box && fields.iter().any(|field| is_field(field, "data_ptr")) {
+            return self.expand_slice(
+                unit_info, debug_info, node, variable, memory, cache, frame_info,
+            );
+        }
+
+        let Some(mut contained) = find_contained(
+            debug_info, memory, cache, variable, &fields, container, frame_info,
+        ) else {
+            return Ok(());
+        };
+
+        contained.variable.parent_key = variable.variable_key;
+        contained.variable.name = VariableName::Named(contained.name);
+        cache.update_variable(&contained.variable)?;
+
+        // The bookkeeping fields are added after the contained value.
+        for (name, mut value) in contained.bookkeeping {
+            value.name = VariableName::Named(name.to_string());
+            value.variable_key = ObjectRef::Invalid;
+            cache.add_variable(variable.variable_key, &mut value)?;
+        }
+
+        // The fields are just noise on the UI, once the contained value is known.
+        for field in fields {
+            if field.variable_key != contained.variable.variable_key {
+                cache.remove_cache_entry(field.variable_key)?;
+            }
+        }
+
+        if has_preview(&contained.variable) {
+            variable.value = contained.variable.value.clone();
+        }
+
+        Ok(())
+    }
+
+    /// Shows the value of an atomic, or of a `VolatileCell<T>` of a base type, on the variable
+    /// itself, instead of on the `UnsafeCell<T>` it wraps. The fields are removed, and the type
+    /// of the variable is kept.
+    ///
+    /// This function may return `Ok(())` even if it does not modify the variable.
+    fn unwrap_scalar(
+        &self,
+        debug_info: &DebugInfo,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        cache.update_variable(variable)?;
+        let Some(scalar) = resolve_base_value(debug_info, memory, cache, variable, frame_info)
+        else {
+            return Ok(());
+        };
+
+        variable.value = match scalar.value {
+            // An `AtomicBool` stores its value in a `u8`.
+            VariableValue::U64(value)
+                if unqualified_type_name(&variable.type_name()) == "AtomicBool" =>
+            {
+                VariableValue::Bool(value != 0)
+            }
+            value => value,
+        };
+        cache.remove_cache_entry_children(variable.variable_key)?;
+        variable.variable_node_type = VariableNodeType::DoNotRecurse;
+
+        Ok(())
+    }
+
+    /// Replaces the fields of a `heapless::Vec<T, N>` with its elements, and shows its length and
+    /// capacity as its value.
+    ///
+    /// This function may return `Ok(())` even if it does not modify the variable.
+    #[expect(clippy::too_many_arguments)]
+    fn expand_heapless_vec(
+        &self,
+        unit_info: &UnitInfo,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        // The text of a `heapless::String` is read from the fields of its `Vec<u8, N>`, see
+        // `Self::read_heapless_string`.
+        if cache
+            .get_variable_ref(variable.parent_key)
+            .is_some_and(|parent| renderer_for(parent) == Some(Renderer::HeaplessString))
+        {
+            return Ok(());
+        }
+
+        let Some(element_type_offset) = find_child_type(
+            debug_info,
+            unit_info,
+            node,
+            gimli::DW_TAG_template_type_parameter,
+            "T",
+        )?
+        else {
+            return Ok(());
+        };
+
+        cache.update_variable(variable)?;
+        let (Some(length), Some(buffer)) = (
+            field_value(debug_info, memory, cache, variable, "len", frame_info)
+                .and_then(|length| length.as_u64()),
+            find_buffer(debug_info, memory, cache, variable, frame_info),
+        ) else {
+            return Ok(());
+        };
+        if length > buffer.capacity {
+            return Ok(());
+        }
+
+        self.replace_with_elements(
+            unit_info,
+            debug_info,
+            variable,
+            memory,
+            cache,
+            element_type_offset,
+            buffer.address,
+            length,
             frame_info,
         )?;
+        variable.value = VariableValue::Valid(format!("len={length}, cap={}", buffer.capacity));
+
+        Ok(())
+    }
+
+    /// Set the value of a `heapless::String<N>` to its text, and keep its children.
+    fn read_heapless_string(
+        &self,
+        debug_info: &DebugInfo,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        // The `Vec<u8, N>` of the `String` looks up its parent, see `Self::expand_heapless_vec`.
+        cache.update_variable(variable)?;
+        let Some(vec) = resolve_field(debug_info, memory, cache, variable, "vec", frame_info)
+        else {
+            return Ok(());
+        };
+        let (Some(length), Some(buffer)) = (
+            field_value(debug_info, memory, cache, &vec, "len", frame_info)
+                .and_then(|length| length.as_u64()),
+            find_buffer(debug_info, memory, cache, &vec, frame_info),
+        ) else {
+            return Ok(());
+        };
+        if length > buffer.capacity {
+            return Ok(());
+        }
+
+        variable.value = read_text(
+            memory,
+            buffer.address,
+            length as usize,
+            debug_info.max_string_length,
+        )
+        .into();
+
+        Ok(())
+    }
+
+    /// Replaces the fields of a ring buffer, like a `VecDeque<T>`, with its elements in the order
+    /// they are dequeued, and shows its length and capacity as its value.
+    ///
+    /// The elements wrap around from the end of the buffer to its start. A ring buffer which is
+    /// not consistent keeps its fields. This function may return `Ok(())` even if it does not
+    /// modify the variable.
+    #[expect(clippy::too_many_arguments)]
+    fn expand_ring_buffer(
+        &self,
+        unit_info: &UnitInfo,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+        ring_buffer: RingBuffer,
+    ) -> Result<(), DebugError> {
+        if !variable.value.is_empty() || !variable.memory_location.valid() {
+            return Ok(());
+        }
+
+        let Some(element_type_offset) = find_child_type(
+            debug_info,
+            unit_info,
+            node,
+            gimli::DW_TAG_template_type_parameter,
+            "T",
+        )?
+        else {
+            return Ok(());
+        };
+        let element_type_node = unit_info.unit.entry(element_type_offset)?;
+        let Some(element_size) = extract_byte_size(&element_type_node) else {
+            return Ok(());
+        };
+
+        cache.update_variable(variable)?;
+        let Some(ring) = find_ring(debug_info, memory, cache, variable, ring_buffer, frame_info)
+        else {
+            return Ok(());
+        };
+        if ring.address == 0 || ring.head >= ring.slots || ring.length > ring.slots {
+            tracing::debug!(
+                "Not expanding the elements of {}, with the head {}, the length {}, the {} slots and the address {:#010x}.",
+                variable.name,
+                ring.head,
+                ring.length,
+                ring.slots,
+                ring.address
+            );
+            return Ok(());
+        }
+
+        // The elements from the head to the end of the buffer come first, followed by the
+        // elements which wrapped around to its start.
+        let first_length = ring.length.min(ring.slots - ring.head);
+        if first_length == ring.length {
+            self.replace_with_elements(
+                unit_info,
+                debug_info,
+                variable,
+                memory,
+                cache,
+                element_type_offset,
+                ring.address + ring.head * element_size,
+                ring.length,
+                frame_info,
+            )?;
+        } else {
+            cache.remove_cache_entry_children(variable.variable_key)?;
+            for (slot, length) in [(ring.head, first_length), (0, ring.length - first_length)] {
+                unit_info.expand_sequence_members(
+                    debug_info,
+                    &element_type_node,
+                    cache,
+                    variable,
+                    memory,
+                    ring.address + slot * element_size,
+                    0..length,
+                    frame_info,
+                )?;
+            }
+
+            // The elements which wrapped around are numbered from zero again.
+            let element_keys = cache
+                .get_children(variable.variable_key)
+                .map(|element| element.variable_key)
+                .collect::<Vec<_>>();
+            for (index, element_key) in element_keys.into_iter().enumerate() {
+                cache.modify_variable(element_key, |element| {
+                    element.name = VariableName::Indexed(index as u64);
+                })?;
+            }
+        }
+        variable.value =
+            VariableValue::Valid(format!("len={}, cap={}", ring.length, ring.capacity));
 
         Ok(())
     }
 }
 
+/// The formatters of the types of the standard library, which are applied like the formatters of
+/// [`DebugInfo::register_type_formatter`].
+pub(crate) fn type_formatters() -> Vec<TypeFormatterEntry> {
+    vec![
+        TypeFormatterEntry::builtin(gimli::DW_LANG_Rust, is_string_type, format_string),
+        TypeFormatterEntry::builtin(
+            gimli::DW_LANG_Rust,
+            // A `heapless::Vec<T, N>` is expanded by `Rust::expand_heapless_vec`.
+            |name| is_vec_type(name) && !has_const_capacity(name),
+            format_vec,
+        ),
+        TypeFormatterEntry::builtin(
+            gimli::DW_LANG_Rust,
+            is_option_or_result_type,
+            format_variant,
+        ),
+    ]
+}
+
+/// Formats a `&str` or `String` as its text, and keeps its children.
+fn format_string(
+    context: &FormatContext<'_>,
+    variable: &mut Variable,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+) -> Result<Option<VariableValue>, DebugError> {
+    if variable.type_name() != "&str" {
+        // The `Vec<u8>` of the `String` looks up its parent, see `format_vec`.
+        cache.update_variable(variable)?;
+
+        // The text of a `String` is nested in a few structs, which are deferred.
+        cache.recurse_deferred_variables_internal(
+            context.debug_info,
+            memory,
+            variable,
+            MAX_FIELD_DEPTH,
+            0,
+            context.frame_info,
+        );
+    }
+
+    Ok(Some(
+        read_string(
+            variable,
+            memory,
+            cache,
+            context.debug_info.max_string_length,
+        )
+        .into(),
+    ))
+}
+
+/// Replaces the fields of a `Vec<T>` with its elements, and formats its length and capacity.
+///
+/// A `Vec` which is not consistent, e.g. because it is longer than its capacity, keeps its fields
+/// and its value.
+fn format_vec(
+    context: &FormatContext<'_>,
+    variable: &mut Variable,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+) -> Result<Option<VariableValue>, DebugError> {
+    let FormatContext {
+        unit_info,
+        debug_info,
+        node,
+        frame_info,
+    } = *context;
+
+    // The text of a `String` is read from the fields of its `Vec<u8>`, see `format_string`.
+    if cache.get_variable_ref(variable.parent_key).is_some_and(|parent| {
+        matches!(parent.type_name.inner(), VariableType::Struct(name) if is_string_type(name))
+    }) {
+        return Ok(None);
+    }
+
+    let Some(element_type_offset) = vec_element_type(debug_info, unit_info, node)? else {
+        return Ok(None);
+    };
+
+    // The fields of a `Vec` are nested in a few structs, which are deferred.
+    cache.recurse_deferred_variables_internal(
+        debug_info,
+        memory,
+        variable,
+        MAX_FIELD_DEPTH,
+        0,
+        frame_info,
+    );
+
+    let Some(length) = find_field(cache, variable.variable_key, |field| is_field(field, "len"))
+        .and_then(|length| length.value.as_u64())
+    else {
+        return Ok(None);
+    };
+
+    // The capacity is wrapped in a struct by newer versions of the standard library.
+    let Some(capacity) = find_field(cache, variable.variable_key, |field| is_field(field, "cap"))
+        .and_then(|capacity| {
+            capacity.value.as_u64().or_else(|| {
+                cache
+                    .get_children(capacity.variable_key)
+                    .next()
+                    .and_then(|inner| inner.value.as_u64())
+            })
+        })
+    else {
+        return Ok(None);
+    };
+
+    let Some(VariableLocation::Address(address)) =
+        find_field(cache, variable.variable_key, |field| {
+            matches!(field.type_name.inner(), VariableType::Pointer(_))
+        })
+        .and_then(|pointer| cache.get_children(pointer.variable_key).next())
+        .map(|data| data.memory_location.clone())
+    else {
+        return Ok(None);
+    };
+
+    // The capacity of a `Vec` of zero sized elements is not stored.
+    let element_type_node = unit_info.unit.entry(element_type_offset)?;
+    let zero_sized = extract_byte_size(&element_type_node) == Some(0);
+    if address == 0
+        || (length > capacity && !zero_sized)
+        || !elements_are_readable(memory, &element_type_node, address, length)
+    {
+        tracing::debug!(
+            "Not expanding the elements of {}, with the length {length}, the capacity {capacity} and the address {address:#010x}.",
+            variable.name
+        );
+        return Ok(None);
+    }
+
+    Rust.replace_with_elements(
+        unit_info,
+        debug_info,
+        variable,
+        memory,
+        cache,
+        element_type_offset,
+        address,
+        length,
+        frame_info,
+    )?;
+
+    Ok(Some(VariableValue::Valid(format!(
+        "len={length}, cap={capacity}"
+    ))))
+}
+
+/// Formats an `Option<T>` or a `Result<T, E>` as its active variant, e.g. `None`, `Some(<payload>)`
+/// or `Err(<payload>)`.
+///
+/// The variant is selected from the `DW_TAG_variant_part` of the type, see
+/// `UnitInfo::process_tree`, so this only formats the single variant which is left as a child.
+fn format_variant(
+    context: &FormatContext<'_>,
+    variable: &mut Variable,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+) -> Result<Option<VariableValue>, DebugError> {
+    if !matches!(variable.role, VariantRole::VariantPart(_)) {
+        return Ok(None);
+    }
+
+    let mut variant = {
+        let mut variants = cache.get_children(variable.variable_key);
+        let (Some(variant), None) = (variants.next(), variants.next()) else {
+            return Ok(None);
+        };
+        variant.clone()
+    };
+    let variant_name = match &variant.name {
+        VariableName::Named(name) if matches!(name.as_str(), "None" | "Some" | "Ok" | "Err") => {
+            name.clone()
+        }
+        _ => return Ok(None),
+    };
+
+    // The payload of the variant is deferred.
+    cache.recurse_deferred_variables_internal(
+        context.debug_info,
+        memory,
+        &mut variant,
+        1,
+        0,
+        context.frame_info,
+    );
+
+    let payload = cache
+        .get_children(variant.variable_key)
+        .find(|field| is_field(field, "__0"))
+        .map(|payload| {
+            if payload.value.is_empty() {
+                payload.type_name()
+            } else {
+                payload.value.to_string()
+            }
+        });
+
+    Ok(Some(VariableValue::Valid(match payload {
+        Some(payload) => format!("{variant_name}({payload})"),
+        None => variant_name,
+    })))
+}
+
+/// How many levels of structs are resolved to find the fields of a `String` or a `Vec<T>`, e.g.
+/// `vec.buf.inner.ptr.pointer.pointer`.
+const MAX_FIELD_DEPTH: usize = 8;
+
+fn is_string_type(name: &str) -> bool {
+    matches!(name, "&str" | "String" | "alloc::string::String")
+}
+
+fn is_vec_type(name: &str) -> bool {
+    name.starts_with("Vec<") || name.starts_with("alloc::vec::Vec<")
+}
+
+/// The name of a type without its path and generic parameters, e.g. `Rc` for
+/// `alloc::rc::Rc<u32, alloc::alloc::Global>`.
+fn unqualified_type_name(name: &str) -> &str {
+    let name = name.split_once('<').map_or(name, |(name, _)| name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// `true` for `Option<T>` and `Result<T, E>`, with or without their path.
+fn is_option_or_result_type(name: &str) -> bool {
+    matches!(unqualified_type_name(name), "Option" | "Result")
+}
+
+/// `true` for the type of a closure, which holds the variables it captures, e.g.
+/// `{closure_env#0}`.
+fn is_closure_type(name: &str) -> bool {
+    unqualified_type_name(name).starts_with("{closure_env#")
+}
+
+/// `true` for the state machine of an `async fn` or an `async` block, e.g. `{async_fn_env#0}`.
+fn is_coroutine_type(name: &str) -> bool {
+    let name = unqualified_type_name(name);
+    [
+        "{async_fn_env#",
+        "{async_block_env#",
+        "{coroutine_env#",
+        "{generator_env#",
+    ]
+    .iter()
+    .any(|prefix| name.starts_with(prefix))
+}
+
+/// `true` if the value of `variable` says more than its type and location, e.g. for a number or
+/// the text of a string.
+fn has_preview(variable: &Variable) -> bool {
+    !variable.value.is_empty()
+        && variable.value.to_string()
+            != format!("{} @ {}", variable.type_name(), variable.memory_location)
+}
+
+/// Resolve the children of `variable`, if they are deferred, and return them.
+fn resolve_children(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    frame_info: StackFrameInfo<'_>,
+) -> Vec<Variable> {
+    let mut variable = variable.clone();
+    if debug_info
+        .cache_deferred_variables(cache, memory, &mut variable, frame_info)
+        .is_err()
+    {
+        return Vec::new();
+    }
+
+    cache.get_children(variable.variable_key).cloned().collect()
+}
+
+/// Resolve the children of `variable`, if they are deferred, and return the field called `name`.
+fn resolve_field(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    name: &str,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Variable> {
+    resolve_children(debug_info, memory, cache, variable, frame_info)
+        .into_iter()
+        .find(|field| is_field(field, name))
+}
+
+/// Find the first field below `variable`, depth first, which matches `predicate`, and resolve
+/// the deferred fields on the way.
+fn resolve_descendant(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    predicate: impl Fn(&Variable) -> bool + Copy,
+    depth: usize,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Variable> {
+    for field in resolve_children(debug_info, memory, cache, variable, frame_info) {
+        if predicate(&field) {
+            return Some(field);
+        }
+
+        if depth > 0
+            && let Some(descendant) = resolve_descendant(
+                debug_info,
+                memory,
+                cache,
+                &field,
+                predicate,
+                depth - 1,
+                frame_info,
+            )
+        {
+            return Some(descendant);
+        }
+    }
+
+    None
+}
+
+/// Find the first pointer below `variable`, depth first, and return the value it points to.
+fn resolve_pointee(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    depth: usize,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Variable> {
+    let pointer = resolve_descendant(
+        debug_info,
+        memory,
+        cache,
+        variable,
+        |field| matches!(field.type_name.inner(), VariableType::Pointer(_)),
+        depth,
+        frame_info,
+    )?;
+
+    resolve_children(debug_info, memory, cache, &pointer, frame_info)
+        .into_iter()
+        .next()
+}
+
+/// Follow the only field of `variable` down to a base type, e.g. to the `usize` in a
+/// `Cell<usize>`. An atomic, which already shows its value, is returned as it is.
+fn resolve_base_value(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Variable> {
+    let mut variable = variable.clone();
+    for _ in 0..MAX_FIELD_DEPTH {
+        let is_scalar = match variable.type_name.inner() {
+            VariableType::Base(_) => true,
+            VariableType::Struct(name) => {
+                is_scalar_wrapper_type(name) && !variable.value.is_empty()
+            }
+            _ => false,
+        };
+        if is_scalar {
+            return Some(variable);
+        }
+
+        let [field] = <[Variable; 1]>::try_from(resolve_children(
+            debug_info, memory, cache, &variable, frame_info,
+        ))
+        .ok()?;
+        variable = field;
+    }
+
+    None
+}
+
+/// Resolve the field called `name` of `variable`, and return the value of the base type it wraps,
+/// see [`resolve_base_value`].
+fn field_value(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    name: &str,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<VariableValue> {
+    let field = resolve_field(debug_info, memory, cache, variable, name, frame_info)?;
+    resolve_base_value(debug_info, memory, cache, &field, frame_info).map(|value| value.value)
+}
+
+/// `true` for the atomics of `core` and `portable-atomic`, except `AtomicPtr<T>`, and for
+/// `VolatileCell<T>`, which only wrap a value in an `UnsafeCell<T>`.
+fn is_scalar_wrapper_type(name: &str) -> bool {
+    matches!(
+        unqualified_type_name(name),
+        "AtomicBool"
+            | "AtomicI8"
+            | "AtomicI16"
+            | "AtomicI32"
+            | "AtomicI64"
+            | "AtomicI128"
+            | "AtomicIsize"
+            | "AtomicU8"
+            | "AtomicU16"
+            | "AtomicU32"
+            | "AtomicU64"
+            | "AtomicU128"
+            | "AtomicUsize"
+            | "AtomicF32"
+            | "AtomicF64"
+            | "VolatileCell"
+    )
+}
+
+/// Smart pointers and cells, which are shown as the value they contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Box,
+    Rc,
+    Arc,
+    Cow,
+    Cell,
+    RefCell,
+    Mutex,
+}
+
+/// The value inside a [`Container`], see `Rust::unwrap_container`.
+struct Contained {
+    /// The name of the value, as a child of the container.
+    name: String,
+    variable: Variable,
+    /// The bookkeeping fields of the container, e.g. the reference counts of an `Rc<T>`, by name.
+    bookkeeping: Vec<(&'static str, Variable)>,
+}
+
+/// Find the value inside `container`, and its bookkeeping fields, below the `fields` of
+/// `variable`.
+fn find_contained(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    fields: &[Variable],
+    container: Container,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Contained> {
+    let field = |name: &str| fields.iter().find(|field| is_field(field, name));
+    let deref_name = format!("*{}", variable.name);
+
+    let (name, contained, bookkeeping_fields) = match container {
+        Container::Box => {
+            let pointee = resolve_pointee(
+                debug_info,
+                memory,
+                cache,
+                variable,
+                MAX_FIELD_DEPTH,
+                frame_info,
+            )?;
+            (deref_name, pointee, vec![])
+        }
+        Container::Rc | Container::Arc => {
+            // The value is stored next to the reference counts, in an `RcInner<T>` or an
+            // `ArcInner<T>`.
+            let inner = resolve_pointee(
+                debug_info,
+                memory,
+                cache,
+                variable,
+                MAX_FIELD_DEPTH,
+                frame_info,
+            )?;
+            let inner_fields = resolve_children(debug_info, memory, cache, &inner, frame_info);
+            let value_name = if container == Container::Rc {
+                "value"
+            } else {
+                "data"
+            };
+            let value = inner_fields
+                .iter()
+                .find(|field| is_field(field, value_name))?
+                .clone();
+            let counts = ["strong", "weak"]
+                .into_iter()
+                .filter_map(|name| {
+                    let count = inner_fields.iter().find(|field| is_field(field, name))?;
+                    Some((name, count.clone()))
+                })
+                .collect::<Vec<_>>();
+            (deref_name, value, counts)
+        }
+        Container::Cow => {
+            // The active variant, `Borrowed` or `Owned`, is the only field.
+            let [variant] = fields else {
+                return None;
+            };
+            let payload = resolve_field(debug_info, memory, cache, variant, "__0", frame_info)?;
+            let value = if matches!(payload.type_name.inner(), VariableType::Pointer(_)) {
+                resolve_children(debug_info, memory, cache, &payload, frame_info)
+                    .into_iter()
+                    .next()?
+            } else {
+                payload
+            };
+            (deref_name, value, vec![])
+        }
+        Container::Cell | Container::RefCell => {
+            let value = resolve_field(
+                debug_info,
+                memory,
+                cache,
+                field("value")?,
+                "value",
+                frame_info,
+            )?;
+            let borrow = field("borrow")
+                .filter(|_| container == Container::RefCell)
+                .map(|borrow| ("borrow", borrow.clone()));
+            (
+                "value".to_string(),
+                value,
+                borrow.into_iter().collect::<Vec<_>>(),
+            )
+        }
+        Container::Mutex => {
+            // The value is in the `data` of a `std::sync::Mutex`, or in the `inner` of a
+            // `critical_section::Mutex`.
+            let cell = field("data").or_else(|| {
+                field("inner")
+                    .filter(|inner| unqualified_type_name(&inner.type_name()) == "UnsafeCell")
+            })?;
+            let value = resolve_field(debug_info, memory, cache, cell, "value", frame_info)?;
+            (cell.name.to_string(), value, vec![])
+        }
+    };
+
+    let bookkeeping = bookkeeping_fields
+        .into_iter()
+        .filter_map(|(name, field)| {
+            let value = resolve_base_value(debug_info, memory, cache, &field, frame_info)?;
+            Some((name, value))
+        })
+        .collect();
+
+    Some(Contained {
+        name,
+        variable: contained,
+        bookkeeping,
+    })
+}
+
+/// Ring buffers, which are shown as their elements in the order they are dequeued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RingBuffer {
+    /// `alloc::collections::VecDeque<T>`
+    VecDeque,
+    /// `heapless::Deque<T, N>`
+    Deque,
+    /// `heapless::spsc::Queue<T, N>`
+    Queue,
+}
+
+/// The state of a [`RingBuffer`], see `Rust::expand_ring_buffer`.
+struct Ring {
+    /// The address of the first slot.
+    address: u64,
+    /// The number of slots of the buffer.
+    slots: u64,
+    /// The number of elements the ring buffer can hold, which is one less than the number of
+    /// slots for a `heapless::spsc::Queue`.
+    capacity: u64,
+    /// The slot of the first element.
+    head: u64,
+    length: u64,
+}
+
+/// Find the state of `ring_buffer` in the fields of `variable`.
+fn find_ring(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    ring_buffer: RingBuffer,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Ring> {
+    let mut number =
+        |name: &str| field_value(debug_info, memory, cache, variable, name, frame_info)?.as_u64();
+
+    match ring_buffer {
+        RingBuffer::VecDeque => {
+            let head = number("head")?;
+            let length = number("len")?;
+
+            // The capacity and the pointer to the buffer are nested in a `RawVec<T>`.
+            let capacity = resolve_descendant(
+                debug_info,
+                memory,
+                cache,
+                variable,
+                |field| is_field(field, "cap"),
+                MAX_FIELD_DEPTH,
+                frame_info,
+            )
+            .and_then(|capacity| {
+                resolve_base_value(debug_info, memory, cache, &capacity, frame_info)
+            })
+            .and_then(|capacity| capacity.value.as_u64())?;
+            let VariableLocation::Address(address) = resolve_pointee(
+                debug_info,
+                memory,
+                cache,
+                variable,
+                MAX_FIELD_DEPTH,
+                frame_info,
+            )?
+            .memory_location
+            else {
+                return None;
+            };
+
+            Some(Ring {
+                address,
+                slots: capacity,
+                capacity,
+                head,
+                length,
+            })
+        }
+        RingBuffer::Deque => {
+            let front = number("front")?;
+            let back = number("back")?;
+            let full =
+                field_value(debug_info, memory, cache, variable, "full", frame_info)?.as_bool()?;
+            let buffer = find_buffer(debug_info, memory, cache, variable, frame_info)?;
+
+            let length = if full {
+                buffer.capacity
+            } else {
+                back.wrapping_sub(front).wrapping_add(buffer.capacity) % buffer.capacity.max(1)
+            };
+
+            Some(Ring {
+                address: buffer.address,
+                slots: buffer.capacity,
+                capacity: buffer.capacity,
+                head: front,
+                length,
+            })
+        }
+        RingBuffer::Queue => {
+            let head = number("head")?;
+            let tail = number("tail")?;
+            let buffer = find_buffer(debug_info, memory, cache, variable, frame_info)?;
+
+            // One slot is always empty, to tell a full queue from an empty one.
+            Some(Ring {
+                address: buffer.address,
+                slots: buffer.capacity,
+                capacity: buffer.capacity.saturating_sub(1),
+                head,
+                length: tail.wrapping_sub(head).wrapping_add(buffer.capacity)
+                    % buffer.capacity.max(1),
+            })
+        }
+    }
+}
+
+/// The inline buffer of a `heapless` collection.
+struct Buffer {
+    address: u64,
+    capacity: u64,
+}
+
+/// Find the `buffer` array of a `heapless` collection, which is wrapped in a storage struct by
+/// newer versions of `heapless`.
+fn find_buffer(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+    variable: &Variable,
+    frame_info: StackFrameInfo<'_>,
+) -> Option<Buffer> {
+    let buffer = resolve_descendant(
+        debug_info,
+        memory,
+        cache,
+        variable,
+        |field| {
+            is_field(field, "buffer")
+                && matches!(field.type_name.inner(), VariableType::Array { .. })
+        },
+        1,
+        frame_info,
+    )?;
+
+    let (VariableType::Array { count, .. }, VariableLocation::Address(address)) =
+        (buffer.type_name.inner(), &buffer.memory_location)
+    else {
+        return None;
+    };
+
+    Some(Buffer {
+        address: *address,
+        capacity: *count as u64,
+    })
+}
+
+/// How the values of a kind of struct are shown, see [`RENDERERS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Renderer {
+    /// `&[T]`, see `Rust::expand_slice`.
+    Slice,
+    /// `heapless::Vec<T, N>`, see `Rust::expand_heapless_vec`.
+    HeaplessVec,
+    /// `heapless::String<N>`, see `Rust::read_heapless_string`.
+    HeaplessString,
+    /// See `Rust::expand_ring_buffer`.
+    RingBuffer(RingBuffer),
+    /// Closures, see `Rust::format_closure`.
+    Closure,
+    /// The state machines of `async fn`s and `async` blocks, see `Rust::format_coroutine`.
+    Coroutine,
+    /// See `Rust::unwrap_container`.
+    Container(Container),
+    /// Atomics and `VolatileCell<T>`, see `Rust::unwrap_scalar`.
+    Scalar,
+}
+
+/// A [`Renderer`] for the structs whose type name matches.
+struct TypeRenderer {
+    /// Matches the type name of a struct, e.g. `alloc::vec::Vec<u8, alloc::alloc::Global>`.
+    matches: fn(&str) -> bool,
+    renderer: Renderer,
+}
+
+/// The renderers of the structs of the standard library and of common crates. The first
+/// renderer which matches the type name of a struct is used.
+const RENDERERS: &[TypeRenderer] = &[
+    TypeRenderer {
+        matches: |name| name.starts_with("&["),
+        renderer: Renderer::Slice,
+    },
+    TypeRenderer {
+        matches: |name| {
+            unqualified_type_name(name) == "VecInner"
+                || (unqualified_type_name(name) == "Vec" && has_const_capacity(name))
+        },
+        renderer: Renderer::HeaplessVec,
+    },
+    TypeRenderer {
+        matches: |name| {
+            unqualified_type_name(name) == "StringInner"
+                || (unqualified_type_name(name) == "String" && has_const_capacity(name))
+        },
+        renderer: Renderer::HeaplessString,
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "VecDeque",
+        renderer: Renderer::RingBuffer(RingBuffer::VecDeque),
+    },
+    TypeRenderer {
+        matches: |name| matches!(unqualified_type_name(name), "Deque" | "DequeInner"),
+        renderer: Renderer::RingBuffer(RingBuffer::Deque),
+    },
+    TypeRenderer {
+        matches: |name| matches!(unqualified_type_name(name), "Queue" | "QueueInner"),
+        renderer: Renderer::RingBuffer(RingBuffer::Queue),
+    },
+    TypeRenderer {
+        matches: is_closure_type,
+        renderer: Renderer::Closure,
+    },
+    TypeRenderer {
+        matches: is_coroutine_type,
+        renderer: Renderer::Coroutine,
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Box",
+        renderer: Renderer::Container(Container::Box),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Rc",
+        renderer: Renderer::Container(Container::Rc),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Arc",
+        renderer: Renderer::Container(Container::Arc),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Cow",
+        renderer: Renderer::Container(Container::Cow),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Cell",
+        renderer: Renderer::Container(Container::Cell),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "RefCell",
+        renderer: Renderer::Container(Container::RefCell),
+    },
+    TypeRenderer {
+        matches: |name| unqualified_type_name(name) == "Mutex",
+        renderer: Renderer::Container(Container::Mutex),
+    },
+    TypeRenderer {
+        matches: is_scalar_wrapper_type,
+        renderer: Renderer::Scalar,
+    },
+];
+
+/// The [`Renderer`] of `variable`, if it is a struct with a renderer.
+fn renderer_for(variable: &Variable) -> Option<Renderer> {
+    let VariableType::Struct(name) = variable.type_name.inner() else {
+        return None;
+    };
+
+    RENDERERS
+        .iter()
+        .find(|renderer| (renderer.matches)(name))
+        .map(|renderer| renderer.renderer)
+}
+
+/// `true` if the last generic parameter of the type name is a number, like the capacity `N` of a
+/// `heapless::Vec<T, N>`.
+fn has_const_capacity(name: &str) -> bool {
+    name.split_once('<')
+        .and_then(|(_, parameters)| parameters.strip_suffix('>'))
+        .and_then(|parameters| parameters.rsplit(',').next())
+        .is_some_and(|capacity| capacity.trim().parse::<u64>().is_ok())
+}
+
+fn is_field(variable: &Variable, name: &str) -> bool {
+    matches!(variable.name, VariableName::Named(ref var_name) if var_name == name)
+}
+
+/// Find the first field below `parent_key`, depth first, which matches `predicate`.
+fn find_field(
+    cache: &VariableCache,
+    parent_key: ObjectRef,
+    predicate: impl Fn(&Variable) -> bool + Copy,
+) -> Option<&Variable> {
+    cache.get_children(parent_key).find_map(|child| {
+        if predicate(child) {
+            Some(child)
+        } else {
+            find_field(cache, child.variable_key, predicate)
+        }
+    })
+}
+
+/// Whether the last of the `length` elements at `address` can be read, so a wild pointer or a
+/// wild length, e.g. of a variable which is not initialized yet, keeps the fields of a sequence.
+///
+/// The elements of an unknown size are assumed to be readable.
+fn elements_are_readable(
+    memory: &mut dyn MemoryInterface,
+    element_type_node: &DebuggingInformationEntry<GimliReader>,
+    address: u64,
+    length: u64,
+) -> bool {
+    let Some(element_size @ 1..) = extract_byte_size(element_type_node) else {
+        return true;
+    };
+    if length == 0 {
+        return true;
+    }
+
+    let Some(last_byte) = length
+        .checked_mul(element_size)
+        .and_then(|size| address.checked_add(size - 1))
+    else {
+        return false;
+    };
+    memory.read_word_8(last_byte).is_ok()
+}
+
+/// Find the element type of a `Vec<T>` at `node`, which is its template type parameter `T`.
+///
+/// Returns `None` if the type does not have the `buf` field of an `alloc::vec::Vec`.
+fn vec_element_type(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    node: &DebuggingInformationEntry<GimliReader>,
+) -> Result<Option<UnitOffset>, DebugError> {
+    if find_child_type(debug_info, unit_info, node, gimli::DW_TAG_member, "buf")?.is_none() {
+        return Ok(None);
+    }
+
+    find_child_type(
+        debug_info,
+        unit_info,
+        node,
+        gimli::DW_TAG_template_type_parameter,
+        "T",
+    )
+}
+
+/// Find the child of the type at `node` with the tag `tag` and the name `name`, e.g. the template
+/// type parameter `T` of a `Vec<T>`, and return its type.
+fn find_child_type(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    node: &DebuggingInformationEntry<GimliReader>,
+    tag: gimli::DwTag,
+    name: &str,
+) -> Result<Option<UnitOffset>, DebugError> {
+    let mut tree = unit_info.unit.entries_tree(Some(node.offset()))?;
+    let mut children = tree.root()?.children();
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        if entry.tag() == tag
+            && extract_name(debug_info, unit_info, entry)?.as_deref() == Some(name)
+            && let Some(gimli::AttributeValue::UnitRef(type_offset)) =
+                entry.attr_value(gimli::DW_AT_type)
+        {
+            return Ok(Some(type_offset));
+        }
+    }
+
+    Ok(None)
+}
+
 impl ProgrammingLanguage for Rust {
     fn read_variable_value(
         &self,
@@ -127,10 +1477,7 @@ impl ProgrammingLanguage for Rust {
             VariableType::Base(type_name) => match type_name.as_str() {
                 "!" => VariableValue::Valid("<Never returns>".to_string()),
                 "()" => VariableValue::Valid("()".to_string()),
-                "bool" => bool::get_value(variable, memory, variable_cache).map_or_else(
-                    |err| VariableValue::Error(format!("{err:?}")),
-                    |value| VariableValue::Valid(value.to_string()),
-                ),
+                "bool" => bool::get_value(variable, memory, variable_cache).into(),
                 "char" => char::get_value(variable, memory, variable_cache).into(),
                 "i8" => i8::get_value(variable, memory, variable_cache).into(),
                 "i16" => i16::get_value(variable, memory, variable_cache).into(),
@@ -146,19 +1493,12 @@ impl ProgrammingLanguage for Rust {
                 "u128" => u128::get_value(variable, memory, variable_cache).into(),
                 // TODO: We can get the actual WORD length from DWARF instead of assuming `u32`
                 "usize" => u32::get_value(variable, memory, variable_cache).into(),
-                "f32" => f32::get_value(variable, memory, variable_cache)
-                    .map(|f| format_float(f as f64))
-                    .into(),
-                "f64" => f64::get_value(variable, memory, variable_cache)
-                    .map(format_float)
-                    .into(),
+                "f32" => f32::get_value(variable, memory, variable_cache).into(),
+                "f64" => f64::get_value(variable, memory, variable_cache).into(),
                 "None" => VariableValue::Valid("None".to_string()),
 
                 _undetermined_value => VariableValue::Empty,
             },
-            VariableType::Struct(name) if name == "&str" => {
-                String::get_value(variable, memory, variable_cache).into()
-            }
             _other => VariableValue::Empty,
         }
     }
@@ -199,6 +1539,52 @@ impl ProgrammingLanguage for Rust {
         }
     }
 
+    fn value_to_bytes(&self, variable: &Variable, new_value: &str) -> Result<Vec<u8>, DebugError> {
+        let endianness = variable.endianness;
+        let VariableType::Base(name) = variable.type_name.inner() else {
+            return Err(DebugError::WarnAndContinue {
+                message: format!(
+                    "Updating {} variables is not yet supported.",
+                    variable.type_name.kind()
+                ),
+            });
+        };
+
+        let bytes = match name.as_str() {
+            "bool" => vec![parse_bool(new_value)? as u8],
+            "char" => (parse_char(new_value)? as u32)
+                .to_target_bytes(endianness)
+                .to_vec(),
+            "i8" => i8::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "i16" => i16::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "i32" => i32::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "i64" => i64::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "i128" => i128::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "isize" if variable.byte_size == Some(8) => {
+                i64::parse_to_bytes(new_value, endianness)?.to_vec()
+            }
+            "isize" => i32::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "u8" => u8::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "u16" => u16::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "u32" => u32::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "u64" => u64::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "u128" => u128::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "usize" if variable.byte_size == Some(8) => {
+                u64::parse_to_bytes(new_value, endianness)?.to_vec()
+            }
+            "usize" => u32::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "f32" => f32::parse_to_bytes(new_value, endianness)?.to_vec(),
+            "f64" => f64::parse_to_bytes(new_value, endianness)?.to_vec(),
+            other => {
+                return Err(DebugError::WarnAndContinue {
+                    message: format!("Updating {other} variables is not yet supported."),
+                });
+            }
+        };
+
+        Ok(bytes)
+    }
+
     fn format_enum_value(&self, type_name: &VariableType, value: &VariableName) -> VariableValue {
         VariableValue::Valid(format!("{}::{}", type_name.display_name(self), value))
     }
@@ -245,14 +1631,21 @@ impl ProgrammingLanguage for Rust {
         }
     }
 
+    fn format_member_name(&self, struct_type: &VariableType, name: String) -> String {
+        // A closure names the fields of the variables it captures by reference `_ref__<variable>`.
+        match (struct_type.inner(), name.strip_prefix("_ref__")) {
+            (VariableType::Struct(closure), Some(variable)) if is_closure_type(closure) => {
+                variable.to_string()
+            }
+            _ => name,
+        }
+    }
+
     fn auto_resolve_children(&self, name: &str) -> bool {
         name.starts_with("&str")
             || name.starts_with("&[")
             || name.starts_with("Option")
-            || name.starts_with("Some")
             || name.starts_with("Result")
-            || name.starts_with("Ok")
-            || name.starts_with("Err")
     }
 
     fn process_struct(
@@ -265,10 +1658,42 @@ impl ProgrammingLanguage for Rust {
         cache: &mut VariableCache,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
-        if variable.type_name().starts_with("&[") {
-            self.expand_slice(
+        let Some(renderer) = renderer_for(variable) else {
+            return Ok(());
+        };
+
+        match renderer {
+            Renderer::Slice => self.expand_slice(
                 unit_info, debug_info, node, variable, memory, cache, frame_info,
-            )?;
+            )?,
+            Renderer::HeaplessVec => self.expand_heapless_vec(
+                unit_info, debug_info, node, variable, memory, cache, frame_info,
+            )?,
+            Renderer::HeaplessString => {
+                self.read_heapless_string(debug_info, variable, memory, cache, frame_info)?
+            }
+            Renderer::RingBuffer(ring_buffer) => self.expand_ring_buffer(
+                unit_info,
+                debug_info,
+                node,
+                variable,
+                memory,
+                cache,
+                frame_info,
+                ring_buffer,
+            )?,
+            Renderer::Closure => {
+                self.format_closure(debug_info, variable, memory, cache, frame_info)?
+            }
+            Renderer::Coroutine => {
+                self.format_coroutine(debug_info, variable, memory, cache, frame_info)?
+            }
+            Renderer::Container(container) => self.unwrap_container(
+                unit_info, debug_info, node, variable, memory, cache, frame_info, container,
+            )?,
+            Renderer::Scalar => {
+                self.unwrap_scalar(debug_info, variable, memory, cache, frame_info)?
+            }
         }
 
         Ok(())
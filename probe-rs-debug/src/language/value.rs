@@ -1,8 +1,8 @@
-use std::str::FromStr;
-
 use crate::{
-    DebugError, Variable, VariableCache, VariableLocation, VariableName, VariableValue,
-    language::parsing::ValueExt,
+    Bitfield, DebugError, ObjectRef, Variable, VariableCache, VariableLocation, VariableName,
+    VariableType, VariableValue,
+    debug_info::DEFAULT_MAX_STRING_LENGTH,
+    language::parsing::{ValueExt, parse_bool, parse_char, read_unsigned, sign_extend},
 };
 
 use probe_rs::MemoryInterface;
@@ -30,23 +30,181 @@ pub trait Value {
 
 impl<V> From<Result<V, DebugError>> for VariableValue
 where
-    V: Value + ToString,
+    V: Value + Into<VariableValue>,
 {
     fn from(val: Result<V, DebugError>) -> Self {
-        val.map_or_else(
-            |err| VariableValue::Error(format!("{err:?}")),
-            |value| VariableValue::Valid(value.to_string()),
-        )
+        val.map_or_else(|err| VariableValue::Error(format!("{err:?}")), Into::into)
+    }
+}
+
+macro_rules! impl_from_for_variable_value {
+    ($variant:ident($target:ty): $($source:ty),*) => {
+        $(
+            impl From<$source> for VariableValue {
+                fn from(value: $source) -> Self {
+                    VariableValue::$variant(<$target>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_variable_value!(Bool(bool): bool);
+impl_from_for_variable_value!(Char(char): char);
+impl_from_for_variable_value!(U64(u64): u8, u16, u32, u64);
+impl_from_for_variable_value!(I64(i64): i8, i16, i32, i64);
+impl_from_for_variable_value!(F64(f64): f32, f64);
+impl_from_for_variable_value!(String(String): String);
+
+// The 128-bit variants are only used for values which do not fit in 64 bits.
+impl From<u128> for VariableValue {
+    fn from(value: u128) -> Self {
+        u64::try_from(value).map_or(VariableValue::U128(value), VariableValue::U64)
+    }
+}
+
+impl From<i128> for VariableValue {
+    fn from(value: i128) -> Self {
+        i64::try_from(value).map_or(VariableValue::I128(value), VariableValue::I64)
+    }
+}
+
+/// Read the value of a base type according to its `DW_AT_encoding`, for the encodings that are
+/// shown the same way in all languages, i.e. booleans, characters, integers and floats.
+///
+/// Returns `None` for the other encodings, which are read by the language of the variable, for
+/// floats which are not 2, 4 or 8 bytes, and for base types without a size, like the Rust `()`.
+/// Bitfields are read with [`read_encoded_bitfield`].
+pub(crate) fn read_encoded_value(
+    variable: &Variable,
+    memory: &mut dyn MemoryInterface,
+) -> Option<VariableValue> {
+    if let VariableType::Bitfield(bitfield, bitfield_type) = &variable.type_name {
+        return read_encoded_bitfield(variable, *bitfield, bitfield_type, memory);
+    }
+
+    let encoding = variable.encoding?;
+    let size = variable.byte_size?;
+    if !matches!(variable.type_name.inner(), VariableType::Base(_))
+        || variable.memory_location == VariableLocation::Unknown
+        || !(1..=16).contains(&size)
+        || ![
+            gimli::DW_ATE_boolean,
+            gimli::DW_ATE_UTF,
+            gimli::DW_ATE_signed_char,
+            gimli::DW_ATE_unsigned_char,
+            gimli::DW_ATE_signed,
+            gimli::DW_ATE_unsigned,
+            gimli::DW_ATE_float,
+        ]
+        .contains(&encoding)
+        || (encoding == gimli::DW_ATE_float && ![2, 4, 8].contains(&size))
+    {
+        return None;
+    }
+
+    let bits = match read_unsigned(variable, memory, size as usize) {
+        Ok(bits) => bits,
+        Err(error) => return Some(VariableValue::Error(format!("{error:?}"))),
+    };
+
+    Some(match encoding {
+        gimli::DW_ATE_boolean => VariableValue::Bool(bits != 0),
+        gimli::DW_ATE_UTF => match u32::try_from(bits).ok().and_then(char::from_u32) {
+            Some(character) => VariableValue::Char(character),
+            None => VariableValue::Valid(format!("<invalid char {bits:#x}>")),
+        },
+        gimli::DW_ATE_signed_char | gimli::DW_ATE_unsigned_char => c_char_value(bits as u8),
+        gimli::DW_ATE_signed => sign_extend(bits, size as u32 * 8).into(),
+        gimli::DW_ATE_float => VariableValue::F64(match size {
+            2 => f16_to_f64(bits as u16),
+            4 => f32::from_bits(bits as u32).into(),
+            _ => f64::from_bits(bits as u64),
+        }),
+        _ => bits.into(),
+    })
+}
+
+/// Read the value of a bitfield according to the `DW_AT_encoding` of its type, by extracting its
+/// bits from the storage unit at the location of the variable.
+///
+/// Booleans and integers are supported. Characters are read as integers, because a bitfield like
+/// `uint8_t mode : 3` holds a small number rather than a character.
+fn read_encoded_bitfield(
+    variable: &Variable,
+    bitfield: Bitfield,
+    bitfield_type: &VariableType,
+    memory: &mut dyn MemoryInterface,
+) -> Option<VariableValue> {
+    let encoding = variable.encoding?;
+    let size = variable.byte_size?;
+    if !matches!(bitfield_type.inner(), VariableType::Base(_))
+        || variable.memory_location == VariableLocation::Unknown
+        || !(1..=16).contains(&size)
+    {
+        return None;
+    }
+
+    let signed = match encoding {
+        gimli::DW_ATE_boolean | gimli::DW_ATE_unsigned | gimli::DW_ATE_unsigned_char => false,
+        gimli::DW_ATE_signed | gimli::DW_ATE_signed_char => true,
+        _ => return None,
+    };
+
+    let bits = match read_unsigned(variable, memory, size as usize) {
+        Ok(bits) => bitfield.extract(bits),
+        Err(error) => return Some(VariableValue::Error(format!("{error:?}"))),
+    };
+
+    Some(if encoding == gimli::DW_ATE_boolean {
+        VariableValue::Bool(bits != 0)
+    } else if signed {
+        sign_extend(bits, bitfield.length() as u32).into()
+    } else {
+        bits.into()
+    })
+}
+
+/// Convert the bits of an IEEE 754 half precision float, like an `f16` or a C `_Float16`, into an
+/// `f64`, which represents all of its values exactly.
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 == 0 { 1.0 } else { -1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let fraction = f64::from(bits & 0x3ff);
+
+    sign * match exponent {
+        0 => fraction * 2_f64.powi(-24),
+        0x1f if fraction == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (1024.0 + fraction) * 2_f64.powi(exponent - 25),
     }
 }
 
+/// The value of a C `char`, which is a character if it is ASCII, and an escaped byte like
+/// `'\xff'` otherwise.
+pub(crate) fn c_char_value(byte: u8) -> VariableValue {
+    if byte.is_ascii() {
+        VariableValue::Char(byte as char)
+    } else {
+        VariableValue::Valid(format!("'\\x{byte:02x}'"))
+    }
+}
+
+/// Remove the quotes around a character as it is shown, e.g. `'a'`, so that it can be parsed.
+pub(crate) fn unquote_char(value: &str) -> &str {
+    value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+        .unwrap_or(value)
+}
+
 impl Value for bool {
     fn get_value(
         variable: &Variable,
         memory: &mut dyn MemoryInterface,
         _variable_cache: &VariableCache,
     ) -> Result<Self, DebugError> {
-        let mem_data = memory.read_word_8(variable.memory_location.memory_address()?)?;
+        let mem_data = u8::read_from_location(variable, memory)?;
         let ret_value: bool = mem_data != 0;
         Ok(ret_value)
     }
@@ -65,13 +223,7 @@ impl Value for bool {
         memory
             .write_word_8(
                 variable.memory_location.memory_address()?,
-                <bool as FromStr>::from_str(new_value).map_err(|error| {
-                    DebugError::WarnAndContinue {
-                        message: format!(
-                            "Invalid data conversion from value: {new_value:?}. {error:?}"
-                        ),
-                    }
-                })? as u8,
+                parse_bool(new_value)? as u8,
             )
             .map_err(|error| DebugError::WarnAndContinue {
                 message: format!("{error:?}"),
@@ -84,7 +236,7 @@ impl Value for char {
         memory: &mut dyn MemoryInterface,
         _variable_cache: &VariableCache,
     ) -> Result<Self, DebugError> {
-        let mem_data = memory.read_word_32(variable.memory_location.memory_address()?)?;
+        let mem_data = u32::read_from_location(variable, memory)?;
         if let Some(return_value) = char::from_u32(mem_data) {
             Ok(return_value)
         } else {
@@ -103,16 +255,11 @@ impl Value for char {
             ));
         }
 
+        let new_char = parse_char(new_value)?;
         memory
-            .write_word_32(
+            .write_8(
                 variable.memory_location.memory_address()?,
-                <char as FromStr>::from_str(new_value).map_err(|error| {
-                    DebugError::WarnAndContinue {
-                        message: format!(
-                            "Invalid data conversion from value: {new_value:?}. {error:?}"
-                        ),
-                    }
-                })? as u32,
+                &(new_char as u32).to_target_bytes(variable.endianness),
             )
             .map_err(|error| DebugError::WarnAndContinue {
                 message: format!("{error:?}"),
@@ -125,64 +272,7 @@ impl Value for String {
         memory: &mut dyn MemoryInterface,
         variable_cache: &VariableCache,
     ) -> Result<Self, DebugError> {
-        let mut str_value: String = "".to_owned();
-        let children: Vec<_> = variable_cache.get_children(variable.variable_key).collect();
-        if !children.is_empty() {
-            let mut string_length = match children.iter().find(|child_variable| {
-                matches!(child_variable.name, VariableName::Named(ref name) if name == "length")
-            }) {
-                Some(string_length) => {
-                    // TODO: maybe avoid accessing value directly?
-                    if let VariableValue::Valid(length_value) = &string_length.value {
-                        length_value.parse().unwrap_or(0_usize)
-                    } else {
-                        0_usize
-                    }
-                }
-                None => 0_usize,
-            };
-
-            let string_location = match children.iter().find(|child_variable| {
-                matches!(child_variable.name, VariableName::Named(ref name) if name == "data_ptr")
-            }) {
-                Some(location_value) => {
-                    let mut child_variables = variable_cache.get_children(location_value.variable_key);
-                    if let Some(first_child) = child_variables.next() {
-                        first_child.memory_location.memory_address()?
-                    } else {
-                        0_u64
-                    }
-                }
-                None => 0_u64,
-            };
-            if string_location == 0 {
-                str_value = "Error: Failed to determine &str memory location".to_string();
-            } else {
-                // Limit string length to work around buggy information, otherwise the debugger
-                // can hang due to buggy debug information.
-                //
-                // TODO: If implemented, the variable should not be fetched automatically,
-                // but only when requested by the user. This workaround can then be removed.
-                if string_length > 200 {
-                    tracing::warn!(
-                        "Very long string ({} bytes), truncating to 200 bytes.",
-                        string_length
-                    );
-                    string_length = 200;
-                }
-
-                if string_length == 0 {
-                    // A string with length 0 doesn't need to be read from memory.
-                } else {
-                    let mut buff = vec![0u8; string_length];
-                    memory.read(string_location, &mut buff)?;
-                    std::str::from_utf8(&buff)?.clone_into(&mut str_value);
-                }
-            }
-        } else {
-            str_value = "Error: Failed to evaluate &str value".to_string();
-        }
-        Ok(str_value)
+        read_string(variable, memory, variable_cache, DEFAULT_MAX_STRING_LENGTH)
     }
 
     fn update_value(
@@ -194,6 +284,171 @@ impl Value for String {
     }
 }
 
+/// Read the text of a string, like a Rust `&str` or `String`, from the children of `variable`.
+///
+/// The children hold the length of the text, and a pointer to it, possibly nested in other structs,
+/// e.g. the `Vec<u8>` of a `String`. Text which is longer than `max_length` bytes is truncated, and
+/// ends with `...`. Invalid UTF-8 is replaced with `U+FFFD`.
+pub(crate) fn read_string(
+    variable: &Variable,
+    memory: &mut dyn MemoryInterface,
+    variable_cache: &VariableCache,
+    max_length: usize,
+) -> Result<String, DebugError> {
+    fn find_pointer(variable_cache: &VariableCache, parent_key: ObjectRef) -> Option<&Variable> {
+        variable_cache.get_children(parent_key).find_map(|child| {
+            if matches!(child.type_name.inner(), VariableType::Pointer(_)) {
+                Some(child)
+            } else {
+                find_pointer(variable_cache, child.variable_key)
+            }
+        })
+    }
+
+    let named_child = |parent_key, names: &[&str]| {
+        variable_cache.get_children(parent_key).find(|child| {
+            matches!(child.name, VariableName::Named(ref name) if names.contains(&name.as_str()))
+        })
+    };
+
+    if !variable_cache.has_children(variable) {
+        return Err(DebugError::WarnAndContinue {
+            message: format!("Failed to evaluate {} value", variable.type_name()),
+        });
+    }
+
+    // A `String` holds its text in a `Vec<u8>`.
+    let text_parent = named_child(variable.variable_key, &["vec"]).unwrap_or(variable);
+
+    let length = named_child(text_parent.variable_key, &["length", "len"])
+        .and_then(|length| length.value.as_u64())
+        .unwrap_or(0) as usize;
+
+    let location = match find_pointer(variable_cache, text_parent.variable_key)
+        .and_then(|pointer| variable_cache.get_children(pointer.variable_key).next())
+    {
+        Some(text) => text.memory_location.memory_address()?,
+        None => 0,
+    };
+    if location == 0 {
+        return Err(DebugError::WarnAndContinue {
+            message: format!(
+                "Failed to determine {} memory location",
+                variable.type_name()
+            ),
+        });
+    }
+
+    read_text(memory, location, length, max_length)
+}
+
+/// Reads the `length` bytes of UTF-8 text at `location`, up to `max_length` bytes.
+pub(crate) fn read_text(
+    memory: &mut dyn MemoryInterface,
+    location: u64,
+    length: usize,
+    max_length: usize,
+) -> Result<String, DebugError> {
+    // A string with length 0 doesn't need to be read from memory, and its pointer may be dangling.
+    if length == 0 {
+        return Ok(String::new());
+    }
+
+    // Limit the string length to work around buggy information, otherwise the debugger can hang
+    // due to buggy debug information.
+    let read_length = length.min(max_length);
+    let mut buff = vec![0u8; read_length];
+    memory.read(location, &mut buff)?;
+
+    let mut text = String::from_utf8_lossy(&buff).into_owned();
+    if read_length < length {
+        tracing::debug!("Very long string ({length} bytes), truncating to {read_length} bytes.");
+        text.push_str("...");
+    }
+    Ok(text)
+}
+
+/// Reads the text of a NUL terminated C string at `location`, which is at most `length` bytes long
+/// if it is known, e.g. for a `char[16]`. At most `max_length` bytes are read, and text which is not
+/// terminated by then is truncated, and ends with `...`. Invalid UTF-8 is replaced with `U+FFFD`.
+pub(crate) fn read_c_string(
+    memory: &mut dyn MemoryInterface,
+    location: u64,
+    length: Option<usize>,
+    max_length: usize,
+) -> Result<String, DebugError> {
+    // The text is read in aligned chunks, and a chunk which runs past the end of the memory the
+    // text is stored in is read again byte by byte, up to the end of the text.
+    const CHUNK_LENGTH: u64 = 32;
+
+    let read_length = length.unwrap_or(usize::MAX).min(max_length);
+    let mut bytes = Vec::new();
+    let mut terminated = false;
+    while bytes.len() < read_length && !terminated {
+        let address = location + bytes.len() as u64;
+        let chunk_length = CHUNK_LENGTH - address % CHUNK_LENGTH;
+        let mut chunk = vec![0u8; (read_length - bytes.len()).min(chunk_length as usize)];
+        if memory.read(address, &mut chunk).is_err() {
+            for (offset, byte) in (0..).zip(chunk.iter_mut()) {
+                *byte = memory.read_word_8(address + offset)?;
+                if *byte == 0 {
+                    break;
+                }
+            }
+        }
+
+        let end = chunk.iter().position(|&byte| byte == 0);
+        terminated = end.is_some();
+        bytes.extend_from_slice(&chunk[..end.unwrap_or(chunk.len())]);
+    }
+
+    let mut text = String::from_utf8_lossy(&bytes).into_owned();
+    // An array which is filled up to its last byte is not truncated.
+    if !terminated && length.is_none_or(|length| length > read_length) {
+        text.push_str("...");
+    }
+    Ok(text)
+}
+
+/// Reads the first `max_length` of the `length` bytes at `location` with a single read, and shows
+/// them in hexadecimal, followed by their ASCII text, e.g. `68 65 6c 6c 6f ... |hello...|`.
+pub(crate) fn read_byte_preview(
+    memory: &mut dyn MemoryInterface,
+    location: u64,
+    length: u64,
+    max_length: usize,
+) -> Result<String, DebugError> {
+    let mut buff = vec![0u8; length.min(max_length as u64) as usize];
+    memory.read(location, &mut buff)?;
+
+    Ok(format_byte_preview(&buff, length))
+}
+
+/// The preview of [`read_byte_preview`], of the bytes in `buff`, which are the first of `length`
+/// bytes.
+pub(crate) fn format_byte_preview(buff: &[u8], length: u64) -> String {
+    let mut hex = buff
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut text = buff
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect::<String>();
+    if (buff.len() as u64) < length {
+        hex.push_str(" ...");
+        text.push_str("...");
+    }
+    format!("{hex} |{text}|")
+}
+
 impl Value for i8 {
     fn get_value(
         variable: &Variable,
@@ -214,7 +469,7 @@ impl Value for i8 {
             ));
         }
 
-        let buff = i8::parse_to_bytes(new_value)?;
+        let buff = i8::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_word_8(variable.memory_location.memory_address()?, buff[0])
             .map_err(|error| DebugError::WarnAndContinue {
@@ -242,7 +497,7 @@ impl Value for i16 {
             ));
         }
 
-        let buff = i16::parse_to_bytes(new_value)?;
+        let buff = i16::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -270,7 +525,7 @@ impl Value for i32 {
             ));
         }
 
-        let buff = i32::parse_to_bytes(new_value)?;
+        let buff = i32::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -298,7 +553,7 @@ impl Value for i64 {
             ));
         }
 
-        let buff = i64::parse_to_bytes(new_value)?;
+        let buff = i64::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -326,7 +581,7 @@ impl Value for i128 {
             ));
         }
 
-        let buff = i128::parse_to_bytes(new_value)?;
+        let buff = i128::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -354,7 +609,7 @@ impl Value for u8 {
             ));
         }
 
-        let buff = u8::parse_to_bytes(new_value)?;
+        let buff = u8::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_word_8(variable.memory_location.memory_address()?, buff[0])
             .map_err(|error| DebugError::WarnAndContinue {
@@ -382,7 +637,7 @@ impl Value for u16 {
             ));
         }
 
-        let buff = u16::parse_to_bytes(new_value)?;
+        let buff = u16::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -410,7 +665,7 @@ impl Value for u32 {
             ));
         }
 
-        let buff = u32::parse_to_bytes(new_value)?;
+        let buff = u32::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -438,7 +693,7 @@ impl Value for u64 {
             ));
         }
 
-        let buff = u64::parse_to_bytes(new_value)?;
+        let buff = u64::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -466,7 +721,7 @@ impl Value for u128 {
             ));
         }
 
-        let buff = u128::parse_to_bytes(new_value)?;
+        let buff = u128::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -494,7 +749,7 @@ impl Value for f32 {
             ));
         }
 
-        let buff = f32::parse_to_bytes(new_value)?;
+        let buff = f32::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -522,7 +777,7 @@ impl Value for f64 {
             ));
         }
 
-        let buff = f64::parse_to_bytes(new_value)?;
+        let buff = f64::parse_to_bytes(new_value, variable.endianness)?;
         memory
             .write_8(variable.memory_location.memory_address()?, &buff)
             .map_err(|error| DebugError::WarnAndContinue {
@@ -532,8 +787,14 @@ impl Value for f64 {
 }
 
 /// Format a float value to a string, preserving at least one fractional digit.
+///
+/// Infinities and NaNs are shown as `inf`, `-inf` and `NaN`.
 pub fn format_float(value: f64) -> String {
     let mut s = format!("{value}");
+    if !value.is_finite() {
+        return s;
+    }
+
     if !s.contains('.') {
         s.push('.');
     }
@@ -3,20 +3,51 @@
 //! The `debug` module contains various debug functionality, which can be
 //! used to implement a debugger based on `probe-rs`.
 
+/// Evaluation of conditions, like `count > 10 && state == 3`.
+pub(crate) mod condition;
 /// Debug information which is parsed from DWARF debugging information.
 pub mod debug_info;
+/// Debug information for multiple images that are resident on the target at the same time.
+pub mod debug_info_set;
 /// Stepping through a program during debug, at various granularities.
 pub mod debug_step;
+/// Demangling of Rust and C++ symbol names.
+pub(crate) mod demangle;
+/// Evaluation of variable paths, like `config.buffers[2].len`.
+pub(crate) mod expression_path;
 /// References to the DIE (debug information entry) of functions.
 pub mod function_die;
 /// Programming languages
 pub(crate) mod language;
+/// A snapshot of a range of target memory, from which several variables are decoded.
+pub(crate) mod memory_snapshot;
+/// Remapping of the source paths in the debug information to local paths.
+pub(crate) mod path_mapping;
 /// Target Register definitions, expanded from [`crate::core::registers::CoreRegister`] to include unwind specific information.
 pub mod registers;
+/// The storage of the DWARF sections, which can be memory mapped.
+pub(crate) mod section_data;
+/// Locating debug information that is stored separately from the binary.
+pub(crate) mod separate_debug_info;
+/// The source files referenced by the debug information.
+pub mod source_file;
 /// The source statement information used while identifying haltpoints for debug stepping and breakpoints.
 pub(crate) mod source_instructions;
+/// Loading of split DWARF units from `.dwo` files and `.dwp` packages.
+pub(crate) mod split_dwarf;
 /// The stack frame information used while unwinding the stack from a specific program counter.
 pub mod stack_frame;
+/// The static variables of the program, at fixed addresses.
+pub mod static_variable;
+/// A summary of the loaded debug information, for diagnostics.
+pub mod statistics;
+/// Loading of supplementary object files, which hold debug information that is shared between binaries.
+pub(crate) mod supplementary;
+/// Helpers for tests that run against the memory image of a binary.
+#[cfg(test)]
+pub(crate) mod test_support;
+/// Custom formatters for the values of variables, by the name of their type.
+pub(crate) mod type_formatter;
 /// Information about a Unit in the debug information.
 pub mod unit_info;
 /// Variable information used during debug.
@@ -27,9 +58,16 @@ pub mod variable_cache;
 pub(crate) mod exception_handling;
 
 pub use self::{
-    debug_info::*, debug_step::SteppingMode, exception_handling::exception_handler_for_core,
-    registers::*, source_instructions::SourceLocation, source_instructions::VerifiedBreakpoint,
-    stack_frame::StackFrame, variable::*, variable_cache::VariableCache,
+    condition::EvalError, debug_info::*, debug_info_set::DebugInfoSet, debug_info_set::ImageId,
+    debug_step::SteppingMode, exception_handling::exception_handler_for_core,
+    function_die::FunctionInfo, function_die::FunctionMatch, function_die::NameMatch, registers::*,
+    section_data::SectionData, source_file::SourceFile, source_instructions::BreakpointLocation,
+    source_instructions::BreakpointOptions, source_instructions::ResolvedSourceLocation,
+    source_instructions::SourceLocation, source_instructions::VerifiedBreakpoint,
+    stack_frame::FrameKind, stack_frame::InlineFrame, stack_frame::StackFrame,
+    static_variable::StaticVariableInfo, statistics::DebugInfoStatistics,
+    unit_info::CompilationUnitInfo, variable::*, variable_cache::VariableCache,
+    variable_cache::VariableChange,
 };
 
 use probe_rs::{Core, MemoryInterface};
@@ -44,16 +82,18 @@ use std::num::ParseIntError;
 use std::{
     io,
     num::NonZeroU32,
+    path::PathBuf,
     str::Utf8Error,
     sync::atomic::{AtomicU32, Ordering},
     vec,
 };
 
 /// A simplified type alias of the [`gimli::EndianReader`] type.
-pub type EndianReader = gimli::EndianReader<RunTimeEndian, std::rc::Rc<[u8]>>;
+pub type EndianReader = gimli::EndianReader<RunTimeEndian, SectionData>;
 
 /// An error occurred while debugging the target.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum DebugError {
     /// An IO error occurred when accessing debug data.
     #[error("IO Error while accessing debug data")]
@@ -64,8 +104,10 @@ pub enum DebugError {
     /// Something failed while parsing debug data.
     #[error("Error parsing debug data")]
     Parse(#[from] gimli::read::Error),
-    /// Non-UTF8 data was found in the debug data.
-    #[error("Non-UTF8 data found in debug data")]
+    /// Non-UTF8 data was found in a string that was read from the target.
+    ///
+    /// Names and paths in the debug information are converted lossily instead.
+    #[error("Non-UTF8 data found in string")]
     NonUtf8(#[from] Utf8Error),
     /// A probe-rs error occurred.
     #[error("Error using the probe")]
@@ -85,6 +127,97 @@ pub enum DebugError {
         message: String,
     },
 
+    /// The separate debug file referenced by the `.gnu_debuglink` section of the binary was not found.
+    #[error(
+        "The debug file {file_name} referenced by .gnu_debuglink was not found, searched in {searched:?}"
+    )]
+    DebugLinkNotFound {
+        /// The file name stored in the `.gnu_debuglink` section.
+        file_name: String,
+        /// The locations that were searched.
+        searched: Vec<PathBuf>,
+    },
+
+    /// The separate debug file does not match the checksum in the `.gnu_debuglink` section of the binary.
+    #[error("The debug file {} does not match .gnu_debuglink, expected CRC32 {expected:#010x} but found {actual:#010x}", path.display())]
+    DebugLinkCrcMismatch {
+        /// The debug file with the mismatching checksum.
+        path: PathBuf,
+        /// The checksum stored in the `.gnu_debuglink` section.
+        expected: u32,
+        /// The checksum of the debug file.
+        actual: u32,
+    },
+
+    /// The binary does not contain any DWARF debug information.
+    #[error("The {0:?} file does not contain DWARF debug information")]
+    NoDwarf(object::BinaryFormat),
+
+    /// The ELF file does not contain any debug information, because it was built without debug
+    /// information, or the debug information was stripped.
+    #[error(
+        "The ELF file does not contain debug information (.debug_info and .debug_abbrev). Make sure it is built with debug information, e.g. with `debug = true` in the release profile of Cargo.toml, and that it is not stripped, e.g. by `strip = true` or `objcopy --strip-debug`."
+    )]
+    NoDebugInfo,
+
+    /// A section of the binary could not be decompressed.
+    ///
+    /// Compressed sections are only supported if the `compression` feature is enabled.
+    #[error("Failed to decompress the {section} section")]
+    SectionDecompression {
+        /// The name of the section.
+        section: String,
+        /// The error reported while decompressing the section.
+        #[source]
+        source: object::read::Error,
+    },
+
+    /// The value of a variable cannot be changed.
+    #[error("Cannot change the value of {name}: {reason}")]
+    VariableNotWritable {
+        /// The name of the variable.
+        name: String,
+        /// Why the value cannot be changed.
+        reason: NotWritableReason,
+    },
+
+    /// A hardware watchpoint cannot be set on a variable, see [`Variable::watch_spec`].
+    #[error("Cannot watch {name}: {reason}")]
+    VariableNotWatchable {
+        /// The name of the variable.
+        name: String,
+        /// Why the variable cannot be watched.
+        reason: NotWatchableReason,
+    },
+
+    /// A variable path could not be evaluated, see [`DebugInfo::evaluate_path`].
+    #[error("Cannot evaluate {path}: {reason}")]
+    InvalidPath {
+        /// The variable path.
+        path: String,
+        /// Why the path could not be evaluated.
+        reason: String,
+    },
+
+    /// A part of a variable path does not name a variable, see [`DebugInfo::evaluate_path`].
+    #[error("{segment} was not found, the candidates are: {}", .candidates.join(", "))]
+    PathSegmentNotFound {
+        /// The part of the path that was not found, e.g. a field name, or an array index like `[7]`.
+        segment: String,
+        /// The names that are available instead.
+        candidates: Vec<String>,
+    },
+
+    /// No more variables can be added to a [`VariableCache`], because it has the maximum number of
+    /// variables, see [`VariableCache::set_max_len`].
+    #[error(
+        "The variable cache is full, with {limit} variables. Collapse other variables, or raise the limit."
+    )]
+    VariableCacheFull {
+        /// The largest number of variables in the cache.
+        limit: usize,
+    },
+
     /// Required functionality is not implemented
     #[error("Not implemented: {0}")]
     NotImplemented(&'static str),
@@ -92,10 +225,123 @@ pub enum DebugError {
     /// Some other error occurred.
     #[error("{0}")]
     Other(String),
+
+    /// An error that occurred while processing a specific part of the debug information.
+    #[error("{error} ({context})")]
+    WithContext {
+        /// The error that occurred.
+        #[source]
+        error: Box<DebugError>,
+        /// Where in the debug information the error occurred.
+        context: ErrorContext,
+    },
+}
+
+impl DebugError {
+    /// Attach the location in the debug information where the error occurred.
+    ///
+    /// If the error already has a context with a DIE, that context is kept, because it is more specific,
+    /// and only a missing address is added. [`DebugError::WarnAndContinue`] and
+    /// [`DebugError::VariableCacheFull`] are returned unchanged, so callers can still match on them.
+    pub(crate) fn with_context(self, context: ErrorContext) -> Self {
+        match self {
+            DebugError::WarnAndContinue { .. } | DebugError::VariableCacheFull { .. } => self,
+            DebugError::WithContext {
+                error,
+                context: inner_context,
+            } => DebugError::WithContext {
+                error,
+                context: inner_context.within(context),
+            },
+            error => DebugError::WithContext {
+                error: Box::new(error),
+                context,
+            },
+        }
+    }
+
+    /// The location in the debug information where the error occurred, if it is known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            DebugError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+}
+
+/// The location in the debug information where a [`DebugError`] occurred.
+///
+/// The offsets are relative to the start of the section that contains the unit, which is `.debug_info`,
+/// or the `.debug_info.dwo` section of a split DWARF file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ErrorContext {
+    /// The offset of the compilation unit header.
+    pub unit_offset: Option<u64>,
+    /// The offset of the DIE (debugging information entry).
+    pub die_offset: Option<u64>,
+    /// The attribute of the DIE that was processed.
+    pub attribute: Option<gimli::DwAt>,
+    /// The target address that was processed.
+    pub address: Option<u64>,
+}
+
+impl ErrorContext {
+    /// The context of an error at a target address.
+    pub(crate) fn at_address(address: u64) -> Self {
+        Self {
+            address: Some(address),
+            ..Default::default()
+        }
+    }
+
+    /// Add the attribute of the DIE that was processed.
+    pub(crate) fn with_attribute(self, attribute: gimli::DwAt) -> Self {
+        Self {
+            attribute: Some(attribute),
+            ..self
+        }
+    }
+
+    /// Combine this context with the context of a caller.
+    ///
+    /// If this context has no DIE, the DIE of the `outer` context is used.
+    fn within(self, outer: ErrorContext) -> Self {
+        let address = self.address.or(outer.address);
+        if self.die_offset.is_some() {
+            Self { address, ..self }
+        } else {
+            Self { address, ..outer }
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(unit_offset) = self.unit_offset {
+            parts.push(format!("unit at {unit_offset:#x}"));
+        }
+        if let Some(die_offset) = self.die_offset {
+            parts.push(format!("DIE at {die_offset:#x}"));
+        }
+        if let Some(attribute) = self.attribute {
+            parts.push(format!("attribute {attribute}"));
+        }
+        if let Some(address) = self.address {
+            parts.push(format!("address {address:#010x}"));
+        }
+
+        if parts.is_empty() {
+            write!(f, "no context")
+        } else {
+            write!(f, "{}", parts.join(", "))
+        }
+    }
 }
 
 /// A copy of [`gimli::ColumnType`] which uses [`u64`] instead of [`NonZeroU64`](std::num::NonZeroU64).
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum ColumnType {
     /// The `LeftEdge` means that the statement begins at the start of the new line.
     LeftEdge,
@@ -173,6 +419,9 @@ impl std::str::FromStr for ObjectRef {
 
 static CACHE_KEY: AtomicU32 = AtomicU32::new(1);
 /// Generate a unique key that can be used to assign id's to StackFrame and Variable structs.
+///
+/// Keys are never reused, so the key of a variable in a cache which was rebuilt is not found in the
+/// new cache, see [`VariableCache::translate_keys`].
 pub fn get_object_reference() -> ObjectRef {
     let key = CACHE_KEY.fetch_add(1, Ordering::SeqCst);
     ObjectRef::Valid(NonZeroU32::new(key).unwrap())
@@ -247,15 +496,18 @@ pub(crate) fn _print_all_attributes(
             AttributeValue::Addr(a) => println!("{a:#010x}"),
             AttributeValue::DebugStrRef(str_ref) => {
                 let val = dwarf.string(str_ref).unwrap();
-                println!("{}", std::str::from_utf8(&val).unwrap());
+                println!("{}", String::from_utf8_lossy(&val));
             }
             AttributeValue::Exprloc(e) => {
+                let endian = gimli::Reader::endian(&e.0);
                 let mut evaluation = e.evaluation(unit.encoding());
 
                 // go for evaluation
                 let mut result = evaluation.evaluate().unwrap();
 
-                while let Some(next) = iterate(result, core, &mut evaluation, stackframe_cfa) {
+                while let Some(next) =
+                    iterate(result, core, endian, &mut evaluation, stackframe_cfa)
+                {
                     result = next;
                 }
 
@@ -275,6 +527,7 @@ pub(crate) fn _print_all_attributes(
 fn iterate(
     result: EvaluationResult<DwarfReader>,
     core: &mut Core,
+    endianness: RunTimeEndian,
     evaluation: &mut gimli::Evaluation<DwarfReader>,
     stackframe_cfa: Option<u64>,
 ) -> Option<EvaluationResult<DwarfReader>> {
@@ -285,14 +538,8 @@ fn iterate(
             core.read(address, &mut buff)
                 .expect("Failed to read memory");
 
-            let value = match size {
-                1 => gimli::Value::U8(buff[0]),
-                2 => gimli::Value::U16(u16::from_be_bytes([buff[0], buff[1]])),
-                4 => gimli::Value::U32(u32::from_be_bytes([buff[0], buff[1], buff[2], buff[3]])),
-                x => unimplemented!("Requested memory with size {x}, which is not supported yet."),
-            };
-
-            evaluation.resume_with_memory(value)
+            let value = language::parsing::u128_from_target_bytes(&buff, endianness);
+            evaluation.resume_with_memory(gimli::Value::Generic(value as u64))
         }
         EvaluationResult::RequiresFrameBase => {
             evaluation.resume_with_frame_base(stackframe_cfa.unwrap())
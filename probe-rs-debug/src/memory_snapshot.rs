@@ -0,0 +1,169 @@
+use gimli::RunTimeEndian;
+use probe_rs::MemoryInterface;
+
+use crate::language::parsing::ValueExt;
+
+/// A view of target memory, in which a range of addresses has been read once, so that all the
+/// variables in it are decoded from the same bytes, like the arms of a union.
+///
+/// Reads which are not entirely in the range, and all writes, go to the target memory.
+pub(crate) struct MemorySnapshot<'a> {
+    memory: &'a mut dyn MemoryInterface,
+    address: u64,
+    bytes: Vec<u8>,
+    /// The byte order of the target, in which the words are decoded from the bytes.
+    endianness: RunTimeEndian,
+}
+
+impl<'a> MemorySnapshot<'a> {
+    /// Create a snapshot of the `bytes` which were read from `memory` at `address`, on a target
+    /// with the byte order `endianness`.
+    pub(crate) fn new(
+        memory: &'a mut dyn MemoryInterface,
+        address: u64,
+        bytes: Vec<u8>,
+        endianness: RunTimeEndian,
+    ) -> Self {
+        Self {
+            memory,
+            address,
+            bytes,
+            endianness,
+        }
+    }
+
+    /// The `len` bytes at `address`, if they are all in the snapshot.
+    fn bytes(&self, address: u64, len: usize) -> Option<&[u8]> {
+        let start = usize::try_from(address.checked_sub(self.address)?).ok()?;
+        self.bytes.get(start..start.checked_add(len)?)
+    }
+
+    /// Decode the words at `address` from the snapshot, or return `false` if they are not all in it.
+    fn read_words<const N: usize, T: ValueExt<Out = [u8; N]>>(
+        &self,
+        address: u64,
+        data: &mut [T],
+    ) -> bool {
+        let Some(bytes) = self.bytes(address, data.len() * N) else {
+            return false;
+        };
+        for (word, bytes) in data.iter_mut().zip(bytes.chunks_exact(N)) {
+            let mut buff = [0u8; N];
+            buff.copy_from_slice(bytes);
+            *word = T::from_target_bytes(buff, self.endianness);
+        }
+        true
+    }
+}
+
+impl MemoryInterface for MemorySnapshot<'_> {
+    fn supports_native_64bit_access(&mut self) -> bool {
+        self.memory.supports_native_64bit_access()
+    }
+
+    fn read_64(&mut self, address: u64, data: &mut [u64]) -> Result<(), probe_rs::Error> {
+        if self.read_words(address, data) {
+            return Ok(());
+        }
+        self.memory.read_64(address, data)
+    }
+
+    fn read_32(&mut self, address: u64, data: &mut [u32]) -> Result<(), probe_rs::Error> {
+        if self.read_words(address, data) {
+            return Ok(());
+        }
+        self.memory.read_32(address, data)
+    }
+
+    fn read_16(&mut self, address: u64, data: &mut [u16]) -> Result<(), probe_rs::Error> {
+        if self.read_words(address, data) {
+            return Ok(());
+        }
+        self.memory.read_16(address, data)
+    }
+
+    fn read_8(&mut self, address: u64, data: &mut [u8]) -> Result<(), probe_rs::Error> {
+        if let Some(bytes) = self.bytes(address, data.len()) {
+            data.copy_from_slice(bytes);
+            return Ok(());
+        }
+        self.memory.read_8(address, data)
+    }
+
+    fn read(&mut self, address: u64, data: &mut [u8]) -> Result<(), probe_rs::Error> {
+        if let Some(bytes) = self.bytes(address, data.len()) {
+            data.copy_from_slice(bytes);
+            return Ok(());
+        }
+        self.memory.read(address, data)
+    }
+
+    fn write_64(&mut self, address: u64, data: &[u64]) -> Result<(), probe_rs::Error> {
+        self.memory.write_64(address, data)
+    }
+
+    fn write_32(&mut self, address: u64, data: &[u32]) -> Result<(), probe_rs::Error> {
+        self.memory.write_32(address, data)
+    }
+
+    fn write_16(&mut self, address: u64, data: &[u16]) -> Result<(), probe_rs::Error> {
+        self.memory.write_16(address, data)
+    }
+
+    fn write_8(&mut self, address: u64, data: &[u8]) -> Result<(), probe_rs::Error> {
+        self.memory.write_8(address, data)
+    }
+
+    fn supports_8bit_transfers(&self) -> Result<bool, probe_rs::Error> {
+        self.memory.supports_8bit_transfers()
+    }
+
+    fn flush(&mut self) -> Result<(), probe_rs::Error> {
+        self.memory.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gimli::RunTimeEndian;
+    use probe_rs::{MemoryInterface, test::MockMemory};
+
+    use super::MemorySnapshot;
+
+    #[test]
+    fn reads_in_the_snapshot_use_its_bytes() {
+        let mut memory = MockMemory::new();
+        memory.add_range(0x2000_0000, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let mut snapshot = MemorySnapshot::new(
+            &mut memory,
+            0x2000_0000,
+            vec![0x78, 0x56, 0x34, 0x12],
+            RunTimeEndian::Little,
+        );
+
+        let mut bytes = [0u8; 2];
+        snapshot.read(0x2000_0002, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x34, 0x12]);
+        assert_eq!(snapshot.read_word_32(0x2000_0000).unwrap(), 0x1234_5678);
+
+        // The bytes after the snapshot are read from the target.
+        snapshot.read_8(0x2000_0004, &mut bytes).unwrap();
+        assert_eq!(bytes, [5, 6]);
+    }
+
+    #[test]
+    fn words_are_decoded_in_the_target_byte_order() {
+        let mut memory = MockMemory::new();
+
+        let mut snapshot = MemorySnapshot::new(
+            &mut memory,
+            0x2000_0000,
+            vec![0x12, 0x34, 0x56, 0x78],
+            RunTimeEndian::Big,
+        );
+
+        assert_eq!(snapshot.read_word_32(0x2000_0000).unwrap(), 0x1234_5678);
+        assert_eq!(snapshot.read_word_16(0x2000_0002).unwrap(), 0x5678);
+    }
+}
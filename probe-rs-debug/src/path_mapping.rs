@@ -0,0 +1,122 @@
+use typed_path::{TypedPath, TypedPathBuf};
+
+/// A mapping of a source path prefix, see [`DebugInfo::add_path_mapping`](crate::DebugInfo::add_path_mapping).
+#[derive(Debug, Clone)]
+pub(crate) struct PathMapping {
+    /// The prefix of the paths in the debug information.
+    from_prefix: String,
+    /// The prefix that replaces `from_prefix`.
+    to_prefix: TypedPathBuf,
+}
+
+impl PathMapping {
+    pub(crate) fn new(from_prefix: TypedPath, to_prefix: TypedPath) -> Self {
+        Self {
+            from_prefix: from_prefix.to_string_lossy().into_owned(),
+            to_prefix: to_prefix.to_path_buf(),
+        }
+    }
+}
+
+/// Apply the mapping with the longest matching prefix to `path`.
+///
+/// Prefixes are matched by whole components, and both `/` and `\` are accepted as separators in the
+/// path and in the prefix, so a prefix can match paths from Windows and Unix builds. If more than one
+/// mapping has the longest prefix, the first one is used.
+///
+/// Returns `path` unchanged if no mapping matches.
+pub(crate) fn map_path(mappings: &[PathMapping], path: TypedPathBuf) -> TypedPathBuf {
+    let path_string = path.to_string_lossy();
+    let components = path_components(&path_string);
+
+    let mut best_match: Option<(&PathMapping, usize)> = None;
+    for mapping in mappings {
+        let prefix = path_components(&mapping.from_prefix);
+        if is_prefix(&prefix, &components)
+            && best_match.is_none_or(|(_, length)| prefix.len() > length)
+        {
+            best_match = Some((mapping, prefix.len()));
+        }
+    }
+
+    let Some((mapping, prefix_length)) = best_match else {
+        return path;
+    };
+
+    components[prefix_length..]
+        .iter()
+        .fold(mapping.to_prefix.clone(), |mapped, component| {
+            mapped.join(component)
+        })
+}
+
+/// Split a path into its components, accepting both `/` and `\` as separators.
+///
+/// The root of an absolute path is the first component, which is `/` for Unix paths, and the drive,
+/// e.g. `C:`, for Windows paths. Empty and `.` components are skipped.
+fn path_components(path: &str) -> Vec<&str> {
+    let mut components = Vec::new();
+    if path.starts_with(['/', '\\']) {
+        components.push("/");
+    }
+
+    components.extend(
+        path.split(['/', '\\'])
+            .filter(|component| !component.is_empty() && *component != "."),
+    );
+
+    components
+}
+
+/// Returns `true` if `prefix` is a prefix of `components`.
+///
+/// An empty prefix, e.g. from a prefix of `.`, only matches relative paths. Windows drives are compared
+/// case-insensitively.
+fn is_prefix(prefix: &[&str], components: &[&str]) -> bool {
+    let is_root = |component: &str| component == "/" || component.ends_with(':');
+
+    if prefix.is_empty() {
+        return components.first().is_none_or(|first| !is_root(first));
+    }
+
+    prefix.len() <= components.len()
+        && prefix
+            .iter()
+            .zip(components)
+            .all(|(prefix_component, component)| {
+                if prefix_component.ends_with(':') {
+                    prefix_component.eq_ignore_ascii_case(component)
+                } else {
+                    prefix_component == component
+                }
+            })
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+    use typed_path::TypedPath;
+
+    use super::{PathMapping, map_path};
+
+    #[test_case("/build/agent/src/main.rs", "/home/dev/project/src/main.rs"; "unix")]
+    #[test_case("C:\\build\\agent\\src\\main.rs", "/home/dev/windows/src/main.rs"; "windows")]
+    #[test_case("c:/build/agent/src/main.rs", "/home/dev/windows/src/main.rs"; "windows with forward slashes")]
+    #[test_case("/build/agent/vendor/lib.rs", "/home/dev/vendor/lib.rs"; "longest prefix")]
+    #[test_case("/build/agent2/src/main.rs", "/build/agent2/src/main.rs"; "partial component")]
+    #[test_case("src/main.rs", "/home/dev/relative/src/main.rs"; "relative")]
+    fn map_paths(path: &str, expected: &str) {
+        let mappings = [
+            ("/build/agent", "/home/dev/project"),
+            ("/build/agent/vendor", "/home/dev/vendor"),
+            ("C:/build/agent", "/home/dev/windows"),
+            (".", "/home/dev/relative"),
+        ]
+        .map(|(from, to)| PathMapping::new(TypedPath::derive(from), TypedPath::derive(to)));
+
+        assert_eq!(
+            map_path(&mappings, TypedPath::derive(path).to_path_buf()),
+            TypedPath::derive(expected).to_path_buf()
+        );
+    }
+}
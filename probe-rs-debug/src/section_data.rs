@@ -0,0 +1,171 @@
+use std::{borrow::Cow, fmt, ops::Deref, path::Path, sync::Arc};
+
+#[cfg(feature = "memmap2")]
+use std::ops::Range;
+
+use crate::DebugError;
+
+/// The data of a debug section, which is either stored in memory, or part of a memory mapped file.
+///
+/// This is the storage of the [`EndianReader`](crate::EndianReader) used for all DWARF sections.
+#[derive(Clone)]
+pub struct SectionData(Storage);
+
+#[derive(Clone)]
+enum Storage {
+    Owned(Arc<[u8]>),
+    #[cfg(feature = "memmap2")]
+    Mapped {
+        map: Arc<memmap2::Mmap>,
+        range: Range<usize>,
+    },
+}
+
+impl SectionData {
+    /// Returns `true` if the data is part of a memory mapped file.
+    pub fn is_mapped(&self) -> bool {
+        match &self.0 {
+            Storage::Owned(_) => false,
+            #[cfg(feature = "memmap2")]
+            Storage::Mapped { .. } => true,
+        }
+    }
+}
+
+impl Deref for SectionData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.0 {
+            Storage::Owned(data) => data,
+            #[cfg(feature = "memmap2")]
+            Storage::Mapped { map, range } => &map[range.clone()],
+        }
+    }
+}
+
+impl fmt::Debug for SectionData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SectionData")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+impl From<&[u8]> for SectionData {
+    fn from(data: &[u8]) -> Self {
+        Self(Storage::Owned(Arc::from(data)))
+    }
+}
+
+impl From<Vec<u8>> for SectionData {
+    fn from(data: Vec<u8>) -> Self {
+        Self(Storage::Owned(Arc::from(data)))
+    }
+}
+
+// The data is owned by an `Arc`, either directly or through the memory map, so it does not move when
+// the `SectionData` is moved or cloned.
+unsafe impl gimli::StableDeref for SectionData {}
+unsafe impl gimli::CloneStableDeref for SectionData {}
+
+/// The contents of a file that debug information is loaded from.
+pub(crate) enum FileData {
+    /// The file was read into memory.
+    #[cfg_attr(feature = "memmap2", expect(dead_code))]
+    Owned(Vec<u8>),
+    /// The file is memory mapped, and stays mapped for as long as any of its sections are in use.
+    #[cfg(feature = "memmap2")]
+    Mapped(Arc<memmap2::Mmap>),
+}
+
+impl FileData {
+    /// Read the file at `path`, by memory mapping it if the `memmap2` feature is enabled.
+    pub(crate) fn read(path: &Path) -> Result<Self, DebugError> {
+        #[cfg(feature = "memmap2")]
+        let data = {
+            let file = std::fs::File::open(path)?;
+            // The file must not be modified while it is mapped, which is documented on
+            // `DebugInfo::from_file_with_options`.
+            let map = unsafe { memmap2::Mmap::map(&file)? };
+            Self::Mapped(Arc::new(map))
+        };
+
+        #[cfg(not(feature = "memmap2"))]
+        let data = Self::Owned(std::fs::read(path)?);
+
+        Ok(data)
+    }
+
+    /// Create the [`SectionData`] for `data`, which was read from this file.
+    ///
+    /// If the file is memory mapped, and `data` is borrowed from the mapped file, the section refers
+    /// to the mapped file instead of copying the data.
+    pub(crate) fn section_data(&self, data: Cow<'_, [u8]>) -> SectionData {
+        match (self, data) {
+            #[cfg(feature = "memmap2")]
+            (Self::Mapped(map), Cow::Borrowed(data)) => {
+                let start = data.as_ptr().addr().wrapping_sub(map.as_ptr().addr());
+                if start <= map.len() && data.len() <= map.len() - start {
+                    return SectionData(Storage::Mapped {
+                        map: map.clone(),
+                        range: start..start + data.len(),
+                    });
+                }
+
+                SectionData::from(data)
+            }
+            (_, data) => SectionData::from(&*data),
+        }
+    }
+}
+
+impl Deref for FileData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(data) => data,
+            #[cfg(feature = "memmap2")]
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{borrow::Cow, path::PathBuf};
+
+    use super::FileData;
+
+    fn get_path_for_test_files(relative_file: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests");
+        path.push(relative_file);
+        path
+    }
+
+    #[test]
+    fn copied_section_data() {
+        let file = FileData::read(&get_path_for_test_files("dwarf5/main.elf")).unwrap();
+
+        let data = file.section_data(Cow::Owned(vec![1, 2, 3]));
+        assert!(!data.is_mapped());
+        assert_eq!(&*data, &[1, 2, 3]);
+    }
+
+    #[cfg(feature = "memmap2")]
+    #[test]
+    fn mapped_section_data() {
+        let file = FileData::read(&get_path_for_test_files("dwarf5/main.elf")).unwrap();
+
+        let data = file.section_data(Cow::Borrowed(&file[0x40..0x80]));
+        assert!(data.is_mapped());
+        assert_eq!(&*data, &file[0x40..0x80]);
+
+        // Data that is not part of the mapped file is copied.
+        let data = file.section_data(Cow::Borrowed(&[1, 2, 3]));
+        assert!(!data.is_mapped());
+    }
+}
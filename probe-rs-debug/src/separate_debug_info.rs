@@ -0,0 +1,250 @@
+use std::path::{Component, Path, PathBuf};
+
+use object::Object;
+
+use crate::{DebugError, DebugInfoOptions};
+
+/// Find the separate debug file for the binary at `path`.
+///
+/// The file is located using the build ID of the binary first, and then using the `.gnu_debuglink` section.
+///
+/// Returns `None` if the binary contains debug information itself, or does not reference a separate debug file.
+pub(crate) fn find_separate_debug_file(
+    path: &Path,
+    data: &[u8],
+    options: &DebugInfoOptions,
+) -> Result<Option<PathBuf>, DebugError> {
+    let object = object::File::parse(data)?;
+
+    if object.section_by_name(".debug_info").is_some() {
+        return Ok(None);
+    }
+
+    if let Some(build_id) = object.build_id()?
+        && let Some(debug_file) = find_build_id_debug_file(build_id, &options.debug_directories)
+    {
+        return Ok(Some(debug_file));
+    }
+
+    let Some((file_name, expected_crc)) = object.gnu_debuglink()? else {
+        return Ok(None);
+    };
+    let file_name = String::from_utf8_lossy(file_name);
+
+    let candidates = debuglink_candidates(path, &file_name, &options.debug_directories);
+
+    let mut mismatch = None;
+    for candidate in candidates.iter().filter(|candidate| candidate.is_file()) {
+        // The debug link cannot refer to the binary itself.
+        if candidate == path {
+            continue;
+        }
+
+        let actual_crc = crc32(&std::fs::read(candidate)?);
+        if actual_crc == expected_crc {
+            return Ok(Some(candidate.clone()));
+        }
+
+        tracing::debug!(
+            "Ignoring debug file {}, because the CRC32 {actual_crc:#010x} does not match {expected_crc:#010x}",
+            candidate.display()
+        );
+        mismatch.get_or_insert((candidate.clone(), actual_crc));
+    }
+
+    match mismatch {
+        Some((path, actual)) => Err(DebugError::DebugLinkCrcMismatch {
+            path,
+            expected: expected_crc,
+            actual,
+        }),
+        None => Err(DebugError::DebugLinkNotFound {
+            file_name: file_name.to_string(),
+            searched: candidates,
+        }),
+    }
+}
+
+/// Find the debug file for the given build ID, using the `<debug-dir>/.build-id/xx/yyyy.debug` layout.
+fn find_build_id_debug_file(build_id: &[u8], debug_directories: &[PathBuf]) -> Option<PathBuf> {
+    let [first, rest @ ..] = build_id else {
+        return None;
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    let directory = format!("{first:02x}");
+    let file_name: String = rest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .chain(std::iter::once(".debug".to_string()))
+        .collect();
+
+    debug_directories
+        .iter()
+        .map(|debug_directory| {
+            debug_directory
+                .join(".build-id")
+                .join(&directory)
+                .join(&file_name)
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// The locations that are searched for the target of a `.gnu_debuglink` section, in order:
+/// - The directory of the binary.
+/// - The `.debug` subdirectory of the binary's directory.
+/// - In each of the global `debug_directories`, the binary's absolute directory, and the debug directory itself.
+fn debuglink_candidates(
+    path: &Path,
+    file_name: &str,
+    debug_directories: &[PathBuf],
+) -> Vec<PathBuf> {
+    let directory = path.parent().unwrap_or(Path::new(""));
+
+    let mut candidates = vec![
+        directory.join(file_name),
+        directory.join(".debug").join(file_name),
+    ];
+
+    let relative_directory = std::path::absolute(directory).ok().map(|directory| {
+        directory
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect::<PathBuf>()
+    });
+
+    for debug_directory in debug_directories {
+        if let Some(relative_directory) = &relative_directory {
+            candidates.push(debug_directory.join(relative_directory).join(file_name));
+        }
+        candidates.push(debug_directory.join(file_name));
+    }
+
+    candidates
+}
+
+/// The lookup table for [`crc32`].
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut index = 0;
+    while index < table.len() {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// The CRC32 checksum used by `.gnu_debuglink`, which is the same as the one used by zlib.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use test_case::test_case;
+
+    use super::{crc32, find_build_id_debug_file};
+    use crate::{DebugError, DebugInfo, DebugInfoOptions};
+
+    fn get_path_for_test_files(relative_file: &str) -> PathBuf {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests");
+        path.push(relative_file);
+        path
+    }
+
+    #[test_case(b"", 0; "empty")]
+    #[test_case(b"123456789", 0xCBF4_3926; "check value")]
+    fn crc32_checksum(data: &[u8], expected: u32) {
+        assert_eq!(crc32(data), expected);
+    }
+
+    #[test]
+    fn build_id_lookup() {
+        let build_id = [0x4b, 0x6a, 0x79, 0xc0];
+        let debug_directories = [get_path_for_test_files("build-id/debug")];
+
+        // The fixture uses a longer build ID, so this file does not exist.
+        assert_eq!(
+            find_build_id_debug_file(&build_id, &debug_directories),
+            None
+        );
+        assert_eq!(find_build_id_debug_file(&[0x4b], &debug_directories), None);
+    }
+
+    #[test]
+    fn debug_file_from_build_id() {
+        let debug_directory = get_path_for_test_files("build-id/debug");
+        let options = DebugInfoOptions {
+            debug_directories: vec![debug_directory.clone()],
+            ..Default::default()
+        };
+
+        let debug_info = DebugInfo::from_file_with_options(
+            get_path_for_test_files("build-id/main.elf"),
+            &options,
+        )
+        .expect("Failed to load debug info");
+
+        assert_eq!(
+            debug_info.debug_file(),
+            Some(
+                debug_directory
+                    .join(".build-id/4b/6a79c047ed7ae20ccba37d814cfd9caeead639.debug")
+                    .as_path()
+            )
+        );
+
+        let location = debug_info.get_source_location(0x40100a).unwrap();
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test]
+    fn debuglink_in_debug_subdirectory() {
+        let debug_info = DebugInfo::from_file(get_path_for_test_files("debuglink/main.elf"))
+            .expect("Failed to load debug info");
+
+        let location = debug_info.get_source_location(0x40100a).unwrap();
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test]
+    fn debuglink_missing_target() {
+        let error = DebugInfo::from_file(get_path_for_test_files("debuglink/missing.elf"))
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(error, DebugError::DebugLinkNotFound { ref file_name, .. } if file_name == "missing.debug"),
+            "Unexpected error: {error:?}"
+        );
+    }
+
+    #[test]
+    fn debuglink_crc_mismatch() {
+        let error = DebugInfo::from_file(get_path_for_test_files("debuglink/mismatch.elf"))
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(error, DebugError::DebugLinkCrcMismatch { .. }),
+            "Unexpected error: {error:?}"
+        );
+    }
+}
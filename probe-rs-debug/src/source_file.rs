@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashSet};
+
+use typed_path::TypedPathBuf;
+
+use crate::{
+    DebugInfo,
+    debug_info::{file_indices, file_md5},
+};
+
+/// A source file that is referenced by the line programs of the debug information,
+/// see [`DebugInfo::source_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    /// The path of the file, combined with the include directory and the compilation directory,
+    /// and normalized.
+    ///
+    /// The path is only relative if the compilation unit does not record its compilation directory.
+    pub path: TypedPathBuf,
+    /// The names of the compilation units which refer to the file, in the order of the units in the
+    /// debug information.
+    pub compilation_units: Vec<String>,
+    /// Whether any machine code maps to the file.
+    ///
+    /// This is `false` for files that are only referenced by declarations, such as C headers
+    /// which only contain type definitions.
+    pub has_code: bool,
+    /// The MD5 checksum of the file, if the line tables record it.
+    ///
+    /// Checksums are only available in DWARF 5, and compilers can omit them.
+    pub checksum: Option<[u8; 16]>,
+}
+
+/// Collect the source files from the line programs of all compilation units, sorted by path.
+pub(crate) fn source_files(debug_info: &DebugInfo) -> Vec<SourceFile> {
+    let mut files = BTreeMap::<Vec<u8>, SourceFile>::new();
+
+    for unit_info in &debug_info.unit_infos {
+        let unit = &unit_info.unit;
+        let Some(line_program) = &unit.line_program else {
+            // Not all compilation units need to have debug line information, so we skip those.
+            continue;
+        };
+
+        let unit_name = unit
+            .name
+            .as_ref()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .unwrap_or_default();
+
+        let mut files_with_code = HashSet::new();
+        let mut rows = line_program.clone().rows();
+        while let Ok(Some((_, row))) = rows.next_row() {
+            if !row.end_sequence() {
+                files_with_code.insert(row.file_index());
+            }
+        }
+
+        let file_indices = file_indices(line_program.header());
+
+        for file_index in file_indices {
+            // Paths which cannot be read are skipped, so they don't hide the other files.
+            let Some(path) = debug_info.get_path(unit, file_index) else {
+                continue;
+            };
+            let path = path.normalize();
+
+            let file = files
+                .entry(path.as_bytes().to_vec())
+                .or_insert_with(|| SourceFile {
+                    path,
+                    compilation_units: Vec::new(),
+                    has_code: false,
+                    checksum: None,
+                });
+
+            file.has_code |= files_with_code.contains(&file_index);
+            if file.checksum.is_none() {
+                file.checksum = file_md5(unit, file_index);
+            }
+            if file.compilation_units.last() != Some(&unit_name) {
+                file.compilation_units.push(unit_name.clone());
+            }
+        }
+    }
+
+    files.into_values().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use typed_path::TypedPathBuf;
+
+    use super::SourceFile;
+    use crate::DebugInfo;
+
+    #[test]
+    fn source_files_of_multiple_units() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/source-files/main.elf");
+        let debug_info = DebugInfo::from_file(&path).unwrap();
+
+        let source_file = |path: &str, compilation_units: &[&str], has_code| SourceFile {
+            path: TypedPathBuf::from_unix(path),
+            compilation_units: compilation_units
+                .iter()
+                .map(|unit| unit.to_string())
+                .collect(),
+            has_code,
+            checksum: None,
+        };
+
+        assert_eq!(
+            debug_info.source_files(),
+            vec![
+                source_file("/tmp/source-files/main.c", &["main.c"], true),
+                source_file("/tmp/source-files/util.c", &["util.c"], true),
+                source_file("/tmp/source-files/util.h", &["main.c", "util.c"], false),
+            ]
+        );
+    }
+}
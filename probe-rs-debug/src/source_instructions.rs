@@ -1,13 +1,17 @@
 use super::{
     ColumnType, DebugError, DebugInfo, GimliReader, canonical_path_eq,
+    debug_info::{file_indices, file_md5},
+    function_die::FunctionDie,
     unit_info::{self, UnitInfo},
 };
 use gimli::LineSequence;
 use serde::Serialize;
 use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
     fmt::{Debug, Formatter},
     num::NonZeroU64,
-    ops::Range,
+    ops::{Range, RangeInclusive},
 };
 use typed_path::{TypedPath, TypedPathBuf};
 
@@ -22,6 +26,10 @@ pub struct VerifiedBreakpoint {
     pub address: u64,
     /// If the breakpoint request was for a specific source location, then this field will contain the resolved source location.
     pub source_location: SourceLocation,
+    /// The MD5 checksum of the source file, if the line tables record it.
+    ///
+    /// If it does not match the file on disk, the source was modified after the binary was built.
+    pub checksum: Option<[u8; 16]>,
 }
 
 impl VerifiedBreakpoint {
@@ -66,109 +74,429 @@ impl VerifiedBreakpoint {
     ///    [`Vec<LineSequence>`][LineSequence].
     /// 3. Filter the [`Vec<LineSequence>`][LineSequence] entries to only include sequences that match the requested path.
     /// 3. Convert remaining [`LineSequence`], to [`InstructionSequence`].
-    /// 4. Return the first [`InstructionSequence`] that contains the requested source location.
-    ///    1. This may be an exact match on file/line/column, or,
-    ///    2. Failing an exact match, a match on file/line only.
-    ///    3. Failing that, a match on file only, where the line number is the "next" available instruction,
-    ///       on the next available line of the specified file.
+    /// 4. Collect the breakpoint locations in all the [`InstructionSequence`]s, see
+    ///    [`VerifiedBreakpoint::candidates_for_source_location`], and return the best one.
+    ///    1. This may be an exact match on file/line/column of a statement, or,
+    ///    2. Failing an exact match, the first statement on file/line only.
+    ///    3. Failing that, the first instruction on file/line, which is not marked as a statement.
+    ///    4. Failing that, if [`BreakpointOptions::next_line_window`] allows it, a match on the
+    ///       next line of the file with code.
     pub(crate) fn for_source_location(
         debug_info: &DebugInfo,
         path: TypedPath,
         line: u64,
         column: Option<u64>,
+        options: BreakpointOptions,
     ) -> Result<Self, DebugError> {
-        for program_unit in &debug_info.unit_infos {
-            let Some(ref line_program) = program_unit.unit.line_program else {
-                // Not all compilation units need to have debug line information, so we skip those.
-                continue;
-            };
-
-            let mut num_files = line_program.header().file_names().len();
+        let mut candidates =
+            Self::candidates_for_source_location(debug_info, path, line, column, options);
+        if candidates.is_empty() {
+            return Err(DebugError::Other(format!(
+                "No valid breakpoint information found for file: {}, line: {line:?}, column: {column:?}",
+                path.display()
+            )));
+        }
 
-            // For DWARF version 5, the current compilation file is included in the file names, with index 0.
-            //
-            // For earlier versions, the current compilation file is not included in the file names, but index 0 still refers to it.
-            // To get the correct number of files, we have to add 1 here.
-            if program_unit.unit.header.version() <= 4 {
-                num_files += 1;
-            }
+        // The first statement which matches the column is the best location.
+        let best = (0..candidates.len())
+            .min_by_key(|&index| {
+                let candidate = &candidates[index];
+                Reverse((candidate.is_stmt, candidate.exact_column))
+            })
+            .unwrap_or(0);
+        Ok(candidates.swap_remove(best).breakpoint)
+    }
 
-            // There can be multiple file indices which match, due to the inclusion of the current compilation file with index 0.
-            //
-            // At least for DWARF 4 there are cases where the current compilation file is also included in the file names with
-            // a non-zero index.
-            let matching_file_indices: Vec<_> = (0..num_files)
-                .filter_map(|file_index| {
-                    let file_index = file_index as u64;
-
-                    debug_info
-                        .get_path(&program_unit.unit, file_index)
-                        .and_then(|combined_path: TypedPathBuf| {
-                            if canonical_path_eq(path, combined_path.to_path()) {
-                                tracing::debug!(
-                                    "Found matching file index: {file_index} for path: {path}",
-                                    file_index = file_index,
-                                    path = path.display()
-                                );
-                                Some(file_index)
-                            } else {
-                                None
-                            }
-                        })
-                })
-                .collect();
+    /// Find all the breakpoint locations of a source location, e.g. one for each monomorphization
+    /// of a generic function, or for each copy of an inlined function, which contains the line.
+    ///
+    /// Like GDB, each function, and each inlined copy of a function, has at most one location: the
+    /// first valid halt location in it which matches the column, or, if there is none, the first one
+    /// on the line. The line program rows which are marked with `is_stmt` are preferred, and the other
+    /// rows are only used if the line has no statement, e.g. in optimized code. The locations are in
+    /// the order of the compilation units and their line programs.
+    ///
+    /// If the line has no code, the locations of the nearest following line with code within
+    /// [`BreakpointOptions::next_line_window`] are returned instead.
+    pub(crate) fn candidates_for_source_location(
+        debug_info: &DebugInfo,
+        path: TypedPath,
+        line: u64,
+        column: Option<u64>,
+        options: BreakpointOptions,
+    ) -> Vec<BreakpointCandidate> {
+        let lines = line..=line.saturating_add(options.next_line_window);
+        let mut candidates: Vec<BreakpointCandidate> = Vec::new();
 
+        for program_unit in &debug_info.unit_infos {
+            let matching_file_indices = matching_file_indices(debug_info, program_unit, path);
             if matching_file_indices.is_empty() {
                 continue;
             }
 
-            let Ok((complete_line_program, line_sequences)) = line_program.clone().sequences()
-            else {
-                tracing::debug!("Failed to get line sequences for line program");
-                continue;
-            };
-
-            for line_sequence in line_sequences {
-                let instruction_sequence = InstructionSequence::from_line_sequence(
-                    debug_info,
-                    program_unit,
-                    &complete_line_program,
-                    &line_sequence,
-                );
-
-                for matching_file_index in &matching_file_indices {
-                    // Cycle through various degrees of matching, to find the most relevant source location.
-                    if let Some(verified_breakpoint) = match_file_line_column(
-                        &instruction_sequence,
-                        *matching_file_index,
-                        line,
-                        column,
-                        debug_info,
-                        program_unit,
-                    ) {
-                        return Ok(verified_breakpoint);
+            for instruction_sequence in unit_instruction_sequences(debug_info, program_unit) {
+                for instruction_location in &instruction_sequence.instructions {
+                    let Some(instruction_line) = instruction_location.line.map(NonZeroU64::get)
+                    else {
+                        continue;
+                    };
+                    if instruction_location.instruction_type == InstructionType::Prologue
+                        || !matching_file_indices.contains(&instruction_location.file_index)
+                        || !lines.contains(&instruction_line)
+                    {
+                        continue;
+                    }
+
+                    let exact_column = instruction_line == line
+                        && column.map(ColumnType::Column) == Some(instruction_location.column);
+                    let halt_location =
+                        instruction_location.instruction_type == InstructionType::HaltLocation;
+                    let preference = (instruction_location.is_stmt, halt_location, exact_column);
+                    let function_die = program_unit
+                        .get_function_dies(debug_info, instruction_location.address)
+                        .ok()
+                        .and_then(|mut functions| functions.pop());
+                    let function = function_die.as_ref().map(|function_die| {
+                        (
+                            program_unit.unit.header.offset(),
+                            function_die.function_die.offset(),
+                        )
+                    });
+
+                    let existing = candidates.iter().position(|candidate| {
+                        candidate.breakpoint.source_location.line == Some(instruction_line)
+                            && (candidate.breakpoint.address == instruction_location.address
+                                || (function.is_some() && candidate.function == function))
+                    });
+                    if existing
+                        .is_some_and(|existing| preference <= candidates[existing].preference())
+                    {
+                        continue;
                     }
 
-                    if let Some(verified_breakpoint) = match_file_line_first_available_column(
-                        &instruction_sequence,
-                        *matching_file_index,
-                        line,
+                    let Some(source_location) = SourceLocation::from_instruction_location(
                         debug_info,
                         program_unit,
-                    ) {
-                        return Ok(verified_breakpoint);
+                        instruction_location,
+                    ) else {
+                        continue;
+                    };
+                    let candidate = BreakpointCandidate {
+                        breakpoint: VerifiedBreakpoint {
+                            address: instruction_location.address,
+                            source_location,
+                            checksum: file_md5(&program_unit.unit, instruction_location.file_index),
+                        },
+                        function_name: function_die
+                            .and_then(|function_die| function_die.function_name(debug_info)),
+                        is_stmt: instruction_location.is_stmt,
+                        halt_location,
+                        exact_column,
+                        function,
+                    };
+                    match existing {
+                        Some(existing) => candidates[existing] = candidate,
+                        None => candidates.push(candidate),
                     }
                 }
             }
         }
-        // If we get here, we have not found a valid breakpoint location.
-        Err(DebugError::Other(format!(
-            "No valid breakpoint information found for file: {}, line: {line:?}, column: {column:?}",
-            path.display()
-        )))
+
+        // Only the nearest line with code is used, which is the requested line if it has code.
+        if let Some(nearest_line) = candidates
+            .iter()
+            .filter_map(|candidate| candidate.breakpoint.source_location.line)
+            .min()
+        {
+            candidates.retain(|candidate| {
+                candidate.breakpoint.source_location.line == Some(nearest_line)
+            });
+        }
+        // The instructions which are not statements are only used if there are no others.
+        if candidates.iter().any(|candidate| candidate.halt_location) {
+            candidates.retain(|candidate| candidate.halt_location);
+        }
+        candidates
+    }
+}
+
+/// Options that control how a breakpoint location is found for a source location or a function, see
+/// [`DebugInfo::get_breakpoint_location_with_options`] and
+/// [`DebugInfo::get_function_breakpoint_locations_with_options`].
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct BreakpointOptions {
+    /// The number of lines after the requested line which are searched for code, when the requested
+    /// line has none, e.g. because it is blank, a comment, or its code was optimized away.
+    ///
+    /// The breakpoint is then set on the next line of the same file with code, and the line of its
+    /// [`SourceLocation`] is that line, so a client can move its breakpoint marker there. The default,
+    /// `0`, only uses the requested line.
+    pub next_line_window: u64,
+    /// Set the breakpoint of a function at its entry address, before its prologue, rather than at
+    /// the end of its prologue, see [`DebugInfo::get_function_breakpoint_locations_with_options`].
+    ///
+    /// The parameters and local variables of a function are generally not valid before the end of
+    /// its prologue, so this is only useful to debug the prologue itself.
+    pub raw_entry: bool,
+}
+
+impl BreakpointOptions {
+    /// Search `next_line_window` lines after the requested line for code, see
+    /// [`BreakpointOptions::next_line_window`].
+    pub fn with_next_line_window(self, next_line_window: u64) -> Self {
+        Self {
+            next_line_window,
+            ..self
+        }
+    }
+
+    /// Set the breakpoint of a function at its entry address, see [`BreakpointOptions::raw_entry`].
+    pub fn with_raw_entry(self, raw_entry: bool) -> Self {
+        Self { raw_entry, ..self }
     }
 }
 
+/// A breakpoint location of [`VerifiedBreakpoint::candidates_for_source_location`].
+pub(crate) struct BreakpointCandidate {
+    pub(crate) breakpoint: VerifiedBreakpoint,
+    /// The name of the innermost function which contains the address.
+    pub(crate) function_name: Option<String>,
+    pub(crate) is_stmt: bool,
+    /// The instruction is a valid halt location, i.e. a statement, or the start of an epilogue.
+    halt_location: bool,
+    /// The column of the instruction is the requested column.
+    exact_column: bool,
+    /// The unit and the DIE of the innermost function which contains the address.
+    function: Option<(gimli::UnitSectionOffset, gimli::UnitOffset)>,
+}
+
+impl BreakpointCandidate {
+    /// The breakpoint location at the end of the prologue of a function, named `function_name`, or at
+    /// its entry with [`BreakpointOptions::raw_entry`].
+    ///
+    /// The end of the prologue is the first row of the line program in the function which is marked
+    /// with `prologue_end`. Without such a row, it is the first statement on a different line than the
+    /// entry, like GDB does, and the entry if there is none.
+    ///
+    /// Returns `None` if the function has no line information.
+    pub(crate) fn for_function(
+        debug_info: &DebugInfo,
+        function: &FunctionDie,
+        function_name: String,
+        options: BreakpointOptions,
+    ) -> Option<Self> {
+        let entry = function.entry_pc()?;
+        // The prologue is in the range of the function which contains its entry.
+        let entry_range = function
+            .ranges
+            .iter()
+            .find(|range| range.contains(&entry))?;
+        let instruction_sequence = InstructionSequence::from_address(debug_info, entry).ok()?;
+        let mut instructions =
+            instruction_sequence
+                .instructions
+                .iter()
+                .filter(|instruction_location| {
+                    instruction_location.address >= entry
+                        && entry_range.contains(&instruction_location.address)
+                });
+        let entry_location = instructions.clone().next()?;
+
+        let instruction_location = if options.raw_entry {
+            entry_location
+        } else if let Some(prologue_end) = instructions
+            .clone()
+            .find(|instruction_location| instruction_location.prologue_end)
+        {
+            prologue_end
+        } else {
+            instructions
+                .find(|instruction_location| {
+                    instruction_location.is_stmt
+                        && instruction_location.line.is_some()
+                        && instruction_location.line != entry_location.line
+                })
+                .unwrap_or(entry_location)
+        };
+
+        let program_unit = instruction_sequence.program_unit;
+        let source_location = SourceLocation::from_instruction_location(
+            debug_info,
+            program_unit,
+            instruction_location,
+        )?;
+        Some(BreakpointCandidate {
+            breakpoint: VerifiedBreakpoint {
+                address: instruction_location.address,
+                source_location,
+                checksum: file_md5(&program_unit.unit, instruction_location.file_index),
+            },
+            function_name: Some(function_name),
+            is_stmt: instruction_location.is_stmt,
+            halt_location: instruction_location.instruction_type == InstructionType::HaltLocation,
+            exact_column: false,
+            function: Some((
+                function.unit_info.unit.header.offset(),
+                function.function_die.offset(),
+            )),
+        })
+    }
+
+    /// The candidate with the greater preference is used, if a function has several candidates.
+    fn preference(&self) -> (bool, bool, bool) {
+        (self.is_stmt, self.halt_location, self.exact_column)
+    }
+
+    /// The public [`BreakpointLocation`], with the runtime address.
+    pub(crate) fn into_location(self, debug_info: &DebugInfo) -> BreakpointLocation {
+        BreakpointLocation {
+            address: debug_info.runtime_address(self.breakpoint.address),
+            line: self.breakpoint.source_location.line,
+            column: self.breakpoint.source_location.column,
+            function_name: self.function_name,
+            is_stmt: self.is_stmt,
+        }
+    }
+}
+
+/// A location where a breakpoint can be set, see [`DebugInfo::get_breakpoint_locations`] and
+/// [`DebugInfo::get_function_breakpoint_locations`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BreakpointLocation {
+    /// The address in target memory, where the breakpoint can be set.
+    pub address: u64,
+    /// The line of the instruction at the address.
+    pub line: Option<u64>,
+    /// The column of the instruction at the address.
+    pub column: Option<ColumnType>,
+    /// The name of the function which contains the address.
+    ///
+    /// If the address is inside an inlined function, this is the name of the innermost inlined function.
+    pub function_name: Option<String>,
+    /// Indicate if the line program marks the instruction as the start of a statement.
+    ///
+    /// Otherwise, the instruction is the start of the epilogue of a function, or the line has no
+    /// statement and this is its first instruction.
+    pub is_stmt: bool,
+}
+
+/// Find the indices of the files in the line program of a unit which match `path`.
+///
+/// There can be multiple file indices which match, due to the inclusion of the current compilation file with index 0.
+///
+/// At least for DWARF 4 there are cases where the current compilation file is also included in the file names with
+/// a non-zero index.
+fn matching_file_indices(
+    debug_info: &DebugInfo,
+    program_unit: &UnitInfo,
+    path: TypedPath,
+) -> Vec<u64> {
+    let Some(ref line_program) = program_unit.unit.line_program else {
+        // Not all compilation units need to have debug line information, so we skip those.
+        return vec![];
+    };
+
+    file_indices(line_program.header())
+        .filter_map(|file_index| {
+            debug_info
+                .get_path(&program_unit.unit, file_index)
+                .and_then(|combined_path: TypedPathBuf| {
+                    if canonical_path_eq(path, combined_path.to_path()) {
+                        tracing::debug!(
+                            "Found matching file index: {file_index} for path: {path}",
+                            file_index = file_index,
+                            path = path.display()
+                        );
+                        Some(file_index)
+                    } else {
+                        None
+                    }
+                })
+        })
+        .collect()
+}
+
+/// Convert all the [`LineSequence`]s of the line program of a unit to [`InstructionSequence`]s.
+fn unit_instruction_sequences<'debug_info>(
+    debug_info: &'debug_info DebugInfo,
+    program_unit: &'debug_info UnitInfo,
+) -> Vec<InstructionSequence<'debug_info>> {
+    let Some(ref line_program) = program_unit.unit.line_program else {
+        return vec![];
+    };
+    let Ok((complete_line_program, line_sequences)) = line_program.clone().sequences() else {
+        tracing::debug!("Failed to get line sequences for line program");
+        return vec![];
+    };
+
+    line_sequences
+        .iter()
+        .map(|line_sequence| {
+            InstructionSequence::from_line_sequence(
+                debug_info,
+                program_unit,
+                &complete_line_program,
+                line_sequence,
+            )
+        })
+        .collect()
+}
+
+/// Find the lines of a source file in `lines` where a breakpoint can be set, and their columns, see
+/// [`DebugInfo::breakpoint_locations_in_range`].
+pub(crate) fn breakpoint_locations_in_range(
+    debug_info: &DebugInfo,
+    path: TypedPath,
+    lines: RangeInclusive<u64>,
+) -> Vec<(u64, Vec<ColumnType>)> {
+    // The columns of each line, and whether they are valid halt locations.
+    let mut locations: BTreeMap<u64, Vec<(ColumnType, bool)>> = BTreeMap::new();
+
+    for program_unit in &debug_info.unit_infos {
+        let matching_file_indices = matching_file_indices(debug_info, program_unit, path);
+        if matching_file_indices.is_empty() {
+            continue;
+        }
+
+        for instruction_sequence in unit_instruction_sequences(debug_info, program_unit) {
+            for instruction_location in &instruction_sequence.instructions {
+                let Some(line) = instruction_location.line.map(NonZeroU64::get) else {
+                    continue;
+                };
+                if instruction_location.instruction_type == InstructionType::Prologue
+                    || !matching_file_indices.contains(&instruction_location.file_index)
+                    || !lines.contains(&line)
+                {
+                    continue;
+                }
+
+                let halt_location =
+                    instruction_location.instruction_type == InstructionType::HaltLocation;
+                locations
+                    .entry(line)
+                    .or_default()
+                    .push((instruction_location.column, halt_location));
+            }
+        }
+    }
+
+    locations
+        .into_iter()
+        .map(|(line, mut columns)| {
+            // Like for a breakpoint on a line, the instructions which are not statements are only
+            // used if there are no others.
+            if columns.iter().any(|(_, halt_location)| *halt_location) {
+                columns.retain(|(_, halt_location)| *halt_location);
+            }
+            let mut columns: Vec<_> = columns.into_iter().map(|(column, _)| column).collect();
+            columns.sort();
+            columns.dedup();
+            (line, columns)
+        })
+        .collect()
+}
+
 /// Find the valid halt instruction location that is equal to, or greater than, the address.
 fn match_address(
     instruction_sequence: &InstructionSequence<'_>,
@@ -194,70 +522,16 @@ fn match_address(
         Some(VerifiedBreakpoint {
             address: instruction_location.address,
             source_location,
+            checksum: file_md5(
+                &instruction_sequence.program_unit.unit,
+                instruction_location.file_index,
+            ),
         })
     } else {
         None
     }
 }
 
-/// Find the valid halt instruction location that matches the file, line and column.
-fn match_file_line_column(
-    instruction_sequence: &InstructionSequence<'_>,
-    matching_file_index: u64,
-    line: u64,
-    column: Option<u64>,
-    debug_info: &DebugInfo,
-    program_unit: &UnitInfo,
-) -> Option<VerifiedBreakpoint> {
-    let instruction_location =
-        instruction_sequence
-            .instructions
-            .iter()
-            .find(|instruction_location| {
-                instruction_location.instruction_type == InstructionType::HaltLocation
-                    && matching_file_index == instruction_location.file_index
-                    && NonZeroU64::new(line) == instruction_location.line
-                    && column
-                        .map(ColumnType::Column)
-                        .is_some_and(|col| col == instruction_location.column)
-            })?;
-
-    let source_location =
-        SourceLocation::from_instruction_location(debug_info, program_unit, instruction_location)?;
-
-    Some(VerifiedBreakpoint {
-        address: instruction_location.address,
-        source_location,
-    })
-}
-
-/// Find the first valid halt instruction location that matches the file and line, ignoring column.
-fn match_file_line_first_available_column(
-    instruction_sequence: &InstructionSequence<'_>,
-    matching_file_index: u64,
-    line: u64,
-    debug_info: &DebugInfo,
-    program_unit: &UnitInfo,
-) -> Option<VerifiedBreakpoint> {
-    let instruction_location =
-        instruction_sequence
-            .instructions
-            .iter()
-            .find(|instruction_location| {
-                instruction_location.instruction_type == InstructionType::HaltLocation
-                    && matching_file_index == instruction_location.file_index
-                    && NonZeroU64::new(line) == instruction_location.line
-            })?;
-
-    let source_location =
-        SourceLocation::from_instruction_location(debug_info, program_unit, instruction_location)?;
-
-    Some(VerifiedBreakpoint {
-        address: instruction_location.address,
-        source_location,
-    })
-}
-
 fn serialize_typed_path<S>(path: &TypedPathBuf, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
@@ -278,6 +552,27 @@ pub struct SourceLocation {
     pub column: Option<ColumnType>,
     /// The address of the source location.
     pub address: Option<u64>,
+    /// The address is in code without a line, e.g. code generated by the compiler, which the line table marks
+    /// with line 0. The line is taken from the nearest preceding row that has one.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub line_fallback: bool,
+}
+
+/// A [`SourceLocation`] for an address, with the details of the line table row that describes the address,
+/// see [`DebugInfo::resolve_source_location`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedSourceLocation {
+    /// The source location of the address.
+    pub location: SourceLocation,
+    /// The addresses described by the line table row, from the address of the row up to the address of
+    /// the next row.
+    ///
+    /// This is the code of the current statement, e.g. to highlight it in an editor.
+    pub address_range: Option<Range<u64>>,
+    /// The row is a recommended breakpoint location.
+    pub is_stmt: bool,
+    /// The row marks the end of a sequence of instructions.
+    pub end_sequence: bool,
 }
 
 impl Debug for SourceLocation {
@@ -306,6 +601,7 @@ impl SourceLocation {
                 column: Some(instruction_location.column),
                 path,
                 address: Some(instruction_location.address),
+                line_fallback: false,
             })
     }
 
@@ -467,45 +763,33 @@ impl<'debug_info> InstructionSequence<'debug_info> {
                 break;
             }
 
-            instruction_sequence.add(prologue_completed, row, previous_row.as_ref());
+            instruction_sequence.add(prologue_completed, row);
             previous_row = Some(*row);
         }
         instruction_sequence
     }
 
     /// Add a instruction location to the list.
-    fn add(
-        &mut self,
-        prologue_completed: bool,
-        row: &gimli::LineRow,
-        previous_row: Option<&gimli::LineRow>,
-    ) {
-        // Workaround the line number issue (if recorded as 0 in the DWARF, then gimli reports it as None).
-        // For debug purposes, it makes more sense to be the same as the previous line, which almost always
-        // has the same file index and column value.
-        // This prevents the debugger from jumping to the top of the file unexpectedly.
-        let mut instruction_line = row.line();
-        if let Some(prev_row) = previous_row
-            && row.line().is_none()
-            && prev_row.line().is_some()
-            && row.file_index() == prev_row.file_index()
-            && prev_row.column() == row.column()
-        {
-            instruction_line = prev_row.line();
-        }
-
+    fn add(&mut self, prologue_completed: bool, row: &gimli::LineRow) {
         let instruction_location = InstructionLocation {
             address: row.address(),
             file_index: row.file_index(),
-            line: instruction_line,
+            line: row.line(),
             column: row.column().into(),
             instruction_type: if !prologue_completed {
                 InstructionType::Prologue
+            } else if row.line().is_none() {
+                // Code without a line (recorded as 0 in the DWARF, which gimli reports as None) is generated by
+                // the compiler, and is not a useful halt location.
+                // This prevents the debugger from jumping to the top of the file unexpectedly.
+                InstructionType::Unspecified
             } else if row.epilogue_begin() || row.is_stmt() {
                 InstructionType::HaltLocation
             } else {
                 InstructionType::Unspecified
             },
+            is_stmt: row.is_stmt(),
+            prologue_end: row.prologue_end(),
         };
 
         self.instructions.push(instruction_location);
@@ -546,6 +830,10 @@ struct InstructionLocation {
     line: Option<NonZeroU64>,
     column: ColumnType,
     instruction_type: InstructionType,
+    /// The line program marks the instruction as the start of a statement.
+    is_stmt: bool,
+    /// The line program marks the instruction as the first one after the prologue of its function.
+    prologue_end: bool,
 }
 
 impl Debug for InstructionLocation {
@@ -0,0 +1,189 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+use gimli::RunTimeEndian;
+use object::Object;
+
+use crate::{
+    DebugError,
+    debug_info::{DwarfReader, GimliReader, uncompressed_section_data},
+    section_data::SectionData,
+};
+
+/// Locates and loads the split DWARF units that are referenced by skeleton units.
+///
+/// Binaries built with `-gsplit-dwarf` only contain skeleton units, with the address ranges and the
+/// line table. The remaining debug information is stored in a `.dwo` file per compilation unit, or
+/// in a single `.dwp` package.
+pub(crate) struct SplitDwarfLoader {
+    /// Directories that are searched for `.dwo` files, after the compilation directory of the unit.
+    search_paths: Vec<PathBuf>,
+    /// The `.dwp` package, if one was found.
+    package: Option<gimli::DwarfPackage<DwarfReader>>,
+}
+
+impl SplitDwarfLoader {
+    /// Create a new loader, which searches for `.dwo` files in the given `search_paths`.
+    ///
+    /// If `package_path` points to an existing file, it is loaded as a `.dwp` package, and used
+    /// in preference to individual `.dwo` files.
+    pub(crate) fn new(search_paths: Vec<PathBuf>, package_path: Option<&Path>) -> Self {
+        let package =
+            package_path
+                .filter(|path| path.is_file())
+                .and_then(|path| match load_package(path) {
+                    Ok(package) => Some(package),
+                    Err(error) => {
+                        tracing::warn!(
+                            "Unable to load split DWARF package {}: {error:?}",
+                            path.display()
+                        );
+                        None
+                    }
+                });
+
+        Self {
+            search_paths,
+            package,
+        }
+    }
+
+    /// Load the split unit that belongs to the given `skeleton` unit, together with the DWARF
+    /// sections it refers to.
+    pub(crate) fn load(
+        &self,
+        parent: &gimli::Dwarf<DwarfReader>,
+        skeleton: &gimli::Unit<GimliReader>,
+    ) -> Result<(gimli::Unit<GimliReader>, gimli::Dwarf<DwarfReader>), DebugError> {
+        let Some(dwo_id) = skeleton.dwo_id else {
+            return Err(DebugError::Other(
+                "The unit is not a skeleton unit, and has no split DWARF unit.".to_string(),
+            ));
+        };
+
+        let packaged_dwarf = match &self.package {
+            Some(package) => package.find_cu(dwo_id, parent)?,
+            None => None,
+        };
+
+        let dwarf = match packaged_dwarf {
+            Some(dwarf) => dwarf,
+            None => {
+                let path = self.find_dwo_file(parent, skeleton)?;
+                let data = std::fs::read(&path)?;
+                let object = object::File::parse(&*data)?;
+
+                let mut dwarf = gimli::Dwarf::load(dwo_section_loader(&object))?;
+                dwarf.make_dwo(parent);
+                dwarf
+            }
+        };
+
+        let mut units = dwarf.units();
+        while let Some(header) = units.next()? {
+            let mut unit = dwarf.unit(header)?;
+            if unit.dwo_id != Some(dwo_id) {
+                continue;
+            }
+
+            unit.copy_relocated_attributes(skeleton);
+
+            // The line table with the address information is only available in the skeleton unit.
+            // The file indices used by the split unit refer to the same table.
+            if skeleton.line_program.is_some() {
+                unit.line_program = skeleton.line_program.clone();
+            }
+            if unit.comp_dir.is_none() {
+                unit.comp_dir = skeleton.comp_dir.clone();
+            }
+
+            return Ok((unit, dwarf));
+        }
+
+        Err(DebugError::Other(format!(
+            "The split DWARF unit with id {:#018x} was not found.",
+            dwo_id.0
+        )))
+    }
+
+    /// Find the `.dwo` file for the given skeleton unit.
+    fn find_dwo_file(
+        &self,
+        parent: &gimli::Dwarf<DwarfReader>,
+        skeleton: &gimli::Unit<GimliReader>,
+    ) -> Result<PathBuf, DebugError> {
+        let Some(dwo_name) = skeleton.dwo_name()? else {
+            return Err(DebugError::Other(
+                "The skeleton unit does not specify a split DWARF file name.".to_string(),
+            ));
+        };
+        let dwo_name = parent.attr_string(skeleton, dwo_name)?;
+        let dwo_name = String::from_utf8_lossy(&dwo_name);
+        let dwo_name = Path::new(&*dwo_name);
+
+        let mut candidates = Vec::new();
+
+        // The file name is relative to the compilation directory.
+        if dwo_name.is_absolute() {
+            candidates.push(dwo_name.to_path_buf());
+        } else if let Some(comp_dir) = skeleton.comp_dir.as_ref() {
+            candidates.push(Path::new(&*String::from_utf8_lossy(comp_dir)).join(dwo_name));
+        }
+
+        for search_path in &self.search_paths {
+            if dwo_name.is_relative() {
+                candidates.push(search_path.join(dwo_name));
+            }
+            if let Some(file_name) = dwo_name.file_name() {
+                candidates.push(search_path.join(file_name));
+            }
+        }
+
+        candidates
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+            .ok_or_else(|| {
+                DebugError::Other(format!(
+                    "Unable to find the split DWARF file {}.",
+                    dwo_name.display()
+                ))
+            })
+    }
+}
+
+/// Load a `.dwp` package from the given path.
+fn load_package(path: &Path) -> Result<gimli::DwarfPackage<DwarfReader>, DebugError> {
+    let data = std::fs::read(path)?;
+    let object = object::File::parse(&*data)?;
+
+    let empty = DwarfReader::new(SectionData::from(&[][..]), endianness(&object));
+    let package = gimli::DwarfPackage::load(dwo_section_loader(&object), empty)?;
+
+    Ok(package)
+}
+
+/// Returns a loader for the `.dwo` variants of the DWARF sections in the given object file.
+fn dwo_section_loader<'a>(
+    object: &'a object::File<'_>,
+) -> impl Fn(gimli::SectionId) -> Result<DwarfReader, DebugError> + 'a {
+    let endianness = endianness(object);
+
+    move |id| {
+        let data = match id.dwo_name().and_then(|name| object.section_by_name(name)) {
+            Some(section) => uncompressed_section_data(&section)?,
+            None => Cow::Borrowed(&[][..]),
+        };
+
+        Ok(DwarfReader::new(SectionData::from(&*data), endianness))
+    }
+}
+
+fn endianness(object: &object::File<'_>) -> RunTimeEndian {
+    if object.is_little_endian() {
+        RunTimeEndian::Little
+    } else {
+        RunTimeEndian::Big
+    }
+}
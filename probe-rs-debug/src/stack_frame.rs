@@ -18,6 +18,47 @@ pub struct StackFrameInfo<'a> {
 
     /// The value of the stack pointer just before the CALL instruction in the parent function.
     pub canonical_frame_address: Option<u64>,
+
+    /// How many levels of pointers are followed automatically while resolving variables.
+    ///
+    /// When `None`, the value a pointer points to is resolved immediately, and structured values
+    /// are deferred. With `Some(depth)`, structured values are resolved immediately, and up to
+    /// `depth` levels of pointers are followed. The remaining pointers are deferred, until their
+    /// value is requested with
+    /// [`DebugInfo::cache_deferred_variables`](crate::DebugInfo::cache_deferred_variables).
+    pub auto_deref_depth: Option<usize>,
+}
+
+impl StackFrameInfo<'_> {
+    /// The frame info to resolve the value a pointer points to, with one level less to follow.
+    pub(crate) fn follow_pointer(self) -> Self {
+        Self {
+            auto_deref_depth: self.auto_deref_depth.map(|depth| depth.saturating_sub(1)),
+            ..self
+        }
+    }
+}
+
+/// Describes what kind of code a [`StackFrame`] represents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum FrameKind {
+    /// A regular (possibly inlined) function.
+    #[default]
+    Function,
+    /// A linker generated veneer (long branch thunk), or a similar trampoline without debug information.
+    /// These frames do not belong to the logical call stack of the program.
+    Trampoline,
+    /// A synthetic frame, inserted where the unwind crosses an exception boundary.
+    /// The registers of this frame are the values that the processor stored on exception entry,
+    /// and the frame does not represent a function that can be stepped through.
+    ExceptionEntry,
+}
+
+impl FrameKind {
+    /// Returns `true` if the frame belongs to a regular function.
+    pub fn is_function(&self) -> bool {
+        matches!(self, FrameKind::Function)
+    }
 }
 
 /// A full stack frame with all its information contained.
@@ -28,6 +69,11 @@ pub struct StackFrame {
     pub id: ObjectRef,
     /// The name of the function this stackframe belongs to.
     pub function_name: String,
+    /// The mangled symbol name of the function, if it is known.
+    ///
+    /// The `function_name` contains the demangled name, or the raw symbol name if it could not be demangled.
+    #[serde(skip_serializing)]
+    pub linkage_name: Option<String>,
     /// The source location the function this stackframe belongs to originates.
     pub source_location: Option<SourceLocation>,
     /// The current register state represented in this stackframe.
@@ -41,6 +87,9 @@ pub struct StackFrame {
     pub frame_base: Option<u64>,
     /// Indicate if this stack frame belongs to an inlined function.
     pub is_inlined: bool,
+    /// The kind of code this stack frame belongs to.
+    #[serde(skip_serializing_if = "FrameKind::is_function")]
+    pub kind: FrameKind,
     /// A cache of 'local' scoped variables for this stackframe, with a `Variable` for each in-scope variable.
     /// - Complex variables and pointers will have additional children.
     ///   - This structure is recursive until a base type is encountered.
@@ -49,6 +98,54 @@ pub struct StackFrame {
     pub canonical_frame_address: Option<u64>,
 }
 
+/// A function that contains an address, as returned by [`DebugInfo::frames_at_address`].
+///
+/// Unlike a [`StackFrame`], this is determined from the debug information only, without reading
+/// the registers or memory of the target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InlineFrame {
+    /// The name of the function, if it is known.
+    pub function_name: Option<String>,
+    /// The source location in this function.
+    ///
+    /// For the innermost function, this is the location of the address. For the other functions, it
+    /// is the call site of the function that is inlined into it.
+    pub source_location: Option<SourceLocation>,
+    /// Indicate if this function is inlined into the next frame.
+    pub is_inlined: bool,
+}
+
+impl StackFrame {
+    /// The name of the function with the values of its parameters, in the order of their
+    /// declaration, e.g. `transfer(buf=0x20000010, len=32)`.
+    ///
+    /// The parameters are read from the [`StackFrame::local_variables`], so they are only included
+    /// after these are resolved with [`DebugInfo::cache_deferred_variables`]. Until then, this is
+    /// only the name of the function.
+    pub fn signature(&self) -> String {
+        let Some(local_variables) = &self.local_variables else {
+            return self.function_name.clone();
+        };
+        let root = local_variables.root_variable();
+        if !local_variables.has_children(root) {
+            return self.function_name.clone();
+        }
+
+        let parameters = local_variables
+            .get_children(root.variable_key())
+            .filter(|variable| variable.is_parameter)
+            .map(|parameter| {
+                format!(
+                    "{}={}",
+                    parameter.name,
+                    parameter.to_string(local_variables)
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("{}({})", self.function_name, parameters.join(", "))
+    }
+}
+
 impl std::fmt::Display for StackFrame {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         // Header info for the StackFrame
@@ -0,0 +1,592 @@
+use std::collections::HashSet;
+
+use gimli::{AttributeValue, EvaluationResult, Location, UnitOffset};
+use probe_rs::MemoryInterface;
+
+use crate::{
+    DebugError, DebugInfo, DebugRegisters, SourceLocation, Variable, VariableCache,
+    debug_info::GimliReader,
+    extract_byte_size,
+    function_die::Die,
+    stack_frame::StackFrameInfo,
+    unit_info::{UnitInfo, extract_name},
+    variable::Modifier,
+};
+
+/// The largest number of typedefs, type modifiers and array element types that are followed to find
+/// the name or size of a type. This also stops malformed type references which form a cycle.
+const MAX_TYPE_DEPTH: usize = 16;
+
+/// The number of levels of children that are read with a static variable. This stops the expansion of
+/// self-referential types, like linked lists.
+const MAX_CHILDREN_DEPTH: usize = 10;
+
+/// A static variable from the debug information, see [`DebugInfo::static_variables`].
+///
+/// This is determined from the debug information only, without reading the memory of the target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticVariableInfo {
+    /// The name of the variable, prefixed with the names of the namespaces that contain it,
+    /// e.g. `my_crate::state::COUNTER`.
+    pub name: String,
+    /// The `DW_AT_linkage_name` of the variable, which is the symbol name, if it is known.
+    pub linkage_name: Option<String>,
+    /// The address of the variable, including the [load bias](DebugInfo::set_load_bias).
+    ///
+    /// This is `None` if the location of the variable is not a fixed address, e.g. if the variable was
+    /// optimized out, or is thread-local.
+    pub address: Option<u64>,
+    /// The name of the type of the variable, if it is known.
+    pub type_name: Option<String>,
+    /// The size of the variable in bytes, if it is known.
+    pub byte_size: Option<u64>,
+    /// The location where the variable is declared.
+    pub source_location: Option<SourceLocation>,
+}
+
+/// Collect the static variables at namespace scope of all compilation units.
+///
+/// Variables with the same name and address are only listed once.
+pub(crate) fn static_variables(
+    debug_info: &DebugInfo,
+) -> Result<Vec<StaticVariableInfo>, DebugError> {
+    let mut variables = Vec::new();
+    let mut seen = HashSet::new();
+
+    visit_static_variables(debug_info, |unit_info, entry| {
+        let variable = static_variable_info(debug_info, unit_info, entry);
+
+        // A variable that is used by more than one unit can have a DIE in each of them.
+        if let Some(address) = variable.address
+            && !seen.insert((variable.name.clone(), address))
+        {
+            return;
+        }
+        variables.push(variable);
+    })?;
+
+    Ok(variables)
+}
+
+/// Read the static variable `name` and its children into `cache`, see [`DebugInfo::read_static_variable`].
+pub(crate) fn read_static_variable(
+    debug_info: &DebugInfo,
+    memory: &mut dyn MemoryInterface,
+    name: &str,
+    cache: &mut VariableCache,
+) -> Result<Variable, DebugError> {
+    let (unit_info, offset) = find_static_variable(debug_info, name)?;
+    let entry = unit_info.unit.entry(offset)?;
+
+    // Static variables are at fixed addresses, so they don't need the registers of a stack frame.
+    let registers = DebugRegisters::default();
+    let frame_info = StackFrameInfo {
+        registers: &registers,
+        frame_base: None,
+        canonical_frame_address: None,
+        auto_deref_depth: None,
+    };
+
+    let mut parent_variable = cache.root_variable().clone();
+    let mut variable = cache.create_variable(parent_variable.variable_key(), Some(unit_info))?;
+    unit_info.process_tree_node_attributes(
+        debug_info,
+        &entry,
+        &mut parent_variable,
+        &mut variable,
+        memory,
+        cache,
+        frame_info,
+    )?;
+
+    cache.recurse_deferred_variables_internal(
+        debug_info,
+        memory,
+        &mut variable,
+        MAX_CHILDREN_DEPTH,
+        0,
+        frame_info,
+    );
+
+    cache
+        .get_variable_by_key(variable.variable_key())
+        .ok_or_else(|| {
+            DebugError::Other(format!(
+                "The static variable {name} was not added to the cache."
+            ))
+        })
+}
+
+/// Find the definition of the static variable `name`.
+///
+/// A qualified name that matches exactly is preferred. Otherwise the name can leave out the outer namespaces,
+/// if it matches only one variable.
+fn find_static_variable<'debug_info>(
+    debug_info: &'debug_info DebugInfo,
+    name: &str,
+) -> Result<(&'debug_info UnitInfo, UnitOffset), DebugError> {
+    let suffix = format!("::{name}");
+
+    let mut exact_match = None;
+    let mut suffix_matches = Vec::new();
+    visit_static_variables(debug_info, |unit_info, entry| {
+        if exact_match.is_some() {
+            return;
+        }
+        let Some(qualified_name) = qualified_name(debug_info, unit_info, entry) else {
+            return;
+        };
+        if qualified_name == name {
+            exact_match = Some((unit_info, entry.offset()));
+        } else if qualified_name.ends_with(&suffix) {
+            suffix_matches.push((unit_info, entry.offset(), qualified_name));
+        }
+    })?;
+
+    if let Some(exact_match) = exact_match {
+        return Ok(exact_match);
+    }
+
+    match suffix_matches.as_slice() {
+        [(unit_info, offset, _)] => Ok((*unit_info, *offset)),
+        [] => Err(DebugError::Other(format!(
+            "The static variable {name} was not found."
+        ))),
+        matches => Err(DebugError::Other(format!(
+            "The static variable name {name} is ambiguous, it matches {}.",
+            matches
+                .iter()
+                .map(|(_, _, qualified_name)| qualified_name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Call `visit` with the DIE of each static variable at namespace scope of all compilation units.
+///
+/// Declarations are skipped, so a variable is only visited for its definition.
+pub(crate) fn visit_static_variables<'debug_info>(
+    debug_info: &'debug_info DebugInfo,
+    mut visit: impl FnMut(&'debug_info UnitInfo, &Die),
+) -> Result<(), DebugError> {
+    for unit_info in &debug_info.unit_infos {
+        let mut tree = unit_info.unit.entries_tree(None)?;
+        visit_namespace(unit_info, tree.root()?, &mut visit)?;
+    }
+
+    Ok(())
+}
+
+/// Visit the static variables in the compilation unit or namespace `node`, and in its nested namespaces.
+fn visit_namespace<'debug_info>(
+    unit_info: &'debug_info UnitInfo,
+    node: gimli::EntriesTreeNode<GimliReader>,
+    visit: &mut impl FnMut(&'debug_info UnitInfo, &Die),
+) -> Result<(), DebugError> {
+    let mut children = node.children();
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        match entry.tag() {
+            gimli::DW_TAG_namespace => visit_namespace(unit_info, child, visit)?,
+            gimli::DW_TAG_variable => {
+                // In C and C++, there can be a declaration as well as the definition of a variable.
+                if let Some(AttributeValue::Flag(true)) = entry.attr_value(gimli::DW_AT_declaration)
+                {
+                    continue;
+                }
+                visit(unit_info, entry);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The name of the static variable `entry`, prefixed with the names of the namespaces and classes that
+/// contain it, like `Box::count` for a static member of a C++ class.
+///
+/// C++ compilers put the definition of a variable in a namespace or class at the top level of the unit, and
+/// refer to the declaration in the namespace or class with `DW_AT_specification`, so the namespaces are
+/// taken from the declaration. Anonymous namespaces are not part of the name, as for [functions](crate::function_die::FunctionDie::qualified_name).
+pub(crate) fn qualified_name(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+) -> Option<String> {
+    let mut name = extract_name(debug_info, unit_info, entry).ok()??;
+
+    // The declaration is the last DIE in the chain, and can be in another unit.
+    let declaration = debug_info.resolve_origin_chain(entry, unit_info).pop();
+    let (unit_info, declaration_offset) = declaration
+        .as_ref()
+        .map_or((unit_info, entry.offset()), |(unit_info, declaration)| {
+            (*unit_info, declaration.offset())
+        });
+
+    let mut parent_offset = unit_info.parent_offset(declaration_offset);
+    while let Some(offset) = parent_offset {
+        let Ok(parent) = unit_info.unit.entry(offset) else {
+            break;
+        };
+        if matches!(
+            parent.tag(),
+            gimli::DW_TAG_namespace
+                | gimli::DW_TAG_class_type
+                | gimli::DW_TAG_structure_type
+                | gimli::DW_TAG_union_type
+        ) && let Ok(Some(namespace)) = extract_name(debug_info, unit_info, &parent)
+        {
+            name = format!("{namespace}::{name}");
+        }
+        parent_offset = unit_info.parent_offset(offset);
+    }
+
+    Some(name)
+}
+
+/// Describe the static variable `entry`, without reading the memory of the target.
+///
+/// Variables without a name get an empty name.
+fn static_variable_info(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+) -> StaticVariableInfo {
+    let linkage_name = debug_info
+        .resolve_attribute(entry, unit_info, gimli::DW_AT_linkage_name)
+        .and_then(|(unit_info, attr)| {
+            unit_info
+                .dwarf(debug_info)
+                .attr_string(&unit_info.unit, attr.value())
+                .ok()
+        })
+        .map(|linkage_name| String::from_utf8_lossy(&linkage_name).into_owned());
+
+    let type_die = debug_info
+        .resolve_attribute(entry, unit_info, gimli::DW_AT_type)
+        .and_then(|(unit_info, attr)| {
+            debug_info
+                .resolve_die_reference_with_unit(&attr, unit_info)
+                .ok()
+        });
+    let (type_name, byte_size) = match &type_die {
+        Some((type_unit_info, type_die)) => (
+            type_name(debug_info, type_unit_info, type_die, 0),
+            type_byte_size(debug_info, type_unit_info, type_die, 0),
+        ),
+        None => (None, None),
+    };
+
+    StaticVariableInfo {
+        name: qualified_name(debug_info, unit_info, entry).unwrap_or_default(),
+        linkage_name,
+        address: static_address(debug_info, unit_info, entry),
+        type_name,
+        byte_size,
+        source_location: unit_info
+            .extract_source_location(debug_info, entry)
+            .ok()
+            .flatten(),
+    }
+}
+
+/// The address of a static variable, if its location is a fixed address which can be determined
+/// without the registers or memory of the target, e.g. `DW_OP_addr`.
+pub(crate) fn static_address(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+) -> Option<u64> {
+    let Some(AttributeValue::Exprloc(expression)) = entry.attr_value(gimli::DW_AT_location) else {
+        return None;
+    };
+
+    let mut evaluation = expression.evaluation(unit_info.unit.encoding());
+    let mut result = evaluation.evaluate().ok()?;
+    loop {
+        result = match result {
+            EvaluationResult::Complete => break,
+            EvaluationResult::RequiresRelocatedAddress(address) => {
+                evaluation.resume_with_relocated_address(address).ok()?
+            }
+            EvaluationResult::RequiresIndexedAddress { index, .. } => {
+                let address = unit_info
+                    .dwarf(debug_info)
+                    .address(&unit_info.unit, index)
+                    .ok()?;
+                evaluation.resume_with_indexed_address(address).ok()?
+            }
+            // Everything else needs the state of the target.
+            _ => return None,
+        };
+    }
+
+    match evaluation.as_result() {
+        [piece] => match piece.location {
+            Location::Address { address } if piece.size_in_bits.is_none() => {
+                Some(debug_info.runtime_address(address))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The name of the type `entry`, formatted for the language of its unit.
+///
+/// Unnamed types, like arrays, pointers and type modifiers, are named after the type they refer to.
+fn type_name(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+    depth: usize,
+) -> Option<String> {
+    if let Ok(Some(name)) = extract_name(debug_info, unit_info, entry) {
+        return Some(name);
+    }
+    if depth >= MAX_TYPE_DEPTH {
+        return None;
+    }
+
+    let referenced_name = || {
+        let (type_unit_info, type_die) = referenced_type(debug_info, unit_info, entry)?;
+        type_name(debug_info, type_unit_info, &type_die, depth + 1)
+    };
+
+    match entry.tag() {
+        gimli::DW_TAG_pointer_type
+        | gimli::DW_TAG_reference_type
+        | gimli::DW_TAG_rvalue_reference_type => Some(
+            unit_info
+                .language
+                .format_pointer_type(referenced_name().as_deref()),
+        ),
+        tag @ (gimli::DW_TAG_const_type
+        | gimli::DW_TAG_volatile_type
+        | gimli::DW_TAG_restrict_type
+        | gimli::DW_TAG_atomic_type) => {
+            let modifier = match tag {
+                gimli::DW_TAG_const_type => Modifier::Const,
+                gimli::DW_TAG_volatile_type => Modifier::Volatile,
+                gimli::DW_TAG_restrict_type => Modifier::Restrict,
+                _ => Modifier::Atomic,
+            };
+            let language = &unit_info.language;
+            Some(
+                if is_qualified_pointer(debug_info, unit_info, entry, depth) {
+                    language.qualified_pointer_type_name(&modifier, &referenced_name()?)
+                } else {
+                    language.modified_type_name(&modifier, &referenced_name()?)
+                },
+            )
+        }
+        gimli::DW_TAG_array_type => {
+            // The innermost dimension is the last one.
+            let dimensions = array_dimensions(unit_info, entry)?;
+            Some(
+                dimensions
+                    .iter()
+                    .rev()
+                    .fold(referenced_name()?, |item_type, length| {
+                        unit_info
+                            .language
+                            .format_array_type(&item_type, *length as usize)
+                    }),
+            )
+        }
+        _ => referenced_name(),
+    }
+}
+
+/// The size of the type `entry` in bytes, following typedefs and type modifiers, which have no size of
+/// their own.
+fn type_byte_size(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+    depth: usize,
+) -> Option<u64> {
+    // The size of a string with a dynamic length is the size of its length.
+    if entry.tag() == gimli::DW_TAG_string_type
+        && entry.attr_value(gimli::DW_AT_string_length).is_some()
+    {
+        return None;
+    }
+    if let Some(byte_size) = extract_byte_size(entry) {
+        return Some(byte_size);
+    }
+    if depth >= MAX_TYPE_DEPTH {
+        return None;
+    }
+
+    match entry.tag() {
+        gimli::DW_TAG_pointer_type
+        | gimli::DW_TAG_reference_type
+        | gimli::DW_TAG_rvalue_reference_type => {
+            Some(u64::from(unit_info.unit.encoding().address_size))
+        }
+        gimli::DW_TAG_array_type => {
+            let (element_unit_info, element_type) = referenced_type(debug_info, unit_info, entry)?;
+            let element_size =
+                type_byte_size(debug_info, element_unit_info, &element_type, depth + 1)?;
+
+            array_dimensions(unit_info, entry)?
+                .into_iter()
+                .try_fold(element_size, u64::checked_mul)
+        }
+        _ => {
+            let (type_unit_info, type_die) = referenced_type(debug_info, unit_info, entry)?;
+            type_byte_size(debug_info, type_unit_info, &type_die, depth + 1)
+        }
+    }
+}
+
+/// `true` if the type qualifier `entry` applies to a pointer, possibly through other qualifiers,
+/// like the `const` of an `int* restrict const`.
+fn is_qualified_pointer(
+    debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
+    entry: &Die,
+    depth: usize,
+) -> bool {
+    if depth >= MAX_TYPE_DEPTH {
+        return false;
+    }
+    let Some((type_unit_info, type_die)) = referenced_type(debug_info, unit_info, entry) else {
+        return false;
+    };
+
+    match type_die.tag() {
+        gimli::DW_TAG_pointer_type => true,
+        gimli::DW_TAG_const_type
+        | gimli::DW_TAG_volatile_type
+        | gimli::DW_TAG_restrict_type
+        | gimli::DW_TAG_atomic_type => {
+            is_qualified_pointer(debug_info, type_unit_info, &type_die, depth + 1)
+        }
+        _ => false,
+    }
+}
+
+/// The type that the `DW_AT_type` attribute of `entry` refers to.
+fn referenced_type<'debug_info>(
+    debug_info: &'debug_info DebugInfo,
+    unit_info: &'debug_info UnitInfo,
+    entry: &Die,
+) -> Option<(&'debug_info UnitInfo, Die)> {
+    let attr = entry.attr(gimli::DW_AT_type)?;
+    debug_info
+        .resolve_die_reference_with_unit(attr, unit_info)
+        .ok()
+}
+
+/// The number of elements in each dimension of the array type `entry`, outermost first.
+///
+/// Multidimensional arrays have a subrange for each dimension.
+fn array_dimensions(unit_info: &UnitInfo, entry: &Die) -> Option<Vec<u64>> {
+    let mut tree = unit_info.unit.entries_tree(Some(entry.offset())).ok()?;
+    let mut subranges = tree.root().ok()?.children();
+
+    let mut dimensions = Vec::new();
+    while let Ok(Some(subrange)) = subranges.next() {
+        let subrange = subrange.entry();
+        if subrange.tag() != gimli::DW_TAG_subrange_type {
+            continue;
+        }
+        let count = match subrange.attr_value(gimli::DW_AT_count) {
+            Some(count) => count.udata_value()?,
+            None => subrange
+                .attr_value(gimli::DW_AT_upper_bound)?
+                .udata_value()?
+                .checked_add(1)?,
+        };
+        dimensions.push(count);
+    }
+
+    Some(dimensions)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use typed_path::TypedPathBuf;
+
+    use crate::DebugInfo;
+
+    #[test]
+    fn static_variables_in_namespaces() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/static-variables/main.elf");
+        let debug_info = DebugInfo::from_file(&path).unwrap();
+
+        let static_variables = debug_info.static_variables().unwrap();
+
+        let summary = static_variables
+            .iter()
+            .map(|variable| {
+                (
+                    variable.name.as_str(),
+                    variable.linkage_name.as_deref(),
+                    variable.address,
+                    variable.type_name.as_deref(),
+                    variable.byte_size,
+                    variable
+                        .source_location
+                        .as_ref()
+                        .and_then(|location| location.line),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "state::current",
+                    Some("_ZN5state7currentE"),
+                    Some(0x403000),
+                    Some("int"),
+                    Some(4),
+                    Some(9)
+                ),
+                (
+                    "state::counters",
+                    Some("_ZN5state8countersE"),
+                    Some(0x403020),
+                    Some("counter_t[2][4]"),
+                    Some(16),
+                    Some(10)
+                ),
+                // The anonymous namespace is not part of the name.
+                (
+                    "state::ticks",
+                    None,
+                    Some(0x403004),
+                    Some("int"),
+                    Some(4),
+                    Some(13)
+                ),
+                (
+                    "settings",
+                    None,
+                    Some(0x403008),
+                    Some("config"),
+                    Some(12),
+                    Some(17)
+                ),
+                // Thread-local variables have no fixed address.
+                ("per_thread", None, None, Some("int"), Some(4), Some(18)),
+            ]
+        );
+
+        assert!(static_variables.iter().all(|variable| {
+            variable
+                .source_location
+                .as_ref()
+                .map(|location| &location.path)
+                == Some(&TypedPathBuf::from_unix("/tmp/static-variables/main.cpp"))
+        }));
+    }
+}
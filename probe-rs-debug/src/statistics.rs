@@ -0,0 +1,146 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::DebugInfo;
+
+/// A summary of the debug information in a binary, see [`DebugInfo::statistics`].
+///
+/// This is intended to help diagnose missing or incomplete debug information.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DebugInfoStatistics {
+    /// The DWARF versions used by the compilation units.
+    pub dwarf_versions: BTreeSet<u16>,
+    /// The number of compilation units.
+    pub compilation_units: usize,
+    /// The size in bytes of each debug section that is present, by section name.
+    ///
+    /// For compressed sections, this is the size after decompression.
+    pub sections: BTreeMap<String, u64>,
+    /// The number of functions that have address ranges.
+    pub functions_with_ranges: usize,
+    /// Whether the binary contains a `.debug_frame` section.
+    pub has_debug_frame: bool,
+    /// Whether the binary contains an `.eh_frame` section.
+    pub has_eh_frame: bool,
+    /// The distinct `DW_AT_producer` values of the compilation units, which identify the compiler.
+    pub producers: BTreeSet<String>,
+    /// The distinct source languages of the compilation units.
+    pub languages: BTreeSet<String>,
+}
+
+impl DebugInfoStatistics {
+    /// Collect the statistics for the given debug information.
+    ///
+    /// This only reads the unit headers and the root DIE of each unit.
+    pub(crate) fn new(debug_info: &DebugInfo) -> Self {
+        let mut statistics = Self {
+            compilation_units: debug_info.unit_infos.len(),
+            sections: debug_info
+                .section_sizes
+                .iter()
+                .map(|(name, size)| (name.to_string(), *size))
+                .collect(),
+            has_debug_frame: debug_info.section_sizes.contains_key(".debug_frame"),
+            has_eh_frame: debug_info.section_sizes.contains_key(".eh_frame"),
+            ..Default::default()
+        };
+
+        for unit_info in &debug_info.unit_infos {
+            statistics
+                .dwarf_versions
+                .insert(unit_info.unit.header.version());
+            statistics.functions_with_ranges += unit_info.function_count();
+
+            if let Some(producer) = unit_info.producer(debug_info) {
+                statistics.producers.insert(producer);
+            }
+
+            let language = unit_info.get_language();
+            statistics.languages.insert(
+                language
+                    .static_string()
+                    .map(|name| name.trim_start_matches("DW_LANG_").to_string())
+                    .unwrap_or_else(|| language.to_string()),
+            );
+        }
+
+        statistics
+    }
+}
+
+impl fmt::Display for DebugInfoStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join<T: ToString>(values: impl IntoIterator<Item = T>) -> String {
+            let values: Vec<_> = values.into_iter().map(|value| value.to_string()).collect();
+            if values.is_empty() {
+                "none".to_string()
+            } else {
+                values.join(", ")
+            }
+        }
+
+        let unwind_sections = [
+            (self.has_debug_frame, ".debug_frame"),
+            (self.has_eh_frame, ".eh_frame"),
+        ]
+        .into_iter()
+        .filter_map(|(present, name)| present.then_some(name));
+
+        writeln!(f, "DWARF versions: {}", join(&self.dwarf_versions))?;
+        writeln!(f, "Compilation units: {}", self.compilation_units)?;
+        writeln!(f, "Languages: {}", join(&self.languages))?;
+        writeln!(f, "Producers: {}", join(&self.producers))?;
+        writeln!(
+            f,
+            "Functions with address ranges: {}",
+            self.functions_with_ranges
+        )?;
+        writeln!(f, "Unwind information: {}", join(unwind_sections))?;
+        write!(f, "Sections:")?;
+        if self.sections.is_empty() {
+            write!(f, " none")?;
+        }
+        for (name, size) in &self.sections {
+            write!(f, "\n  {name}: {size} bytes")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::DebugInfo;
+
+    #[test]
+    fn dwarf5_statistics() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/dwarf5/main.elf");
+        let debug_info = DebugInfo::from_file(&path).unwrap();
+
+        let statistics = debug_info.statistics();
+
+        assert_eq!(
+            statistics.to_string(),
+            "DWARF versions: 5
+Compilation units: 1
+Languages: C11
+Producers: clang version 14.0.6
+Functions with address ranges: 2
+Unwind information: .eh_frame
+Sections:
+  .debug_abbrev: 114 bytes
+  .debug_addr: 32 bytes
+  .debug_info: 109 bytes
+  .debug_line: 99 bytes
+  .debug_line_str: 19 bytes
+  .debug_str: 65 bytes
+  .debug_str_offsets: 44 bytes
+  .eh_frame: 88 bytes"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use gimli::{Reader, RunTimeEndian};
+use object::Object;
+
+use crate::{
+    DebugError,
+    debug_info::{DwarfReader, uncompressed_section_data},
+    section_data::{FileData, SectionData},
+};
+
+/// Locates and loads the supplementary object file of a binary.
+///
+/// Tools like `dwz` move the debug information that is shared between binaries, such as common types,
+/// into a supplementary file. The binary refers to it with `DW_FORM_ref_sup` and `DW_FORM_strp_sup`
+/// attributes, and names the file in its `.debug_sup` section, or in the `.gnu_debugaltlink` section
+/// for the GNU extension that preceded DWARF 5.
+pub(crate) struct SupplementaryLoader {
+    /// The supplementary file from the options, which is used instead of the file named by the binary.
+    path: Option<PathBuf>,
+    /// The directory that relative file names are resolved against, which is the directory of the binary.
+    directory: Option<PathBuf>,
+}
+
+impl SupplementaryLoader {
+    /// Create a new loader, which loads the file at `path`, or if that is not set, the file that is
+    /// named by the binary, relative to `directory`.
+    pub(crate) fn new(path: Option<PathBuf>, directory: Option<PathBuf>) -> Self {
+        Self { path, directory }
+    }
+
+    /// Load the DWARF sections of the supplementary file for the binary `object`.
+    ///
+    /// Returns `None` if no file was given, and the binary does not refer to a supplementary file.
+    pub(crate) fn load(
+        &self,
+        object: &object::File<'_>,
+        endianness: RunTimeEndian,
+    ) -> Result<Option<gimli::Dwarf<DwarfReader>>, DebugError> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => {
+                let Some(file_name) = supplementary_file_name(object, endianness)? else {
+                    return Ok(None);
+                };
+                self.find_file(&file_name)?
+            }
+        };
+
+        tracing::debug!(
+            "Loading supplementary debug information from {}",
+            path.display()
+        );
+        let file = FileData::read(&path)?;
+        let supplementary_object = object::File::parse(&*file)?;
+
+        let dwarf = gimli::Dwarf::load(|id| -> Result<DwarfReader, DebugError> {
+            let data = match supplementary_object.section_by_name(id.name()) {
+                Some(section) => file.section_data(uncompressed_section_data(&section)?),
+                None => SectionData::from(&[][..]),
+            };
+
+            Ok(DwarfReader::new(data, endianness))
+        })?;
+
+        Ok(Some(dwarf))
+    }
+
+    /// Find the supplementary file with the given name, which is relative to the directory of the binary.
+    fn find_file(&self, file_name: &Path) -> Result<PathBuf, DebugError> {
+        // Without the directory of the binary, a relative name cannot be resolved.
+        let candidate = if file_name.is_absolute() {
+            Some(file_name.to_path_buf())
+        } else {
+            self.directory
+                .as_ref()
+                .map(|directory| directory.join(file_name))
+        };
+
+        candidate
+            .filter(|candidate| candidate.is_file())
+            .ok_or_else(|| {
+                DebugError::Other(format!(
+                    "Unable to find the supplementary debug file {}.",
+                    file_name.display()
+                ))
+            })
+    }
+}
+
+/// The name of the supplementary file, from the `.debug_sup` or `.gnu_debugaltlink` section of the binary.
+///
+/// Returns `None` if the binary does not refer to a supplementary file, or is a supplementary file itself.
+fn supplementary_file_name(
+    object: &object::File<'_>,
+    endianness: RunTimeEndian,
+) -> Result<Option<PathBuf>, DebugError> {
+    if let Some(section) = object.section_by_name(".debug_sup") {
+        let data = uncompressed_section_data(&section)?;
+        let mut reader = gimli::EndianSlice::new(&data, endianness);
+
+        let version = reader.read_u16()?;
+        if version != 5 {
+            return Err(DebugError::Other(format!(
+                "Unsupported .debug_sup version {version}."
+            )));
+        }
+        let is_supplementary = reader.read_u8()? != 0;
+        if is_supplementary {
+            return Ok(None);
+        }
+        let file_name = reader.read_null_terminated_slice()?;
+
+        return Ok(Some(path_from_bytes(file_name.slice())));
+    }
+
+    // The build ID which follows the file name is not checked.
+    Ok(object
+        .gnu_debugaltlink()?
+        .map(|(file_name, _build_id)| path_from_bytes(file_name)))
+}
+
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
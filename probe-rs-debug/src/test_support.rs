@@ -0,0 +1,97 @@
+//! Helpers for tests that run the debug functionality against the memory image of a binary,
+//! instead of a target or a coredump.
+
+use std::{collections::HashMap, path::Path};
+
+use object::{Object, ObjectSegment};
+use probe_rs::{
+    CoreRegisters, RegisterId, RegisterRole, RegisterValue,
+    architecture::arm::core::registers::cortex_m::CORTEX_M_CORE_REGISTERS, test::MockMemory,
+};
+
+use crate::{DebugInfo, DebugRegisters, VariableCache};
+
+/// Create the memory of a target that has just loaded the ELF file at `path`.
+///
+/// Every loadable segment is placed at its virtual address, so initialized statics have their
+/// initial values, and the part of a segment that is not in the file, like `.bss`, is zeroed.
+/// Stack contents can be added with [`MockMemory::add_word_range`], as long as they do not overlap
+/// with a segment.
+pub(crate) fn memory_from_elf(path: &Path) -> MockMemory {
+    let data = std::fs::read(path)
+        .unwrap_or_else(|error| panic!("Failed to read {}: {error}", path.display()));
+    let file = object::File::parse(&*data).unwrap();
+
+    let mut memory = MockMemory::new();
+    for segment in file.segments() {
+        let mut bytes = segment.data().unwrap().to_vec();
+        bytes.resize(segment.size() as usize, 0);
+
+        if !bytes.is_empty() {
+            memory.add_range(segment.address(), bytes);
+        }
+    }
+
+    memory
+}
+
+/// Load the ELF file `elf_file` in the `tests` directory, for tests that read static variables.
+///
+/// Returns its debug information, the memory of a target that has just loaded it, and an empty
+/// static scope cache.
+pub(crate) fn static_fixture(elf_file: &str) -> (DebugInfo, MockMemory, VariableCache) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join(elf_file);
+    let debug_info = DebugInfo::from_file(&path)
+        .unwrap_or_else(|error| panic!("Failed to open file {}: {error:?}", path.display()));
+    let memory = memory_from_elf(&path);
+    let cache = debug_info.create_static_scope_cache();
+
+    (debug_info, memory, cache)
+}
+
+/// Builds the [`DebugRegisters`] of a halted core, from the values of a few registers.
+///
+/// The registers without a value are unknown, like they are in a coredump that did not capture them.
+pub(crate) struct RegistersBuilder {
+    core_registers: &'static CoreRegisters,
+    values: HashMap<RegisterId, RegisterValue>,
+}
+
+impl RegistersBuilder {
+    /// Start with the registers of a Cortex-M core, which all have unknown values.
+    pub(crate) fn cortex_m() -> Self {
+        Self {
+            core_registers: &CORTEX_M_CORE_REGISTERS,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Set the value of the register with the given role, e.g. the program counter.
+    pub(crate) fn with_role(mut self, role: RegisterRole, value: u32) -> Self {
+        let register = self
+            .core_registers
+            .core_registers()
+            .find(|register| register.register_has_role(role))
+            .unwrap_or_else(|| panic!("The core has no register with role {role:?}"));
+
+        self.values.insert(register.id(), RegisterValue::U32(value));
+        self
+    }
+
+    /// Set the value of the register with the given DWARF register number.
+    pub(crate) fn with_register(mut self, dwarf_id: usize, value: u32) -> Self {
+        let register = self.core_registers.core_register(dwarf_id);
+
+        self.values.insert(register.id(), RegisterValue::U32(value));
+        self
+    }
+
+    /// Create the registers, in the same way as for a target or a coredump.
+    pub(crate) fn build(self) -> DebugRegisters {
+        DebugRegisters::from_core_registers(self.core_registers, |register_id| {
+            self.values.get(register_id).cloned()
+        })
+    }
+}
@@ -0,0 +1,128 @@
+use gimli::{DebuggingInformationEntry, DwLang};
+use probe_rs::MemoryInterface;
+
+use crate::{
+    DebugError, DebugInfo, Variable, VariableCache, VariableNodeType, VariableValue,
+    debug_info::GimliReader, stack_frame::StackFrameInfo, unit_info::UnitInfo,
+};
+
+/// The state of the decoding of the variable which is formatted.
+pub(crate) struct FormatContext<'a> {
+    pub(crate) unit_info: &'a UnitInfo,
+    pub(crate) debug_info: &'a DebugInfo,
+    /// The type of the variable.
+    pub(crate) node: &'a DebuggingInformationEntry<GimliReader>,
+    pub(crate) frame_info: StackFrameInfo<'a>,
+}
+
+/// Formats the value of a variable, and may replace its children. Returns `None` to keep the value.
+type FormatValue = dyn Fn(
+        &FormatContext<'_>,
+        &mut Variable,
+        &mut dyn MemoryInterface,
+        &mut VariableCache,
+    ) -> Result<Option<VariableValue>, DebugError>
+    + Send
+    + Sync;
+
+/// A formatter of the types of a standard library, see [`TypeFormatterEntry::builtin`].
+type BuiltinFormatValue = fn(
+    &FormatContext<'_>,
+    &mut Variable,
+    &mut dyn MemoryInterface,
+    &mut VariableCache,
+) -> Result<Option<VariableValue>, DebugError>;
+
+/// A formatter for the variables whose type name matches, see
+/// [`DebugInfo::register_type_formatter`].
+pub(crate) struct TypeFormatterEntry {
+    /// The formatter only applies to variables of this language, if it is set.
+    language: Option<DwLang>,
+    /// Matches the name of the type of a variable, as it is shown, e.g. `Option<u32>`.
+    matches: Box<dyn Fn(&str) -> bool + Send + Sync>,
+    format: Box<FormatValue>,
+    /// Remove the children of a variable when its value is formatted.
+    hide_children: bool,
+}
+
+impl TypeFormatterEntry {
+    /// A formatter for the types of the standard library of `language`.
+    pub(crate) fn builtin(
+        language: DwLang,
+        matches: fn(&str) -> bool,
+        format: BuiltinFormatValue,
+    ) -> Self {
+        Self {
+            language: Some(language),
+            matches: Box::new(matches),
+            format: Box::new(format),
+            hide_children: false,
+        }
+    }
+
+    /// A formatter of [`DebugInfo::register_type_formatter`].
+    pub(crate) fn custom(
+        matches: impl Fn(&str) -> bool + Send + Sync + 'static,
+        hide_children: bool,
+        formatter: impl Fn(&Variable, &mut dyn MemoryInterface, &VariableCache) -> Option<String>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            language: None,
+            matches: Box::new(matches),
+            format: Box::new(
+                move |_: &FormatContext<'_>,
+                      variable: &mut Variable,
+                      memory: &mut dyn MemoryInterface,
+                      cache: &mut VariableCache|
+                      -> Result<_, DebugError> {
+                    Ok(formatter(variable, memory, cache).map(VariableValue::Valid))
+                },
+            ),
+            hide_children,
+        }
+    }
+}
+
+/// Apply the formatters of the type of `variable`, after its standard decoding.
+///
+/// All the formatters which match are applied, in the order in which they are registered, so the
+/// value of a later formatter replaces the value of an earlier one.
+pub(crate) fn format_variable(
+    context: &FormatContext<'_>,
+    variable: &mut Variable,
+    memory: &mut dyn MemoryInterface,
+    cache: &mut VariableCache,
+) -> Result<(), DebugError> {
+    if matches!(variable.value, VariableValue::Error(_)) || !variable.memory_location.valid() {
+        return Ok(());
+    }
+
+    // The name is only needed, and determined, if there is a formatter for the language.
+    let mut type_name = None;
+    for formatter in &context.debug_info.type_formatters {
+        if formatter
+            .language
+            .is_some_and(|language| language != variable.language)
+        {
+            continue;
+        }
+        let type_name = type_name.get_or_insert_with(|| variable.type_name());
+        if !(formatter.matches)(type_name) {
+            continue;
+        }
+
+        let Some(value) = (formatter.format)(context, variable, memory, cache)? else {
+            continue;
+        };
+        variable.value = value;
+        if formatter.hide_children {
+            cache.remove_cache_entry_children(variable.variable_key)?;
+            variable.variable_node_type = VariableNodeType::DoNotRecurse;
+        }
+    }
+
+    Ok(())
+}
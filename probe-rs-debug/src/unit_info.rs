@@ -1,15 +1,45 @@
-use std::{collections::HashMap, ops::Range};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 use super::{
-    DebugError, DebugRegisters, EndianReader, SourceLocation, VariableCache, debug_info::*,
-    extract_byte_size, extract_file, extract_line, function_die::FunctionDie, variable::*,
+    DebugError, DebugRegisters, EndianReader, ErrorContext, SourceLocation, VariableCache,
+    debug_info::*,
+    extract_byte_size, extract_file, extract_line,
+    function_die::{Die, FunctionDie, FunctionInfo, FunctionMatch, NameMatch},
+    memory_snapshot::MemorySnapshot,
+    variable::*,
+};
+use crate::{
+    language::{
+        self,
+        parsing::{
+            ValueExt, read_unsigned, sign_extend, u128_from_target_bytes, u128_to_target_bytes,
+        },
+    },
+    stack_frame::StackFrameInfo,
+    type_formatter::{self, FormatContext},
 };
-use crate::{language, stack_frame::StackFrameInfo};
 use gimli::{
     AttributeValue, DebugInfoOffset, DebuggingInformationEntry, EvaluationResult, Location,
-    UnitOffset,
+    RunTimeEndian, UnitOffset,
 };
-use probe_rs::MemoryInterface;
+use probe_rs::{MemoryInterface, RegisterValue};
+use typed_path::{TypedPath, TypedPathBuf};
+
+/// The largest number of typedefs and qualifiers which are followed to find the type they refer to.
+const MAX_TYPE_DEPTH: usize = 8;
+
+/// The largest union whose bytes are read once for all its arms, see `UnitInfo::read_union_bytes`.
+const MAX_UNION_SNAPSHOT_SIZE: u64 = 4096;
+
+/// The largest number of bytes of a variable, e.g. with an unsupported type, which are shown as
+/// raw bytes, see `UnitInfo::add_raw_bytes`.
+const MAX_RAW_BYTES: u64 = 256;
+
+/// The number of bytes of each child of the raw bytes of a variable.
+const RAW_WORD_SIZE: usize = 4;
 
 /// The result of `UnitInfo::evaluate_expression()` can be the value of a variable, or a memory location.
 #[derive(Debug)]
@@ -18,37 +48,112 @@ pub(crate) enum ExpressionResult {
     Location(VariableLocation),
 }
 
+/// An error of `UnitInfo::expression_to_piece()`.
+#[derive(Debug)]
+pub(crate) enum ExpressionError {
+    /// The expression cannot be evaluated at the current location in the program, which is shown as
+    /// the value of the variable.
+    Unavailable(UnavailableReason),
+    /// Any other error.
+    Debug(DebugError),
+}
+
+impl From<DebugError> for ExpressionError {
+    fn from(error: DebugError) -> Self {
+        ExpressionError::Debug(error)
+    }
+}
+
+impl From<gimli::Error> for ExpressionError {
+    fn from(error: gimli::Error) -> Self {
+        ExpressionError::Debug(error.into())
+    }
+}
+
+/// Whether a local variable is available at the program counter of its frame, see
+/// `UnitInfo::variable_scope()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VariableScope {
+    InScope,
+    /// The program counter has not reached the declaration of the variable yet.
+    NotYetInScope,
+    /// The location list of the variable does not describe the program counter, e.g. because the
+    /// variable is not used anymore.
+    NoLocation,
+}
+
+/// Metadata of a compilation unit, see [`DebugInfo::units_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilationUnitInfo {
+    /// The name of the unit, which is usually the path of its primary source file.
+    pub name: Option<String>,
+    /// The `DW_AT_producer` attribute, which identifies the compiler.
+    pub producer: Option<String>,
+    /// The `DW_AT_language` attribute, or `None` if the unit does not declare its language.
+    pub language: Option<gimli::DwLang>,
+    /// The `DW_AT_comp_dir` attribute, which is the directory the compiler was invoked in.
+    pub comp_dir: Option<TypedPathBuf>,
+    /// The address ranges covered by the unit, including the [load bias](DebugInfo::set_load_bias).
+    pub ranges: Vec<Range<u64>>,
+    /// The DWARF version of the unit.
+    pub dwarf_version: u16,
+}
+
 /// A struct containing information about a single compilation unit.
 pub struct UnitInfo {
     pub(crate) unit: gimli::Unit<GimliReader, usize>,
-    dwarf_language: gimli::DwLang,
-    language: Box<dyn language::ProgrammingLanguage>,
+    /// The `DW_AT_language` attribute of the unit, if it is present.
+    declared_language: Option<gimli::DwLang>,
+    /// The language used to decode the variables of this unit, which is Rust if the unit does not declare its language.
+    pub(crate) dwarf_language: gimli::DwLang,
+    pub(crate) language: Box<dyn language::ProgrammingLanguage>,
+    /// The byte order of the target, as used in the debug information.
+    pub(crate) endianness: RunTimeEndian,
     // A mapping from child die to parent die.
     parents: HashMap<UnitOffset, UnitOffset>,
     // Address => function DIE offset
     function_dies: Vec<(Range<u64>, UnitOffset)>,
+    /// The split DWARF data, if this unit was loaded from a `.dwo` file or `.dwp` package.
+    split: Option<SplitUnit>,
+    /// Whether this unit is in the supplementary object file, see [`crate::DebugInfoOptions::supplementary_file`].
+    pub(crate) supplementary: bool,
+}
+
+/// The data of a split unit, which is stored outside of the main binary.
+struct SplitUnit {
+    /// The DWARF sections from the `.dwo` file or `.dwp` package.
+    dwarf: gimli::Dwarf<GimliReader>,
+    /// The skeleton unit in the main binary, which holds the address ranges of the unit.
+    skeleton: gimli::Unit<GimliReader, usize>,
 }
 
 impl UnitInfo {
     /// Create a new `UnitInfo` from a `gimli::Unit`.
     pub fn new(unit: gimli::Unit<GimliReader, usize>, dwarf: &gimli::Dwarf<GimliReader>) -> Self {
-        let dwarf_language = if let Some(AttributeValue::Language(unit_language)) = unit
+        use gimli::{Reader, Section};
+
+        let declared_language = if let Some(AttributeValue::Language(unit_language)) = unit
             .entry(unit.root_offset())
             .ok()
             .and_then(|root| root.attr_value(gimli::DW_AT_language))
         {
-            unit_language
+            Some(unit_language)
         } else {
             tracing::warn!("Unable to retrieve DW_AT_language attribute, assuming Rust.");
-            gimli::DW_LANG_Rust
+            None
         };
+        let dwarf_language = declared_language.unwrap_or(gimli::DW_LANG_Rust);
 
         let mut this = Self {
             unit,
+            declared_language,
             dwarf_language,
             language: language::from_dwarf(dwarf_language),
+            endianness: dwarf.debug_info.reader().endian(),
             parents: HashMap::new(),
             function_dies: Vec::new(),
+            split: None,
+            supplementary: false,
         };
 
         this.process_unit(dwarf);
@@ -56,6 +161,51 @@ impl UnitInfo {
         this
     }
 
+    /// Create a new `UnitInfo` from a split unit, which was loaded from a `.dwo` file or `.dwp` package.
+    pub(crate) fn new_split(
+        unit: gimli::Unit<GimliReader, usize>,
+        dwarf: gimli::Dwarf<GimliReader>,
+        skeleton: gimli::Unit<GimliReader, usize>,
+    ) -> Self {
+        let mut this = Self::new(unit, &dwarf);
+        this.split = Some(SplitUnit { dwarf, skeleton });
+        this
+    }
+
+    /// Create a new `UnitInfo` from a unit of the supplementary object file.
+    pub(crate) fn new_supplementary(
+        unit: gimli::Unit<GimliReader, usize>,
+        dwarf: &gimli::Dwarf<GimliReader>,
+    ) -> Self {
+        let mut this = Self::new(unit, dwarf);
+        this.supplementary = true;
+        this
+    }
+
+    /// The DWARF sections that this unit refers to.
+    ///
+    /// For split units these come from the `.dwo` file or `.dwp` package, and for supplementary units
+    /// from the supplementary object file. Otherwise they come from the main binary.
+    pub(crate) fn dwarf<'a>(&'a self, debug_info: &'a DebugInfo) -> &'a gimli::Dwarf<GimliReader> {
+        match (&self.split, debug_info.dwarf.sup()) {
+            (Some(split), _) => &split.dwarf,
+            (None, Some(supplementary_dwarf)) if self.supplementary => supplementary_dwarf,
+            (None, _) => &debug_info.dwarf,
+        }
+    }
+
+    /// The address ranges covered by this unit.
+    pub(crate) fn unit_ranges(
+        &self,
+        debug_info: &DebugInfo,
+    ) -> Result<gimli::RangeIter<GimliReader>, gimli::Error> {
+        match &self.split {
+            // The split unit does not contain any address information.
+            Some(split) => debug_info.dwarf.unit_ranges(&split.skeleton),
+            None => self.dwarf(debug_info).unit_ranges(&self.unit),
+        }
+    }
+
     fn process_unit(&mut self, dwarf: &gimli::Dwarf<GimliReader>) {
         let mut entries_cursor = self.unit.entries();
 
@@ -109,12 +259,173 @@ impl UnitInfo {
         self.dwarf_language
     }
 
+    /// The number of functions in this unit that have address ranges.
+    pub(crate) fn function_count(&self) -> usize {
+        self.function_dies
+            .iter()
+            .map(|(_, offset)| offset)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Collect all functions of this unit, see [`DebugInfo::functions`].
+    pub(crate) fn functions(&self, debug_info: &DebugInfo) -> Vec<FunctionInfo> {
+        let mut functions = Vec::new();
+
+        self.visit_functions(debug_info, |function| {
+            functions.push(function.function_info(debug_info));
+        });
+
+        functions
+    }
+
+    /// Find the functions of this unit with code, whose name matches `name`,
+    /// see [`DebugInfo::find_functions_by_name`].
+    pub(crate) fn find_functions_by_name(
+        &self,
+        debug_info: &DebugInfo,
+        name: &str,
+        match_mode: NameMatch,
+    ) -> Vec<FunctionMatch> {
+        self.find_function_dies_by_name(debug_info, name, match_mode)
+            .into_iter()
+            .filter_map(|(function_name, function)| {
+                let address = function.entry_pc()?;
+                let mut source_location = function.declaration_location(debug_info);
+                if let Some(source_location) = &mut source_location {
+                    source_location.address = Some(debug_info.runtime_address(address));
+                }
+
+                Some(FunctionMatch {
+                    name: function_name,
+                    address: debug_info.runtime_address(address),
+                    source_location,
+                })
+            })
+            .collect()
+    }
+
+    /// Find the DIEs of the functions of this unit with code, whose name matches `name`, with the
+    /// name that matched.
+    pub(crate) fn find_function_dies_by_name<'a>(
+        &'a self,
+        debug_info: &'a DebugInfo,
+        name: &str,
+        match_mode: NameMatch,
+    ) -> Vec<(String, FunctionDie<'a>)> {
+        let mut matches = Vec::new();
+
+        self.visit_functions(debug_info, |function| {
+            if function.entry_pc().is_none() {
+                // The abstract instance of an inlined function has no entry address.
+                return;
+            }
+
+            if let Some(function_name) = match_mode.matches(name, &function, debug_info) {
+                matches.push((function_name, function));
+            }
+        });
+
+        matches
+    }
+
+    /// Call `visit` for each function of this unit which has code, or is the abstract instance of an inlined function.
+    fn visit_functions<'a>(
+        &'a self,
+        debug_info: &'a DebugInfo,
+        mut visit: impl FnMut(FunctionDie<'a>),
+    ) {
+        let mut entries_cursor = self.unit.entries();
+        while let Ok(Some(current)) = entries_cursor.next_dfs() {
+            // Inlined copies of a function are part of the function they are inlined into.
+            if current.tag() != gimli::DW_TAG_subprogram {
+                continue;
+            }
+
+            let Ok(Some(ranges)) =
+                FunctionDie::function_ranges(current, self, self.dwarf(debug_info))
+            else {
+                continue;
+            };
+
+            // Declarations don't have any code, and are not abstract instances of inlined functions.
+            if ranges.is_empty() && current.attr(gimli::DW_AT_inline).is_none() {
+                continue;
+            }
+
+            if let Some(function) =
+                FunctionDie::with_ranges(current.clone(), self, debug_info, ranges)
+            {
+                visit(function);
+            }
+        }
+    }
+
+    /// Retrieve the value of the `DW_AT_producer` attribute of the compilation unit, which identifies the compiler.
+    pub(crate) fn producer(&self, debug_info: &DebugInfo) -> Option<String> {
+        let root = self.unit.entry(self.unit.root_offset()).ok()?;
+        let producer = root.attr_value(gimli::DW_AT_producer)?;
+        let producer = self
+            .dwarf(debug_info)
+            .attr_string(&self.unit, producer)
+            .ok()?;
+
+        Some(String::from_utf8_lossy(&producer).into_owned())
+    }
+
+    /// Collect the metadata of this unit, see [`DebugInfo::units_info`].
+    ///
+    /// This only reads the root DIE of the unit.
+    pub(crate) fn compilation_unit_info(&self, debug_info: &DebugInfo) -> CompilationUnitInfo {
+        let mut ranges = Vec::new();
+        if let Ok(mut unit_ranges) = self.unit_ranges(debug_info) {
+            while let Ok(Some(range)) = unit_ranges.next() {
+                if range.begin < range.end {
+                    ranges.push(
+                        debug_info.runtime_address(range.begin)
+                            ..debug_info.runtime_address(range.end),
+                    );
+                }
+            }
+        }
+
+        let comp_dir =
+            self.unit.comp_dir.as_ref().map(|comp_dir| {
+                TypedPath::derive(&*String::from_utf8_lossy(comp_dir)).to_path_buf()
+            });
+
+        CompilationUnitInfo {
+            name: self
+                .unit
+                .name
+                .as_ref()
+                .map(|name| String::from_utf8_lossy(name).into_owned()),
+            producer: self.producer(debug_info),
+            language: self.declared_language,
+            comp_dir,
+            ranges,
+            dwarf_version: self.unit.header.version(),
+        }
+    }
+
     pub(crate) fn debug_info_offset(&self) -> Result<DebugInfoOffset, DebugError> {
         self.unit.header.offset().to_debug_info_offset(&self.unit.header).ok_or_else(|| DebugError::Other(
             "Failed to convert unit header offset to debug info offset. This is a bug, please report it.".to_string()
         ))
     }
 
+    /// The location of a DIE of this unit, to attach to errors with [`DebugError::with_context`].
+    pub(crate) fn error_context(
+        &self,
+        entry: &DebuggingInformationEntry<GimliReader>,
+    ) -> ErrorContext {
+        ErrorContext {
+            unit_offset: Some(self.unit.header.offset().0 as u64),
+            die_offset: Some(entry.offset().to_unit_section_offset(&self.unit.header).0 as u64),
+            ..Default::default()
+        }
+    }
+
     /// Get the compilation unit DIEs for the function containing the given address.
     /// - The first entry in the vector will be the outermost function containing the address.
     /// - If the address is inlined, the innermost function will be the last entry in the vector.
@@ -196,9 +507,18 @@ impl UnitInfo {
                 break;
             }
 
-            // Keep the current DIE only if it is an inlined function
-            let Some(die) = FunctionDie::new(current.clone(), self, debug_info, address)? else {
-                continue;
+            // Keep the current DIE only if it is an inlined function.
+            // A malformed DIE only hides the inlined function, instead of the whole frame.
+            let die = match FunctionDie::new(current.clone(), self, debug_info, address) {
+                Ok(Some(die)) if die.is_inline() => die,
+                Ok(_) => continue,
+                Err(error) => {
+                    tracing::warn!(
+                        "Skipping malformed function DIE: {}",
+                        error.with_context(self.error_context(current))
+                    );
+                    continue;
+                }
             };
 
             // Every time we find a qualifying inlined-function, we set the abort depth
@@ -213,6 +533,8 @@ impl UnitInfo {
 
     /// Recurse the ELF structure below the `tree_node`,
     /// and updates the `cache` with the updated value of the `child_variable`.
+    ///
+    /// Errors are annotated with the location of the `tree_node` in the debug information.
     #[expect(clippy::too_many_arguments)]
     pub(crate) fn process_tree_node_attributes(
         &self,
@@ -223,254 +545,259 @@ impl UnitInfo {
         memory: &mut dyn MemoryInterface,
         cache: &mut VariableCache,
         frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        self.process_tree_node_attributes_impl(
+            debug_info,
+            tree_node,
+            parent_variable,
+            child_variable,
+            memory,
+            cache,
+            frame_info,
+        )
+        .map_err(|error| error.with_context(self.error_context(tree_node)))
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn process_tree_node_attributes_impl(
+        &self,
+        debug_info: &DebugInfo,
+        tree_node: &gimli::DebuggingInformationEntry<GimliReader>,
+        parent_variable: &mut Variable,
+        child_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
         // Identify the parent.
         child_variable.parent_key = parent_variable.variable_key;
 
-        let abstract_entry;
-
-        // We need to determine if we are working with a 'abstract` location, and use that node for the attributes we need
-        let attributes_entry = if let Some(abstract_origin) =
-            tree_node.attr(gimli::DW_AT_abstract_origin)
-        {
-            match abstract_origin.value() {
-                gimli::AttributeValue::UnitRef(unit_ref) => {
-                    // The abstract origin is a reference to another DIE, so we need to resolve that,
-                    // but first we need to process the (optional) memory location using the current DIE.
-                    self.process_memory_location(
-                        debug_info,
-                        tree_node,
-                        parent_variable,
-                        child_variable,
-                        memory,
-                        frame_info,
-                    )?;
-
-                    abstract_entry = self.unit.entry(unit_ref)?;
+        // The DIE can refer to another DIE with `DW_AT_abstract_origin`, e.g. the parameters of an inlined function,
+        // or with `DW_AT_specification`, e.g. the definition of a static member which refers to its declaration.
+        // The attributes which are absent on this DIE are taken from the referenced DIEs. Only references within
+        // this unit are followed, because the attributes are resolved in the context of this unit.
+        let origin_entries: Vec<_> = debug_info
+            .resolve_origin_chain(tree_node, self)
+            .into_iter()
+            .take_while(|(unit_info, _)| std::ptr::eq(*unit_info, self))
+            .map(|(_, entry)| entry)
+            .collect();
+
+        if !origin_entries.is_empty() {
+            // The location is described by this DIE, so it has to be processed before the
+            // attributes of the referenced DIEs.
+            self.process_memory_location(
+                debug_info,
+                tree_node,
+                parent_variable,
+                child_variable,
+                memory,
+                frame_info,
+            )?;
+        }
 
-                    Some(&abstract_entry)
-                }
-                other_attribute_value => {
-                    child_variable.set_value(VariableValue::Error(format!(
-                        "Unimplemented: Attribute Value for DW_AT_abstract_origin {other_attribute_value:?}"
-                    )));
-                    None
+        let entries: Vec<_> = std::iter::once(tree_node)
+            .chain(origin_entries.iter())
+            .collect();
+
+        // The attributes are processed starting with the farthest DIE, and an attribute of a nearer DIE
+        // replaces the same attribute of the DIEs it refers to.
+        let mut attributes = Vec::new();
+        for (index, entry) in entries.iter().enumerate().rev() {
+            for attr in entry.attrs() {
+                if entries[..index]
+                    .iter()
+                    .all(|nearer_entry| nearer_entry.attr(attr.name()).is_none())
+                {
+                    attributes.push((*entry, attr));
                 }
             }
-        } else {
-            Some(tree_node)
-        };
+        }
 
-        let specification_entry;
+        // For variable attribute resolution, we need to resolve a few attributes in advance of looping through all the other ones.
+        // Try to exact the name first, for easier debugging
+        if let Ok(Some(name)) = extract_name(debug_info, self, tree_node) {
+            let name = if tree_node.tag() == gimli::DW_TAG_member {
+                self.language
+                    .format_member_name(&parent_variable.type_name, name)
+            } else {
+                name
+            };
+            child_variable.name = VariableName::Named(name);
+        }
 
-        // We need to determine if we are working with a variable definition which refers to a declaration,
-        // and use that node for the attributes we need
-        let attributes_entry = if let Some(specification) =
-            tree_node.attr(gimli::DW_AT_specification)
-        {
-            match specification.value() {
-                gimli::AttributeValue::UnitRef(unit_ref) => {
-                    // The abstract origin is a reference to another DIE, so we need to resolve that,
-                    // but first we need to process the (optional) memory location using the current DIE.
-                    self.process_memory_location(
+        child_variable.source_location = self.extract_source_location(debug_info, tree_node)?;
+
+        match tree_node.tag() {
+            gimli::DW_TAG_variable => {
+                child_variable.is_static = self.has_static_storage(debug_info, tree_node);
+            }
+            gimli::DW_TAG_formal_parameter => child_variable.is_parameter = true,
+            _ => {}
+        }
+
+        // Now loop through all the unit attributes to extract the remainder of the `Variable` definition.
+        for (attributes_entry, attr) in attributes {
+            match attr.name() {
+                gimli::DW_AT_location | gimli::DW_AT_data_member_location => {
+                    // The child_variable.location is calculated with attribute gimli::DW_AT_type, to ensure it
+                    // gets done before DW_AT_type is processed
+                }
+                gimli::DW_AT_name => {
+                    // This was done before we started looping through attributes, so we can ignore it.
+                }
+                gimli::DW_AT_decl_file | gimli::DW_AT_decl_line | gimli::DW_AT_decl_column => {
+                    // Handled in extract_source_location()
+                }
+                gimli::DW_AT_containing_type => {
+                    // TODO: Implement [documented RUST extensions to DWARF standard](https://rustc-dev-guide.rust-lang.org/debugging-support-in-rustc.html?highlight=dwarf#dwarf-and-rustc)
+                }
+                gimli::DW_AT_type => {
+                    // The rules to calculate the type of a child variable are complex, and depend on a number of
+                    // other attributes.
+                    // Depending on the presence and value of these attributes, the [Variable::memory_location] may
+                    // need to be calculated differently.
+                    // - The `DW_AT_type` of the parent (e.g. is it a pointer, or a struct, or an array, etc.).
+                    // - The `DW_AT_address_class of the child (we need to know if it is present, and if it has a
+                    //   value of 0 - unspecified)
+                    // - The `DW_AT_data_member_location` of the child.
+                    // - The `DW_AT_location` of the child.
+                    // - The `DW_AT_byte_size` of the child.
+                    // - The `DW_AT_name` of the data type node.
+                    self.process_type_attribute(
+                        attr,
                         debug_info,
-                        tree_node,
+                        attributes_entry,
                         parent_variable,
                         child_variable,
                         memory,
                         frame_info,
-                    )?;
-
-                    specification_entry = self.unit.entry(unit_ref)?;
-
-                    Some(&specification_entry)
-                }
-                other_attribute_value => {
-                    child_variable.set_value(VariableValue::Error(format!(
-                        "Unimplemented: Attribute Value for DW_AT_specification {other_attribute_value:?}"
-                    )));
-                    None
+                        cache,
+                    )
+                    .map_err(|error| {
+                        error.with_context(
+                            self.error_context(attributes_entry)
+                                .with_attribute(attr.name()),
+                        )
+                    })?;
                 }
-            }
-        } else {
-            attributes_entry
-        };
-
-        // For variable attribute resolution, we need to resolve a few attributes in advance of looping through all the other ones.
-        // Try to exact the name first, for easier debugging
-        if let Some(entry) = attributes_entry.as_ref()
-            && let Ok(Some(name)) = extract_name(debug_info, entry)
-        {
-            child_variable.name = VariableName::Named(name);
-        }
-
-        if let Some(attributes_entry) = attributes_entry {
-            child_variable.source_location =
-                self.extract_source_location(debug_info, attributes_entry)?;
-
-            // Now loop through all the unit attributes to extract the remainder of the `Variable` definition.
-            for attr in attributes_entry.attrs() {
-                match attr.name() {
-                    gimli::DW_AT_location | gimli::DW_AT_data_member_location => {
-                        // The child_variable.location is calculated with attribute gimli::DW_AT_type, to ensure it
-                        // gets done before DW_AT_type is processed
+                gimli::DW_AT_enum_class => match attr.value() {
+                    gimli::AttributeValue::Flag(true) => {
+                        child_variable.set_value(VariableValue::Valid(child_variable.type_name()));
                     }
-                    gimli::DW_AT_name => {
-                        // This was done before we started looping through attributes, so we can ignore it.
-                    }
-                    gimli::DW_AT_decl_file | gimli::DW_AT_decl_line | gimli::DW_AT_decl_column => {
-                        // Handled in extract_source_location()
+                    gimli::AttributeValue::Flag(false) => {
+                        child_variable.set_value(VariableValue::Error(
+                            "Unimplemented: DW_AT_enum_class(false)".to_string(),
+                        ));
                     }
-                    gimli::DW_AT_containing_type => {
-                        // TODO: Implement [documented RUST extensions to DWARF standard](https://rustc-dev-guide.rust-lang.org/debugging-support-in-rustc.html?highlight=dwarf#dwarf-and-rustc)
+                    other_attribute_value => {
+                        child_variable.set_value(VariableValue::Error(format!(
+                            "Unimplemented: Attribute Value for DW_AT_enum_class: {other_attribute_value:?}"
+                        )));
                     }
-                    gimli::DW_AT_type => {
-                        // The rules to calculate the type of a child variable are complex, and depend on a number of
-                        // other attributes.
-                        // Depending on the presence and value of these attributes, the [Variable::memory_location] may
-                        // need to be calculated differently.
-                        // - The `DW_AT_type` of the parent (e.g. is it a pointer, or a struct, or an array, etc.).
-                        // - The `DW_AT_address_class of the child (we need to know if it is present, and if it has a
-                        //   value of 0 - unspecified)
-                        // - The `DW_AT_data_member_location` of the child.
-                        // - The `DW_AT_location` of the child.
-                        // - The `DW_AT_byte_size` of the child.
-                        // - The `DW_AT_name` of the data type node.
-                        self.process_type_attribute(
-                            attr,
+                },
+                gimli::DW_AT_const_value => {
+                    let attr_value = attr.value();
+                    let variable_value = if let Some(const_value) = attr_value.udata_value() {
+                        VariableValue::U64(const_value)
+                    } else if let Some(const_value) = attr_value.sdata_value() {
+                        VariableValue::I64(const_value)
+                    } else {
+                        VariableValue::Error(format!(
+                            "Unimplemented: Attribute Value for DW_AT_const_value: {attr_value:?}"
+                        ))
+                    };
+
+                    child_variable.set_value(variable_value)
+                }
+                gimli::DW_AT_alignment => {
+                    // TODO: Figure out when (if at all) we need to do anything with DW_AT_alignment for the
+                    // purposes of decoding data values.
+                }
+                gimli::DW_AT_artificial => {
+                    // These are references for entries like discriminant values of `VariantParts`.
+                    child_variable.name = VariableName::Artificial;
+                }
+                gimli::DW_AT_discr => match attr.value() {
+                    // This calculates the active discriminant value for the `VariantPart`.
+                    gimli::AttributeValue::UnitRef(unit_ref) => {
+                        let discriminant_node = self.unit.entry(unit_ref)?;
+                        let mut discriminant_variable =
+                            cache.create_variable(parent_variable.variable_key, Some(self))?;
+                        self.process_tree_node_attributes(
                             debug_info,
-                            attributes_entry,
+                            &discriminant_node,
                             parent_variable,
-                            child_variable,
+                            &mut discriminant_variable,
                             memory,
-                            frame_info,
                             cache,
+                            frame_info,
                         )?;
-                    }
-                    gimli::DW_AT_enum_class => match attr.value() {
-                        gimli::AttributeValue::Flag(true) => {
-                            child_variable
-                                .set_value(VariableValue::Valid(child_variable.type_name()));
-                        }
-                        gimli::AttributeValue::Flag(false) => {
-                            child_variable.set_value(VariableValue::Error(
-                                "Unimplemented: DW_AT_enum_class(false)".to_string(),
-                            ));
-                        }
-                        other_attribute_value => {
-                            child_variable.set_value(VariableValue::Error(format!(
-                                "Unimplemented: Attribute Value for DW_AT_enum_class: {other_attribute_value:?}"
-                            )));
-                        }
-                    },
-                    gimli::DW_AT_const_value => {
-                        let attr_value = attr.value();
-                        let variable_value = if let Some(const_value) = attr_value.udata_value() {
-                            VariableValue::Valid(const_value.to_string())
-                        } else if let Some(const_value) = attr_value.sdata_value() {
-                            VariableValue::Valid(const_value.to_string())
-                        } else {
-                            VariableValue::Error(format!(
-                                "Unimplemented: Attribute Value for DW_AT_const_value: {attr_value:?}"
-                            ))
-                        };
 
-                        child_variable.set_value(variable_value)
-                    }
-                    gimli::DW_AT_alignment => {
-                        // TODO: Figure out when (if at all) we need to do anything with DW_AT_alignment for the
-                        // purposes of decoding data values.
+                        // The discriminant can overlap the payload of a variant, for niche
+                        // optimized layouts like `Option<&T>`, so it is matched by its raw value.
+                        // If it can not be read, the default variant is used.
+                        let variant_part =
+                            discriminant_value(&discriminant_variable.value).unwrap_or(u64::MAX);
+
+                        parent_variable.role = VariantRole::VariantPart(variant_part);
+                        cache.remove_cache_entry(discriminant_variable.variable_key)?;
                     }
-                    gimli::DW_AT_artificial => {
-                        // These are references for entries like discriminant values of `VariantParts`.
-                        child_variable.name = VariableName::Artificial;
+                    other_attribute_value => {
+                        child_variable.set_value(VariableValue::Error(format!(
+                            "Unimplemented: Attribute Value for DW_AT_discr {other_attribute_value:?}"
+                        )));
                     }
-                    gimli::DW_AT_discr => match attr.value() {
-                        // This calculates the active discriminant value for the `VariantPart`.
-                        gimli::AttributeValue::UnitRef(unit_ref) => {
-                            let discriminant_node = self.unit.entry(unit_ref)?;
-                            let mut discriminant_variable =
-                                cache.create_variable(parent_variable.variable_key, Some(self))?;
-                            self.process_tree_node_attributes(
-                                debug_info,
-                                &discriminant_node,
-                                parent_variable,
-                                &mut discriminant_variable,
-                                memory,
-                                cache,
-                                frame_info,
-                            )?;
+                },
+                gimli::DW_AT_linkage_name => {
+                    let value = attr.value();
+                    let raw_str = self.dwarf(debug_info).attr_string(&self.unit, value).ok();
 
-                            let variant_part = if discriminant_variable.is_valid() {
-                                discriminant_variable
-                                    .to_string(cache)
-                                    .parse()
-                                    .unwrap_or(u64::MAX)
-                            } else {
-                                u64::MAX
-                            };
+                    let linkage_name = raw_str.map(|r| String::from_utf8_lossy(&r).into_owned());
 
-                            parent_variable.role = VariantRole::VariantPart(variant_part);
-                            cache.remove_cache_entry(discriminant_variable.variable_key)?;
-                        }
-                        other_attribute_value => {
-                            child_variable.set_value(VariableValue::Error(format!(
-                                "Unimplemented: Attribute Value for DW_AT_discr {other_attribute_value:?}"
-                            )));
-                        }
-                    },
-                    gimli::DW_AT_linkage_name => {
-                        let value = attr.value();
-                        let raw_str = debug_info.dwarf.attr_string(&self.unit, value).ok();
+                    child_variable.linkage_name = linkage_name;
+                }
+                gimli::DW_AT_accessibility => {
+                    // Silently ignore these for now.
+                    // TODO: Add flag for public/private/protected for `Variable`, once we have a use case.
+                }
+                gimli::DW_AT_external => {
+                    // TODO: Implement globally visible variables.
+                }
+                gimli::DW_AT_declaration => {
+                    // Unimplemented.
+                }
+                gimli::DW_AT_encoding => {
+                    // Processed for base types by `extract_type()`.
+                }
+                gimli::DW_AT_discr_value => {
+                    // Processed by `extract_variant_discriminant()`.
+                }
+                gimli::DW_AT_byte_size => {
+                    // Processed by `extract_byte_size()`.
+                }
+                gimli::DW_AT_abstract_origin | gimli::DW_AT_specification => {
+                    // Processed before looping through all attributes
+                }
+                gimli::DW_AT_address_class => {
+                    // Processed by `extract_type()`
+                }
+                gimli::DW_AT_data_bit_offset | gimli::DW_AT_bit_offset | gimli::DW_AT_bit_size => {
+                    // Processed by `extract_bitfield_info()`
+                }
+                other_attribute => {
+                    tracing::info!(
+                        "Unimplemented: Variable Attribute {:.100} : {:.100}, with children = {}",
+                        format!("{:?}", other_attribute.static_string()),
+                        format!("{:?}", attributes_entry.attr_value(other_attribute)),
+                        attributes_entry.has_children()
+                    );
+                }
+            }
+        }
 
-                        let linkage_name = raw_str.and_then(|r| String::from_utf8(r.to_vec()).ok());
-
-                        child_variable.linkage_name = linkage_name;
-                    }
-                    gimli::DW_AT_accessibility => {
-                        // Silently ignore these for now.
-                        // TODO: Add flag for public/private/protected for `Variable`, once we have a use case.
-                    }
-                    gimli::DW_AT_external => {
-                        // TODO: Implement globally visible variables.
-                    }
-                    gimli::DW_AT_declaration => {
-                        // Unimplemented.
-                    }
-                    gimli::DW_AT_encoding => {
-                        // Ignore these. RUST data types handle this intrinsically.
-                    }
-                    gimli::DW_AT_discr_value => {
-                        // Processed by `extract_variant_discriminant()`.
-                    }
-                    gimli::DW_AT_byte_size => {
-                        // Processed by `extract_byte_size()`.
-                    }
-                    gimli::DW_AT_abstract_origin => {
-                        // Processed before looping through all attributes
-                    }
-                    gimli::DW_AT_address_class => {
-                        // Processed by `extract_type()`
-                    }
-                    gimli::DW_AT_data_bit_offset
-                    | gimli::DW_AT_bit_offset
-                    | gimli::DW_AT_bit_size => {
-                        // Processed by `extract_bitfield_info()`
-                    }
-                    other_attribute => {
-                        tracing::info!(
-                            "Unimplemented: Variable Attribute {:.100} : {:.100}, with children = {}",
-                            format!("{:?}", other_attribute.static_string()),
-                            format!("{:?}", attributes_entry.attr_value(other_attribute)),
-                            attributes_entry.has_children()
-                        );
-                    }
-                }
-            }
-        }
-
-        // Need to process bitfields last as they need type information to be resolved first.
-        self.process_bitfield_info(child_variable, tree_node, cache)?;
+        // Need to process bitfields last as they need type information to be resolved first.
+        self.process_bitfield_info(child_variable, tree_node, cache)?;
 
         child_variable.extract_value(memory, cache);
         cache.update_variable(child_variable)?;
@@ -544,16 +871,22 @@ impl UnitInfo {
 
         tracing::trace!("process_tree for parent {:?}", parent_variable.variable_key);
 
+        // The variants of a `DW_TAG_variant_part` are matched against its discriminant, whose
+        // signedness determines how their `DW_AT_discr_value` is read.
+        let parent_offset = parent_node.entry().offset();
+        let mut signed_discriminant = None;
+
         let mut child_nodes = parent_node.children();
         while let Some(child_node) = child_nodes.next()? {
             match child_node.entry().tag() {
                 gimli::DW_TAG_namespace => {
-                    let variable_name =
-                        if let Ok(Some(name)) = extract_name(debug_info, child_node.entry()) {
-                            VariableName::Namespace(name)
-                        } else {
-                            VariableName::AnonymousNamespace
-                        };
+                    let variable_name = if let Ok(Some(name)) =
+                        extract_name(debug_info, self, child_node.entry())
+                    {
+                        VariableName::Namespace(name)
+                    } else {
+                        VariableName::AnonymousNamespace
+                    };
 
                     // See if this namespace already exists in the cache.
                     let mut namespace_variable = if let Some(existing_var) = cache
@@ -596,8 +929,27 @@ impl UnitInfo {
                     //  - Typical top-level variables.
                     //  - Members of structured types.
                     //  - Possible values for enumerators, used by extract_type() when processing DW_TAG_enumeration_type.
+                    self.process_variable_node(
+                        debug_info,
+                        child_node,
+                        parent_variable,
+                        memory,
+                        cache,
+                        frame_info,
+                    )?;
+                }
+                gimli::DW_TAG_inheritance => {
+                    // A base class of a C++ class. Its members are shown as the children of a member
+                    // which is named after the base class, at the `DW_AT_data_member_location` of the
+                    // base class in the derived class.
                     let mut child_variable =
                         cache.create_variable(parent_variable.variable_key, Some(self))?;
+                    let base_class_name = self
+                        .extract_type_name(debug_info, child_node.entry())
+                        .ok()
+                        .flatten()
+                        .unwrap_or_else(|| "<unnamed base class>".to_string());
+                    child_variable.name = VariableName::Named(base_class_name);
                     self.process_tree_node_attributes(
                         debug_info,
                         child_node.entry(),
@@ -607,34 +959,6 @@ impl UnitInfo {
                         cache,
                         frame_info,
                     )?;
-
-                    // In the case of C code, we can have entries for both the declaration and the definition of a variable.
-                    // We don't do anything with the declaration right now, so we remove it from the cache.
-                    let is_declaration = if let Some(AttributeValue::Flag(value)) =
-                        child_node.entry().attr_value(gimli::DW_AT_declaration)
-                    {
-                        value
-                    } else {
-                        false
-                    };
-
-                    // Do not keep or process PhantomData nodes, or variant parts that we have already used.
-                    if is_declaration
-                        || child_variable.type_name.is_phantom_data()
-                        || child_variable.name == VariableName::Artificial
-                    {
-                        cache.remove_cache_entry(child_variable.variable_key)?;
-                    } else if child_variable.is_valid() {
-                        // Recursively process each child.
-                        self.process_tree(
-                            debug_info,
-                            child_node,
-                            &mut child_variable,
-                            memory,
-                            cache,
-                            frame_info,
-                        )?;
-                    }
                 }
                 gimli::DW_TAG_variant_part => {
                     // We need to recurse through the children, to find the DW_TAG_variant with discriminant matching
@@ -692,7 +1016,13 @@ impl UnitInfo {
                     if !cache.has_children(parent_variable) {
                         let mut child_variable =
                             cache.create_variable(parent_variable.variable_key, Some(self))?;
-                        self.extract_variant_discriminant(&child_node, &mut child_variable)?;
+                        let signed = *signed_discriminant
+                            .get_or_insert_with(|| self.is_signed_discriminant(parent_offset));
+                        self.extract_variant_discriminant(
+                            &child_node,
+                            &mut child_variable,
+                            signed,
+                        )?;
                         self.process_tree_node_attributes(
                             debug_info,
                             child_node.entry(),
@@ -753,25 +1083,32 @@ impl UnitInfo {
                                     .to_string(),
                         });
                     };
-                    let program_counter = program_counter.try_into()?;
+                    // The ranges of the block are addresses in the debug information.
+                    let program_counter = debug_info.debug_address(program_counter.try_into()?);
 
                     // Determine the low and high ranges for which this DIE and children are in scope. These can be
                     // specified discreetly, or in ranges.
                     let mut in_scope = false;
                     if let Some(low_pc_attr) = child_node.entry().attr(gimli::DW_AT_low_pc) {
-                        let low_pc = match low_pc_attr.value() {
-                            gimli::AttributeValue::Addr(value) => value,
-                            _other => u64::MAX,
-                        };
+                        let low_pc = self
+                            .dwarf(debug_info)
+                            .attr_address(&self.unit, low_pc_attr.value())
+                            .ok()
+                            .flatten()
+                            .unwrap_or(u64::MAX);
                         let high_pc = if let Some(high_pc_attr) =
                             child_node.entry().attr(gimli::DW_AT_high_pc)
                         {
                             match high_pc_attr.value() {
-                                gimli::AttributeValue::Addr(addr) => addr,
                                 gimli::AttributeValue::Udata(unsigned_offset) => {
                                     low_pc + unsigned_offset
                                 }
-                                _other => 0_u64,
+                                other => self
+                                    .dwarf(debug_info)
+                                    .attr_address(&self.unit, other)
+                                    .ok()
+                                    .flatten()
+                                    .unwrap_or(0_u64),
                             }
                         } else {
                             0_u64
@@ -793,25 +1130,22 @@ impl UnitInfo {
                     // Searching for ranges has a bit more overhead, so ONLY do this if do not have scope confirmed yet.
                     if !in_scope && let Some(ranges) = child_node.entry().attr(gimli::DW_AT_ranges)
                     {
-                        match ranges.value() {
-                            gimli::AttributeValue::RangeListsRef(raw_range_lists_offset) => {
-                                let range_lists_offset = debug_info
-                                    .dwarf
-                                    .ranges_offset_from_raw(&self.unit, raw_range_lists_offset);
-
-                                if let Ok(mut range_iter) =
-                                    debug_info.dwarf.ranges(&self.unit, range_lists_offset)
-                                {
-                                    in_scope = range_iter.contains(program_counter);
-                                }
+                        match self
+                            .dwarf(debug_info)
+                            .attr_ranges(&self.unit, ranges.value())
+                        {
+                            Ok(Some(mut range_iter)) => {
+                                in_scope = range_iter.contains(program_counter);
                             }
-                            other_range_attribute => {
+                            Ok(None) => {
                                 let error = format!(
                                     "Found unexpected scope attribute: {:?} for variable {:?}",
-                                    other_range_attribute, parent_variable.name
+                                    ranges.value(),
+                                    parent_variable.name
                                 );
                                 parent_variable.set_value(VariableValue::Error(error));
                             }
+                            Err(_) => {}
                         }
                     }
                     if in_scope {
@@ -846,6 +1180,7 @@ impl UnitInfo {
                 | gimli::DW_TAG_base_type
                 | gimli::DW_TAG_pointer_type
                 | gimli::DW_TAG_structure_type
+                | gimli::DW_TAG_class_type
                 | gimli::DW_TAG_enumeration_type
                 | gimli::DW_TAG_array_type
                 | gimli::DW_TAG_subroutine_type
@@ -853,7 +1188,9 @@ impl UnitInfo {
                 | gimli::DW_TAG_union_type
                 | gimli::DW_TAG_typedef
                 | gimli::DW_TAG_const_type
-                | gimli::DW_TAG_volatile_type => {
+                | gimli::DW_TAG_volatile_type
+                | gimli::DW_TAG_restrict_type
+                | gimli::DW_TAG_atomic_type => {
                     // These will be processed elsewhere, or not at all, until we discover a use case that needs to be
                     // implemented.
                 }
@@ -873,6 +1210,251 @@ impl UnitInfo {
         Ok(())
     }
 
+    /// Add the variable, parameter or member at `node` to the children of `parent_variable`, and
+    /// recurse into its children.
+    fn process_variable_node(
+        &self,
+        debug_info: &DebugInfo,
+        node: gimli::EntriesTreeNode<GimliReader>,
+        parent_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let scope = self.variable_scope(debug_info, node.entry(), frame_info);
+        if scope != VariableScope::InScope && debug_info.hide_out_of_scope_variables {
+            return Ok(());
+        }
+
+        let mut child_variable = cache.create_variable(parent_variable.variable_key, Some(self))?;
+        if scope == VariableScope::NotYetInScope {
+            // The location of the variable does not hold its value yet, so it is not read.
+            child_variable.memory_location = VariableLocation::Unavailable;
+        }
+        self.process_tree_node_attributes(
+            debug_info,
+            node.entry(),
+            parent_variable,
+            &mut child_variable,
+            memory,
+            cache,
+            frame_info,
+        )?;
+        if scope == VariableScope::NotYetInScope {
+            child_variable.value = VariableValue::Error("not yet in scope".to_string());
+            child_variable.variable_node_type = VariableNodeType::DoNotRecurse;
+            cache.update_variable(&child_variable)?;
+        }
+
+        // In the case of C code, we can have entries for both the declaration and the definition of a variable.
+        // We don't do anything with the declaration right now, so we remove it from the cache.
+        let is_declaration = if let Some(AttributeValue::Flag(value)) =
+            node.entry().attr_value(gimli::DW_AT_declaration)
+        {
+            value
+        } else {
+            false
+        };
+
+        // Do not keep or process PhantomData nodes, or variant parts that we have already used.
+        if is_declaration
+            || (self.dwarf_language == gimli::DW_LANG_Rust
+                && child_variable.type_name.is_phantom_data())
+            || child_variable.name == VariableName::Artificial
+        {
+            cache.remove_cache_entry(child_variable.variable_key)?;
+        } else if child_variable.is_valid() {
+            let is_anonymous_member = self.is_anonymous_member(node.entry());
+
+            // Recursively process each child.
+            self.process_tree(
+                debug_info,
+                node,
+                &mut child_variable,
+                memory,
+                cache,
+                frame_info,
+            )?;
+
+            // The members of an anonymous struct or union are accessed like members of
+            // the struct that contains it, so they are shown as its children.
+            if is_anonymous_member {
+                cache.adopt_grand_children(parent_variable, &child_variable)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the local variable `entry` is available at the program counter of the frame.
+    ///
+    /// A variable is not in scope before the offset or outside of the ranges of its
+    /// `DW_AT_start_scope`, and before the first entry of its location list.
+    fn variable_scope(
+        &self,
+        debug_info: &DebugInfo,
+        entry: &DebuggingInformationEntry<GimliReader>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> VariableScope {
+        if entry.tag() != gimli::DW_TAG_variable {
+            return VariableScope::InScope;
+        }
+        let Some(program_counter) = frame_info
+            .registers
+            .get_program_counter()
+            .and_then(|reg| reg.value)
+            .and_then(|value| value.try_into().ok())
+            // The ranges are addresses in the debug information.
+            .map(|value| debug_info.debug_address(value))
+        else {
+            return VariableScope::InScope;
+        };
+
+        if let Some(start_scope) = entry.attr_value(gimli::DW_AT_start_scope) {
+            let scope = if let Some(offset) = start_scope.udata_value() {
+                // The offset is relative to the start of the block or function which declares the
+                // variable.
+                let scope_start = self
+                    .parent_offset(entry.offset())
+                    .and_then(|parent_offset| self.unit.entry(parent_offset).ok())
+                    .and_then(|parent| {
+                        let mut ranges = self
+                            .dwarf(debug_info)
+                            .die_ranges(&self.unit, &parent)
+                            .ok()?;
+                        ranges.next().ok().flatten()
+                    });
+                match scope_start {
+                    Some(range) if program_counter < range.begin + offset => {
+                        VariableScope::NotYetInScope
+                    }
+                    _ => VariableScope::InScope,
+                }
+            } else if let Ok(Some(mut ranges)) =
+                self.dwarf(debug_info).attr_ranges(&self.unit, start_scope)
+            {
+                let mut scope_ranges = Vec::new();
+                while let Ok(Some(range)) = ranges.next() {
+                    scope_ranges.push(range);
+                }
+                scope_of_ranges(&scope_ranges, program_counter)
+            } else {
+                VariableScope::InScope
+            };
+
+            if scope != VariableScope::InScope {
+                return scope;
+            }
+        }
+
+        let location_list_offset = match entry.attr_value(gimli::DW_AT_location) {
+            Some(AttributeValue::LocationListsRef(offset)) => offset,
+            Some(AttributeValue::DebugLocListsIndex(index)) => {
+                match self.dwarf(debug_info).locations_offset(&self.unit, index) {
+                    Ok(offset) => offset,
+                    Err(_) => return VariableScope::InScope,
+                }
+            }
+            _ => return VariableScope::InScope,
+        };
+        // A location list which cannot be read is reported when the location is evaluated.
+        let Ok(mut locations) = self
+            .dwarf(debug_info)
+            .locations(&self.unit, location_list_offset)
+        else {
+            return VariableScope::InScope;
+        };
+        let mut location_ranges = Vec::new();
+        while let Ok(Some(location)) = locations.next() {
+            location_ranges.push(location.range);
+        }
+        scope_of_ranges(&location_ranges, program_counter)
+    }
+
+    /// Add the statics which are declared in the body of the function at `function_offset` to
+    /// the children of `parent_variable`.
+    ///
+    /// C describes them as variables of the function, so they are found with its other variables,
+    /// but Rust describes them in a namespace which is named after the function, next to it.
+    pub(crate) fn process_function_statics(
+        &self,
+        debug_info: &DebugInfo,
+        function_offset: UnitOffset,
+        parent_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let Some(namespace_offset) =
+            self.function_statics_namespace(debug_info, function_offset)?
+        else {
+            return Ok(());
+        };
+
+        let mut namespace_tree = self.unit.entries_tree(Some(namespace_offset))?;
+        let mut child_nodes = namespace_tree.root()?.children();
+        while let Some(child_node) = child_nodes.next()? {
+            // The namespace also holds the items of nested functions, in namespaces of their own.
+            if child_node.entry().tag() == gimli::DW_TAG_variable {
+                self.process_variable_node(
+                    debug_info,
+                    child_node,
+                    parent_variable,
+                    memory,
+                    cache,
+                    frame_info,
+                )?;
+            }
+        }
+
+        parent_variable.extract_value(memory, cache);
+        cache.update_variable(parent_variable)?;
+
+        Ok(())
+    }
+
+    /// The namespace of the statics which are declared in the body of the Rust function at
+    /// `function_offset`.
+    fn function_statics_namespace(
+        &self,
+        debug_info: &DebugInfo,
+        function_offset: UnitOffset,
+    ) -> Result<Option<UnitOffset>, DebugError> {
+        if self.dwarf_language != gimli::DW_LANG_Rust {
+            return Ok(None);
+        }
+
+        let function = self.unit.entry(function_offset)?;
+        let Some(function_name) = extract_name(debug_info, self, &function)? else {
+            return Ok(None);
+        };
+
+        // An inlined function refers to the declaration of the function, which is next to the
+        // namespace.
+        let declaration_offset = debug_info
+            .resolve_origin_chain(&function, self)
+            .into_iter()
+            .take_while(|(unit_info, _)| std::ptr::eq(*unit_info, self))
+            .last()
+            .map_or(function_offset, |(_, entry)| entry.offset());
+        let Some(container_offset) = self.parent_offset(declaration_offset) else {
+            return Ok(None);
+        };
+
+        let mut container_tree = self.unit.entries_tree(Some(container_offset))?;
+        let mut child_nodes = container_tree.root()?.children();
+        while let Some(child_node) = child_nodes.next()? {
+            let entry = child_node.entry();
+            if entry.tag() == gimli::DW_TAG_namespace
+                && extract_name(debug_info, self, entry)?.as_deref() == Some(function_name.as_str())
+            {
+                return Ok(Some(entry.offset()));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Extract the range information for an array.
     ///
     /// This is expected to be contained in an entry with type `DW_TAG_subrange_type`,
@@ -975,17 +1557,50 @@ impl UnitInfo {
         }
     }
 
+    /// Read the bytes of a union in memory, which are shared by its arms, and return them with their
+    /// address.
+    ///
+    /// Returns `None` if the union is not in memory, is larger than [`MAX_UNION_SNAPSHOT_SIZE`], or
+    /// could not be read. Its arms then read their own bytes.
+    fn read_union_bytes(
+        &self,
+        union_variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+    ) -> Option<(u64, Vec<u8>)> {
+        let VariableLocation::Address(address) = union_variable.memory_location else {
+            return None;
+        };
+        let byte_size = union_variable
+            .byte_size
+            .filter(|&byte_size| byte_size <= MAX_UNION_SNAPSHOT_SIZE)?;
+
+        let mut bytes = vec![0u8; byte_size as usize];
+        memory.read(address, &mut bytes).ok()?;
+
+        Some((address, bytes))
+    }
+
     /// Compute the discriminant value of a DW_TAG_variant variable. If it is not explicitly captured in the DWARF,
     /// then it is the default value.
+    ///
+    /// The `DW_AT_discr_value` is a constant of any size, which is sign extended if the discriminant is `signed`, so
+    /// that it compares equal to the value of the discriminant, see `discriminant_value`.
     pub(crate) fn extract_variant_discriminant(
         &self,
         node: &gimli::EntriesTreeNode<GimliReader>,
         variable: &mut Variable,
+        signed: bool,
     ) -> Result<(), DebugError> {
         variable.role = match node.entry().attr(gimli::DW_AT_discr_value) {
             Some(discr_value_attr) => {
                 let attr_value = discr_value_attr.value();
-                let variant = if let Some(const_value) = attr_value.udata_value() {
+                let signed_value = || attr_value.sdata_value().map(|value| value as u64);
+                let const_value = if signed {
+                    signed_value().or_else(|| attr_value.udata_value())
+                } else {
+                    attr_value.udata_value().or_else(signed_value)
+                };
+                let variant = if let Some(const_value) = const_value {
                     const_value
                 } else {
                     variable.set_value(VariableValue::Error(format!(
@@ -1007,6 +1622,36 @@ impl UnitInfo {
         Ok(())
     }
 
+    /// `true` if the discriminant of the `DW_TAG_variant_part` at `offset` has a signed type, like the `#[repr(i16)]`
+    /// discriminant of a Rust enum.
+    fn is_signed_discriminant(&self, offset: UnitOffset) -> bool {
+        let Ok(variant_part) = self.unit.entry(offset) else {
+            return false;
+        };
+        let Some(AttributeValue::UnitRef(discriminant_offset)) =
+            variant_part.attr_value(gimli::DW_AT_discr)
+        else {
+            return false;
+        };
+        let Ok(discriminant) = self.unit.entry(discriminant_offset) else {
+            return false;
+        };
+        let Some(AttributeValue::UnitRef(type_offset)) = discriminant.attr_value(gimli::DW_AT_type)
+        else {
+            return false;
+        };
+
+        self.resolve_base_type(type_offset)
+            .is_some_and(|base_type| {
+                matches!(
+                    base_type.attr_value(gimli::DW_AT_encoding),
+                    Some(AttributeValue::Encoding(
+                        gimli::DW_ATE_signed | gimli::DW_ATE_signed_char
+                    ))
+                )
+            })
+    }
+
     /// Compute the type (base to complex) of a variable. Only base types have values.
     /// Complex types are references to node trees, that require traversal in similar ways to other DIE's like functions.
     /// This means [`extract_type()`][e] will call the recursive [`process_tree()`][p] method to build an integrated
@@ -1014,6 +1659,8 @@ impl UnitInfo {
     ///
     /// [e]: Self::extract_type()
     /// [p]: Self::process_tree()
+    ///
+    /// Errors are annotated with the location of the type `node` in the debug information.
     #[expect(clippy::too_many_arguments)]
     fn extract_type(
         &self,
@@ -1025,7 +1672,35 @@ impl UnitInfo {
         cache: &mut VariableCache,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
-        let type_name = match self.extract_type_name(debug_info, node) {
+        self.extract_type_impl(
+            debug_info,
+            node,
+            parent_variable,
+            child_variable,
+            memory,
+            cache,
+            frame_info,
+        )
+        .map_err(|error| error.with_context(self.error_context(node)))
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn extract_type_impl(
+        &self,
+        debug_info: &DebugInfo,
+        node: &gimli::DebuggingInformationEntry<GimliReader>,
+        parent_variable: &Variable,
+        child_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let type_name = if node.tag() == gimli::DW_TAG_pointer_type {
+            self.extract_pointer_type_name(debug_info, node)
+        } else {
+            self.extract_type_name(debug_info, node)
+        };
+        let type_name = match type_name {
             Ok(name) => name,
             Err(error) => {
                 let message = format!("Error: evaluating type name: {error:?}");
@@ -1046,6 +1721,11 @@ impl UnitInfo {
                 child_variable.type_name = VariableType::Base(
                     type_name.unwrap_or_else(|| "<unnamed base type>".to_string()),
                 );
+                if let Some(gimli::AttributeValue::Encoding(encoding)) =
+                    node.attr_value(gimli::DW_AT_encoding)
+                {
+                    child_variable.encoding = Some(encoding);
+                }
                 self.process_memory_location(
                     debug_info,
                     node,
@@ -1070,41 +1750,32 @@ impl UnitInfo {
                 match node.attr_value(gimli::DW_AT_type) {
                     Some(gimli::AttributeValue::UnitRef(unit_ref)) => {
                         // NOTE: surprisingly, as opposed to `void*`, this can be a `const void*`.
-                        if !cache.has_children(child_variable) {
-                            let mut referenced_variable =
-                                cache.create_variable(child_variable.variable_key, Some(self))?;
-
-                            // TODO: This is language specific, and should be moved to the language implementations.
-                            referenced_variable.name = match &child_variable.name {
-                                VariableName::Named(name) if name.starts_with("Some ") => {
-                                    VariableName::Named(name.replacen('&', "*", 1))
-                                }
-                                VariableName::Named(name) => {
-                                    VariableName::Named(format!("*{name}"))
-                                }
-                                other => VariableName::Named(format!(
-                                    "Error: Unable to generate name, parent variable does not have a name but is special variable {other:?}"
-                                )),
-                            };
-
-                            let referenced_node = self.unit.entry(unit_ref)?;
-
-                            self.extract_type(
+                        if frame_info.auto_deref_depth == Some(0) {
+                            // The pointer is only followed when its value is requested.
+                            child_variable.variable_node_type =
+                                self.deferred_type_node(node.offset())?;
+                        } else {
+                            self.extract_pointer_target(
                                 debug_info,
-                                &referenced_node,
+                                unit_ref,
                                 child_variable,
-                                &mut referenced_variable,
                                 memory,
                                 cache,
-                                frame_info,
+                                frame_info.follow_pointer(),
                             )?;
-
-                            if matches!(referenced_variable.type_name.inner(), VariableType::Base(name) if name == "()")
-                            {
-                                // Only use this, if it is NOT a unit datatype.
-                                cache.remove_cache_entry(referenced_variable.variable_key)?;
-                            }
                         }
+                        self.set_c_string_pointer_value(
+                            debug_info,
+                            unit_ref,
+                            child_variable,
+                            memory,
+                        );
+                        self.set_function_pointer_value(
+                            debug_info,
+                            unit_ref,
+                            child_variable,
+                            memory,
+                        );
                     }
                     Some(other_attribute_value) => {
                         child_variable.set_value(VariableValue::Error(format!(
@@ -1126,7 +1797,7 @@ impl UnitInfo {
                     }
                 }
             }
-            gimli::DW_TAG_structure_type => {
+            gimli::DW_TAG_structure_type | gimli::DW_TAG_class_type => {
                 self.extract_struct(
                     type_name,
                     debug_info,
@@ -1174,18 +1845,34 @@ impl UnitInfo {
 
                 let mut tree = self.unit.entries_tree(Some(node.offset()))?;
 
-                // Recursively process a child types.
-                self.process_tree(
-                    debug_info,
-                    tree.root()?,
-                    child_variable,
-                    memory,
-                    cache,
-                    frame_info,
-                )?;
-                if child_variable.is_valid() && !cache.has_children(child_variable) {
-                    // Empty structs don't have values.
-                    child_variable.set_value(VariableValue::Valid(child_variable.type_name()));
+                // Each member is an arm of the union. They overlap, so the bytes of the union are
+                // read once, and all the arms are decoded from them.
+                match self.read_union_bytes(child_variable, memory) {
+                    Some((address, bytes)) => self.process_tree(
+                        debug_info,
+                        tree.root()?,
+                        child_variable,
+                        &mut MemorySnapshot::new(memory, address, bytes, self.endianness),
+                        cache,
+                        frame_info,
+                    )?,
+                    None => self.process_tree(
+                        debug_info,
+                        tree.root()?,
+                        child_variable,
+                        memory,
+                        cache,
+                        frame_info,
+                    )?,
+                }
+
+                if child_variable.is_valid() {
+                    let arms = cache.get_children(child_variable.variable_key).count();
+                    let value = match child_variable.byte_size {
+                        Some(byte_size) => format!("union ({arms} arms, {byte_size} bytes)"),
+                        None => format!("union ({arms} arms)"),
+                    };
+                    child_variable.set_value(VariableValue::Valid(value));
                 }
             }
             gimli::DW_TAG_subroutine_type => {
@@ -1198,7 +1885,7 @@ impl UnitInfo {
                                 self.unit.header.entry(&self.unit.abbreviations, unit_ref)?;
 
                             child_variable.type_name =
-                                match extract_name(debug_info, &subroutine_type_node) {
+                                match extract_name(debug_info, self, &subroutine_type_node) {
                                     Ok(Some(name_attr)) => VariableType::Other(name_attr),
                                     Ok(None) => VariableType::Unknown,
                                     Err(error) => VariableType::Other(format!(
@@ -1272,22 +1959,169 @@ impl UnitInfo {
                 ),
             },
 
-            // Do not expand this type.
-            other => {
-                child_variable.set_value(VariableValue::Error(format!(
-                    "<unimplemented: type: {other}>"
-                )));
+            gimli::DW_TAG_string_type => {
+                child_variable.type_name =
+                    VariableType::Base(type_name.unwrap_or_else(|| "string".to_string()));
+                self.process_memory_location(
+                    debug_info,
+                    node,
+                    parent_variable,
+                    child_variable,
+                    memory,
+                    frame_info,
+                )?;
+                self.extract_string_type(
+                    debug_info,
+                    node,
+                    child_variable,
+                    memory,
+                    cache,
+                    frame_info,
+                )?;
+            }
+
+            // Do not expand this type, but show its memory, if it is known.
+            other => {
+                let location = self.process_memory_location(
+                    debug_info,
+                    node,
+                    parent_variable,
+                    child_variable,
+                    memory,
+                    frame_info,
+                );
+                child_variable.set_value(VariableValue::Error(format!(
+                    "<unimplemented: type: {other}>"
+                )));
                 child_variable.type_name = VariableType::Other("unimplemented".to_string());
                 cache.remove_cache_entry_children(child_variable.variable_key)?;
+                match location {
+                    Ok(()) => self.add_raw_bytes(debug_info, child_variable, memory, cache)?,
+                    Err(error) => tracing::debug!(
+                        "Cannot show the raw bytes of {}: {error}",
+                        child_variable.name
+                    ),
+                }
             }
         }
 
         child_variable.extract_value(memory, cache);
+        type_formatter::format_variable(
+            &FormatContext {
+                unit_info: self,
+                debug_info,
+                node,
+                frame_info,
+            },
+            child_variable,
+            memory,
+            cache,
+        )?;
         cache.update_variable(child_variable)?;
 
         Ok(())
     }
 
+    /// Create the variable that `pointer_variable` points to, from the type at `unit_ref`.
+    pub(crate) fn extract_pointer_target(
+        &self,
+        debug_info: &DebugInfo,
+        unit_ref: UnitOffset,
+        pointer_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if cache.has_children(pointer_variable) {
+            return Ok(());
+        }
+
+        let mut referenced_variable =
+            cache.create_variable(pointer_variable.variable_key, Some(self))?;
+
+        referenced_variable.name = match &pointer_variable.name {
+            VariableName::Named(name) => VariableName::Named(format!("*{name}")),
+            other => VariableName::Named(format!(
+                "Error: Unable to generate name, parent variable does not have a name but is special variable {other:?}"
+            )),
+        };
+
+        let referenced_node = self.unit.entry(unit_ref)?;
+
+        self.extract_type(
+            debug_info,
+            &referenced_node,
+            pointer_variable,
+            &mut referenced_variable,
+            memory,
+            cache,
+            frame_info,
+        )?;
+
+        if matches!(referenced_variable.type_name.inner(), VariableType::Base(name) if name == "()")
+        {
+            // Only use this, if it is NOT a unit datatype.
+            cache.remove_cache_entry(referenced_variable.variable_key)?;
+        }
+
+        Ok(())
+    }
+
+    /// `true` if the location of the variable `entry` is a fixed address, which is how the location
+    /// of a variable with static storage is described.
+    fn has_static_storage(
+        &self,
+        debug_info: &DebugInfo,
+        entry: &DebuggingInformationEntry<GimliReader>,
+    ) -> bool {
+        let Some((unit_info, attr)) =
+            debug_info.resolve_attribute(entry, self, gimli::DW_AT_location)
+        else {
+            return false;
+        };
+        let AttributeValue::Exprloc(expression) = attr.value() else {
+            return false;
+        };
+
+        let mut operations = expression.operations(unit_info.unit.encoding());
+        matches!(
+            (operations.next(), operations.next()),
+            (
+                Ok(Some(
+                    gimli::Operation::Address { .. } | gimli::Operation::AddressIndex { .. }
+                )),
+                Ok(None)
+            )
+        )
+    }
+
+    /// `true` if `entry` is an anonymous struct or union member, like the `union { ... };` in a C
+    /// struct, which has no name, and whose type has no name.
+    fn is_anonymous_member(&self, entry: &DebuggingInformationEntry<GimliReader>) -> bool {
+        if entry.tag() != gimli::DW_TAG_member || entry.attr(gimli::DW_AT_name).is_some() {
+            return false;
+        }
+        let Some(AttributeValue::UnitRef(type_offset)) = entry.attr_value(gimli::DW_AT_type) else {
+            return false;
+        };
+
+        self.unit.entry(type_offset).is_ok_and(|type_node| {
+            matches!(
+                type_node.tag(),
+                gimli::DW_TAG_structure_type | gimli::DW_TAG_class_type | gimli::DW_TAG_union_type
+            ) && type_node.attr(gimli::DW_AT_name).is_none()
+        })
+    }
+
+    /// The node type to resolve the children of the type at `type_offset` on demand.
+    fn deferred_type_node(&self, type_offset: UnitOffset) -> Result<VariableNodeType, DebugError> {
+        Ok(if self.supplementary {
+            VariableNodeType::SupplementaryTypeOffset(self.debug_info_offset()?, type_offset)
+        } else {
+            VariableNodeType::TypeOffset(self.debug_info_offset()?, type_offset)
+        })
+    }
+
     #[expect(clippy::too_many_arguments)]
     fn extract_struct(
         &self,
@@ -1300,7 +2134,13 @@ impl UnitInfo {
         cache: &mut VariableCache,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
-        let type_name = type_name.unwrap_or_else(|| "<unnamed struct>".to_string());
+        // The members of an anonymous struct member are resolved now, because they are shown as the
+        // members of the struct that contains it, see `process_tree`.
+        let is_anonymous = type_name.is_none() && child_variable.name == VariableName::Unknown;
+        let type_name = type_name.unwrap_or_else(|| match node.tag() {
+            gimli::DW_TAG_class_type => "<unnamed class>".to_string(),
+            _ => "<unnamed struct>".to_string(),
+        });
         child_variable.type_name = VariableType::Struct(type_name.clone());
         self.process_memory_location(
             debug_info,
@@ -1311,18 +2151,35 @@ impl UnitInfo {
             frame_info,
         )?;
 
+        if let Some(target) = cache.find_pointer_cycle(child_variable) {
+            // The struct was reached through a cycle of pointers, e.g. in a doubly linked list, so
+            // resolving its children would only repeat the same variables.
+            child_variable.set_value(VariableValue::Valid(format!(
+                "<cycle: points back to {} {}>",
+                child_variable.memory_location, target.name
+            )));
+            child_variable.variable_node_type = VariableNodeType::DoNotRecurse;
+            return Ok(());
+        }
+
         if child_variable.memory_location != VariableLocation::Unavailable {
             // The default behaviour is to defer the processing of child types.
-            child_variable.variable_node_type =
-                VariableNodeType::TypeOffset(self.debug_info_offset()?, node.offset());
+            child_variable.variable_node_type = self.deferred_type_node(node.offset())?;
             // In some cases, it really simplifies the UX if we can auto resolve the
             // children and derive a value that is visible at first glance to the user.
-            if self.language.auto_resolve_children(&type_name) {
+            // The children are also resolved when a pointer depth is given, which limits the recursion.
+            if self.language.auto_resolve_children(&type_name)
+                || frame_info.auto_deref_depth.is_some()
+                || is_anonymous
+            {
                 let temp_node_type = std::mem::replace(
                     &mut child_variable.variable_node_type,
                     VariableNodeType::RecurseToBaseType,
                 );
 
+                // The children look for pointer cycles in the cache, so it has to know where this
+                // struct is.
+                cache.update_variable(child_variable)?;
                 let mut tree = self.unit.entries_tree(Some(node.offset()))?;
 
                 self.process_tree(
@@ -1386,18 +2243,56 @@ impl UnitInfo {
 
                 // Now we can explode the array members.
                 if let Ok(array_member_type_node) = self.unit.entry(unit_ref) {
-                    // - Next, process this DW_TAG_array_type's DW_AT_type full tree.
-                    // - We have to do this repeatedly, for every array member in the range.
-                    // - We have to do this recursively because some compilers encode nested arrays as multiple subranges on the same node.
-                    self.expand_array_members(
-                        debug_info,
-                        &array_member_type_node,
-                        cache,
-                        child_variable,
-                        memory,
-                        &subranges,
-                        frame_info,
-                    )?;
+                    match subranges.as_slice() {
+                        // Long arrays are only expanded when their members are requested. This
+                        // is limited to single dimensional arrays, which start at index 0.
+                        [range]
+                            if !self.supplementary
+                                && range.start == 0
+                                && range.end > debug_info.max_eager_array_length as u64 =>
+                        {
+                            self.defer_array_members(
+                                debug_info,
+                                &array_member_type_node,
+                                cache,
+                                child_variable,
+                                memory,
+                                range.end,
+                                frame_info,
+                            )?;
+                        }
+                        _ => {
+                            // - Next, process this DW_TAG_array_type's DW_AT_type full tree.
+                            // - We have to do this repeatedly, for every array member in the range.
+                            // - We have to do this recursively because some compilers encode nested arrays as multiple subranges on the same node.
+                            self.expand_array_members(
+                                debug_info,
+                                &array_member_type_node,
+                                cache,
+                                child_variable,
+                                memory,
+                                &subranges,
+                                frame_info,
+                            )?;
+                        }
+                    }
+
+                    if let [range] = subranges.as_slice() {
+                        self.set_byte_preview(
+                            debug_info,
+                            &array_member_type_node,
+                            child_variable,
+                            range,
+                            memory,
+                        );
+                        self.set_c_string_value(
+                            debug_info,
+                            &array_member_type_node,
+                            child_variable,
+                            range,
+                            memory,
+                        );
+                    }
                 };
             }
             Some(other_attribute_value) => {
@@ -1413,218 +2308,819 @@ impl UnitInfo {
             }
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn extract_enumeration_type(
+        &self,
+        child_variable: &mut Variable,
+        type_name: Option<String>,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        parent_variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+        frame_info: StackFrameInfo,
+    ) -> Result<(), DebugError> {
+        child_variable.type_name =
+            VariableType::Enum(type_name.unwrap_or_else(|| "<unnamed enum>".to_string()));
+
+        self.process_memory_location(
+            debug_info,
+            node,
+            parent_variable,
+            child_variable,
+            memory,
+            frame_info,
+        )?;
+
+        let mut tree = self.unit.entries_tree(Some(node.offset()))?;
+        let enumerator_values = self.process_enumerator(debug_info, tree.root()?)?;
+
+        if !(parent_variable.is_valid() && child_variable.is_valid()) {
+            return Ok(());
+        }
+
+        let value =
+            match self.read_enumeration_value(node, &enumerator_values, child_variable, memory) {
+                Ok((bits, signed)) => {
+                    let size = child_variable.byte_size.unwrap_or(1) as u32;
+                    let mask = u128::MAX >> (128 - size * 8);
+                    let enumerator = enumerator_values.iter().find(|(_name, value)| {
+                        let enumerator_bits = match value {
+                            VariableValue::U64(value) => *value as u128,
+                            VariableValue::I64(value) => *value as i128 as u128,
+                            _ => return false,
+                        };
+                        enumerator_bits & mask == bits
+                    });
+
+                    match enumerator {
+                        Some((name, _value)) => self
+                            .language
+                            .format_enum_value(&child_variable.type_name, name),
+                        None => {
+                            let value = if signed && sign_extend(bits, size * 8) < 0 {
+                                format!("-{:#X}", sign_extend(bits, size * 8).unsigned_abs())
+                            } else {
+                                format!("{bits:#X}")
+                            };
+                            VariableValue::Valid(format!(
+                                "{}(<unknown: {value}>)",
+                                child_variable
+                                    .type_name
+                                    .display_name(self.language.as_ref())
+                            ))
+                        }
+                    }
+                }
+                Err(error) => VariableValue::Error(format!("{error:?}")),
+            };
+
+        child_variable.set_value(value);
+
+        Ok(())
+    }
+
+    /// Read the bits of the value of an enumeration, which is as wide as its `DW_AT_byte_size`, and
+    /// whether they are signed.
+    ///
+    /// The signedness comes from the `DW_AT_encoding` of the enumeration, or of the type it is
+    /// based on. Enumerations without either are signed if they have a negative enumerator.
+    fn read_enumeration_value(
+        &self,
+        node: &DebuggingInformationEntry<GimliReader>,
+        enumerator_values: &[(VariableName, VariableValue)],
+        child_variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<(u128, bool), DebugError> {
+        let size = child_variable.byte_size.unwrap_or(1);
+        if !(1..=16).contains(&size) {
+            return Err(DebugError::WarnAndContinue {
+                message: format!("Unimplemented: Enumeration of {size} bytes"),
+            });
+        }
+
+        let encoding = node.attr_value(gimli::DW_AT_encoding).or_else(|| {
+            let Some(AttributeValue::UnitRef(type_offset)) = node.attr_value(gimli::DW_AT_type)
+            else {
+                return None;
+            };
+            self.resolve_base_type(type_offset)?
+                .attr_value(gimli::DW_AT_encoding)
+        });
+        let signed = match encoding {
+            Some(AttributeValue::Encoding(encoding)) => {
+                matches!(encoding, gimli::DW_ATE_signed | gimli::DW_ATE_signed_char)
+            }
+            _ => enumerator_values
+                .iter()
+                .any(|(_name, value)| matches!(value, VariableValue::I64(value) if *value < 0)),
+        };
+
+        let bits = read_unsigned(child_variable, memory, size as usize)?;
+
+        Ok((bits, signed))
+    }
+
+    /// Extract the different variants of an enumeration
+    ///
+    /// This is used for C-style enums, where the enum is an integer type,
+    /// and all the different variants are different integer values.
+    fn process_enumerator(
+        &self,
+        debug_info: &DebugInfo,
+        parent_node: gimli::EntriesTreeNode<GimliReader>,
+    ) -> Result<Vec<(VariableName, VariableValue)>, DebugError> {
+        let mut enumerator_values = Vec::new();
+
+        let mut child_nodes = parent_node.children();
+        while let Some(child_node) = child_nodes.next()? {
+            match child_node.entry().tag() {
+                gimli::DW_TAG_enumerator => {
+                    let attributes_entry = child_node.entry();
+
+                    let name_result = extract_name(debug_info, self, attributes_entry);
+
+                    let Some(attr_value) = attributes_entry.attr_value(gimli::DW_AT_const_value)
+                    else {
+                        // Ignore enumerators without a value.
+                        continue;
+                    };
+                    let variable_value = if let Some(const_value) = attr_value.udata_value() {
+                        VariableValue::U64(const_value)
+                    } else if let Some(const_value) = attr_value.sdata_value() {
+                        VariableValue::I64(const_value)
+                    } else {
+                        VariableValue::Error(format!(
+                            "Unimplemented: Attribute Value for DW_AT_const_value: {attr_value:?}"
+                        ))
+                    };
+
+                    let enumerator_name = if let Ok(Some(ref name)) = name_result {
+                        name.to_string()
+                    } else {
+                        tracing::warn!("Enumerator has no name");
+
+                        format!("<unknown enumerator {}", enumerator_values.len())
+                    };
+
+                    enumerator_values.push((VariableName::Named(enumerator_name), variable_value))
+                }
+                // Function implemented on the enum type, ignored here.
+                gimli::DW_TAG_subprogram => (),
+                other => {
+                    tracing::debug!("Ignoring tag {other} under DW_TAG_enumeration_type");
+                }
+            }
+        }
+
+        Ok(enumerator_values)
+    }
+
+    /// Create child variable entries to represent array members and their values.
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn expand_array_members(
+        &self,
+        debug_info: &DebugInfo,
+        array_member_type_node: &DebuggingInformationEntry<GimliReader>,
+        cache: &mut VariableCache,
+        array_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        subranges: &[Range<u64>],
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        let Some((current_range, remaining_ranges)) = subranges.split_first() else {
+            array_variable.set_value(VariableValue::Error(
+                "Error processing range for array, unexpected empty range. \
+                    This is a known issue, see https://github.com/probe-rs/probe-rs/issues/2687"
+                    .to_string(),
+            ));
+            return Ok(());
+        };
+
+        // We need to process at least one element to get the array's type right.
+        let explode_range = if current_range.is_empty() {
+            0..1
+        } else {
+            current_range.clone()
+        };
+
+        for member_index in explode_range.clone() {
+            let mut array_member_variable =
+                cache.create_variable(array_variable.variable_key, Some(self))?;
+            array_member_variable.name = VariableName::Indexed(member_index);
+            array_member_variable.source_location = array_variable.source_location.clone();
+
+            // Set the byte size and push the element to its correct location.
+            // This call only sets size if:
+            //  - The parent array's size is known (after processing its first index)
+            //  - Or the member is a leaf member (i.e. not an array)
+            // The first index of the parent array will receive its binary size after processing
+            // its children.
+            self.process_memory_location(
+                debug_info,
+                array_member_type_node,
+                array_variable,
+                &mut array_member_variable,
+                memory,
+                frame_info,
+            )?;
+
+            if !remaining_ranges.is_empty() {
+                // Recursively process the nested array and place
+                // its items under the current variable.
+                self.expand_array_members(
+                    debug_info,
+                    array_member_type_node,
+                    cache,
+                    &mut array_member_variable,
+                    memory,
+                    remaining_ranges,
+                    frame_info,
+                )?;
+                if let [range] = remaining_ranges {
+                    self.set_byte_preview(
+                        debug_info,
+                        array_member_type_node,
+                        &mut array_member_variable,
+                        range,
+                        memory,
+                    );
+                    self.set_c_string_value(
+                        debug_info,
+                        array_member_type_node,
+                        &mut array_member_variable,
+                        range,
+                        memory,
+                    );
+                }
+            } else {
+                self.extract_type(
+                    debug_info,
+                    array_member_type_node,
+                    array_variable,
+                    &mut array_member_variable,
+                    memory,
+                    cache,
+                    frame_info,
+                )?;
+            }
+
+            if member_index == explode_range.start {
+                let item_count = current_range.clone().count();
+
+                array_variable.type_name = VariableType::Array {
+                    count: item_count,
+                    item_type_name: Box::new(array_member_variable.type_name.clone()),
+                };
+                if let Some(item_byte_size) = array_member_variable.byte_size {
+                    array_variable.byte_size = Some(item_byte_size * item_count as u64);
+                }
+            }
+
+            array_member_variable.extract_value(memory, cache);
+            cache.update_variable(&array_member_variable)?;
+        }
+
+        // We want to remove the child entry if the array is empty. It was needed to process the
+        // array type, but it doesn't actually exist.
+        if current_range.is_empty() {
+            cache.remove_cache_entry_children(array_variable.variable_key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Show the first bytes of an array of bytes, like a `[u8; 64]`, as its value, see
+    /// [`DebugInfoOptions::max_byte_preview_length`]. The members remain its children.
+    fn set_byte_preview(
+        &self,
+        debug_info: &DebugInfo,
+        array_member_type_node: &DebuggingInformationEntry<GimliReader>,
+        array_variable: &mut Variable,
+        range: &Range<u64>,
+        memory: &mut dyn MemoryInterface,
+    ) {
+        let VariableLocation::Address(address) = array_variable.memory_location else {
+            return;
+        };
+        if !array_variable.value.is_empty() {
+            return;
+        }
+
+        if let Some(preview) = self.byte_preview(
+            debug_info,
+            array_member_type_node.offset(),
+            address,
+            range.end - range.start,
+            memory,
+        ) {
+            array_variable.set_value(VariableValue::Valid(preview));
+        }
+    }
+
+    /// Show a `DW_TAG_string_type`, like a Fortran `CHARACTER` variable, as its text, see
+    /// [`DebugInfoOptions::max_string_length`]. Its bytes are shown as its `<raw bytes>` child, see
+    /// [`Self::add_raw_bytes`].
+    fn extract_string_type(
+        &self,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        string_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !string_variable.value.is_empty() {
+            // The location of the string could not be determined, and the value is the reason.
+            return Ok(());
+        }
+        let VariableLocation::Address(address) = string_variable.memory_location else {
+            string_variable.set_value(VariableValue::Error(format!(
+                "<unsupported location of a string: {}>",
+                string_variable.memory_location
+            )));
+            return Ok(());
+        };
+
+        let Some(length) = self.string_length(debug_info, node, memory, frame_info) else {
+            string_variable.byte_size = None;
+            string_variable.set_value(VariableValue::Error(
+                "<unknown length of a string>".to_string(),
+            ));
+            return Ok(());
+        };
+        string_variable.byte_size = Some(length);
+
+        match language::value::read_text(
+            memory,
+            address,
+            length as usize,
+            debug_info.max_string_length,
+        ) {
+            Ok(text) => string_variable.set_value(VariableValue::String(text)),
+            Err(error) => string_variable.set_value(VariableValue::Error(format!(
+                "<cannot read the string at {address:#010x}: {error}>"
+            ))),
+        }
+
+        self.add_raw_bytes(debug_info, string_variable, memory, cache)
+    }
+
+    /// The length in bytes of the string type `node`. It is either the size of the type, or the
+    /// value of `DW_AT_string_length`, which is the location of the length, or the variable which
+    /// holds it.
+    fn string_length(
+        &self,
+        debug_info: &DebugInfo,
+        node: &DebuggingInformationEntry<GimliReader>,
+        memory: &mut dyn MemoryInterface,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Option<u64> {
+        let Some(string_length) = node.attr_value(gimli::DW_AT_string_length) else {
+            return extract_byte_size(node);
+        };
+
+        // Before DWARF 5, the size of the length is the size of the type.
+        let length_size = node
+            .attr_value(gimli::DW_AT_string_length_byte_size)
+            .and_then(|size| size.udata_value())
+            .or_else(|| extract_byte_size(node))
+            .unwrap_or(u64::from(self.unit.encoding().address_size));
+
+        let location = match string_length {
+            AttributeValue::Exprloc(expression) => {
+                self.evaluate_expression(debug_info, memory, expression, frame_info)
+            }
+            AttributeValue::UnitRef(offset) => self
+                .unit
+                .entry(offset)
+                .map_err(DebugError::from)
+                .and_then(|length_node| {
+                    self.extract_location(
+                        debug_info,
+                        &length_node,
+                        &VariableLocation::Unknown,
+                        memory,
+                        frame_info,
+                    )
+                }),
+            other => return other.udata_value(),
+        };
+
+        match location {
+            Ok(ExpressionResult::Value(value)) => value.as_u64(),
+            Ok(ExpressionResult::Location(VariableLocation::Address(address))) => {
+                let mut bytes = vec![0; length_size.min(8) as usize];
+                match memory.read(address, &mut bytes) {
+                    Ok(()) => Some(u128_from_target_bytes(&bytes, self.endianness) as u64),
+                    Err(error) => {
+                        tracing::debug!(
+                            "Cannot read the length of a string at {address:#010x}: {error}"
+                        );
+                        None
+                    }
+                }
+            }
+            Ok(ExpressionResult::Location(VariableLocation::RegisterValue(value))) => {
+                value.try_into().ok()
+            }
+            Ok(_) => None,
+            Err(error) => {
+                tracing::debug!("Cannot evaluate the length of a string: {error}");
+                None
+            }
+        }
+    }
+
+    /// Add the memory of `variable` as a `<raw bytes>` child, so it can be inspected, e.g. when its
+    /// type can't be decoded. Its value is a preview of the bytes, like that of a byte array, and its
+    /// children are the words of up to [`MAX_RAW_BYTES`] bytes, in hexadecimal.
+    ///
+    /// Nothing is added if the address or the size of the variable is not known.
+    fn add_raw_bytes(
+        &self,
+        debug_info: &DebugInfo,
+        variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+        cache: &mut VariableCache,
+    ) -> Result<(), DebugError> {
+        let (VariableLocation::Address(address), Some(byte_size @ 1..)) =
+            (&variable.memory_location, variable.byte_size)
+        else {
+            return Ok(());
+        };
+        let address = *address;
+
+        let mut bytes = vec![0; byte_size.min(MAX_RAW_BYTES) as usize];
+        if let Err(error) = memory.read(address, &mut bytes) {
+            tracing::debug!("Cannot read the raw bytes at {address:#010x}: {error}");
+            return Ok(());
+        }
+
+        let raw_bytes_key = cache
+            .create_variable(variable.variable_key, Some(self))?
+            .variable_key;
+        cache.modify_variable(raw_bytes_key, |raw_bytes| {
+            raw_bytes.name = VariableName::Named("<raw bytes>".to_string());
+            raw_bytes.type_name = VariableType::Other(format!("[u8; {byte_size}]"));
+            raw_bytes.memory_location = VariableLocation::Address(address);
+            raw_bytes.byte_size = Some(byte_size);
+            raw_bytes.variable_node_type = VariableNodeType::DoNotRecurse;
+            if debug_info.max_byte_preview_length > 0 {
+                let preview_length = bytes.len().min(debug_info.max_byte_preview_length);
+                raw_bytes.set_value(VariableValue::Valid(language::value::format_byte_preview(
+                    &bytes[..preview_length],
+                    byte_size,
+                )));
+            }
+        })?;
+
+        for (index, word) in bytes.chunks(RAW_WORD_SIZE).enumerate() {
+            let value = match self.endianness {
+                RunTimeEndian::Little => word_value(word.iter().rev()),
+                RunTimeEndian::Big => word_value(word.iter()),
+            };
+
+            let raw_word_key = cache
+                .create_variable(raw_bytes_key, Some(self))?
+                .variable_key;
+            cache.modify_variable(raw_word_key, |raw_word| {
+                raw_word.name = VariableName::Indexed(index as u64);
+                raw_word.type_name = VariableType::Other("raw word".to_string());
+                raw_word.memory_location =
+                    VariableLocation::Address(address + (index * RAW_WORD_SIZE) as u64);
+                raw_word.byte_size = Some(word.len() as u64);
+                raw_word.variable_node_type = VariableNodeType::DoNotRecurse;
+                raw_word.set_value(VariableValue::Valid(format!(
+                    "{value:#0width$x}",
+                    width = 2 + 2 * word.len()
+                )));
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// The first bytes of the `length` members at `address`, in hexadecimal and as ASCII text, if
+    /// the members are bytes, like the `u8` of a `&[u8]`. The bytes are read at once.
+    pub(crate) fn byte_preview(
+        &self,
+        debug_info: &DebugInfo,
+        member_type_offset: UnitOffset,
+        address: u64,
+        length: u64,
+        memory: &mut dyn MemoryInterface,
+    ) -> Option<String> {
+        if length == 0
+            || debug_info.max_byte_preview_length == 0
+            || !self.is_byte_type(debug_info, member_type_offset)
+        {
+            return None;
+        }
+
+        language::value::read_byte_preview(
+            memory,
+            address,
+            length,
+            debug_info.max_byte_preview_length,
+        )
+        .inspect_err(|error| tracing::debug!("Cannot read the bytes at {address:#010x}: {error}"))
+        .ok()
+    }
+
+    /// `true` if the type at `offset` is an unsigned byte, like a Rust `u8` or a C `uint8_t`,
+    /// after following typedefs and qualifiers.
+    ///
+    /// A C `char` is not a byte, even where it is unsigned, because `char` arrays are text.
+    fn is_byte_type(&self, debug_info: &DebugInfo, offset: UnitOffset) -> bool {
+        let Some(node) = self.resolve_base_type(offset) else {
+            return false;
+        };
+
+        extract_byte_size(&node) == Some(1)
+            && match node.attr_value(gimli::DW_AT_encoding) {
+                Some(AttributeValue::Encoding(gimli::DW_ATE_unsigned)) => true,
+                Some(AttributeValue::Encoding(gimli::DW_ATE_unsigned_char)) => !matches!(
+                    extract_name(debug_info, self, &node),
+                    Ok(Some(name)) if name == "char"
+                ),
+                _ => false,
+            }
+    }
+
+    /// Show a C `char` array as its text, up to the first NUL character, see
+    /// [`DebugInfoOptions::max_string_length`]. The members remain its children.
+    fn set_c_string_value(
+        &self,
+        debug_info: &DebugInfo,
+        array_member_type_node: &DebuggingInformationEntry<GimliReader>,
+        array_variable: &mut Variable,
+        range: &Range<u64>,
+        memory: &mut dyn MemoryInterface,
+    ) {
+        let VariableLocation::Address(address) = array_variable.memory_location else {
+            return;
+        };
+        if !array_variable.value.is_empty()
+            || !self.is_c_char_type(debug_info, array_member_type_node.offset())
+        {
+            return;
+        }
+
+        if let Ok(text) = language::value::read_c_string(
+            memory,
+            address,
+            Some((range.end - range.start) as usize),
+            debug_info.max_string_length,
+        )
+        .inspect_err(|error| tracing::debug!("Cannot read the string at {address:#010x}: {error}"))
+        {
+            array_variable.set_value(VariableValue::String(text));
+        }
+    }
+
+    /// Show a C `char` pointer as the text it points to, up to the first NUL character, see
+    /// [`DebugInfoOptions::max_string_length`]. Null pointers, and pointers to memory which cannot be
+    /// read, keep their address as their value.
+    fn set_c_string_pointer_value(
+        &self,
+        debug_info: &DebugInfo,
+        pointee_offset: UnitOffset,
+        pointer_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+    ) {
+        if !pointer_variable.value.is_empty() || !self.is_c_char_type(debug_info, pointee_offset) {
+            return;
+        }
+        let Some(address) = self.read_pointer(pointer_variable, memory) else {
+            return;
+        };
+
+        if let Ok(text) =
+            language::value::read_c_string(memory, address, None, debug_info.max_string_length)
+                .inspect_err(|error| {
+                    tracing::debug!("Cannot read the string at {address:#010x}: {error}")
+                })
+        {
+            pointer_variable.set_value(VariableValue::String(text));
+        }
+    }
+
+    /// Show a function pointer as its address, followed by the name of the function it points to,
+    /// like `0x08001235 (uart_rx_handler)`. Null pointers, and pointers which are not in a function,
+    /// keep their default value.
+    fn set_function_pointer_value(
+        &self,
+        debug_info: &DebugInfo,
+        pointee_offset: UnitOffset,
+        pointer_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+    ) {
+        let is_function = self
+            .unit
+            .entry(pointee_offset)
+            .is_ok_and(|pointee| pointee.tag() == gimli::DW_TAG_subroutine_type);
+        if !pointer_variable.value.is_empty() || !is_function {
+            return;
+        }
+        let Some(address) = self.read_pointer(pointer_variable, memory) else {
+            return;
+        };
+
+        // The outermost function is the one that contains the code, and not a function which is
+        // inlined at its start.
+        let function_name = debug_info
+            .get_function_dies(address & debug_info.code_address_mask)
+            .ok()
+            .and_then(|(_, functions)| functions.first()?.function_name(debug_info));
+        if let Some(function_name) = function_name {
+            pointer_variable.set_value(VariableValue::Valid(format!(
+                "{address:#010X} ({function_name})"
+            )));
+        }
+    }
+
+    /// The address that `pointer_variable` holds, or `None` if it is null or cannot be read.
+    fn read_pointer(
+        &self,
+        pointer_variable: &Variable,
+        memory: &mut dyn MemoryInterface,
+    ) -> Option<u64> {
+        let pointer_size = pointer_variable
+            .byte_size
+            .unwrap_or(u64::from(self.unit.encoding().address_size))
+            .min(8) as usize;
+
+        match read_unsigned(pointer_variable, memory, pointer_size) {
+            Ok(0) | Err(_) => None,
+            Ok(address) => Some(address as u64),
+        }
+    }
+
+    /// `true` if the type at `offset` is a C `char`, after following typedefs and qualifiers, in a
+    /// language where arrays of `char` and pointers to `char` hold NUL terminated strings.
+    fn is_c_char_type(&self, debug_info: &DebugInfo, offset: UnitOffset) -> bool {
+        if !self.language.has_c_strings() {
+            return false;
+        }
+        let Some(node) = self.resolve_base_type(offset) else {
+            return false;
+        };
+
+        extract_byte_size(&node) == Some(1)
+            && matches!(extract_name(debug_info, self, &node), Ok(Some(name)) if name == "char")
+    }
+
+    /// The `DW_TAG_base_type` of the type at `offset`, after following typedefs and qualifiers.
+    fn resolve_base_type(
+        &self,
+        mut offset: UnitOffset,
+    ) -> Option<DebuggingInformationEntry<GimliReader>> {
+        for _ in 0..MAX_TYPE_DEPTH {
+            let node = self.unit.entry(offset).ok()?;
+            match node.tag() {
+                gimli::DW_TAG_base_type => return Some(node),
+                gimli::DW_TAG_typedef
+                | gimli::DW_TAG_const_type
+                | gimli::DW_TAG_volatile_type
+                | gimli::DW_TAG_restrict_type
+                | gimli::DW_TAG_atomic_type => {
+                    let Some(AttributeValue::UnitRef(type_offset)) =
+                        node.attr_value(gimli::DW_AT_type)
+                    else {
+                        return None;
+                    };
+                    offset = type_offset;
+                }
+                _ => return None,
+            }
+        }
+
+        None
     }
 
+    /// Set the type of a long array, and defer the creation of its members to
+    /// [`DebugInfo::cache_deferred_variables`], see [`VariableNodeType::ArrayMembers`].
     #[expect(clippy::too_many_arguments)]
-    fn extract_enumeration_type(
+    fn defer_array_members(
         &self,
-        child_variable: &mut Variable,
-        type_name: Option<String>,
         debug_info: &DebugInfo,
-        node: &DebuggingInformationEntry<GimliReader>,
-        parent_variable: &Variable,
+        array_member_type_node: &DebuggingInformationEntry<GimliReader>,
+        cache: &mut VariableCache,
+        array_variable: &mut Variable,
         memory: &mut dyn MemoryInterface,
-        frame_info: StackFrameInfo,
+        member_count: u64,
+        frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
-        child_variable.type_name =
-            VariableType::Enum(type_name.unwrap_or_else(|| "<unnamed enum>".to_string()));
-
-        self.process_memory_location(
+        // The first member tells us the type and size of the members, but it is discarded, like
+        // the members of an empty array.
+        self.expand_array_members(
             debug_info,
-            node,
-            parent_variable,
-            child_variable,
+            array_member_type_node,
+            cache,
+            array_variable,
             memory,
+            std::slice::from_ref(&(0..1)),
             frame_info,
         )?;
-
-        let mut tree = self.unit.entries_tree(Some(node.offset()))?;
-        let enumerator_values = self.process_enumerator(debug_info, tree.root()?)?;
-
-        if !(parent_variable.is_valid() && child_variable.is_valid()) {
-            return Ok(());
-        }
-
-        let value = if let VariableLocation::Address(address) = child_variable.memory_location {
-            // NOTE: hard-coding value of variable.byte_size to 1 ... replace with code if necessary.
-            let mut buff = 0u8;
-            memory.read(address, std::slice::from_mut(&mut buff))?;
-            let this_enum_const_value = buff.to_string();
-
-            let enumerator_value = match enumerator_values
-                .iter()
-                .find(|(_name, value)| value.to_string() == this_enum_const_value)
-            {
-                Some((name, _value)) => name,
-                None => &VariableName::Named("<Error: Unresolved enum value>".to_string()),
+        let member_byte_size = cache
+            .get_children(array_variable.variable_key)
+            .next()
+            .and_then(|member| member.byte_size);
+        cache.remove_cache_entry_children(array_variable.variable_key)?;
+
+        if let VariableType::Array { item_type_name, .. } = &array_variable.type_name {
+            array_variable.type_name = VariableType::Array {
+                count: member_count as usize,
+                item_type_name: item_type_name.clone(),
             };
-
-            self.language
-                .format_enum_value(&child_variable.type_name, enumerator_value)
-        } else {
-            VariableValue::Error(format!(
-                "Unsupported variable location {:?}",
-                child_variable.memory_location
-            ))
-        };
-
-        child_variable.set_value(value);
+        }
+        array_variable.byte_size = member_byte_size.map(|byte_size| byte_size * member_count);
+        array_variable.variable_node_type = VariableNodeType::ArrayMembers(
+            self.debug_info_offset()?,
+            array_member_type_node.offset(),
+        );
 
         Ok(())
     }
 
-    /// Extract the different variants of an enumeration
+    /// Create the `count` members of a sequence, e.g. the elements of a Rust `Vec<T>`, which are
+    /// stored at `address`, as the children of `sequence_variable`.
     ///
-    /// This is used for C-style enums, where the enum is an integer type,
-    /// and all the different variants are different integer values.
-    fn process_enumerator(
+    /// Long sequences are deferred like long arrays, see [`VariableNodeType::SequenceMembers`].
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn extract_sequence_members(
         &self,
         debug_info: &DebugInfo,
-        parent_node: gimli::EntriesTreeNode<GimliReader>,
-    ) -> Result<Vec<(VariableName, VariableValue)>, DebugError> {
-        let mut enumerator_values = Vec::new();
-
-        let mut child_nodes = parent_node.children();
-        while let Some(child_node) = child_nodes.next()? {
-            match child_node.entry().tag() {
-                gimli::DW_TAG_enumerator => {
-                    let attributes_entry = child_node.entry();
-
-                    let name_result = extract_name(debug_info, attributes_entry);
-
-                    let Some(attr_value) = attributes_entry.attr_value(gimli::DW_AT_const_value)
-                    else {
-                        // Ignore enumerators without a value.
-                        continue;
-                    };
-                    let variable_value = if let Some(const_value) = attr_value.udata_value() {
-                        VariableValue::Valid(const_value.to_string())
-                    } else if let Some(const_value) = attr_value.sdata_value() {
-                        VariableValue::Valid(const_value.to_string())
-                    } else {
-                        VariableValue::Error(format!(
-                            "Unimplemented: Attribute Value for DW_AT_const_value: {attr_value:?}"
-                        ))
-                    };
-
-                    let enumerator_name = if let Ok(Some(ref name)) = name_result {
-                        name.to_string()
-                    } else {
-                        tracing::warn!("Enumerator has no name");
-
-                        format!("<unknown enumerator {}", enumerator_values.len())
-                    };
-
-                    enumerator_values.push((VariableName::Named(enumerator_name), variable_value))
-                }
-                // Function implemented on the enum type, ignored here.
-                gimli::DW_TAG_subprogram => (),
-                other => {
-                    tracing::debug!("Ignoring tag {other} under DW_TAG_enumeration_type");
-                }
-            }
+        member_type_node: &DebuggingInformationEntry<GimliReader>,
+        cache: &mut VariableCache,
+        sequence_variable: &mut Variable,
+        memory: &mut dyn MemoryInterface,
+        address: u64,
+        count: u64,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<(), DebugError> {
+        if !self.supplementary && count > debug_info.max_eager_array_length as u64 {
+            sequence_variable.variable_node_type = VariableNodeType::SequenceMembers(
+                self.debug_info_offset()?,
+                member_type_node.offset(),
+                address,
+                count,
+            );
+            return Ok(());
         }
 
-        Ok(enumerator_values)
+        self.expand_sequence_members(
+            debug_info,
+            member_type_node,
+            cache,
+            sequence_variable,
+            memory,
+            address,
+            0..count,
+            frame_info,
+        )
     }
 
-    /// Create child variable entries to represent array members and their values.
+    /// Create the `members` of a sequence now, see [`Self::extract_sequence_members`].
     #[expect(clippy::too_many_arguments)]
-    pub(crate) fn expand_array_members(
+    pub(crate) fn expand_sequence_members(
         &self,
         debug_info: &DebugInfo,
-        array_member_type_node: &DebuggingInformationEntry<GimliReader>,
+        member_type_node: &DebuggingInformationEntry<GimliReader>,
         cache: &mut VariableCache,
-        array_variable: &mut Variable,
+        sequence_variable: &Variable,
         memory: &mut dyn MemoryInterface,
-        subranges: &[Range<u64>],
+        address: u64,
+        members: Range<u64>,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<(), DebugError> {
-        let Some((current_range, remaining_ranges)) = subranges.split_first() else {
-            array_variable.set_value(VariableValue::Error(
-                "Error processing range for array, unexpected empty range. \
-                    This is a known issue, see https://github.com/probe-rs/probe-rs/issues/2687"
-                    .to_string(),
-            ));
+        // The address of an empty sequence may be dangling.
+        if members.is_empty() {
             return Ok(());
-        };
-
-        // We need to process at least one element to get the array's type right.
-        let explode_range = if current_range.is_empty() {
-            0..1
-        } else {
-            current_range.clone()
-        };
-
-        for member_index in explode_range.clone() {
-            let mut array_member_variable =
-                cache.create_variable(array_variable.variable_key, Some(self))?;
-            array_member_variable.name = VariableName::Indexed(member_index);
-            array_member_variable.source_location = array_variable.source_location.clone();
-
-            // Set the byte size and push the element to its correct location.
-            // This call only sets size if:
-            //  - The parent array's size is known (after processing its first index)
-            //  - Or the member is a leaf member (i.e. not an array)
-            // The first index of the parent array will receive its binary size after processing
-            // its children.
-            self.process_memory_location(
-                debug_info,
-                array_member_type_node,
-                array_variable,
-                &mut array_member_variable,
-                memory,
-                frame_info,
-            )?;
-
-            if !remaining_ranges.is_empty() {
-                // Recursively process the nested array and place
-                // its items under the current variable.
-                self.expand_array_members(
-                    debug_info,
-                    array_member_type_node,
-                    cache,
-                    &mut array_member_variable,
-                    memory,
-                    remaining_ranges,
-                    frame_info,
-                )?;
-            } else {
-                self.extract_type(
-                    debug_info,
-                    array_member_type_node,
-                    array_variable,
-                    &mut array_member_variable,
-                    memory,
-                    cache,
-                    frame_info,
-                )?;
-            }
-
-            if member_index == explode_range.start {
-                let item_count = current_range.clone().count();
-
-                array_variable.type_name = VariableType::Array {
-                    count: item_count,
-                    item_type_name: Box::new(array_member_variable.type_name.clone()),
-                };
-                if let Some(item_byte_size) = array_member_variable.byte_size {
-                    array_variable.byte_size = Some(item_byte_size * item_count as u64);
-                }
-            }
-
-            array_member_variable.extract_value(memory, cache);
-            cache.update_variable(&array_member_variable)?;
         }
 
-        // We want to remove the child entry if the array is empty. It was needed to process the
-        // array type, but it doesn't actually exist.
-        if current_range.is_empty() {
-            cache.remove_cache_entry_children(array_variable.variable_key)?;
-        }
+        // The members are located like the members of an array at `address`, but they belong to
+        // the sequence variable, so the array is only a template for their parent.
+        let mut array_variable = sequence_variable.clone();
+        array_variable.memory_location = VariableLocation::Address(address);
+        array_variable.byte_size = None;
+        array_variable.type_name = VariableType::Array {
+            item_type_name: Box::new(VariableType::Unknown),
+            count: members.end as usize,
+        };
 
-        Ok(())
+        self.expand_array_members(
+            debug_info,
+            member_type_node,
+            cache,
+            &mut array_variable,
+            memory,
+            std::slice::from_ref(&members),
+            frame_info,
+        )
     }
 
     /// Process a memory location for a variable, by first evaluating the `byte_size`, and then calling the `self.extract_location`.
@@ -1643,7 +3139,7 @@ impl UnitInfo {
             .byte_size
             .or_else(|| extract_byte_size(node_die))
             .or_else(|| {
-                if let VariableType::Array { count, .. } = parent_variable.type_name {
+                if let VariableType::Array { count, .. } = *parent_variable.type_name.inner() {
                     parent_variable.byte_size.map(|byte_size| {
                         let array_member_count = count as u64;
                         if array_member_count > 0 {
@@ -1681,12 +3177,16 @@ impl UnitInfo {
             };
 
             match expression_result {
-                ExpressionResult::Value(value_from_expression @ VariableValue::Valid(_)) => {
-                    // The ELF contained the actual value, not just a location to it.
-                    child_variable.memory_location = VariableLocation::Value;
+                ExpressionResult::Value(
+                    value_from_expression @ (VariableValue::Error(_)
+                    | VariableValue::Unavailable(_)
+                    | VariableValue::Empty),
+                ) => {
                     child_variable.set_value(value_from_expression);
                 }
                 ExpressionResult::Value(value_from_expression) => {
+                    // The ELF contained the actual value, not just a location to it.
+                    child_variable.memory_location = VariableLocation::Value;
                     child_variable.set_value(value_from_expression);
                 }
                 ExpressionResult::Location(VariableLocation::Unavailable) => {
@@ -1750,28 +3250,24 @@ impl UnitInfo {
         }
 
         for attr in node_die.attrs() {
+            let attribute_context = |error: DebugError| {
+                error.with_context(self.error_context(node_die).with_attribute(attr.name()))
+            };
+
             let result = match attr.name() {
                 gimli::DW_AT_location
                 | gimli::DW_AT_frame_base
                 | gimli::DW_AT_data_member_location => match attr.value() {
                     gimli::AttributeValue::Exprloc(expression) => self
-                        .evaluate_expression(memory, expression, frame_info)
-                        .convert_incomplete()?,
+                        .evaluate_expression(debug_info, memory, expression, frame_info)
+                        .convert_incomplete()
+                        .map_err(attribute_context)?,
 
                     gimli::AttributeValue::Udata(offset_from_location) => {
-                        let location = if let VariableLocation::Address(address) = parent_location {
-                            let Some(location) = address.checked_add(offset_from_location) else {
-                                return Err(DebugError::WarnAndContinue {
-                                    message: "Overflow calculating variable address".to_string(),
-                                });
-                            };
-
-                            VariableLocation::Address(location)
-                        } else {
-                            parent_location.clone()
-                        };
-
-                        ExpressionResult::Location(location)
+                        ExpressionResult::Location(member_location(
+                            parent_location,
+                            offset_from_location,
+                        )?)
                     }
 
                     gimli::AttributeValue::LocationListsRef(location_list_offset) => self
@@ -1781,7 +3277,23 @@ impl UnitInfo {
                             frame_info,
                             memory,
                         )
-                        .convert_incomplete()?,
+                        .convert_incomplete()
+                        .map_err(attribute_context)?,
+
+                    gimli::AttributeValue::DebugLocListsIndex(index) => {
+                        let location_list_offset = self
+                            .dwarf(debug_info)
+                            .locations_offset(&self.unit, index)
+                            .map_err(|error| attribute_context(error.into()))?;
+                        self.evaluate_location_list_ref(
+                            debug_info,
+                            location_list_offset,
+                            frame_info,
+                            memory,
+                        )
+                        .convert_incomplete()
+                        .map_err(attribute_context)?
+                    }
 
                     other_attribute_value => {
                         ExpressionResult::Location(VariableLocation::Unsupported(format!(
@@ -1831,55 +3343,61 @@ impl UnitInfo {
         frame_info: StackFrameInfo<'_>,
         memory: &mut dyn MemoryInterface,
     ) -> Result<ExpressionResult, DebugError> {
-        let mut locations = match debug_info.locations_section.locations(
-            location_list_offset,
-            self.unit.header.encoding(),
-            self.unit.low_pc,
-            &debug_info.address_section,
-            self.unit.addr_base,
-        ) {
-            Ok(locations) => locations,
-            Err(error) => {
-                return Ok(ExpressionResult::Location(VariableLocation::Error(
-                    format!("Error: Resolving variable Location: {error:?}"),
-                )));
+        match self.location_list_expression(debug_info, location_list_offset, frame_info) {
+            Ok(Some(valid_expression)) => {
+                self.evaluate_expression(debug_info, memory, valid_expression, frame_info)
             }
-        };
+            Ok(None) => Ok(ExpressionResult::Value(VariableValue::Unavailable(
+                UnavailableReason::NoLocationAtProgramCounter,
+            ))),
+            Err(message) => Ok(ExpressionResult::Location(VariableLocation::Error(message))),
+        }
+    }
+
+    /// Find the expression of a location list that applies to the program counter of the frame.
+    ///
+    /// Returns `None` if no entry of the list covers the program counter, and the message for
+    /// [`VariableLocation::Error`] if the list cannot be read.
+    fn location_list_expression(
+        &self,
+        debug_info: &DebugInfo,
+        location_list_offset: gimli::LocationListsOffset,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<Option<gimli::Expression<GimliReader>>, String> {
+        let mut locations = self
+            .dwarf(debug_info)
+            .locations(&self.unit, location_list_offset)
+            .map_err(|error| format!("Error: Resolving variable Location: {error:?}"))?;
         let Some(program_counter) = frame_info
             .registers
             .get_program_counter()
             .and_then(|reg| reg.value)
         else {
-            return Ok(ExpressionResult::Location(VariableLocation::Error(
+            return Err(
                 "Cannot determine variable location without a valid program counter.".to_string(),
-            )));
+            );
         };
 
-        let mut expression = None;
-        'find_range: loop {
+        loop {
             let location = match locations.next() {
                 Ok(Some(location_lists_entry)) => location_lists_entry,
-                Ok(None) => break 'find_range,
+                Ok(None) => return Ok(None),
                 Err(error) => {
-                    return Ok(ExpressionResult::Location(VariableLocation::Error(
-                        format!("Error while iterating LocationLists for this variable: {error:?}"),
-                    )));
+                    return Err(format!(
+                        "Error while iterating LocationLists for this variable: {error:?}"
+                    ));
                 }
             };
 
+            // The ranges of the location list are addresses in the debug information.
             if let Ok(program_counter) = program_counter.try_into()
-                && location.range.contains(program_counter)
+                && location
+                    .range
+                    .contains(debug_info.debug_address(program_counter))
             {
-                expression = Some(location.data);
-                break 'find_range;
+                return Ok(Some(location.data));
             }
         }
-
-        let Some(valid_expression) = expression else {
-            return Ok(ExpressionResult::Location(VariableLocation::Unavailable));
-        };
-
-        self.evaluate_expression(memory, valid_expression, frame_info)
     }
 
     /// Evaluate a [`gimli::Expression`] as a valid memory location.
@@ -1889,8 +3407,39 @@ impl UnitInfo {
     /// - `Result<ExpressionResult::Location(),_>`: One of the variants of VariableLocation, and needs to be interpreted for handling the 'expected' errors we encounter during evaluation.
     pub(crate) fn evaluate_expression(
         &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<ExpressionResult, DebugError> {
+        let pieces = match self.expression_to_piece(debug_info, memory, expression, frame_info) {
+            Ok(pieces) => pieces,
+            Err(ExpressionError::Unavailable(reason)) => {
+                return Ok(ExpressionResult::Value(VariableValue::Unavailable(reason)));
+            }
+            Err(ExpressionError::Debug(error)) => return Err(error),
+        };
+
+        if pieces.is_empty() {
+            return Ok(ExpressionResult::Location(VariableLocation::Error(
+                "Error: expr_to_piece() returned 0 results".to_string(),
+            )));
+        }
+        if pieces.len() > 1 {
+            return Ok(ExpressionResult::Location(VariableLocation::Error(
+                "<unsupported memory implementation>".to_string(),
+            )));
+        }
+
+        self.location_to_result(debug_info, memory, &pieces[0].location, frame_info)
+    }
+
+    /// Convert the location of a piece of a DWARF expression to an [`ExpressionResult`].
+    fn location_to_result(
+        &self,
+        debug_info: &DebugInfo,
         memory: &mut dyn MemoryInterface,
-        expression: gimli::Expression<GimliReader>,
+        location: &Location<GimliReader>,
         frame_info: StackFrameInfo<'_>,
     ) -> Result<ExpressionResult, DebugError> {
         fn evaluate_address(address: u64, memory: &mut dyn MemoryInterface) -> ExpressionResult {
@@ -1905,20 +3454,7 @@ impl UnitInfo {
             ExpressionResult::Location(location)
         }
 
-        let pieces = self.expression_to_piece(memory, expression, frame_info)?;
-
-        if pieces.is_empty() {
-            return Ok(ExpressionResult::Location(VariableLocation::Error(
-                "Error: expr_to_piece() returned 0 results".to_string(),
-            )));
-        }
-        if pieces.len() > 1 {
-            return Ok(ExpressionResult::Location(VariableLocation::Error(
-                "<unsupported memory implementation>".to_string(),
-            )));
-        }
-
-        let result = match &pieces[0].location {
+        let result = match location {
             Location::Empty => {
                 // This means the value was optimized away.
                 ExpressionResult::Location(VariableLocation::Unavailable)
@@ -1929,21 +3465,21 @@ impl UnitInfo {
             }
             Location::Address { address } => evaluate_address(*address, memory),
             Location::Value { value } => {
-                let value = match value {
-                    gimli::Value::Generic(value) => value.to_string(),
-                    gimli::Value::I8(value) => value.to_string(),
-                    gimli::Value::U8(value) => value.to_string(),
-                    gimli::Value::I16(value) => value.to_string(),
-                    gimli::Value::U16(value) => value.to_string(),
-                    gimli::Value::I32(value) => value.to_string(),
-                    gimli::Value::U32(value) => value.to_string(),
-                    gimli::Value::I64(value) => value.to_string(),
-                    gimli::Value::U64(value) => value.to_string(),
-                    gimli::Value::F32(value) => value.to_string(),
-                    gimli::Value::F64(value) => value.to_string(),
+                let value = match *value {
+                    gimli::Value::Generic(value) => VariableValue::U64(value),
+                    gimli::Value::I8(value) => value.into(),
+                    gimli::Value::U8(value) => value.into(),
+                    gimli::Value::I16(value) => value.into(),
+                    gimli::Value::U16(value) => value.into(),
+                    gimli::Value::I32(value) => value.into(),
+                    gimli::Value::U32(value) => value.into(),
+                    gimli::Value::I64(value) => value.into(),
+                    gimli::Value::U64(value) => value.into(),
+                    gimli::Value::F32(value) => value.into(),
+                    gimli::Value::F64(value) => value.into(),
                 };
 
-                ExpressionResult::Value(VariableValue::Valid(value))
+                ExpressionResult::Value(value)
             }
             Location::Register { register } => {
                 if let Some(value) = frame_info
@@ -1958,30 +3494,279 @@ impl UnitInfo {
                     )))
                 }
             }
-            l => ExpressionResult::Location(VariableLocation::Error(format!(
-                "Unimplemented: extract_location() found a location type: {:.100}",
-                format!("{l:?}")
-            ))),
+            Location::Bytes { value } => {
+                // `DW_OP_implicit_value`, the value is stored in the expression.
+                ExpressionResult::Location(VariableLocation::Implicit(value.bytes().to_vec()))
+            }
+            Location::ImplicitPointer { value, byte_offset } => {
+                let target = self.implicit_pointer_target(
+                    debug_info,
+                    memory,
+                    *value,
+                    *byte_offset,
+                    frame_info,
+                )?;
+                ExpressionResult::Location(VariableLocation::ImplicitPointer(Box::new(target)))
+            }
         };
 
         Ok(result)
     }
 
+    /// Resolve the location of the value that an implicit pointer points to.
+    ///
+    /// The pointer refers to the DIE of a variable, which can be part of another unit. The value is
+    /// the value of that variable, starting at `byte_offset`.
+    fn implicit_pointer_target(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        target: DebugInfoOffset,
+        byte_offset: i64,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<VariableLocation, DebugError> {
+        let Some((unit_info, unit_offset)) = debug_info.unit_infos.iter().find_map(|unit_info| {
+            target
+                .to_unit_offset(&unit_info.unit.header)
+                .map(|unit_offset| (unit_info, unit_offset))
+        }) else {
+            return Ok(VariableLocation::Error(format!(
+                "Cannot find the target of the implicit pointer at {:#x}.",
+                target.0
+            )));
+        };
+        let mut unit_info = unit_info;
+        let mut entry = unit_info.unit.entry(unit_offset)?;
+
+        if entry.attr_value(gimli::DW_AT_location).is_none()
+            && entry.attr_value(gimli::DW_AT_const_value).is_none()
+        {
+            // The target can be the variable of an abstract instance, e.g. of an inlined function,
+            // which has no location. The concrete instance of the variable is part of the function of the frame.
+            let concrete_variable = frame_info
+                .registers
+                .get_program_counter()
+                .and_then(|program_counter| program_counter.value)
+                .and_then(|program_counter| program_counter.try_into().ok())
+                .and_then(|program_counter| debug_info.get_function_dies(program_counter).ok())
+                .and_then(|(function_unit_info, functions)| {
+                    let function = functions.first()?;
+                    let variable = function_unit_info
+                        .find_concrete_variable(function.function_die.offset(), target)?;
+                    Some((function_unit_info, variable))
+                });
+
+            if let Some((concrete_unit_info, concrete_entry)) = concrete_variable {
+                unit_info = concrete_unit_info;
+                entry = concrete_entry;
+            }
+        }
+
+        let location = match entry.attr_value(gimli::DW_AT_const_value) {
+            Some(const_value) => match const_value {
+                AttributeValue::Block(bytes) => VariableLocation::Implicit(bytes.bytes().to_vec()),
+                AttributeValue::Data1(value) => VariableLocation::Implicit(vec![value]),
+                AttributeValue::Data2(value) => {
+                    VariableLocation::Implicit(value.to_target_bytes(unit_info.endianness).to_vec())
+                }
+                AttributeValue::Data4(value) => {
+                    VariableLocation::Implicit(value.to_target_bytes(unit_info.endianness).to_vec())
+                }
+                AttributeValue::Data8(value) => {
+                    VariableLocation::Implicit(value.to_target_bytes(unit_info.endianness).to_vec())
+                }
+                other => VariableLocation::Unsupported(format!(
+                    "Unimplemented: The target of the implicit pointer has the constant value {:.100}",
+                    format!("{other:?}")
+                )),
+            },
+            None => unit_info.value_location(debug_info, memory, &entry, frame_info)?,
+        };
+
+        let location = match location {
+            location if byte_offset == 0 => location,
+            VariableLocation::Address(address) => {
+                address.checked_add_signed(byte_offset).map_or_else(
+                    || VariableLocation::Error("Overflow calculating variable address".to_string()),
+                    VariableLocation::Address,
+                )
+            }
+            VariableLocation::Implicit(bytes) => usize::try_from(byte_offset)
+                .ok()
+                .and_then(|offset| bytes.get(offset..))
+                .map_or_else(
+                    || {
+                        VariableLocation::Error(format!(
+                            "The implicit pointer offset {byte_offset} is outside of the value."
+                        ))
+                    },
+                    |bytes| VariableLocation::Implicit(bytes.to_vec()),
+                ),
+            other => VariableLocation::Unsupported(format!(
+                "Unimplemented: An implicit pointer with an offset into the location {other}."
+            )),
+        };
+
+        Ok(location)
+    }
+
+    /// Find the variable in the subtree of `function_offset` which is the concrete instance of the
+    /// variable at `abstract_offset`.
+    fn find_concrete_variable(
+        &self,
+        function_offset: UnitOffset,
+        abstract_offset: DebugInfoOffset,
+    ) -> Option<Die> {
+        let mut cursor = self.unit.entries_at_offset(function_offset).ok()?;
+        let function_depth = cursor.next_dfs().ok()??.depth();
+
+        while let Ok(Some(current)) = cursor.next_dfs() {
+            if current.depth() <= function_depth {
+                // We have left the subtree of the function.
+                break;
+            }
+
+            let origin = match current.attr_value(gimli::DW_AT_abstract_origin) {
+                Some(AttributeValue::UnitRef(unit_ref)) => {
+                    unit_ref.to_debug_info_offset(&self.unit.header)
+                }
+                Some(AttributeValue::DebugInfoRef(offset)) => Some(offset),
+                _ => None,
+            };
+            if origin == Some(abstract_offset) {
+                return Some(current.clone());
+            }
+        }
+
+        None
+    }
+
+    /// Evaluate the `DW_AT_location` of a variable, which is the target of an implicit pointer.
+    ///
+    /// Unlike [`Self::extract_location`], a computed value is kept as its bytes in target byte order,
+    /// so it can be read like any other value that the pointer points to.
+    fn value_location(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        entry: &Die,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<VariableLocation, DebugError> {
+        let from_location_list =
+            |offset| match self.location_list_expression(debug_info, offset, frame_info) {
+                Ok(Some(expression)) => Ok(expression),
+                Ok(None) => Err(VariableLocation::Unavailable),
+                Err(message) => Err(VariableLocation::Error(message)),
+            };
+        let expression = match entry.attr_value(gimli::DW_AT_location) {
+            Some(AttributeValue::Exprloc(expression)) => Ok(expression),
+            Some(AttributeValue::LocationListsRef(offset)) => from_location_list(offset),
+            Some(AttributeValue::DebugLocListsIndex(index)) => {
+                from_location_list(self.dwarf(debug_info).locations_offset(&self.unit, index)?)
+            }
+            _ => Err(VariableLocation::Unknown),
+        };
+        let expression = match expression {
+            Ok(expression) => expression,
+            Err(location) => return Ok(location),
+        };
+
+        let pieces = match self.expression_to_piece(debug_info, memory, expression, frame_info) {
+            Ok(pieces) => pieces,
+            Err(ExpressionError::Unavailable(_)) => return Ok(VariableLocation::Unavailable),
+            Err(ExpressionError::Debug(error)) => return Err(error),
+        };
+        let [piece] = pieces.as_slice() else {
+            return Ok(VariableLocation::Error(
+                "<unsupported memory implementation>".to_string(),
+            ));
+        };
+
+        let location = if let Location::Value { value } = piece.location {
+            VariableLocation::Implicit(self.value_to_target_bytes(value, entry))
+        } else {
+            match self.location_to_result(debug_info, memory, &piece.location, frame_info)? {
+                ExpressionResult::Location(location) => location,
+                ExpressionResult::Value(value) => VariableLocation::Error(value.to_string()),
+            }
+        };
+
+        Ok(location)
+    }
+
+    /// Convert a value computed by a DWARF expression to its bytes in target byte order.
+    ///
+    /// A generic value has the size of the type of `entry`, or the size of an address if the type
+    /// has no size. The concrete instance of a variable can leave the type to its abstract origin.
+    fn value_to_target_bytes(&self, value: gimli::Value, entry: &Die) -> Vec<u8> {
+        let endianness = self.endianness;
+        match value {
+            gimli::Value::Generic(value) => {
+                let type_offset = match entry.attr_value(gimli::DW_AT_type) {
+                    Some(AttributeValue::UnitRef(type_offset)) => Some(type_offset),
+                    _ => match entry.attr_value(gimli::DW_AT_abstract_origin) {
+                        Some(AttributeValue::UnitRef(origin_offset)) => {
+                            self.unit.entry(origin_offset).ok().and_then(|origin| {
+                                match origin.attr_value(gimli::DW_AT_type) {
+                                    Some(AttributeValue::UnitRef(type_offset)) => Some(type_offset),
+                                    _ => None,
+                                }
+                            })
+                        }
+                        _ => None,
+                    },
+                };
+                let size = type_offset
+                    .and_then(|type_offset| self.unit.entry(type_offset).ok())
+                    .and_then(|type_entry| extract_byte_size(&type_entry))
+                    .and_then(|size| usize::try_from(size).ok())
+                    .filter(|size| *size <= 8)
+                    .unwrap_or(self.unit.encoding().address_size as usize);
+
+                u128_to_target_bytes(value as u128, size, endianness)
+            }
+            gimli::Value::I8(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::U8(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::I16(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::U16(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::I32(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::U32(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::I64(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::U64(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::F32(value) => value.to_target_bytes(endianness).to_vec(),
+            gimli::Value::F64(value) => value.to_target_bytes(endianness).to_vec(),
+        }
+    }
+
     /// Tries to get the result of a DWARF expression in the form of a Piece.
     pub(crate) fn expression_to_piece(
         &self,
+        debug_info: &DebugInfo,
         memory: &mut dyn MemoryInterface,
         expression: gimli::Expression<GimliReader>,
         frame_info: StackFrameInfo<'_>,
-    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, DebugError> {
+    ) -> Result<Vec<gimli::Piece<GimliReader, usize>>, ExpressionError> {
         let mut evaluation = expression.evaluation(self.unit.encoding());
         let mut result = evaluation.evaluate()?;
 
         loop {
             result = match result {
                 EvaluationResult::Complete => return Ok(evaluation.result()),
-                EvaluationResult::RequiresMemory { address, size, .. } => {
-                    read_memory(size, memory, address, &mut evaluation)?
+                EvaluationResult::RequiresMemory {
+                    address,
+                    size,
+                    base_type,
+                    ..
+                } => {
+                    let value_type = self.base_value_type(base_type)?;
+                    read_memory(
+                        size,
+                        value_type,
+                        memory,
+                        address,
+                        self.endianness,
+                        &mut evaluation,
+                    )?
                 }
                 EvaluationResult::RequiresFrameBase => {
                     provide_frame_base(frame_info.frame_base, &mut evaluation)?
@@ -1989,25 +3774,143 @@ impl UnitInfo {
                 EvaluationResult::RequiresRegister {
                     register,
                     base_type,
-                } => provide_register(frame_info.registers, register, base_type, &mut evaluation)?,
+                } => {
+                    let value_type = self.base_value_type(base_type)?;
+                    provide_register(frame_info.registers, register, value_type, &mut evaluation)?
+                }
                 EvaluationResult::RequiresRelocatedAddress(address_index) => {
                     // The address_index as an offset from 0, so just pass it into the next step.
                     evaluation.resume_with_relocated_address(address_index)?
                 }
+                EvaluationResult::RequiresIndexedAddress { index, .. } => {
+                    // DWARF 5 `DW_OP_addrx` and `DW_OP_constx` refer to an entry in `.debug_addr`.
+                    let address = self.dwarf(debug_info).address(&self.unit, index)?;
+                    evaluation.resume_with_indexed_address(address)?
+                }
                 EvaluationResult::RequiresCallFrameCfa => {
-                    provide_cfa(frame_info.canonical_frame_address, &mut evaluation)?
+                    // `DW_OP_call_frame_cfa` is commonly used for the frame base.
+                    let cfa = frame_info
+                        .canonical_frame_address
+                        .or_else(|| debug_info.canonical_frame_address(frame_info.registers));
+                    provide_cfa(cfa, &mut evaluation)?
+                }
+                EvaluationResult::RequiresEntryValue(entry_expression) => {
+                    let value =
+                        self.entry_value(debug_info, memory, entry_expression, frame_info)?;
+                    evaluation.resume_with_entry_value(value)?
+                }
+                EvaluationResult::RequiresTls(offset) => {
+                    // `DW_OP_form_tls_address` and `DW_OP_GNU_push_tls_address`.
+                    let Some(resolver) = &debug_info.tls_resolver else {
+                        return Err(ExpressionError::Unavailable(
+                            UnavailableReason::TlsResolverNotConfigured,
+                        ));
+                    };
+                    let Some(address) = resolver(offset, frame_info.registers) else {
+                        return Err(DebugError::WarnAndContinue {
+                            message: format!(
+                                "Cannot resolve the address of the thread-local storage at offset {offset:#x}."
+                            ),
+                        }
+                        .into());
+                    };
+                    evaluation.resume_with_tls(address)?
                 }
                 unimplemented_expression => {
                     return Err(DebugError::WarnAndContinue {
                         message: format!(
                             "Unimplemented: Expressions that include {unimplemented_expression:?} are not currently supported."
                         ),
-                    });
+                    }
+                    .into());
                 }
             }
         }
     }
 
+    /// Evaluate the expression of a `DW_OP_entry_value` operation, which describes a value on entry to
+    /// the function of the frame.
+    ///
+    /// The registers on entry are recovered by unwinding the frame, see [`DebugInfo::entry_registers`].
+    fn entry_value(
+        &self,
+        debug_info: &DebugInfo,
+        memory: &mut dyn MemoryInterface,
+        expression: gimli::Expression<GimliReader>,
+        frame_info: StackFrameInfo<'_>,
+    ) -> Result<gimli::Value, ExpressionError> {
+        let unavailable = || ExpressionError::Unavailable(UnavailableReason::EntryValue);
+
+        let entry_registers = debug_info
+            .entry_registers(frame_info.registers, memory)
+            .ok_or_else(unavailable)?;
+        let entry_frame_info = StackFrameInfo {
+            registers: &entry_registers,
+            frame_base: None,
+            canonical_frame_address: None,
+            auto_deref_depth: frame_info.auto_deref_depth,
+        };
+
+        let pieces = self
+            .expression_to_piece(debug_info, memory, expression, entry_frame_info)
+            .map_err(|_| unavailable())?;
+
+        // The expression is either a register location, which refers to the value of the register, or
+        // a DWARF expression, which computes the value.
+        match pieces.as_slice() {
+            [
+                gimli::Piece {
+                    location: Location::Register { register },
+                    ..
+                },
+            ] => {
+                let value = entry_registers
+                    .get_register_by_dwarf_id(register.0)
+                    .and_then(|register| register.value)
+                    .ok_or_else(unavailable)?;
+                Ok(gimli::Value::Generic(
+                    value.try_into().map_err(DebugError::from)?,
+                ))
+            }
+            [
+                gimli::Piece {
+                    location: Location::Address { address },
+                    ..
+                },
+            ] => Ok(gimli::Value::Generic(*address)),
+            [
+                gimli::Piece {
+                    location: Location::Value { value },
+                    ..
+                },
+            ] => Ok(*value),
+            _ => Err(unavailable()),
+        }
+    }
+
+    /// Resolve the base type of a typed DWARF expression operation, such as `DW_OP_deref_type`.
+    ///
+    /// Returns `None` for the generic type, which is referenced with an offset of zero.
+    fn base_value_type(
+        &self,
+        base_type: UnitOffset,
+    ) -> Result<Option<gimli::ValueType>, DebugError> {
+        if base_type == UnitOffset(0) {
+            return Ok(None);
+        }
+
+        let entry = self.unit.entry(base_type)?;
+        match gimli::ValueType::from_entry(&entry)? {
+            Some(value_type) => Ok(Some(value_type)),
+            None => Err(DebugError::WarnAndContinue {
+                message: format!(
+                    "Unimplemented: The base type at {:#x} of a DWARF expression is not supported.",
+                    base_type.0
+                ),
+            }),
+        }
+    }
+
     /// A helper function, to handle memory_location for special cases, such as array members, pointers, and intermediate nodes.
     /// Normally, the memory_location is calculated before the type is calculated,
     ///     but special cases require the type related info of the variable to correctly compute the memory_location.
@@ -2041,15 +3944,28 @@ impl UnitInfo {
             }
         } else if child_variable.memory_location == VariableLocation::Unknown {
             // Non-array members can inherit their memory location from their parent, but only if the parent has a valid memory location.
-            if self.is_pointer(child_variable, parent_variable, unit_ref) {
+            if let VariableLocation::ImplicitPointer(target) = &parent_variable.memory_location {
+                // The pointer only exists in the debug information, which also describes the location
+                // of the value it points to.
+                target.as_ref().clone()
+            } else if self.is_pointer(child_variable, parent_variable, unit_ref) {
                 match &parent_variable.memory_location {
-                    address @ (VariableLocation::Address(_)
-                    | VariableLocation::RegisterValue(_)) => {
+                    VariableLocation::RegisterValue(value) => match (*value).try_into() {
+                        // The pointer itself is stored in the register.
+                        Ok(address) => VariableLocation::Address(address),
+                        Err(error) => VariableLocation::Error(format!(
+                            "Failed to use the register value {value} as the referenced variable address: {error}."
+                        )),
+                    },
+                    VariableLocation::Address(address) => {
                         // Now, retrieve the location by reading the address pointed to by the parent variable.
-                        match memory.read_word_32(address.memory_address().unwrap()) {
-                            Ok(memory_location) => {
-                                VariableLocation::Address(memory_location as u64)
-                            }
+                        let mut buff = [0u8; 4];
+                        match memory.read(*address, &mut buff) {
+                            Ok(()) => VariableLocation::Address(u32::from_target_bytes(
+                                buff,
+                                self.endianness,
+                            )
+                                as u64),
                             Err(error) => {
                                 tracing::debug!(
                                     "Failed to read referenced variable address from memory location {} : {error}.",
@@ -2088,15 +4004,16 @@ impl UnitInfo {
         // Address Pointer Conditions (any of):
         // 1. Variable names that start with '*' (e.g '*__0), AND the variable is a variant of the parent.
         // 2. Pointer names that start with '*' (e.g. '*const u8')
-        // 3. Pointers to base types (includes &str types)
+        // 3. Pointers to base types (includes Rust &str types)
         // 4. Pointers to variable names that start with `*`
         // 5. Pointers to types with referenced memory addresses (e.g. variants, generics, arrays, etc.)
         (matches!(child_variable.name, VariableName::Named(ref var_name) if var_name.starts_with('*'))
                 && matches!(parent_variable.role, VariantRole::VariantPart(_)))
-            || matches!(&parent_variable.type_name, VariableType::Pointer(Some(pointer_name)) if pointer_name.starts_with('*'))
-            || (matches!(&parent_variable.type_name, VariableType::Pointer(_))
-                && (matches!(child_variable.type_name, VariableType::Base(_))
-                    || matches!(child_variable.type_name, VariableType::Struct(ref type_name) if type_name.starts_with("&str"))
+            || matches!(parent_variable.type_name.inner(), VariableType::Pointer(Some(pointer_name)) if pointer_name.starts_with('*'))
+            || (matches!(parent_variable.type_name.inner(), VariableType::Pointer(_))
+                && (matches!(child_variable.type_name.inner(), VariableType::Base(_))
+                    || (child_variable.language == gimli::DW_LANG_Rust
+                        && matches!(child_variable.type_name.inner(), VariableType::Struct(type_name) if type_name.starts_with("&str")))
                     || matches!(child_variable.name, VariableName::Named(ref var_name) if var_name.starts_with('*'))
                     || self.has_address_pointer(unit_ref).unwrap_or_else(|error| {
                         child_variable.set_value(VariableValue::Error(format!("Failed to determine if a struct has variant or generic type fields: {error}")));
@@ -2130,40 +4047,67 @@ impl UnitInfo {
         debug_info: &DebugInfo,
         entry: &gimli::DebuggingInformationEntry<GimliReader>,
     ) -> Result<Option<String>, gimli::Error> {
-        match entry.attr(gimli::DW_AT_name) {
-            Some(attr) => {
-                let name = match attr.value() {
-                    gimli::AttributeValue::DebugStrRef(name_ref) => {
-                        if let Ok(name_raw) = debug_info.dwarf.string(name_ref) {
-                            String::from_utf8_lossy(&name_raw).to_string()
-                        } else {
-                            "Invalid DW_AT_name value".to_string()
-                        }
-                    }
-                    gimli::AttributeValue::String(name) => {
-                        String::from_utf8_lossy(&name).to_string()
-                    }
-                    other => format!("Unimplemented: Evaluate name from {other:?}"),
-                };
+        if let Some(name) = extract_name(debug_info, self, entry)? {
+            return Ok(Some(name));
+        }
 
-                Ok(Some(name))
-            }
-            None => {
-                let Some(attr) = entry.attr(gimli::DW_AT_type) else {
-                    // No type attribute.
-                    return Ok(None);
-                };
+        // The type can also be part of a DIE that this entry refers to, e.g. the declaration of a C++ class
+        // that is defined out of line.
+        let Some((unit_info, attr)) = debug_info.resolve_attribute(entry, self, gimli::DW_AT_type)
+        else {
+            // No type attribute.
+            return Ok(None);
+        };
 
-                let gimli::AttributeValue::UnitRef(unit_ref) = attr.value() else {
-                    // TODO: should we handle other types of references?
-                    return Ok(None);
-                };
+        let gimli::AttributeValue::UnitRef(unit_ref) = attr.value() else {
+            // TODO: should we handle other types of references?
+            return Ok(None);
+        };
 
-                // Try to read the name of the referenced type node.
-                let node = self.unit.header.entry(&self.unit.abbreviations, unit_ref)?;
-                self.extract_type_name(debug_info, &node)
-            }
+        // Try to read the name of the referenced type node.
+        let node = unit_info
+            .unit
+            .header
+            .entry(&unit_info.unit.abbreviations, unit_ref)?;
+        unit_info.extract_type_name(debug_info, &node)
+    }
+
+    /// Returns the name of a pointer type, or of the type it points to if the pointer has no name.
+    ///
+    /// Unlike [`Self::extract_type_name`], this keeps the qualifiers of the type the pointer points
+    /// to, e.g. the `const` of a C `const char*`.
+    fn extract_pointer_type_name(
+        &self,
+        debug_info: &DebugInfo,
+        entry: &gimli::DebuggingInformationEntry<GimliReader>,
+    ) -> Result<Option<String>, gimli::Error> {
+        if let Some(name) = extract_name(debug_info, self, entry)? {
+            return Ok(Some(name));
         }
+
+        let Some((unit_info, attr)) = debug_info.resolve_attribute(entry, self, gimli::DW_AT_type)
+        else {
+            return Ok(None);
+        };
+        let gimli::AttributeValue::UnitRef(unit_ref) = attr.value() else {
+            return Ok(None);
+        };
+        let node = unit_info
+            .unit
+            .header
+            .entry(&unit_info.unit.abbreviations, unit_ref)?;
+
+        let modifier = match node.tag() {
+            gimli::DW_TAG_const_type => Modifier::Const,
+            gimli::DW_TAG_volatile_type => Modifier::Volatile,
+            gimli::DW_TAG_restrict_type => Modifier::Restrict,
+            gimli::DW_TAG_atomic_type => Modifier::Atomic,
+            _ => return unit_info.extract_type_name(debug_info, &node),
+        };
+
+        // The qualified type is named like a pointer, by the type it qualifies.
+        let name = unit_info.extract_pointer_type_name(debug_info, &node)?;
+        Ok(name.map(|name| self.language.modified_type_name(&modifier, &name)))
     }
 
     fn process_bitfield_info(
@@ -2178,24 +4122,75 @@ impl UnitInfo {
         }
         match self.extract_bitfield_info(child_variable, entry) {
             Ok(Some(bitfield)) => {
-                if let Some(byte_size) = child_variable.byte_size {
-                    let bitfield = bitfield.normalize(byte_size);
-                    child_variable.type_name = VariableType::Bitfield(
-                        bitfield,
-                        Box::new(std::mem::replace(
-                            &mut child_variable.type_name,
-                            VariableType::Unknown,
-                        )),
-                    );
-                    // Invalidate value that was read before we knew about the bitfield.
-                    child_variable.value = VariableValue::Empty;
-                    cache.update_variable(child_variable)?;
-                } else {
+                let Some(byte_size) = child_variable.byte_size.filter(|&size| size > 0) else {
                     child_variable.set_value(VariableValue::Error(
                         "Error: Failed to decode bitfield information: byte_size not found"
                             .to_string(),
                     ));
+                    return Ok(());
+                };
+
+                let bitfield = match bitfield.offset {
+                    // The `DW_AT_data_bit_offset` is counted from the start of the parent, where
+                    // the member is located, so the member is moved to the storage unit which
+                    // contains its bits.
+                    BitOffset::FromLsb(data_bit_offset) => {
+                        if bitfield.length == 0 {
+                            // Not a bitfield, but a member which is located by its bit offset.
+                            if data_bit_offset != 0 {
+                                child_variable.set_value(VariableValue::Error(format!(
+                                    "Unimplemented: DW_AT_data_bit_offset {data_bit_offset} without DW_AT_bit_size"
+                                )));
+                            }
+                            return Ok(());
+                        }
+
+                        let (unit_offset, bit_offset) =
+                            bitfield_storage_unit(data_bit_offset, bitfield.length, byte_size);
+                        match member_location(&child_variable.memory_location, unit_offset) {
+                            Ok(location) => child_variable.memory_location = location,
+                            Err(error) => {
+                                child_variable.set_value(VariableValue::Error(format!(
+                                    "Error: Failed to decode bitfield information: {error}"
+                                )));
+                                return Ok(());
+                            }
+                        }
+
+                        // In big endian targets, the bits are counted from the most significant
+                        // bit of the first byte.
+                        let offset = match child_variable.endianness {
+                            RunTimeEndian::Little => BitOffset::FromLsb(bit_offset),
+                            RunTimeEndian::Big => BitOffset::FromMsb(bit_offset),
+                        };
+                        Bitfield {
+                            offset,
+                            length: bitfield.length,
+                        }
+                    }
+                    BitOffset::FromMsb(_) => bitfield,
+                };
+
+                let (BitOffset::FromLsb(bit_offset) | BitOffset::FromMsb(bit_offset)) =
+                    bitfield.offset;
+                if bit_offset + bitfield.length > byte_size * 8 {
+                    child_variable.set_value(VariableValue::Error(format!(
+                        "Unimplemented: Bitfield of {} bits which does not fit in its storage unit of {byte_size} bytes",
+                        bitfield.length
+                    )));
+                    return Ok(());
                 }
+
+                child_variable.type_name = VariableType::Bitfield(
+                    bitfield.normalize(byte_size),
+                    Box::new(std::mem::replace(
+                        &mut child_variable.type_name,
+                        VariableType::Unknown,
+                    )),
+                );
+                // Invalidate value that was read before we knew about the bitfield.
+                child_variable.value = VariableValue::Empty;
+                cache.update_variable(child_variable)?;
             }
             Ok(None) => {}
             Err(e) => child_variable.set_value(VariableValue::Error(format!(
@@ -2265,47 +4260,41 @@ impl UnitInfo {
         }))
     }
 
-    fn extract_source_location(
+    pub(crate) fn extract_source_location(
         &self,
         debug_info: &DebugInfo,
         entry: &gimli::DebuggingInformationEntry<GimliReader>,
     ) -> Result<Option<SourceLocation>, gimli::Error> {
-        let Some(file_attr) = entry.attr_value(gimli::DW_AT_decl_file) else {
+        let Some((file_unit_info, file_attr)) =
+            debug_info.resolve_attribute(entry, self, gimli::DW_AT_decl_file)
+        else {
             return Ok(None);
         };
 
-        let Some(path) = extract_file(debug_info, &self.unit, file_attr) else {
+        let Some(path) = extract_file(debug_info, &file_unit_info.unit, file_attr.value()) else {
             return Ok(None);
         };
 
-        let mut source_location = SourceLocation {
+        // The definition of a variable can have its own line and column, and refer to the declaration for the file.
+        let line = debug_info
+            .resolve_attribute(entry, self, gimli::DW_AT_decl_line)
+            .and_then(|(_, attr)| extract_line(attr.value()));
+
+        // According to the DWARF standard, a value of 0 means no column is specified.
+        let column = debug_info
+            .resolve_attribute(entry, self, gimli::DW_AT_decl_column)
+            .and_then(|(_, attr)| attr.udata_value())
+            .filter(|column| *column != 0)
+            .map(super::ColumnType::Column);
+
+        let source_location = SourceLocation {
             path,
-            line: None,
-            column: None,
+            line,
+            column,
             address: None,
+            line_fallback: false,
         };
 
-        // Now loop through all the unit attributes to extract the remainder of the `Variable` definition.
-        for attr in entry.attrs() {
-            match attr.name() {
-                gimli::DW_AT_decl_line => {
-                    if let Some(line_number) = extract_line(attr.value()) {
-                        source_location.line = Some(line_number);
-                    }
-                }
-                gimli::DW_AT_decl_column => {
-                    if let Some(column_number) = attr.udata_value() {
-                        // According to the DWARF standard, a value of 0 means no column is specified.
-                        if column_number != 0 {
-                            source_location.column = Some(super::ColumnType::Column(column_number));
-                        }
-                    }
-                }
-                // Other attributes are not relevant for extracting source location.
-                _ => (),
-            }
-        }
-
         Ok(Some(source_location))
     }
 
@@ -2314,47 +4303,130 @@ impl UnitInfo {
     }
 }
 
-fn extract_name(
+/// The location of a member at `offset` bytes from the start of its parent at `parent_location`.
+fn member_location(
+    parent_location: &VariableLocation,
+    offset: u64,
+) -> Result<VariableLocation, DebugError> {
+    Ok(match parent_location {
+        VariableLocation::Address(address) => {
+            let Some(location) = address.checked_add(offset) else {
+                return Err(DebugError::WarnAndContinue {
+                    message: "Overflow calculating variable address".to_string(),
+                });
+            };
+
+            VariableLocation::Address(location)
+        }
+        VariableLocation::Implicit(bytes) => {
+            // The member is part of the bytes of the implicit value.
+            let member_bytes = usize::try_from(offset)
+                .ok()
+                .and_then(|offset| bytes.get(offset..))
+                .unwrap_or_default();
+            VariableLocation::Implicit(member_bytes.to_vec())
+        }
+        other => other.clone(),
+    })
+}
+
+/// The value of the bytes of a word, from the most to the least significant byte.
+fn word_value<'b>(bytes: impl Iterator<Item = &'b u8>) -> u32 {
+    bytes.fold(0, |value, byte| (value << 8) | u32::from(*byte))
+}
+
+/// Split the `DW_AT_data_bit_offset` of a bitfield of `bit_size` bits into the byte offset of the
+/// storage unit of `byte_size` bytes which contains its bits, and the offset of the bits in it.
+///
+/// The storage unit is aligned to its size, like the members of a struct, unless the bits cross
+/// into the next one, e.g. in a packed struct. Then it starts at the byte of the first bit.
+fn bitfield_storage_unit(data_bit_offset: u64, bit_size: u64, byte_size: u64) -> (u64, u64) {
+    let unit_bits = byte_size * 8;
+    let bit_offset = data_bit_offset % unit_bits;
+    if bit_offset + bit_size <= unit_bits {
+        (data_bit_offset / unit_bits * byte_size, bit_offset)
+    } else {
+        (data_bit_offset / 8, data_bit_offset % 8)
+    }
+}
+
+/// Returns the `DW_AT_name` of the given entry.
+///
+/// If the entry has no name, the name is taken from the DIEs it refers to with `DW_AT_abstract_origin`
+/// or `DW_AT_specification`.
+///
+/// All string forms are supported, including the indexed `DW_FORM_strx*` forms introduced in DWARF 5.
+pub(crate) fn extract_name(
     debug_info: &DebugInfo,
+    unit_info: &UnitInfo,
     entry: &gimli::DebuggingInformationEntry<GimliReader>,
 ) -> Result<Option<String>, gimli::Error> {
-    let Some(attr) = entry.attr_value(gimli::DW_AT_name) else {
+    let Some((unit_info, attr)) = debug_info.resolve_attribute(entry, unit_info, gimli::DW_AT_name)
+    else {
         return Ok(None);
     };
 
-    let name = match attr {
-        gimli::AttributeValue::DebugStrRef(name_ref) => {
-            if let Ok(name_raw) = debug_info.dwarf.string(name_ref) {
-                String::from_utf8_lossy(&name_raw).to_string()
-            } else {
-                "Invalid DW_AT_name value".to_string()
-            }
-        }
-        gimli::AttributeValue::String(name) => String::from_utf8_lossy(&name).to_string(),
-        other => format!("Unimplemented: Evaluate name from {other:?}"),
+    let name = match unit_info
+        .dwarf(debug_info)
+        .attr_string(&unit_info.unit, attr.value())
+    {
+        Ok(name_raw) => String::from_utf8_lossy(&name_raw).to_string(),
+        Err(error) => format!("Invalid DW_AT_name value: {error:?}"),
     };
 
     Ok(Some(name))
 }
 
+/// Returns the bits of the value of a discriminant member, to be matched against the
+/// `DW_AT_discr_value` of the variants. Signed discriminants are sign extended to 64 bits, like
+/// their `DW_AT_discr_value`, whatever the size of their type.
+///
+/// Returns `None` if the value is not an integer, e.g. because it could not be read.
+fn discriminant_value(value: &VariableValue) -> Option<u64> {
+    value.as_numeric().map(|value| value as u64)
+}
+
 /// Gets necessary register information for the DWARF resolver.
+///
+/// The register value is converted to a value of `value_type`, or of the generic type if it is `None`.
 fn provide_register(
     stack_frame_registers: &DebugRegisters,
     register: gimli::Register,
-    base_type: UnitOffset,
+    value_type: Option<gimli::ValueType>,
     evaluation: &mut gimli::Evaluation<EndianReader>,
 ) -> Result<EvaluationResult<EndianReader>, DebugError> {
     match stack_frame_registers
         .get_register_by_dwarf_id(register.0)
         .and_then(|reg| reg.value)
     {
-        Some(raw_value) if base_type == gimli::UnitOffset(0) => {
-            let register_value = gimli::Value::Generic(raw_value.try_into()?);
+        Some(raw_value) => {
+            let register_value = match value_type {
+                None => gimli::Value::Generic(raw_value.try_into()?),
+                Some(value_type) => {
+                    let register_size = match raw_value {
+                        RegisterValue::U32(_) => 4,
+                        RegisterValue::U64(_) => 8,
+                        RegisterValue::U128(_) => 16,
+                    };
+                    if value_type.bit_size(u64::MAX) / 8 > register_size {
+                        return Err(DebugError::WarnAndContinue {
+                            message: format!(
+                                "Cannot read a value of type {value_type:?} from the {}-byte register #:{}.",
+                                register_size, register.0
+                            ),
+                        });
+                    }
+
+                    // The value is stored in the least significant bytes of the register.
+                    let raw_value: u128 = raw_value.try_into()?;
+                    gimli::Value::parse(
+                        value_type,
+                        gimli::EndianSlice::new(&raw_value.to_le_bytes(), gimli::LittleEndian),
+                    )?
+                }
+            };
             Ok(evaluation.resume_with_register(register_value)?)
         }
-        Some(_) => Err(DebugError::WarnAndContinue {
-            message: format!("Unimplemented: Support for type {base_type:?} in `RequiresRegister`"),
-        }),
         None => Err(DebugError::WarnAndContinue {
             message: format!(
                 "Error while calculating `Variable::memory_location`. No value for register #:{}.",
@@ -2390,7 +4462,7 @@ fn provide_cfa(
 ) -> Result<EvaluationResult<EndianReader>, DebugError> {
     let Some(cfa) = cfa else {
         return Err(DebugError::WarnAndContinue {
-            message: "Cannot unwind `Variable` location without a valid canonical frame address.)"
+            message: "Cannot unwind `Variable` location without a valid canonical frame address."
                 .to_string(),
         });
     };
@@ -2403,51 +4475,58 @@ fn provide_cfa(
 }
 
 /// Reads memory requested by the DWARF resolver.
+///
+/// The bytes are converted to a value of `value_type`, or of the generic type if it is `None`.
 fn read_memory(
     size: u8,
+    value_type: Option<gimli::ValueType>,
     memory: &mut dyn MemoryInterface,
     address: u64,
+    endianness: RunTimeEndian,
     evaluation: &mut gimli::Evaluation<EndianReader>,
 ) -> Result<EvaluationResult<EndianReader>, DebugError> {
-    /// Reads `SIZE` bytes from the memory.
-    fn read<const SIZE: usize>(
-        memory: &mut dyn MemoryInterface,
-        address: u64,
-    ) -> Result<[u8; SIZE], DebugError> {
-        let mut buff = [0u8; SIZE];
-        memory.read(address, &mut buff).map_err(|error| {
-            DebugError::WarnAndContinue {
-                message: format!("Unexpected error while reading debug expressions from target memory: {error:?}. Please report this as a bug.")
-            }
-        })?;
-        Ok(buff)
-    }
+    let unsupported_size = || DebugError::WarnAndContinue {
+        message: format!(
+            "Unimplemented: Requested memory with size {size}, which is not supported yet."
+        ),
+    };
 
-    let val = match size {
-        1 => {
-            let buff = read::<1>(memory, address)?;
-            gimli::Value::U8(buff[0])
-        }
-        2 => {
-            let buff = read::<2>(memory, address)?;
-            gimli::Value::U16(u16::from_le_bytes(buff))
+    let mut buff = [0u8; 16];
+    let buff = buff
+        .get_mut(..usize::from(size))
+        .ok_or_else(unsupported_size)?;
+    memory.read(address, buff).map_err(|error| {
+        DebugError::WarnAndContinue {
+            message: format!("Unexpected error while reading debug expressions from target memory: {error:?}. Please report this as a bug.")
         }
-        4 => {
-            let buff = read::<4>(memory, address)?;
-            gimli::Value::U32(u32::from_le_bytes(buff))
+    })?;
+
+    let val = match value_type {
+        // Values of the generic type are zero-extended to the size of an address.
+        None if buff.len() <= 8 => {
+            gimli::Value::Generic(u128_from_target_bytes(buff, endianness) as u64)
         }
-        x => {
-            return Err(DebugError::WarnAndContinue {
-                message: format!(
-                    "Unimplemented: Requested memory with size {x}, which is not supported yet."
-                ),
-            });
+        None => return Err(unsupported_size()),
+        Some(value_type) => {
+            gimli::Value::parse(value_type, gimli::EndianSlice::new(buff, endianness))?
         }
     };
 
     Ok(evaluation.resume_with_memory(val)?)
 }
 
+/// Whether `program_counter` is in one of the `ranges` in which a variable is available, or
+/// before all of them.
+fn scope_of_ranges(ranges: &[gimli::Range], program_counter: u64) -> VariableScope {
+    if ranges.iter().any(|range| range.contains(program_counter)) {
+        VariableScope::InScope
+    } else if !ranges.is_empty() && ranges.iter().all(|range| program_counter < range.begin) {
+        VariableScope::NotYetInScope
+    } else {
+        VariableScope::NoLocation
+    }
+}
+
 pub(crate) trait RangeExt {
     fn contains(self, addr: u64) -> bool;
 }
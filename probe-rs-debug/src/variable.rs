@@ -1,14 +1,14 @@
 use crate::{language::ProgrammingLanguage, unit_info::UnitInfo};
 
 use super::*;
-use gimli::{DebugInfoOffset, DwLang, UnitOffset};
+use gimli::{DebugInfoOffset, DwAte, DwLang, RunTimeEndian, UnitOffset};
 use itertools::Itertools;
 use probe_rs::RegisterValue;
 use std::ops::Range;
 
 /// Define the role that a variable plays in a Variant relationship. See section '5.7.10 Variant
 /// Entries' of the DWARF 5 specification
-#[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize)]
 pub enum VariantRole {
     /// A (parent) Variable that can have any number of Variant's as its value
     VariantPart(u64),
@@ -24,15 +24,38 @@ pub enum VariantRole {
 /// - If we encounter expected errors, they will be displayed to the user as defined below.
 /// - If we encounter unexpected errors, they will be treated as proper errors and will propagated
 ///   to the calling process as an `Err()`
-#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub enum VariableValue {
-    /// A valid value of this variable
+    /// A valid value of this variable, which is already formatted for display, e.g. the name of
+    /// an enum variant, or the address of a pointer.
     Valid(String),
+    /// A boolean value.
+    Bool(bool),
+    /// A character value.
+    Char(char),
+    /// An unsigned integer value.
+    U64(u64),
+    /// A signed integer value.
+    I64(i64),
+    /// An unsigned integer value which does not fit in a `u64`, e.g. of a `u128`.
+    U128(u128),
+    /// A signed integer value which does not fit in an `i64`, e.g. of an `i128`.
+    I128(i128),
+    /// A floating point value. Values of smaller floating point types are converted without loss.
+    F64(f64),
+    /// The contents of a string, e.g. a `&str`.
+    String(String),
     /// Notify the user that we encountered a problem correctly resolving the variable.
     /// - The variable will be visible to the user, as will the other field of the variable.
     /// - The contained warning message will be displayed to the user.
     /// - The debugger will not attempt to resolve additional fields or children of this variable.
     Error(String),
+    /// The variable has no value at the current location in the program, for a known reason, e.g.
+    /// because the compiler optimized it out.
+    ///
+    /// This is not an error of the debugger, but like for an error, the debugger does not attempt
+    /// to resolve the children of this variable.
+    Unavailable(UnavailableReason),
     /// The value has not been set. This could be because ...
     /// - It is too early in the process to have discovered its value, or ...
     /// - The variable cannot have a stored value, e.g. a `struct`. In this case, please use
@@ -45,7 +68,17 @@ impl std::fmt::Display for VariableValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             VariableValue::Valid(value) => value.fmt(f),
+            VariableValue::Bool(value) => value.fmt(f),
+            // Quoted, with escapes for characters which are not printable, e.g. `'\n'`.
+            VariableValue::Char(value) => write!(f, "{value:?}"),
+            VariableValue::U64(value) => value.fmt(f),
+            VariableValue::I64(value) => value.fmt(f),
+            VariableValue::U128(value) => value.fmt(f),
+            VariableValue::I128(value) => value.fmt(f),
+            VariableValue::F64(value) => f.write_str(&language::value::format_float(*value)),
+            VariableValue::String(value) => value.fmt(f),
             VariableValue::Error(error) => write!(f, "< {error} >"),
+            VariableValue::Unavailable(reason) => write!(f, "<{reason}>"),
             VariableValue::Empty => write!(
                 f,
                 "Value not set. Please use Variable::get_value() to infer a human readable variable value"
@@ -55,19 +88,169 @@ impl std::fmt::Display for VariableValue {
 }
 
 impl VariableValue {
-    /// Returns `true` if the variable resolver did not encounter an error, `false` otherwise.
+    /// Returns `true` if the variable resolver did not encounter an error, and the value is not
+    /// [unavailable](VariableValue::Unavailable), `false` otherwise.
     pub fn is_valid(&self) -> bool {
-        !matches!(self, VariableValue::Error(_))
+        !matches!(
+            self,
+            VariableValue::Error(_) | VariableValue::Unavailable(_)
+        )
     }
 
     /// Returns `true` if no value or error is present, `false` otherwise.
     pub fn is_empty(&self) -> bool {
         matches!(self, VariableValue::Empty)
     }
+
+    /// Returns the value as a `u64`, if it is an integer that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            VariableValue::U64(value) => Some(value),
+            VariableValue::I64(value) => u64::try_from(value).ok(),
+            VariableValue::U128(value) => u64::try_from(value).ok(),
+            VariableValue::I128(value) => u64::try_from(value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `i64`, if it is an integer that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            VariableValue::U64(value) => i64::try_from(value).ok(),
+            VariableValue::I64(value) => Some(value),
+            VariableValue::U128(value) => i64::try_from(value).ok(),
+            VariableValue::I128(value) => i64::try_from(value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as an `f64`, if it is a floating point value.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            VariableValue::F64(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `bool`, if it is a boolean value.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            VariableValue::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `char`, if it is a character value.
+    pub fn as_char(&self) -> Option<char> {
+        match *self {
+            VariableValue::Char(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `u128`, if it is an integer that fits.
+    pub fn as_u128(&self) -> Option<u128> {
+        match *self {
+            VariableValue::U64(value) => Some(value.into()),
+            VariableValue::I64(value) => u128::try_from(value).ok(),
+            VariableValue::U128(value) => Some(value),
+            VariableValue::I128(value) => u128::try_from(value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the numeric value of an integer, a boolean or a character, e.g. `65` for `'A'`,
+    /// which is the alternate representation of booleans and characters.
+    ///
+    /// Returns `None` for unsigned integers which do not fit in an `i128`, see [`Self::as_u128`].
+    pub fn as_numeric(&self) -> Option<i128> {
+        match *self {
+            VariableValue::U64(value) => Some(value.into()),
+            VariableValue::I64(value) => Some(value.into()),
+            VariableValue::U128(value) => i128::try_from(value).ok(),
+            VariableValue::I128(value) => Some(value),
+            VariableValue::Bool(value) => Some(value.into()),
+            VariableValue::Char(value) => Some(u32::from(value).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the contents of a string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VariableValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Format the value for display in the given [`ValueFormat`].
+    ///
+    /// The `byte_size` of the type is used to show negative integers in hexadecimal and binary as
+    /// their two's complement bits, zero padded to the width of the type, e.g. `0xfffe` for an `i16`
+    /// of `-2`. Values which are not integers, booleans or characters are always shown as they are.
+    pub fn format(&self, format: ValueFormat, byte_size: Option<u64>) -> String {
+        let numeric = match *self {
+            VariableValue::U128(value) => Some(value),
+            _ => self.as_numeric().map(|value| value as u128),
+        };
+        let Some(numeric) = numeric else {
+            return self.to_string();
+        };
+
+        let byte_size = byte_size
+            .filter(|size| (1..=16).contains(size))
+            .unwrap_or(match self {
+                VariableValue::Bool(_) => 1,
+                VariableValue::Char(_) => 4,
+                VariableValue::U128(_) | VariableValue::I128(_) => 16,
+                _ => 8,
+            }) as usize;
+        let bits = if byte_size < 16 {
+            numeric & ((1 << (byte_size * 8)) - 1)
+        } else {
+            numeric
+        };
+
+        match format {
+            ValueFormat::Auto => self.to_string(),
+            ValueFormat::Dec => match *self {
+                VariableValue::U128(value) => value.to_string(),
+                _ => (numeric as i128).to_string(),
+            },
+            ValueFormat::Hex => format!("{bits:#0width$x}", width = 2 + byte_size * 2),
+            ValueFormat::Bin => format!("{bits:#0width$b}", width = 2 + byte_size * 8),
+            ValueFormat::Char if matches!(self, VariableValue::Bool(_)) => self.to_string(),
+            ValueFormat::Char => u32::try_from(numeric)
+                .ok()
+                .and_then(char::from_u32)
+                .map_or_else(|| self.to_string(), |value| format!("{value:?}")),
+        }
+    }
+}
+
+/// The format in which the values of integers, booleans and characters are displayed.
+///
+/// The format can be set for all variables of a [`VariableCache`], with
+/// [`VariableCache::set_value_format`], and for a single variable with
+/// [`Variable::set_value_format`]. Values can be written in any of the formats, regardless of the
+/// format they are displayed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
+pub enum ValueFormat {
+    /// The natural format of the type, e.g. decimal for integers, and `'A'` for characters.
+    #[default]
+    Auto,
+    /// Decimal, which shows booleans and characters as their numeric value.
+    Dec,
+    /// Hexadecimal, zero padded to the size of the type, e.g. `0x002a` for a `u16`.
+    Hex,
+    /// Binary, zero padded to the size of the type, e.g. `0b00101010` for a `u8`.
+    Bin,
+    /// Quoted characters, e.g. `'*'` for the integer `42`, if the value is a valid character.
+    Char,
 }
 
 /// The type of variable we have at hand.
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize)]
 pub enum VariableName {
     /// Top-level variable for static variables, child of a stack frame variable,
     /// and holds all the static scoped variables which are directly visible to the
@@ -85,7 +268,7 @@ pub enum VariableName {
     Namespace(String),
     /// Variable with a specific name
     Named(String),
-    /// Entry of an array or similar
+    /// Member of an array or similar, by its index, which is displayed as `[index]`.
     Indexed(u64),
     /// Variable with an unknown name
     #[default]
@@ -102,12 +285,27 @@ impl std::fmt::Display for VariableName {
             VariableName::AnonymousNamespace => write!(f, "<anonymous_namespace>"),
             VariableName::Namespace(name) => name.fmt(f),
             VariableName::Named(name) => name.fmt(f),
-            VariableName::Indexed(index) => write!(f, "__{index}"),
+            VariableName::Indexed(index) => write!(f, "[{index}]"),
             VariableName::Unknown => write!(f, "<unknown>"),
         }
     }
 }
 
+impl VariableName {
+    /// The name of the variable as a segment of a path, see [`VariableCache::get_variable_by_path`].
+    ///
+    /// The scope roots are `static_scope`, `local_scope` and `registers`, and all other variables
+    /// use their displayed name, e.g. `[2]` for array members.
+    pub(crate) fn path_segment(&self) -> String {
+        match self {
+            VariableName::StaticScopeRoot => "static_scope".to_string(),
+            VariableName::LocalScopeRoot => "local_scope".to_string(),
+            VariableName::RegistersRoot => "registers".to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
 /// Encode the nature of the Debug Information Entry in a way that we can resolve child nodes of a
 /// [Variable].
 ///
@@ -122,11 +320,28 @@ pub enum VariableNodeType {
     ///   have enough information to expand it on demand. Except if they fall into one of the
     ///   special cases handled by [VariableNodeType::RecurseToBaseType]
     TypeOffset(DebugInfoOffset, UnitOffset),
+    /// Like [VariableNodeType::TypeOffset], for a type in the supplementary object file, see
+    /// [`DebugInfoOptions::supplementary_file`](crate::DebugInfoOptions::supplementary_file).
+    SupplementaryTypeOffset(DebugInfoOffset, UnitOffset),
     /// Use the `header_offset` and `entries_offset` as direct references for recursing the variable
     /// children.
     /// - Rule: All top level variables in a [StackFrame] are automatically deferred, i.e
     ///   [VariableName::LocalScopeRoot], [VariableName::RegistersRoot].
     DirectLookup(DebugInfoOffset, UnitOffset),
+    /// Use the `header_offset` and the `type_offset` of the member type to create the members of
+    /// an array on demand. The number of members is the `count` of the [VariableType::Array], and
+    /// each member is located at the address of the array, plus its index times the byte size of
+    /// the member type.
+    /// - Rule: Arrays with more members than
+    ///   [`DebugInfoOptions::max_eager_array_length`](crate::DebugInfoOptions::max_eager_array_length)
+    ///   are deferred, so that large buffers are only read when they are requested.
+    ArrayMembers(DebugInfoOffset, UnitOffset),
+    /// Use the `header_offset` and the `type_offset` of the member type to create the members of
+    /// a sequence on demand, e.g. the elements of a Rust `Vec<T>` or slice. The members are
+    /// located like the members of an array, at the address and with the count that follow the
+    /// offsets, but they are the children of the sequence variable itself.
+    /// - Rule: Sequences are deferred like arrays, see [VariableNodeType::ArrayMembers].
+    SequenceMembers(DebugInfoOffset, UnitOffset, u64, u64),
     /// Look up information from all compilation units. This is used to resolve static variables, so
     /// when [`VariableName::StaticScopeRoot`] is used.
     UnitsLookup,
@@ -149,8 +364,8 @@ pub enum VariableNodeType {
     ///   arrays.
     /// - Rule: Enumerated types WILL ALWAYS BE recursed, because we only ever want to see the
     ///   'active' child as the value.
-    /// - Rule: For now, Array types WILL ALWAYS BE recursed. TODO: Evaluate if it is beneficial to
-    ///   defer these.
+    /// - Rule: Array types WILL BE recursed, unless they are too long, see
+    ///   [VariableNodeType::ArrayMembers].
     /// - Rule: For now, Union types WILL ALWAYS BE recursed. TODO: Evaluate if it is beneficial to
     ///   defer these.
     #[default]
@@ -163,11 +378,23 @@ impl VariableNodeType {
     pub fn is_deferred(&self) -> bool {
         match self {
             VariableNodeType::TypeOffset(_, _)
+            | VariableNodeType::SupplementaryTypeOffset(_, _)
             | VariableNodeType::DirectLookup(_, _)
+            | VariableNodeType::ArrayMembers(_, _)
+            | VariableNodeType::SequenceMembers(..)
             | VariableNodeType::UnitsLookup => true,
             VariableNodeType::DoNotRecurse | VariableNodeType::RecurseToBaseType => false,
         }
     }
+
+    /// Will return `true` if the members of the variable are created on demand, so they can be
+    /// resolved a page at a time, see [`DebugInfo::cache_deferred_variables_paged`](crate::DebugInfo::cache_deferred_variables_paged).
+    pub fn has_deferred_members(&self) -> bool {
+        matches!(
+            self,
+            VariableNodeType::ArrayMembers(..) | VariableNodeType::SequenceMembers(..)
+        )
+    }
 }
 
 /// The starting bit (and direction) of a bit field type.
@@ -246,7 +473,7 @@ impl Bitfield {
     }
 }
 
-/// A modifier to a variable type. Currently only used to format the type name.
+/// A modifier to a variable type, i.e. a type qualifier or a typedef alias.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub enum Modifier {
     /// The type is declared as `volatile`.
@@ -319,6 +546,43 @@ impl VariableType {
         }
     }
 
+    /// The number of bytes this type name uses on the heap, see [`VariableCache::approximate_bytes`].
+    fn heap_bytes(&self) -> usize {
+        match self {
+            Self::Base(name) | Self::Struct(name) | Self::Enum(name) | Self::Other(name) => {
+                name.capacity()
+            }
+            Self::Pointer(name) => name.as_ref().map_or(0, String::capacity),
+            Self::Bitfield(_, ty)
+            | Self::Array {
+                item_type_name: ty, ..
+            } => std::mem::size_of::<Self>() + ty.heap_bytes(),
+            Self::Modified(modifier, ty) => {
+                let alias = match modifier {
+                    Modifier::Typedef(name) => name.capacity(),
+                    _ => 0,
+                };
+                alias + std::mem::size_of::<Self>() + ty.heap_bytes()
+            }
+            Self::Namespace | Self::Unknown => 0,
+        }
+    }
+
+    /// The type qualifiers, like `const` and `volatile`, which apply to this type, including the
+    /// qualifiers in the typedefs it refers to. The qualifiers of pointed to types and of array
+    /// items do not apply to the type itself.
+    pub fn qualifiers(&self) -> impl Iterator<Item = &Modifier> {
+        std::iter::successors(Some(self), |ty| match ty {
+            Self::Modified(_, ty) => Some(ty.as_ref()),
+            _ => None,
+        })
+        .filter_map(|ty| match ty {
+            Self::Modified(Modifier::Typedef(_), _) => None,
+            Self::Modified(modifier, _) => Some(modifier),
+            _ => None,
+        })
+    }
+
     /// Is this variable of a Rust PhantomData marker type?
     pub fn is_phantom_data(&self) -> bool {
         match self {
@@ -351,6 +615,11 @@ impl VariableType {
     pub(crate) fn display_name(&self, language: &dyn ProgrammingLanguage) -> String {
         match self {
             VariableType::Modified(Modifier::Typedef(name), _) => name.clone(),
+            VariableType::Modified(modifier, ty)
+                if matches!(ty.skip_modifiers(), VariableType::Pointer(_)) =>
+            {
+                language.qualified_pointer_type_name(modifier, &ty.display_name(language))
+            }
             VariableType::Modified(modifier, ty) => {
                 language.modified_type_name(modifier, &ty.display_name(language))
             }
@@ -415,25 +684,147 @@ pub enum VariableLocation {
     Address(u64),
     /// The value of the variable is directly available.
     Value,
-    /// The variable is stored in a register, and the value is read from there.
+    /// The variable is stored in a register, and this is the value of the register.
+    ///
+    /// The value is not an address, the variable does not have a memory location.
     RegisterValue(RegisterValue),
+    /// The value of the variable only exists in the debug information, e.g. because the compiler
+    /// propagated a constant. The bytes are stored in the byte order of the target.
+    ///
+    /// The value cannot be changed.
+    Implicit(Vec<u8>),
+    /// The variable is a pointer which only exists in the debug information, and this is the location
+    /// of the value it points to.
+    ///
+    /// The pointer cannot be changed.
+    ImplicitPointer(Box<VariableLocation>),
     /// There was an error evaluating the variable location.
     Error(String),
     /// Support for handling the location of this variable is not (yet) implemented.
     Unsupported(String),
 }
 
+/// The reason why a [`Variable`] has no value, see [`VariableValue::Unavailable`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum UnavailableReason {
+    /// The location of the variable is its value on entry to the function, `DW_OP_entry_value`,
+    /// which could not be recovered by unwinding the frame.
+    #[error("optimized out (entry value unavailable)")]
+    EntryValue,
+    /// The location of the variable is a location list, and none of its entries covers the program
+    /// counter of the frame.
+    #[error("optimized out at this PC")]
+    NoLocationAtProgramCounter,
+    /// The variable is thread-local, and there is no resolver for the addresses of thread-local
+    /// variables, see [`DebugInfo::set_tls_resolver`](crate::DebugInfo::set_tls_resolver).
+    #[error("thread-local (resolver not configured)")]
+    TlsResolverNotConfigured,
+}
+
+/// The reason why the value of a [`Variable`] cannot be changed, see
+/// [`VariableCache::update_variable_value`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum NotWritableReason {
+    /// The value only exists in the debug information, e.g. because the compiler propagated a
+    /// constant, or it was computed by a DWARF expression.
+    #[error("its value only exists in the debug information")]
+    Synthetic,
+    /// The variable is stored in a register.
+    ///
+    /// The location only has the value of the register, not which register it is, so it cannot be
+    /// written.
+    #[error("it is stored in a register")]
+    Register,
+    /// The location of the variable is not known, e.g. because it was optimized out.
+    #[error("its location is not known")]
+    NoLocation,
+    /// Only variables with a base type, like integers, floats, `bool` and `char`, can be changed.
+    #[error("only variables with a base type can be changed, not {0} variables")]
+    NotBaseType(String),
+    /// The new value does not have the size of the variable.
+    #[error("the new value has {actual} bytes, but the variable has {expected} bytes")]
+    SizeMismatch {
+        /// The size of the variable.
+        expected: u64,
+        /// The size of the new value.
+        actual: usize,
+    },
+    /// The memory of the variable did not change when it was written, e.g. because it is in flash.
+    #[error("the memory at {address:#010x} is read-only")]
+    ReadOnly {
+        /// The address of the variable.
+        address: u64,
+    },
+}
+
+/// The reason why a [`Variable`] cannot be watched, see [`Variable::watch_spec`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum NotWatchableReason {
+    /// The value only exists in the debug information, e.g. because the compiler propagated a
+    /// constant, or it was computed by a DWARF expression.
+    #[error("its value only exists in the debug information")]
+    Synthetic,
+    /// The variable is stored in a register.
+    #[error("it is stored in a register")]
+    Register,
+    /// The location of the variable is not known, e.g. because it was optimized out.
+    #[error("its location is not known")]
+    NoLocation,
+    /// The size of the variable is not known, or it has no bytes.
+    #[error("its size is not known")]
+    UnknownSize,
+    /// The variable does not fit in the aligned range of [`MAX_WATCH_SIZE`] bytes a watchpoint can
+    /// cover.
+    #[error(
+        "it has {byte_size} bytes, but a watchpoint covers at most {MAX_WATCH_SIZE} aligned bytes"
+    )]
+    TooLarge {
+        /// The size of the variable.
+        byte_size: u64,
+    },
+}
+
+/// The largest number of bytes that a hardware watchpoint can cover, see [`Variable::watch_spec`].
+pub const MAX_WATCH_SIZE: u64 = 8;
+
+/// The accesses which trigger a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum WatchKind {
+    /// The memory is read.
+    Read,
+    /// The memory is written.
+    Write,
+    /// The memory is read or written.
+    ReadWrite,
+}
+
+/// The memory range of a hardware watchpoint on a [`Variable`], see [`Variable::watch_spec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WatchSpec {
+    /// The start of the range, which is aligned to its size.
+    pub address: u64,
+    /// The size of the range, which is 1, 2, 4 or 8 bytes. It can be larger than the variable, to
+    /// align the range.
+    pub size: u64,
+    /// The accesses which trigger the watchpoint.
+    pub kind: WatchKind,
+}
+
 impl VariableLocation {
     /// Return the memory address, if available. Otherwise an error is returned.
+    ///
+    /// A variable that is stored in a register has no memory address, the contents of the
+    /// register are its value.
     pub fn memory_address(&self) -> Result<u64, DebugError> {
         match self {
             VariableLocation::Address(address) => Ok(*address),
-            VariableLocation::RegisterValue(address) => match TryInto::<u64>::try_into(*address) {
-                Ok(address) => Ok(address),
-                Err(_) => Err(DebugError::WarnAndContinue {
-                    message: "Register value is not a valid address".to_string(),
-                }),
-            },
+            VariableLocation::RegisterValue(_) => Err(DebugError::WarnAndContinue {
+                message: "Variable is stored in a register, and does not have a memory location"
+                    .to_string(),
+            }),
             VariableLocation::Error(error) => Err(DebugError::WarnAndContinue {
                 message: error.clone(),
             }),
@@ -448,6 +839,8 @@ impl VariableLocation {
         match self {
             VariableLocation::Address(_)
             | VariableLocation::RegisterValue(_)
+            | VariableLocation::Implicit(_)
+            | VariableLocation::ImplicitPointer(_)
             | VariableLocation::Value
             | VariableLocation::Unknown => true,
             _other => false,
@@ -468,6 +861,8 @@ impl std::fmt::Display for VariableLocation {
                 RegisterValue::U64(value) => write!(f, "{value:#018X}"),
                 RegisterValue::U128(value) => write!(f, "{value:#034X}"),
             },
+            VariableLocation::Implicit(_) => "<implicit value>".fmt(f),
+            VariableLocation::ImplicitPointer(_) => "<implicit pointer>".fmt(f),
             VariableLocation::Value => "<not applicable - statically stored value>".fmt(f),
             VariableLocation::Error(error) => error.fmt(f),
             VariableLocation::Unsupported(reason) => reason.fmt(f),
@@ -502,6 +897,8 @@ pub struct Variable {
     pub source_location: Option<SourceLocation>,
     /// Programming language of the defining compilation unit.
     pub language: DwLang,
+    /// The byte order used to store the value of this variable in target memory.
+    pub(crate) endianness: RunTimeEndian,
 
     /// The name of the type of this variable.
     pub type_name: VariableType,
@@ -512,8 +909,18 @@ pub struct Variable {
     pub variable_node_type: VariableNodeType,
     /// The starting location/address in memory where this Variable's value is stored.
     pub memory_location: VariableLocation,
+    /// `true` if the variable has static storage, like a `static` which is declared in a function,
+    /// instead of being stored on the stack or in a register while its function runs.
+    pub is_static: bool,
+    /// `true` if the variable is a parameter of its function, instead of a local variable.
+    pub is_parameter: bool,
     /// The size of this variable in bytes.
     pub byte_size: Option<u64>,
+    /// The `DW_AT_encoding` of a base type, e.g. `DW_ATE_boolean`, which selects how its value is
+    /// read.
+    pub(crate) encoding: Option<DwAte>,
+    /// The format of the value of this variable, which overrides the format of the cache.
+    value_format: Option<ValueFormat>,
     /// The role of this variable.
     pub role: VariantRole,
 }
@@ -526,6 +933,9 @@ impl Variable {
             language: unit_info
                 .map(|info| info.get_language())
                 .unwrap_or(gimli::DW_LANG_Rust),
+            endianness: unit_info
+                .map(|info| info.endianness)
+                .unwrap_or(RunTimeEndian::Little),
             type_node_offset: None,
             variable_key: Default::default(),
             parent_key: Default::default(),
@@ -536,22 +946,175 @@ impl Variable {
             type_name: Default::default(),
             variable_node_type: Default::default(),
             memory_location: Default::default(),
+            is_static: false,
+            is_parameter: false,
             byte_size: None,
+            encoding: None,
+            value_format: None,
             role: Default::default(),
         }
     }
 
     /// Returns the readable name of the variable type.
+    ///
+    /// This is the name of the alias for types declared with a typedef, like `status_t`, see
+    /// [`Self::resolved_type_name`].
     pub fn type_name(&self) -> String {
         self.type_name
             .display_name(language::from_dwarf(self.language).as_ref())
     }
 
+    /// Returns the readable name of the variable type, after resolving typedefs and their chains,
+    /// e.g. `unsigned int` for a `status_t` which is declared as a `uint32_t`.
+    pub fn resolved_type_name(&self) -> String {
+        self.type_name
+            .type_name(language::from_dwarf(self.language).as_ref())
+    }
+
+    /// Returns the type qualifiers of the variable, like `const` and `volatile`, see
+    /// [`VariableType::qualifiers`].
+    pub fn qualifiers(&self) -> impl Iterator<Item = &Modifier> {
+        self.type_name.qualifiers()
+    }
+
+    /// Returns `true` if the variable is declared `volatile`, like a memory mapped register, so
+    /// its value can change without the program writing it.
+    pub fn is_volatile(&self) -> bool {
+        self.qualifiers()
+            .any(|modifier| *modifier == Modifier::Volatile)
+    }
+
+    /// The number of bytes this variable uses on the heap, for its name, type name and value, see
+    /// [`VariableCache::approximate_bytes`].
+    pub(crate) fn approximate_heap_bytes(&self) -> usize {
+        let name = match &self.name {
+            VariableName::Namespace(name) | VariableName::Named(name) => name.capacity(),
+            _ => 0,
+        };
+        let value = match &self.value {
+            VariableValue::Valid(value)
+            | VariableValue::String(value)
+            | VariableValue::Error(value) => value.capacity(),
+            _ => 0,
+        };
+        let linkage_name = self.linkage_name.as_ref().map_or(0, String::capacity);
+
+        name + value + linkage_name + self.type_name.heap_bytes()
+    }
+
     /// Get a unique key for this variable.
     pub fn variable_key(&self) -> ObjectRef {
         self.variable_key
     }
 
+    /// The path of this variable in `cache`, e.g. `local_scope.device.buffers[2].len`.
+    ///
+    /// The path starts with the root of the cache, and can be used to find the variable again with
+    /// [`VariableCache::get_variable_by_path`], e.g. in the cache of the next stop.
+    pub fn path(&self, cache: &VariableCache) -> String {
+        let mut segments = vec![self.name.path_segment()];
+        let mut parent_key = self.parent_key;
+        while let Some(parent) = cache.get_variable_ref(parent_key) {
+            segments.push(parent.name.path_segment());
+            parent_key = parent.parent_key;
+        }
+
+        let mut path = String::new();
+        for segment in segments.iter().rev() {
+            if !path.is_empty() && !segment.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+        path
+    }
+
+    /// The address of the memory in which this variable is stored, e.g. to set a data breakpoint on
+    /// it.
+    ///
+    /// Variables which are stored in registers, or only exist in the debug information, don't have
+    /// an address.
+    pub fn address(&self) -> Option<u64> {
+        match self.memory_location {
+            VariableLocation::Address(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    /// The range of a hardware watchpoint which triggers when the memory of this variable is
+    /// accessed with `kind`, e.g. to program a comparator of the Arm DWT, or a RISC-V trigger.
+    ///
+    /// Comparators match 1, 2, 4 or 8 bytes at an address which is aligned to that size, so the
+    /// range is the smallest of them which contains the whole variable. Variables which are not in
+    /// memory, or which don't fit in [`MAX_WATCH_SIZE`] aligned bytes, cannot be watched.
+    pub fn watch_spec(&self, kind: WatchKind) -> Result<WatchSpec, DebugError> {
+        let not_watchable = |reason| DebugError::VariableNotWatchable {
+            name: self.name.to_string(),
+            reason,
+        };
+
+        let address = match self.memory_location {
+            VariableLocation::Address(address) => address,
+            VariableLocation::Value
+            | VariableLocation::Implicit(_)
+            | VariableLocation::ImplicitPointer(_) => {
+                return Err(not_watchable(NotWatchableReason::Synthetic));
+            }
+            VariableLocation::RegisterValue(_) => {
+                return Err(not_watchable(NotWatchableReason::Register));
+            }
+            VariableLocation::Unknown
+            | VariableLocation::Unavailable
+            | VariableLocation::Error(_)
+            | VariableLocation::Unsupported(_) => {
+                return Err(not_watchable(NotWatchableReason::NoLocation));
+            }
+        };
+        let Some(byte_size @ 1..) = self.byte_size else {
+            return Err(not_watchable(NotWatchableReason::UnknownSize));
+        };
+
+        let end = address.saturating_add(byte_size);
+        std::iter::successors(Some(1), |size| Some(size * 2))
+            .take_while(|size| *size <= MAX_WATCH_SIZE)
+            .map(|size| (address & !(size - 1), size))
+            .find(|(start, size)| start + size >= end)
+            .map(|(address, size)| WatchSpec {
+                address,
+                size,
+                kind,
+            })
+            .ok_or_else(|| not_watchable(NotWatchableReason::TooLarge { byte_size }))
+    }
+
+    /// The value that was read for this variable. Use [`Variable::to_string`] to get a human
+    /// readable value, which also covers variables with children, like structs.
+    pub fn value(&self) -> &VariableValue {
+        &self.value
+    }
+
+    /// The format in which the value of this variable is displayed, if it overrides the format of
+    /// the [`VariableCache`].
+    pub fn value_format(&self) -> Option<ValueFormat> {
+        self.value_format
+    }
+
+    /// Display the value of this variable in `format`, instead of the format of the
+    /// [`VariableCache`], or remove the override with `None`.
+    ///
+    /// Use [`VariableCache::update_variable`] to store the format in the cache.
+    pub fn set_value_format(&mut self, format: Option<ValueFormat>) {
+        self.value_format = format;
+    }
+
+    /// The value of this variable, formatted in its own format, or that of the `variable_cache`.
+    fn display_value(&self, variable_cache: &VariableCache) -> String {
+        let format = self
+            .value_format
+            .unwrap_or_else(|| variable_cache.value_format());
+        self.value.format(format, self.byte_size)
+    }
+
     /// This ensures debug frontends can see the errors, but doesn't fail because of a single
     /// variable not being able to decode correctly.
     pub fn set_value(&mut self, new_value: VariableValue) {
@@ -584,7 +1147,20 @@ impl Variable {
         let valid_value = self.is_valid();
         let valid_type = self.type_name != VariableType::Unknown;
         let valid_memory = self.memory_location.valid();
-        if !valid_value || !valid_type || !valid_memory {
+        if matches!(
+            self.memory_location,
+            VariableLocation::Implicit(_) | VariableLocation::ImplicitPointer(_)
+        ) {
+            Err(DebugError::Other(format!(
+                "Cannot update variable: {:?}, its value only exists in the debug information.",
+                self.name
+            )))
+        } else if let VariableLocation::RegisterValue(_) = self.memory_location {
+            Err(DebugError::VariableNotWritable {
+                name: self.name.to_string(),
+                reason: NotWritableReason::Register,
+            })
+        } else if !valid_value || !valid_type || !valid_memory {
             // Insufficient data available.
             Err(DebugError::Other(format!(
                 "Cannot update variable: {:?}, with supplied information (value={:?}, type={:?}, memory location={:#010x?}).",
@@ -615,7 +1191,7 @@ impl Variable {
             // The `value` for this `Variable` is non empty because either
             // - It is base data type for which a value was determined based on the core runtime
             // - We encountered an error somewhere, so report it to the user
-            return format!("{}", self.value);
+            return self.display_value(variable_cache);
         }
 
         if matches!(
@@ -645,9 +1221,11 @@ impl Variable {
                 // a logic problem in the stack unwind
                 "Error: This is a bug! Attempted to evaluate a Variable with no type or no memory location".to_string()
             }
-        } else if matches!(self.type_name, VariableType::Struct(ref name) if name == "None") {
+        } else if self.language == gimli::DW_LANG_Rust
+            && matches!(self.type_name.inner(), VariableType::Struct(name) if name == "None")
+        {
             "None".to_string()
-        } else if matches!(self.type_name, VariableType::Array { count: 0, .. }) {
+        } else if matches!(self.type_name.inner(), VariableType::Array { count: 0, .. }) {
             self.formatted_variable_value(variable_cache, 0, false)
                 .unwrap_or_default()
         } else {
@@ -664,7 +1242,7 @@ impl Variable {
         memory: &mut dyn MemoryInterface,
         variable_cache: &VariableCache,
     ) {
-        if let VariableValue::Error(_) = self.value {
+        if !self.value.is_valid() {
             // Nothing more to do ...
             return;
         }
@@ -681,7 +1259,7 @@ impl Variable {
         }
 
         if self.variable_node_type.is_deferred()
-            || matches!(self.type_name, VariableType::Pointer(_))
+            || matches!(self.type_name.inner(), VariableType::Pointer(_))
         {
             // And we have not previously assigned the value, then assign the type and address as
             // the value.
@@ -696,21 +1274,25 @@ impl Variable {
             self.type_name
         );
 
-        self.value =
-            language::from_dwarf(self.language).read_variable_value(self, memory, variable_cache);
+        // Booleans, characters and integers are read according to their encoding, in all languages.
+        self.value = language::value::read_encoded_value(self, memory).unwrap_or_else(|| {
+            language::from_dwarf(self.language).read_variable_value(self, memory, variable_cache)
+        });
     }
 
-    /// The variable is considered to be an 'indexed' variable if the name starts with two
-    /// underscores followed by a number. e.g. "__1".
+    /// The variable is considered to be an 'indexed' variable if it is a member of an array, or
+    /// if the name starts with two underscores followed by a number, e.g. "__1" for Rust tuple fields.
     // TODO: Consider replacing this logic with `std::str::pattern::Pattern` when that API stabilizes
     pub fn is_indexed(&self) -> bool {
         match &self.name {
             VariableName::Named(name) => {
-                name.starts_with("__")
+                self.language == gimli::DW_LANG_Rust
+                    && name.starts_with("__")
                     && name
                         .find(char::is_numeric)
                         .is_some_and(|zero_based_position| zero_based_position == 2)
             }
+            VariableName::Indexed(_) => true,
             // Other kind of variables are never indexed
             _ => false,
         }
@@ -722,7 +1304,7 @@ impl Variable {
     }
 
     /// `true` if the Variable has a valid value, or an empty value.
-    /// `false` if the Variable has a VariableValue::Error(_) value
+    /// `false` if the Variable has a VariableValue::Error(_) or VariableValue::Unavailable(_) value
     pub fn is_valid(&self) -> bool {
         self.value.is_valid()
     }
@@ -740,10 +1322,11 @@ impl Variable {
             // This is the end of the recursion where we already have a scalar
             // value for a variable and we can just move it up.
             let line_start = line_indent_string(indentation);
+            let value = self.display_value(variable_cache);
             return Some(if show_name {
-                format!("{line_start}{}: {} = {}", self.name, type_name, self.value)
+                format!("{line_start}{}: {} = {}", self.name, type_name, value)
             } else {
-                format!("{line_start}{}", self.value)
+                format!("{line_start}{value}")
             });
         } else if matches!(
             self.name,
@@ -766,7 +1349,10 @@ impl Variable {
             VariableType::Array { .. } => {
                 format_array_value(variable_cache, indentation, children, &type_name)
             }
-            VariableType::Struct(name) if name == "Some" || name == "Ok" || name == "Err" => {
+            VariableType::Struct(name)
+                if self.language == gimli::DW_LANG_Rust
+                    && (name == "Some" || name == "Ok" || name == "Err") =>
+            {
                 format_struct_value(variable_cache, indentation, children, &type_name)
             }
             _ if first_child.is_none() => {
@@ -794,6 +1380,17 @@ impl Variable {
         })
     }
 
+    /// The width of this variable in bits, if it is a bitfield, like `unsigned int mode : 3`.
+    ///
+    /// The bits are stored in the [`Self::byte_size`] bytes of the storage unit at the
+    /// [`Self::memory_location`] of the bitfield.
+    pub fn bit_size(&self) -> Option<u64> {
+        match self.type_name {
+            VariableType::Bitfield(bitfield, _) => Some(bitfield.length()),
+            _ => None,
+        }
+    }
+
     /// Calculate the memory range that contains the value of this variable.
     ///
     /// If the location and/or byte size is not known, then return None.
@@ -815,6 +1412,77 @@ impl Variable {
     }
 }
 
+/// A variable is serialized like a variable of [`VariableCache::serialize_tree`], without its
+/// children.
+impl Serialize for Variable {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SerializedVariable::new(self, None).serialize(serializer)
+    }
+}
+
+/// The serialized form of a [`Variable`], with its descendants if they are read from a
+/// [`VariableCache`]. The fields are described at [`VariableCache::serialize_tree`].
+#[derive(Serialize)]
+pub(crate) struct SerializedVariable<'c> {
+    name: &'c VariableName,
+    type_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    byte_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'c VariantRole>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_static: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_parameter: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_location: Option<&'c SourceLocation>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    deferred: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<SerializedVariable<'c>>,
+}
+
+impl<'c> SerializedVariable<'c> {
+    /// The serialized form of `variable`, with its descendants in `cache`, if it is given.
+    pub(crate) fn new(variable: &'c Variable, cache: Option<&'c VariableCache>) -> Self {
+        let children = cache
+            .map(|cache| {
+                cache
+                    .get_children(variable.variable_key)
+                    .map(|child| Self::new(child, Some(cache)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let format = variable
+            .value_format
+            .or_else(|| cache.map(VariableCache::value_format))
+            .unwrap_or_default();
+
+        Self {
+            name: &variable.name,
+            type_name: variable.type_name(),
+            value: (!variable.value.is_empty())
+                .then(|| variable.value.format(format, variable.byte_size)),
+            location: (variable.memory_location != VariableLocation::Unknown)
+                .then(|| variable.memory_location.to_string()),
+            byte_size: variable.byte_size,
+            role: (variable.role != VariantRole::NonVariant).then_some(&variable.role),
+            is_static: variable.is_static,
+            is_parameter: variable.is_parameter,
+            source_location: variable.source_location.as_ref(),
+            deferred: variable.variable_node_type.is_deferred() && children.is_empty(),
+            children,
+        }
+    }
+}
+
 /// Format a pointer value
 ///
 /// Formats the pointed to value and potential subsequent children as well.
@@ -974,3 +1642,42 @@ fn line_indent_string(indentation: usize) -> String {
     let line_feed = if indentation == 0 { "" } else { "\n" };
     format!("{line_feed}{:\t<indentation$}", "")
 }
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{ValueFormat, VariableValue};
+
+    #[test]
+    fn variable_value_display() {
+        assert_eq!(VariableValue::F64(1.0).to_string(), "1.0");
+        assert_eq!(VariableValue::F64(f64::from(0.5_f32)).to_string(), "0.5");
+        assert_eq!(VariableValue::Char('x').to_string(), "'x'");
+        assert_eq!(VariableValue::Char('\n').to_string(), "'\\n'");
+        assert_eq!(VariableValue::I64(-7).to_string(), "-7");
+        assert_eq!(
+            VariableValue::Error("Failed".to_string()).to_string(),
+            "< Failed >"
+        );
+    }
+
+    #[test_case(VariableValue::U64(42), Some(2), ValueFormat::Hex, "0x002a"; "hex padded")]
+    #[test_case(VariableValue::I64(-2), Some(1), ValueFormat::Hex, "0xfe"; "hex negative")]
+    #[test_case(VariableValue::U64(5), Some(1), ValueFormat::Bin, "0b00000101"; "binary padded")]
+    #[test_case(VariableValue::Bool(true), Some(1), ValueFormat::Dec, "1"; "decimal bool")]
+    #[test_case(VariableValue::Char('A'), Some(4), ValueFormat::Dec, "65"; "decimal char")]
+    #[test_case(VariableValue::U64(42), Some(1), ValueFormat::Char, "'*'"; "char")]
+    #[test_case(VariableValue::I64(-1), Some(4), ValueFormat::Char, "-1"; "invalid char")]
+    #[test_case(VariableValue::U128(u128::MAX), Some(16), ValueFormat::Dec, "340282366920938463463374607431768211455"; "u128 decimal")]
+    #[test_case(VariableValue::I64(-1), None, ValueFormat::Hex, "0xffffffffffffffff"; "unknown size")]
+    #[test_case(VariableValue::F64(0.5), Some(4), ValueFormat::Hex, "0.5"; "float")]
+    fn variable_value_format(
+        value: VariableValue,
+        size: Option<u64>,
+        format: ValueFormat,
+        expected: &str,
+    ) {
+        assert_eq!(value.format(format, size), expected);
+    }
+}
@@ -1,20 +1,66 @@
 use super::*;
-use crate::{stack_frame::StackFrameInfo, unit_info::UnitInfo};
+use crate::{stack_frame::StackFrameInfo, unit_info::UnitInfo, variable::SerializedVariable};
 use gimli::UnitOffset;
+use itertools::Either;
 use probe_rs::Error;
 use probe_rs_target::MemoryRange;
 use serde::{Serialize, Serializer};
 use std::{
-    collections::{BTreeMap, btree_map::Entry},
+    collections::{BTreeMap, HashSet, btree_map::Entry},
     ops::Range,
 };
 
+/// A difference between the variables of two caches, see [`VariableCache::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableChange {
+    /// The value of a variable that is in both caches changed.
+    Changed {
+        /// The path of the variable, see [`Variable::path`].
+        path: String,
+        /// The value in the old cache.
+        old_value: String,
+        /// The value in the new cache.
+        new_value: String,
+    },
+    /// The variable is only in the new cache, e.g. a local variable that came into scope.
+    Added {
+        /// The path of the variable in the new cache.
+        path: String,
+    },
+    /// The variable is only in the old cache.
+    Removed {
+        /// The path of the variable in the old cache.
+        path: String,
+    },
+}
+
+impl VariableChange {
+    /// The path of the variable that changed, see [`Variable::path`].
+    pub fn path(&self) -> &str {
+        match self {
+            VariableChange::Changed { path, .. }
+            | VariableChange::Added { path }
+            | VariableChange::Removed { path } => path,
+        }
+    }
+}
+
 /// VariableCache stores available `Variable`s, and provides methods to create and navigate the parent-child relationships of the Variables.
 #[derive(Debug, Clone, PartialEq)]
 pub struct VariableCache {
     root_variable_key: ObjectRef,
 
     variable_hash_map: BTreeMap<ObjectRef, Variable>,
+
+    /// The keys of the children of each variable, by the key of their parent, in the order of their
+    /// keys. The root variable is the child of its parent key, [`ObjectRef::Invalid`].
+    child_keys: BTreeMap<ObjectRef, Vec<ObjectRef>>,
+
+    /// The format of the values of the variables, unless a variable overrides it.
+    value_format: ValueFormat,
+
+    /// The largest number of variables in the cache, see [`VariableCache::set_max_len`].
+    max_len: Option<usize>,
 }
 
 impl Serialize for VariableCache {
@@ -117,7 +163,10 @@ impl VariableCache {
 
         VariableCache {
             root_variable_key: key,
+            child_keys: BTreeMap::from([(variable.parent_key, vec![key])]),
             variable_hash_map: BTreeMap::from([(key, variable)]),
+            value_format: ValueFormat::default(),
+            max_len: None,
         }
     }
 
@@ -157,6 +206,17 @@ impl VariableCache {
         &self.variable_hash_map[&self.root_variable_key]
     }
 
+    /// The format in which the values of the variables are displayed, unless a variable overrides
+    /// it with [`Variable::set_value_format`].
+    pub fn value_format(&self) -> ValueFormat {
+        self.value_format
+    }
+
+    /// Display the values of all variables in `format`, unless a variable overrides it.
+    pub fn set_value_format(&mut self, format: ValueFormat) {
+        self.value_format = format;
+    }
+
     /// Returns the number of `Variable`s in the cache.
     // These caches are constructed with a single root variable, so this should never be empty.
     pub fn len(&self) -> usize {
@@ -168,6 +228,53 @@ impl VariableCache {
         self.variable_hash_map.is_empty()
     }
 
+    /// The largest number of variables in the cache, if it is limited.
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Limit the number of variables in the cache, e.g. to stop the expansion of a large static
+    /// from using all the memory of the debugger. There is no limit by default.
+    ///
+    /// When the cache is full, adding a variable fails with [`DebugError::VariableCacheFull`].
+    /// [`DebugInfo::cache_deferred_variables`] then removes the children it already added, so the
+    /// variable can be expanded again after other variables are removed, or the limit is raised.
+    /// Variables which are already in the cache are kept when the limit is lowered.
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        self.max_len = max_len;
+    }
+
+    /// An estimate of the memory used by the variables in the cache, in bytes.
+    ///
+    /// This includes the variables and the strings they own, like their names and values, but not
+    /// the overhead of the allocator, or of the map that stores them.
+    pub fn approximate_bytes(&self) -> usize {
+        self.variables()
+            .map(|variable| {
+                std::mem::size_of::<(ObjectRef, Variable)>() + variable.approximate_heap_bytes()
+            })
+            .sum()
+    }
+
+    /// All the variables in the cache, in the order of their keys.
+    ///
+    /// The children of a variable are found with [`VariableCache::get_children`] instead, which
+    /// doesn't visit all the variables.
+    fn variables(&self) -> impl Iterator<Item = &Variable> {
+        #[cfg(test)]
+        test::FULL_SCANS.with(|scans| scans.set(scans.get() + 1));
+
+        self.variable_hash_map.values()
+    }
+
+    /// Returns an error if no more variables can be added, see [`VariableCache::set_max_len`].
+    fn check_max_len(&self) -> Result<(), DebugError> {
+        match self.max_len {
+            Some(limit) if self.len() >= limit => Err(DebugError::VariableCacheFull { limit }),
+            _ => Ok(()),
+        }
+    }
+
     /// Create a new variable in the cache
     pub fn create_variable(
         &mut self,
@@ -180,6 +287,7 @@ impl VariableCache {
                 "VariableCache: Attempted to add a new variable with non existent `parent_key`: {parent_key:?}. Please report this as a bug"
             )));
         }
+        self.check_max_len()?;
 
         let mut variable_to_add = Variable::new(unit_info);
         variable_to_add.parent_key = parent_key;
@@ -205,6 +313,7 @@ impl VariableCache {
                 entry.insert(variable_to_add.clone());
             }
         }
+        self.link_child(parent_key, variable_to_add.variable_key);
 
         Ok(variable_to_add)
     }
@@ -225,6 +334,7 @@ impl VariableCache {
                 cache_variable.name, parent_key
             )));
         }
+        self.check_max_len()?;
 
         cache_variable.parent_key = parent_key;
 
@@ -254,6 +364,7 @@ impl VariableCache {
                 cache_variable.name, old_variable.variable_key
             )));
         }
+        self.link_child(parent_key, cache_variable.variable_key);
 
         Ok(())
     }
@@ -279,17 +390,182 @@ impl VariableCache {
         if cache_variable != prev_entry {
             tracing::trace!("Updated:  {:?}", cache_variable);
             tracing::trace!("Previous: {:?}", prev_entry);
+            let previous_parent_key = prev_entry.parent_key;
             *prev_entry = cache_variable.clone();
+            self.relink_child(
+                cache_variable.variable_key,
+                previous_parent_key,
+                cache_variable.parent_key,
+            );
         }
 
         Ok(())
     }
 
+    /// Change the variable with the key `variable_key` in place, with `modify`, instead of updating
+    /// it with a modified clone, see [`VariableCache::update_variable`].
+    ///
+    /// The key of the variable can't be changed. If the parent key is changed, the variable is
+    /// moved to the children of its new parent.
+    pub fn modify_variable<R>(
+        &mut self,
+        variable_key: ObjectRef,
+        modify: impl FnOnce(&mut Variable) -> R,
+    ) -> Result<R, DebugError> {
+        let Some(variable) = self.variable_hash_map.get_mut(&variable_key) else {
+            return Err(DebugError::Other(format!(
+                "Attempt to modify a `Variable` with a non-existent cache key: {variable_key:?}. Please report this as a bug."
+            )));
+        };
+
+        let previous_parent_key = variable.parent_key;
+        let result = modify(variable);
+        variable.variable_key = variable_key;
+        let parent_key = variable.parent_key;
+        self.relink_child(variable_key, previous_parent_key, parent_key);
+
+        Ok(result)
+    }
+
+    /// Add `child_key` to the children of `parent_key`, in the order of the keys.
+    fn link_child(&mut self, parent_key: ObjectRef, child_key: ObjectRef) {
+        let children = self.child_keys.entry(parent_key).or_default();
+        // New keys are larger than all existing keys, so they are usually added at the end.
+        let index = children.partition_point(|key| *key < child_key);
+        children.insert(index, child_key);
+    }
+
+    /// Remove `child_key` from the children of `parent_key`.
+    fn unlink_child(&mut self, parent_key: ObjectRef, child_key: ObjectRef) {
+        if let Some(children) = self.child_keys.get_mut(&parent_key) {
+            if let Ok(index) = children.binary_search(&child_key) {
+                children.remove(index);
+            }
+            if children.is_empty() {
+                self.child_keys.remove(&parent_key);
+            }
+        }
+    }
+
+    /// Move `child_key` from the children of `previous_parent_key` to those of `parent_key`.
+    fn relink_child(
+        &mut self,
+        child_key: ObjectRef,
+        previous_parent_key: ObjectRef,
+        parent_key: ObjectRef,
+    ) {
+        if parent_key != previous_parent_key {
+            self.unlink_child(previous_parent_key, child_key);
+            self.link_child(parent_key, child_key);
+        }
+    }
+
+    /// Write a new value for a variable to the target, and update the variable in the cache.
+    ///
+    /// The `new_value` is parsed according to the type of the variable, e.g. integers can be decimal
+    /// or hexadecimal. Only variables with a base type, which are stored in memory, can be changed.
+    /// The memory is read back after writing, to confirm that the value was changed, and the
+    /// updated variable is returned.
+    pub fn update_variable_value(
+        &mut self,
+        variable_key: ObjectRef,
+        new_value: &str,
+        memory: &mut dyn MemoryInterface,
+    ) -> Result<Variable, DebugError> {
+        let Some(mut variable) = self.get_variable_by_key(variable_key) else {
+            return Err(DebugError::Other(format!(
+                "The variable with key {variable_key:?} was not found."
+            )));
+        };
+
+        let not_writable = |reason| DebugError::VariableNotWritable {
+            name: variable.name.to_string(),
+            reason,
+        };
+
+        let address = match variable.memory_location {
+            VariableLocation::Address(address) => address,
+            VariableLocation::Value
+            | VariableLocation::Implicit(_)
+            | VariableLocation::ImplicitPointer(_) => {
+                return Err(not_writable(NotWritableReason::Synthetic));
+            }
+            VariableLocation::RegisterValue(_) => {
+                return Err(not_writable(NotWritableReason::Register));
+            }
+            VariableLocation::Unknown
+            | VariableLocation::Unavailable
+            | VariableLocation::Error(_)
+            | VariableLocation::Unsupported(_) => {
+                return Err(not_writable(NotWritableReason::NoLocation));
+            }
+        };
+
+        if !matches!(variable.type_name.inner(), VariableType::Base(_)) {
+            return Err(not_writable(NotWritableReason::NotBaseType(
+                variable.type_name.kind().to_string(),
+            )));
+        }
+
+        let bytes = language::from_dwarf(variable.language).value_to_bytes(&variable, new_value)?;
+        if let Some(byte_size) = variable.byte_size
+            && byte_size != bytes.len() as u64
+        {
+            return Err(not_writable(NotWritableReason::SizeMismatch {
+                expected: byte_size,
+                actual: bytes.len(),
+            }));
+        }
+
+        memory.write_8(address, &bytes)?;
+
+        let mut written = vec![0; bytes.len()];
+        memory.read(address, &mut written)?;
+        if written != bytes {
+            return Err(not_writable(NotWritableReason::ReadOnly { address }));
+        }
+
+        variable.value = VariableValue::Empty;
+        variable.extract_value(memory, self);
+        self.update_variable(&variable)?;
+
+        Ok(variable)
+    }
+
     /// Retrieve a clone of a specific `Variable`, using the `variable_key`.
     pub fn get_variable_by_key(&self, variable_key: ObjectRef) -> Option<Variable> {
         self.variable_hash_map.get(&variable_key).cloned()
     }
 
+    /// Retrieve a reference to a specific `Variable`, using the `variable_key`, without cloning it.
+    pub fn get_variable_ref(&self, variable_key: ObjectRef) -> Option<&Variable> {
+        self.variable_hash_map.get(&variable_key)
+    }
+
+    /// Find an ancestor of `variable` with the same type and memory address.
+    ///
+    /// Such a variable is reached through a cycle of pointers, e.g. in a doubly linked list.
+    pub(crate) fn find_pointer_cycle(&self, variable: &Variable) -> Option<Variable> {
+        let VariableLocation::Address(address) = variable.memory_location else {
+            return None;
+        };
+        let type_offset = variable.type_node_offset?;
+
+        let mut parent_key = variable.parent_key;
+        while let Some(parent) = self.get_variable_ref(parent_key) {
+            // The array of the elements of a slice keeps the type of its elements.
+            if parent.type_node_offset == Some(type_offset)
+                && parent.type_name == variable.type_name
+                && parent.memory_location == VariableLocation::Address(address)
+            {
+                return Some(parent.clone());
+            }
+            parent_key = parent.parent_key;
+        }
+
+        None
+    }
+
     /// Retrieve a clone of a specific `Variable`, using the `name` and `parent_key`.
     /// If there is more than one, it will be logged (tracing::error!), and only the last will be returned.
     pub fn get_variable_by_name_and_parent(
@@ -297,9 +573,9 @@ impl VariableCache {
         variable_name: &VariableName,
         parent_key: ObjectRef,
     ) -> Option<Variable> {
-        let child_variables = self.variable_hash_map.values().filter(|child_variable| {
-            &child_variable.name == variable_name && child_variable.parent_key == parent_key
-        });
+        let child_variables = self
+            .get_children(parent_key)
+            .filter(|child_variable| &child_variable.name == variable_name);
 
         // Clone the iterator. This is cheap and makes rewinding easier.
         let mut first_iter = child_variables.clone();
@@ -325,8 +601,7 @@ impl VariableCache {
     /// It is possible for a hierarchy of variables in a cache to have duplicate names under different parents.
     pub fn get_variable_by_name(&self, variable_name: &VariableName) -> Option<Variable> {
         let mut child_variables = self
-            .variable_hash_map
-            .values()
+            .variables()
             .filter(|child_variable| child_variable.name.eq(variable_name));
 
         let first = child_variables.next();
@@ -343,19 +618,300 @@ impl VariableCache {
         first.cloned()
     }
 
+    /// Retrieve a clone of the `Variable` at `path`, if it is in the cache.
+    ///
+    /// The path starts with the name of the root variable, e.g.
+    /// `["local_scope", "device", "buffers", "[2]", "len"]`, see [`Variable::path`]. Deferred
+    /// children are not resolved, so only variables which are already in the cache are found.
+    pub fn get_variable_by_path(&self, path: &[&str]) -> Option<Variable> {
+        let (root, path) = path.split_first()?;
+        let mut variable = self.root_variable();
+        if variable.name.path_segment() != *root {
+            return None;
+        }
+
+        for segment in path {
+            variable = self
+                .get_children(variable.variable_key)
+                .find(|child| child.name.path_segment() == *segment)?;
+        }
+
+        Some(variable.clone())
+    }
+
+    /// An expression which evaluates to the variable with `variable_key`, e.g.
+    /// `(*config.uart).baud`, so it can be watched, or evaluated again after the next stop with
+    /// [`DebugInfo::evaluate_path`].
+    ///
+    /// Unlike [`Variable::path`], the expression follows the syntax of the source language: the
+    /// scope roots are left out, the value a pointer points to is a dereference, and array members
+    /// are indexed. Returns `None` for the roots, and for variables which can't be named in an
+    /// expression, like the variants of an enum or the raw bytes of a variable.
+    pub fn expression_path(&self, variable_key: ObjectRef) -> Option<String> {
+        let variable = self.get_variable_ref(variable_key)?;
+        if matches!(variable.name, VariableName::Namespace(_)) {
+            return None;
+        }
+        self.expression_of(variable)
+    }
+
+    /// The expression of `variable`, see [`VariableCache::expression_path`]. Namespaces are
+    /// included, as the qualifier of the variables in them.
+    fn expression_of(&self, variable: &Variable) -> Option<String> {
+        // A variant is a type, which is not part of the expression of its members.
+        if matches!(variable.role, VariantRole::Variant(_)) {
+            return None;
+        }
+
+        let parent = self.get_variable_ref(variable.parent_key)?;
+        let parent_expression = match parent.name {
+            VariableName::StaticScopeRoot
+            | VariableName::LocalScopeRoot
+            | VariableName::RegistersRoot => None,
+            _ => Some(self.expression_of(parent)?),
+        };
+
+        match (&variable.name, parent_expression) {
+            (VariableName::Namespace(name) | VariableName::Named(name), None)
+                if is_identifier_path(name) =>
+            {
+                Some(name.clone())
+            }
+            (VariableName::Namespace(name), Some(parent_expression))
+                if is_identifier_path(name) =>
+            {
+                Some(format!("{parent_expression}::{name}"))
+            }
+            (VariableName::Named(name), Some(parent_expression)) => {
+                if matches!(parent.name, VariableName::Namespace(_)) && is_identifier_path(name) {
+                    Some(format!("{parent_expression}::{name}"))
+                } else if *name == format!("*{}", parent.name) {
+                    // The value that a pointer, or a smart pointer, points to.
+                    Some(format!("*{parent_expression}"))
+                } else if is_identifier_path(name) {
+                    // Rust tuple fields are named `__0`, `__1`, and so on, but accessed as `.0`.
+                    let field = name
+                        .strip_prefix("__")
+                        .filter(|index| {
+                            variable.language == gimli::DW_LANG_Rust
+                                && !index.is_empty()
+                                && index.bytes().all(|byte| byte.is_ascii_digit())
+                        })
+                        .unwrap_or(name);
+                    Some(format!("{}.{field}", operand(parent_expression)))
+                } else {
+                    None
+                }
+            }
+            (VariableName::Indexed(index), Some(parent_expression)) => {
+                Some(format!("{}[{index}]", operand(parent_expression)))
+            }
+            _ => None,
+        }
+    }
+
     /// Retrieve `clone`d version of all the children of a `Variable`.
     /// If `parent_key == None`, it will return all the top level variables (no parents) in this cache.
     pub fn get_children(&self, parent_key: ObjectRef) -> impl Iterator<Item = &Variable> + Clone {
-        self.variable_hash_map
-            .values()
-            .filter(move |child_variable| child_variable.parent_key == parent_key)
+        self.child_keys
+            .get(&parent_key)
+            .into_iter()
+            .flatten()
+            .filter_map(|child_key| self.variable_hash_map.get(child_key))
+    }
+
+    /// Retrieve `count` children of a `Variable`, starting with the child at index `start`.
+    ///
+    /// The children are in the same order as [`VariableCache::get_children`], so a client can request
+    /// the elements of a large array one page at a time. The members of a long array or sequence
+    /// may only be resolved in part, see [`DebugInfo::cache_deferred_variables_paged`], so for
+    /// those, these are the members with the indices `start..start + count`, in the order of
+    /// their indices.
+    pub fn get_children_paged(
+        &self,
+        parent_key: ObjectRef,
+        start: usize,
+        count: usize,
+    ) -> impl Iterator<Item = &Variable> + Clone {
+        let has_deferred_members = self
+            .variable_hash_map
+            .get(&parent_key)
+            .is_some_and(|parent| parent.variable_node_type.has_deferred_members());
+        if !has_deferred_members {
+            return Either::Left(self.get_children(parent_key).skip(start).take(count));
+        }
+
+        let indices = start as u64..start.saturating_add(count) as u64;
+        let mut members: Vec<_> = self
+            .get_children(parent_key)
+            .filter(|member| member_index(member).is_some_and(|index| indices.contains(&index)))
+            .collect();
+        members.sort_by_key(|member| member_index(member));
+        Either::Right(members.into_iter())
+    }
+
+    /// The number of children of a `Variable`, without resolving deferred children.
+    ///
+    /// If the children of an array or a sequence, like a Rust `Vec<T>`, are deferred, this is the
+    /// number of its elements, even if some of them are already in the cache. Otherwise, only the
+    /// children that are already in the cache are counted.
+    pub fn child_count(&self, parent_key: ObjectRef) -> usize {
+        let cached = self.get_children(parent_key).count();
+        let Some(parent) = self.variable_hash_map.get(&parent_key) else {
+            return cached;
+        };
+        if cached > 0 && !parent.variable_node_type.has_deferred_members() {
+            return cached;
+        }
+
+        match (&parent.variable_node_type, parent.type_name.inner()) {
+            (VariableNodeType::SequenceMembers(.., count), _) => *count as usize,
+            (node_type, VariableType::Array { count, .. }) if node_type.is_deferred() => *count,
+            _ => cached,
+        }
     }
 
     /// Check if variable has children. If the variable doesn't exist, it will return false.
     pub fn has_children(&self, parent_variable: &Variable) -> bool {
-        self.get_children(parent_variable.variable_key)
-            .next()
-            .is_some()
+        self.child_keys.contains_key(&parent_variable.variable_key)
+    }
+
+    /// Serialize the variable with the key `parent_key` and all its descendants in the cache, as a
+    /// nested tree, e.g. to store the variables of a stack frame in a JSON file.
+    ///
+    /// The output is stable, so it can be compared between sessions, or with the output of an
+    /// earlier version in a test. Each variable is a map with the following entries, in this order.
+    /// The optional entries are left out when they don't apply.
+    ///
+    /// - `name`: the [`VariableName`], e.g. `{"Named": "count"}`, `{"Indexed": 2}` or
+    ///   `"LocalScopeRoot"`.
+    /// - `type_name`: the name of the type, as it is shown, e.g. `Option<u32>`.
+    /// - `value` (optional): the value, in the [`ValueFormat`] of the variable or of the cache.
+    ///   Structs and arrays, which are shown by their children, have no value.
+    /// - `location` (optional): the location of the value, e.g. the address `0x20000010`.
+    /// - `byte_size` (optional): the size of the value in bytes.
+    /// - `role` (optional): the [`VariantRole`] of an enum with variants, or of one of its
+    ///   variants.
+    /// - `is_static` (optional): `true` for a static which is declared in a function.
+    /// - `is_parameter` (optional): `true` for a parameter of a function.
+    /// - `source_location` (optional): the [`SourceLocation`] of the declaration.
+    /// - `deferred` (optional): `true` if the children are not resolved yet, see
+    ///   [`DebugInfo::cache_deferred_variables`].
+    /// - `children` (optional): the children, in the order of their declaration.
+    ///
+    /// Returns an error if there is no variable with the key `parent_key`.
+    pub fn serialize_tree<S>(&self, parent_key: ObjectRef, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Some(variable) = self.variable_hash_map.get(&parent_key) else {
+            return Err(serde::ser::Error::custom(format!(
+                "There is no variable with the key {parent_key:?}"
+            )));
+        };
+
+        SerializedVariable::new(variable, Some(self)).serialize(serializer)
+    }
+
+    /// Compare the variables of this cache with those of `other`, e.g. the variables of a stack
+    /// frame before and after a step, to highlight the values that changed.
+    ///
+    /// The variables are matched by their [`Variable::path`], because their keys are different in
+    /// each cache. The changes are listed in the order of the variables in the tree, with the
+    /// paths in the cache where the variable is found. If a variable is added or removed, its
+    /// descendants are not listed separately.
+    ///
+    /// The children of a variable are only compared if they are resolved in both caches. If they
+    /// are deferred in one of them, see [`DebugInfo::cache_deferred_variables`], only the value of
+    /// the variable itself is compared.
+    pub fn diff(&self, other: &VariableCache) -> Vec<VariableChange> {
+        let mut changes = Vec::new();
+        self.match_variables(other, &mut |old, new| match (old, new) {
+            (Some(old), Some(new)) => {
+                if (!old.value.is_empty() || !new.value.is_empty()) && old.value != new.value {
+                    changes.push(VariableChange::Changed {
+                        path: new.path(other),
+                        old_value: old.to_string(self),
+                        new_value: new.to_string(other),
+                    });
+                }
+            }
+            (Some(old), None) => changes.push(VariableChange::Removed {
+                path: old.path(self),
+            }),
+            (None, Some(new)) => changes.push(VariableChange::Added {
+                path: new.path(other),
+            }),
+            (None, None) => {}
+        });
+        changes
+    }
+
+    /// Map the keys of the variables in this cache to the keys of the same variables in `other`,
+    /// e.g. to find the variables which a debugger client refers to after the caches of a stack
+    /// frame are rebuilt at the next stop.
+    ///
+    /// The variables are matched by their [`Variable::path`], like in [`VariableCache::diff`], so
+    /// only the variables which are resolved in both caches are included. The keys of the other
+    /// variables are left out, instead of being mapped to an unrelated variable. Keys are never
+    /// reused, see [`get_object_reference`], so a key of this cache is not found in `other` either.
+    pub fn translate_keys(&self, other: &VariableCache) -> BTreeMap<ObjectRef, ObjectRef> {
+        let mut keys = BTreeMap::from([(self.root_variable_key, other.root_variable_key)]);
+        self.match_variables(other, &mut |old, new| {
+            if let (Some(old), Some(new)) = (old, new) {
+                keys.insert(old.variable_key, new.variable_key);
+            }
+        });
+        keys
+    }
+
+    /// Match the descendants of the root variables of this cache and `other` by their paths, and
+    /// call `visit` with each pair, or with the variable which is only in one of the caches.
+    fn match_variables<'c>(
+        &'c self,
+        other: &'c VariableCache,
+        visit: &mut impl FnMut(Option<&'c Variable>, Option<&'c Variable>),
+    ) {
+        self.match_children(self.root_variable(), other, other.root_variable(), visit);
+    }
+
+    /// Match the children of `old` in this cache with the children of `new` in `other`, see
+    /// [`VariableCache::match_variables`].
+    fn match_children<'c>(
+        &'c self,
+        old: &Variable,
+        other: &'c VariableCache,
+        new: &Variable,
+        visit: &mut impl FnMut(Option<&'c Variable>, Option<&'c Variable>),
+    ) {
+        let old_children = self.get_children(old.variable_key).collect::<Vec<_>>();
+        let mut new_children = other.get_children(new.variable_key).collect::<Vec<_>>();
+        if old_children.is_empty() && old.variable_node_type.is_deferred()
+            || new_children.is_empty() && new.variable_node_type.is_deferred()
+        {
+            // The children are not resolved in one of the caches, so they can't be matched.
+            return;
+        }
+
+        for old_child in old_children {
+            // Variables with the same name, e.g. shadowed locals, are matched in their order.
+            let segment = old_child.name.path_segment();
+            match new_children
+                .iter()
+                .position(|new_child| new_child.name.path_segment() == segment)
+            {
+                Some(index) => {
+                    let new_child = new_children.remove(index);
+                    visit(Some(old_child), Some(new_child));
+                    self.match_children(old_child, other, new_child, visit);
+                }
+                None => visit(Some(old_child), None),
+            }
+        }
+
+        for new_child in new_children {
+            visit(None, Some(new_child));
+        }
     }
 
     /// Sometimes DWARF uses intermediate nodes that are not part of the coded variable structure.
@@ -373,12 +929,27 @@ impl VariableCache {
             || obsolete_child_variable.variable_node_type != VariableNodeType::DoNotRecurse
         {
             // Make sure we pass children up, past any intermediate nodes.
-            self.variable_hash_map
-                .values_mut()
-                .filter(|search_variable| {
-                    search_variable.parent_key == obsolete_child_variable.variable_key
-                })
-                .for_each(|grand_child| grand_child.parent_key = parent_variable.variable_key);
+            let grand_child_keys = self
+                .child_keys
+                .remove(&obsolete_child_variable.variable_key)
+                .unwrap_or_default();
+            for grand_child_key in &grand_child_keys {
+                if let Some(grand_child) = self.variable_hash_map.get_mut(grand_child_key) {
+                    grand_child.parent_key = parent_variable.variable_key;
+                }
+            }
+            if !grand_child_keys.is_empty() {
+                match self.child_keys.entry(parent_variable.variable_key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(grand_child_keys);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        // Both lists of keys are sorted, so they are merged instead of sorted.
+                        let children = std::mem::take(entry.get_mut());
+                        *entry.get_mut() = merge_sorted_keys(children, grand_child_keys);
+                    }
+                }
+            }
             // Remove the intermediate variable from the cache
             self.remove_cache_entry(obsolete_child_variable.variable_key)?;
         }
@@ -390,29 +961,76 @@ impl VariableCache {
         &mut self,
         parent_variable_key: ObjectRef,
     ) -> Result<(), Error> {
-        let children = self
-            .variable_hash_map
-            .values()
-            .filter(|child_variable| child_variable.parent_key == parent_variable_key)
-            .cloned()
-            .collect::<Vec<Variable>>();
+        let child_keys = self
+            .child_keys
+            .remove(&parent_variable_key)
+            .unwrap_or_default();
 
-        for child in children {
-            self.remove_cache_entry(child.variable_key)?;
+        for child_key in child_keys {
+            self.remove_variable_tree(child_key)?;
         }
 
         Ok(())
     }
     /// Removing an entry from the `VariableCache` will recursively remove all its children
     pub fn remove_cache_entry(&mut self, variable_key: ObjectRef) -> Result<(), Error> {
+        let variable = self.remove_variable_tree(variable_key)?;
+        self.unlink_child(variable.parent_key, variable_key);
+        Ok(())
+    }
+
+    /// Remove a variable and its descendants, without removing it from the children of its parent.
+    fn remove_variable_tree(&mut self, variable_key: ObjectRef) -> Result<Variable, Error> {
         self.remove_cache_entry_children(variable_key)?;
-        if self.variable_hash_map.remove(&variable_key).is_none() {
-            return Err(Error::Other(format!(
+        self.variable_hash_map.remove(&variable_key).ok_or_else(|| {
+            Error::Other(format!(
                 "Failed to remove a `VariableCache` entry with key: {variable_key:?}. Please report this as a bug."
-            )));
+            ))
+        })
+    }
+
+    /// Remove the static variables below `static_root_key` that are listed more than once, because more than one
+    /// compilation unit has a DIE for them, e.g. for generic statics.
+    ///
+    /// Variables are duplicates if they have the same name in the same namespace, and the same address. Variables
+    /// with the same name at different addresses are distinct items, and are kept. Of the duplicates, the variable
+    /// that was added first is kept.
+    pub(crate) fn remove_duplicate_statics(
+        &mut self,
+        static_root_key: ObjectRef,
+    ) -> Result<(), Error> {
+        let is_scope = |key: ObjectRef| {
+            key == static_root_key
+                || self
+                    .variable_hash_map
+                    .get(&key)
+                    .is_some_and(|parent| parent.type_name == VariableType::Namespace)
         };
+
+        // The variables are ordered by their key, which is the order they were added in.
+        let statics: Vec<_> = self
+            .variables()
+            .filter_map(|variable| match variable.memory_location {
+                VariableLocation::Address(address) if is_scope(variable.parent_key) => Some((
+                    variable.variable_key,
+                    variable.parent_key,
+                    variable.name.clone(),
+                    address,
+                )),
+                _ => None,
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for (variable_key, parent_key, name, address) in statics {
+            if !seen.insert((parent_key, name, address)) {
+                self.remove_cache_entry(variable_key)?;
+            }
+        }
+
         Ok(())
     }
+
     /// Recursively process the deferred variables in the variable cache,
     /// and add their children to the cache.
     /// Enforce a max level, so that we don't recurse infinitely on circular references.
@@ -435,7 +1053,7 @@ impl VariableCache {
         )
     }
 
-    fn recurse_deferred_variables_internal(
+    pub(crate) fn recurse_deferred_variables_internal(
         &mut self,
         debug_info: &DebugInfo,
         memory: &mut dyn MemoryInterface,
@@ -476,7 +1094,7 @@ impl VariableCache {
     /// This is used to determine which memory ranges to read from the target when creating a 'default' [`crate::CoreDump`].
     pub fn get_discrete_memory_ranges(&self) -> Vec<Range<u64>> {
         let mut memory_ranges: Vec<Range<u64>> = Vec::new();
-        for variable in self.variable_hash_map.values() {
+        for variable in self.variables() {
             if let Some(mut memory_range) = variable.memory_range() {
                 // This memory may need to be read by 32-bit aligned words, so make sure
                 // the range is aligned to 32 bits.
@@ -544,15 +1162,71 @@ impl VariableCache {
     }
 }
 
+/// Check if `name` can be used in an expression as it is, e.g. `count` or `Box::count`, unlike
+/// `<raw bytes>`.
+fn is_identifier_path(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Wrap `expression` in parentheses if it is a dereference, so it can be the operand of a member
+/// access or an index, which bind tighter than the dereference.
+fn operand(expression: String) -> String {
+    if expression.starts_with('*') {
+        format!("({expression})")
+    } else {
+        expression
+    }
+}
+
+/// The index of `member`, if it is a member of an array or a sequence.
+fn member_index(member: &Variable) -> Option<u64> {
+    match member.name {
+        VariableName::Indexed(index) => Some(index),
+        _ => None,
+    }
+}
+
+/// Merge the sorted keys `left` and `right` into one sorted vector.
+fn merge_sorted_keys(left: Vec<ObjectRef>, right: Vec<ObjectRef>) -> Vec<ObjectRef> {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(left_key), Some(right_key)) = (left.peek(), right.peek()) {
+        if left_key <= right_key {
+            merged.extend(left.next());
+        } else {
+            merged.extend(right.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    merged
+}
+
 #[cfg(test)]
 mod test {
+    use gimli::{DebugInfoOffset, UnitOffset};
+    use serde_json::json;
+    use std::cell::Cell;
     use termtree::Tree;
 
+    use probe_rs::RegisterValue;
+
     use crate::{
-        Variable, VariableCache, VariableLocation, VariableName, VariableNodeType, VariableType,
-        VariantRole,
+        DebugError, NotWatchableReason, ObjectRef, ValueFormat, Variable, VariableCache,
+        VariableChange, VariableLocation, VariableName, VariableNodeType, VariableType,
+        VariableValue, VariantRole, WatchKind, WatchSpec,
     };
 
+    thread_local! {
+        /// The number of times all the variables of a cache are visited by the current test.
+        pub(super) static FULL_SCANS: Cell<usize> = const { Cell::new(0) };
+    }
+
     fn show_tree(cache: &VariableCache) {
         let tree = build_tree(cache, cache.root_variable());
 
@@ -754,4 +1428,575 @@ mod test {
 
         assert_eq!(new_children, vec![&vars[4], &vars[5]]);
     }
+
+    /// Expand a struct with 5000 members, like `UnitInfo::process_tree` does, where each member is
+    /// created below an intermediate variable which is removed again.
+    #[test]
+    fn expand_large_struct_without_scans() {
+        const MEMBERS: usize = 5000;
+
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+        let mut parent = cache.create_variable(root_key, None).unwrap();
+        parent.type_name = VariableType::Struct("Large".to_string());
+        cache.update_variable(&parent).unwrap();
+
+        FULL_SCANS.with(|scans| scans.set(0));
+        for index in 0..MEMBERS {
+            let intermediate = cache.create_variable(parent.variable_key, None).unwrap();
+            let mut member = cache
+                .create_variable(intermediate.variable_key, None)
+                .unwrap();
+            member.name = VariableName::Named(format!("member_{index}"));
+            member.set_value(VariableValue::U64(index as u64));
+            cache.update_variable(&member).unwrap();
+            assert!(cache.has_children(&intermediate));
+
+            cache.adopt_grand_children(&parent, &intermediate).unwrap();
+            assert!(!cache.has_children(&member));
+        }
+
+        assert_eq!(cache.child_count(parent.variable_key), MEMBERS);
+        let last = cache
+            .get_children_paged(parent.variable_key, MEMBERS - 1, 10)
+            .collect::<Vec<_>>();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].name.to_string(), format!("member_{}", MEMBERS - 1));
+        assert!(
+            cache
+                .get_variable_by_name_and_parent(
+                    &VariableName::Named("member_2500".to_string()),
+                    parent.variable_key,
+                )
+                .is_some()
+        );
+        assert_eq!(FULL_SCANS.with(Cell::get), 0);
+
+        cache
+            .remove_cache_entry_children(parent.variable_key)
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(FULL_SCANS.with(Cell::get), 0);
+    }
+
+    #[test]
+    fn adopt_grand_children_in_key_order() {
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+        let parent = cache.create_variable(root_key, None).unwrap();
+        let intermediate = cache.create_variable(parent.variable_key, None).unwrap();
+        let first = cache
+            .create_variable(intermediate.variable_key, None)
+            .unwrap();
+        let sibling = cache.create_variable(parent.variable_key, None).unwrap();
+        let second = cache
+            .create_variable(intermediate.variable_key, None)
+            .unwrap();
+
+        cache.adopt_grand_children(&parent, &intermediate).unwrap();
+
+        let children: Vec<_> = cache
+            .get_children(parent.variable_key)
+            .map(|child| child.variable_key)
+            .collect();
+        assert_eq!(
+            children,
+            vec![
+                first.variable_key,
+                sibling.variable_key,
+                second.variable_key
+            ]
+        );
+    }
+
+    #[test]
+    fn modify_variable() {
+        let (mut cache, vars) = build_test_tree();
+
+        let name = VariableName::Named("renamed".to_string());
+        let key = cache
+            .modify_variable(vars[4].variable_key, |variable| {
+                variable.name = name.clone();
+                // Only the parent key can be changed.
+                variable.parent_key = vars[1].variable_key;
+                variable.variable_key = ObjectRef::Invalid;
+                variable.variable_key
+            })
+            .unwrap();
+        assert_eq!(key, ObjectRef::Invalid);
+
+        let modified = cache.get_variable_ref(vars[4].variable_key).unwrap();
+        assert_eq!(modified.name, name);
+        assert_eq!(modified.variable_key, vars[4].variable_key);
+        let children: Vec<_> = cache.get_children(vars[1].variable_key).collect();
+        assert_eq!(children, vec![modified]);
+        let children: Vec<_> = cache.get_children(vars[2].variable_key).collect();
+        assert_eq!(children, vec![&vars[3]]);
+
+        assert!(cache.modify_variable(ObjectRef::Invalid, |_| ()).is_err());
+    }
+
+    #[test]
+    fn children_after_removal() {
+        let (mut cache, vars) = build_test_tree();
+
+        // The root is the only variable without a parent.
+        let roots: Vec<_> = cache.get_children(ObjectRef::Invalid).collect();
+        assert_eq!(roots, vec![&vars[0]]);
+
+        assert!(cache.has_children(&vars[6]));
+        cache
+            .remove_cache_entry_children(vars[6].variable_key)
+            .unwrap();
+        assert!(!cache.has_children(&vars[6]));
+        assert_eq!(cache.child_count(vars[6].variable_key), 0);
+
+        // A variable that is moved to another parent is only a child of its new parent.
+        let mut moved = vars[5].clone();
+        moved.parent_key = vars[6].variable_key;
+        cache.update_variable(&moved).unwrap();
+        assert!(!cache.has_children(&vars[3]));
+        let children: Vec<_> = cache.get_children(vars[6].variable_key).collect();
+        assert_eq!(children, vec![&moved]);
+    }
+
+    #[test]
+    fn paged_children() {
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let mut array = cache.create_variable(root_key, None).unwrap();
+        array.type_name = VariableType::Array {
+            item_type_name: Box::new(VariableType::Base("u8".to_string())),
+            count: 5,
+        };
+
+        // Deferred elements are counted without creating them.
+        array.variable_node_type = VariableNodeType::TypeOffset(DebugInfoOffset(0), UnitOffset(0));
+        cache.update_variable(&array).unwrap();
+        assert_eq!(cache.child_count(array.variable_key), 5);
+        assert_eq!(cache.len(), 2);
+
+        array.variable_node_type = VariableNodeType::DoNotRecurse;
+        cache.update_variable(&array).unwrap();
+        assert_eq!(cache.child_count(array.variable_key), 0);
+
+        let elements: Vec<_> = (0..5)
+            .map(|index| {
+                let mut element = cache.create_variable(array.variable_key, None).unwrap();
+                element.name = VariableName::Indexed(index);
+                cache.update_variable(&element).unwrap();
+                element
+            })
+            .collect();
+        assert_eq!(cache.child_count(array.variable_key), 5);
+
+        let page: Vec<_> = cache.get_children_paged(array.variable_key, 1, 2).collect();
+        assert_eq!(page, vec![&elements[1], &elements[2]]);
+
+        let page: Vec<_> = cache
+            .get_children_paged(array.variable_key, 4, 10)
+            .collect();
+        assert_eq!(page, vec![&elements[4]]);
+
+        assert_eq!(
+            cache.get_children_paged(array.variable_key, 5, 1).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn variable_paths() {
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let add = |cache: &mut VariableCache, parent_key, name| {
+            let mut variable = cache.create_variable(parent_key, None).unwrap();
+            variable.name = name;
+            cache.update_variable(&variable).unwrap();
+            variable
+        };
+        let device = add(
+            &mut cache,
+            root_key,
+            VariableName::Named("device".to_string()),
+        );
+        let buffers = add(
+            &mut cache,
+            device.variable_key,
+            VariableName::Named("buffers".to_string()),
+        );
+        let buffer = add(&mut cache, buffers.variable_key, VariableName::Indexed(2));
+        let len = add(
+            &mut cache,
+            buffer.variable_key,
+            VariableName::Named("len".to_string()),
+        );
+
+        assert_eq!(len.path(&cache), "static_scope.device.buffers[2].len");
+        assert_eq!(cache.root_variable().path(&cache), "static_scope");
+
+        let path = ["static_scope", "device", "buffers", "[2]", "len"];
+        assert_eq!(cache.get_variable_by_path(&path), Some(len));
+        assert_eq!(cache.get_variable_by_path(&path[..4]), Some(buffer));
+
+        assert_eq!(
+            cache.get_variable_by_path(&["static_scope", "device", "len"]),
+            None
+        );
+        assert_eq!(cache.get_variable_by_path(&["local_scope", "device"]), None);
+        assert_eq!(cache.get_variable_by_path(&[]), None);
+    }
+
+    #[test]
+    fn expression_paths() {
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let add = |cache: &mut VariableCache, parent_key, name: VariableName, role| {
+            let mut variable = cache.create_variable(parent_key, None).unwrap();
+            variable.name = name;
+            variable.role = role;
+            cache.update_variable(&variable).unwrap();
+            variable
+        };
+        let named = |name: &str| VariableName::Named(name.to_string());
+        let config = add(
+            &mut cache,
+            root_key,
+            named("config"),
+            VariantRole::NonVariant,
+        );
+        let uart = add(
+            &mut cache,
+            config.variable_key,
+            named("uart"),
+            VariantRole::NonVariant,
+        );
+        let pointee = add(
+            &mut cache,
+            uart.variable_key,
+            named("*uart"),
+            VariantRole::NonVariant,
+        );
+        let mut baud = add(
+            &mut cache,
+            pointee.variable_key,
+            named("baud"),
+            VariantRole::NonVariant,
+        );
+        let buffer = add(
+            &mut cache,
+            pointee.variable_key,
+            VariableName::Indexed(2),
+            VariantRole::NonVariant,
+        );
+
+        assert_eq!(
+            cache.expression_path(config.variable_key).unwrap(),
+            "config"
+        );
+        assert_eq!(
+            cache.expression_path(pointee.variable_key).unwrap(),
+            "*config.uart"
+        );
+        assert_eq!(
+            cache.expression_path(baud.variable_key).unwrap(),
+            "(*config.uart).baud"
+        );
+        assert_eq!(
+            cache.expression_path(buffer.variable_key).unwrap(),
+            "(*config.uart)[2]"
+        );
+
+        // Rust tuple fields are accessed by their index.
+        let pair = add(&mut cache, root_key, named("pair"), VariantRole::NonVariant);
+        let second = add(
+            &mut cache,
+            pair.variable_key,
+            named("__1"),
+            VariantRole::NonVariant,
+        );
+        assert_eq!(
+            cache.expression_path(second.variable_key).unwrap(),
+            "pair.1"
+        );
+
+        // Statics in a namespace are qualified by it.
+        let drivers = add(
+            &mut cache,
+            root_key,
+            VariableName::Namespace("drivers".to_string()),
+            VariantRole::NonVariant,
+        );
+        let state = add(
+            &mut cache,
+            drivers.variable_key,
+            named("STATE"),
+            VariantRole::NonVariant,
+        );
+        assert_eq!(
+            cache.expression_path(state.variable_key).unwrap(),
+            "drivers::STATE"
+        );
+        assert_eq!(cache.expression_path(drivers.variable_key), None);
+
+        // Variants and synthetic variables can't be named in an expression.
+        let some = add(
+            &mut cache,
+            config.variable_key,
+            named("Some"),
+            VariantRole::Variant(1),
+        );
+        let field = add(
+            &mut cache,
+            some.variable_key,
+            named("__0"),
+            VariantRole::NonVariant,
+        );
+        let raw_bytes = add(
+            &mut cache,
+            config.variable_key,
+            named("<raw bytes>"),
+            VariantRole::NonVariant,
+        );
+        assert_eq!(cache.expression_path(field.variable_key), None);
+        assert_eq!(cache.expression_path(raw_bytes.variable_key), None);
+        assert_eq!(cache.expression_path(root_key), None);
+
+        // Only variables in memory have an address.
+        baud.memory_location = VariableLocation::Address(0x2000_0010);
+        assert_eq!(baud.address(), Some(0x2000_0010));
+        baud.memory_location = VariableLocation::RegisterValue(RegisterValue::U32(9600));
+        assert_eq!(baud.address(), None);
+        baud.memory_location = VariableLocation::Implicit(vec![0x80, 0x25]);
+        assert_eq!(baud.address(), None);
+    }
+
+    #[test]
+    fn watch_specs() {
+        let mut variable = Variable::new(None);
+        variable.name = VariableName::Named("baud".to_string());
+        let watch = |variable: &Variable| variable.watch_spec(WatchKind::Write);
+        let spec = |address, size| WatchSpec {
+            address,
+            size,
+            kind: WatchKind::Write,
+        };
+
+        // The range is aligned to its size, and contains the whole variable.
+        for (address, byte_size, expected) in [
+            (0x2000_0010, 4, spec(0x2000_0010, 4)),
+            (0x2000_0011, 1, spec(0x2000_0011, 1)),
+            (0x2000_0012, 2, spec(0x2000_0012, 2)),
+            (0x2000_0013, 2, spec(0x2000_0010, 8)),
+            (0x2000_0011, 3, spec(0x2000_0010, 4)),
+            (0x2000_0018, 8, spec(0x2000_0018, 8)),
+        ] {
+            variable.memory_location = VariableLocation::Address(address);
+            variable.byte_size = Some(byte_size);
+            assert_eq!(
+                watch(&variable).unwrap(),
+                expected,
+                "{address:#x} {byte_size}"
+            );
+        }
+
+        let reason = |variable: &Variable| match watch(variable) {
+            Err(DebugError::VariableNotWatchable { reason, .. }) => reason,
+            other => panic!("{other:?}"),
+        };
+        variable.memory_location = VariableLocation::Address(0x2000_0014);
+        variable.byte_size = Some(8);
+        assert_eq!(
+            reason(&variable),
+            NotWatchableReason::TooLarge { byte_size: 8 }
+        );
+        variable.byte_size = None;
+        assert_eq!(reason(&variable), NotWatchableReason::UnknownSize);
+
+        variable.byte_size = Some(4);
+        variable.memory_location = VariableLocation::RegisterValue(RegisterValue::U32(9600));
+        assert_eq!(reason(&variable), NotWatchableReason::Register);
+        variable.memory_location = VariableLocation::Implicit(vec![0x80, 0x25, 0, 0]);
+        assert_eq!(reason(&variable), NotWatchableReason::Synthetic);
+        variable.memory_location = VariableLocation::Unavailable;
+        assert_eq!(reason(&variable), NotWatchableReason::NoLocation);
+    }
+
+    #[test]
+    fn serialize_tree() {
+        let mut cache = VariableCache::new_static_cache();
+        let root_key = cache.root_variable().variable_key;
+
+        let mut point = cache.create_variable(root_key, None).unwrap();
+        point.name = VariableName::Named("point".to_string());
+        point.type_name = VariableType::Struct("Point".to_string());
+        point.memory_location = VariableLocation::Address(0x2000_0000);
+        point.byte_size = Some(4);
+        point.is_parameter = true;
+        cache.update_variable(&point).unwrap();
+
+        let mut fields = Vec::new();
+        for (name, value, address) in [("x", 3, 0x2000_0000), ("y", -4, 0x2000_0002)] {
+            let mut field = cache.create_variable(point.variable_key, None).unwrap();
+            field.name = VariableName::Named(name.to_string());
+            field.type_name = VariableType::Base("i16".to_string());
+            field.memory_location = VariableLocation::Address(address);
+            field.byte_size = Some(2);
+            field.set_value(VariableValue::I64(value));
+            cache.update_variable(&field).unwrap();
+            fields.push(field);
+        }
+
+        let mut next = cache.create_variable(point.variable_key, None).unwrap();
+        next.name = VariableName::Named("next".to_string());
+        next.type_name = VariableType::Struct("Point".to_string());
+        next.variable_node_type = VariableNodeType::TypeOffset(DebugInfoOffset(0), UnitOffset(0));
+        cache.update_variable(&next).unwrap();
+
+        cache.set_value_format(ValueFormat::Hex);
+        let tree = cache
+            .serialize_tree(point.variable_key, serde_json::value::Serializer)
+            .unwrap();
+        assert_eq!(
+            tree,
+            json!({
+                "name": { "Named": "point" },
+                "type_name": "Point",
+                "location": "0x20000000",
+                "byte_size": 4,
+                "is_parameter": true,
+                "children": [
+                    {
+                        "name": { "Named": "x" },
+                        "type_name": "i16",
+                        "value": "0x0003",
+                        "location": "0x20000000",
+                        "byte_size": 2,
+                    },
+                    {
+                        "name": { "Named": "y" },
+                        "type_name": "i16",
+                        "value": "0xfffc",
+                        "location": "0x20000002",
+                        "byte_size": 2,
+                    },
+                    {
+                        "name": { "Named": "next" },
+                        "type_name": "Point",
+                        "deferred": true,
+                    },
+                ],
+            })
+        );
+
+        // A single variable has no children, and its value in the default format.
+        assert_eq!(
+            serde_json::to_value(&fields[1]).unwrap(),
+            json!({
+                "name": { "Named": "y" },
+                "type_name": "i16",
+                "value": "-4",
+                "location": "0x20000002",
+                "byte_size": 2,
+            })
+        );
+
+        assert!(
+            cache
+                .serialize_tree(ObjectRef::Invalid, serde_json::value::Serializer)
+                .is_err()
+        );
+    }
+
+    /// Add a variable named `name` to `cache`, with `value` if it is set.
+    fn add_named(
+        cache: &mut VariableCache,
+        parent_key: ObjectRef,
+        name: &str,
+        value: Option<i64>,
+    ) -> Variable {
+        let mut variable = cache.create_variable(parent_key, None).unwrap();
+        variable.name = VariableName::Named(name.to_string());
+        if let Some(value) = value {
+            variable.type_name = VariableType::Base("i32".to_string());
+            variable.set_value(VariableValue::I64(value));
+        } else {
+            variable.type_name = VariableType::Struct("Point".to_string());
+        }
+        cache.update_variable(&variable).unwrap();
+        variable
+    }
+
+    #[test]
+    fn diff() {
+        let mut old = VariableCache::new_static_cache();
+        let root_key = old.root_variable().variable_key;
+        add_named(&mut old, root_key, "count", Some(1));
+        let point = add_named(&mut old, root_key, "point", None);
+        add_named(&mut old, point.variable_key, "x", Some(1));
+        let mut deferred = add_named(&mut old, root_key, "deferred", None);
+        deferred.variable_node_type =
+            VariableNodeType::TypeOffset(DebugInfoOffset(0), UnitOffset(0));
+        old.update_variable(&deferred).unwrap();
+        add_named(&mut old, root_key, "removed", Some(5));
+
+        let mut new = VariableCache::new_static_cache();
+        let root_key = new.root_variable().variable_key;
+        add_named(&mut new, root_key, "count", Some(2));
+        let point = add_named(&mut new, root_key, "point", None);
+        add_named(&mut new, point.variable_key, "x", Some(1));
+        add_named(&mut new, point.variable_key, "y", Some(2));
+        let deferred = add_named(&mut new, root_key, "deferred", None);
+        add_named(&mut new, deferred.variable_key, "z", Some(3));
+        add_named(&mut new, root_key, "added", Some(7));
+
+        let changes = old.diff(&new);
+        assert_eq!(
+            changes,
+            vec![
+                VariableChange::Changed {
+                    path: "static_scope.count".to_string(),
+                    old_value: "1".to_string(),
+                    new_value: "2".to_string(),
+                },
+                VariableChange::Added {
+                    path: "static_scope.point.y".to_string()
+                },
+                VariableChange::Removed {
+                    path: "static_scope.removed".to_string()
+                },
+                VariableChange::Added {
+                    path: "static_scope.added".to_string()
+                },
+            ]
+        );
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn translate_keys() {
+        let mut old = VariableCache::new_static_cache();
+        let root_key = old.root_variable().variable_key;
+        let old_point = add_named(&mut old, root_key, "point", None);
+        let old_x = add_named(&mut old, old_point.variable_key, "x", Some(1));
+        let old_removed = add_named(&mut old, root_key, "removed", Some(5));
+
+        let mut new = VariableCache::new_static_cache();
+        let root_key = new.root_variable().variable_key;
+        add_named(&mut new, root_key, "added", Some(7));
+        let new_point = add_named(&mut new, root_key, "point", None);
+        let new_x = add_named(&mut new, new_point.variable_key, "x", Some(2));
+
+        let keys = old.translate_keys(&new);
+        assert_eq!(keys[&old.root_variable().variable_key], root_key);
+        assert_eq!(keys[&old_point.variable_key], new_point.variable_key);
+        assert_eq!(keys[&old_x.variable_key], new_x.variable_key);
+        assert_eq!(keys.len(), 3);
+
+        // A stale key is not found in the new cache.
+        assert!(!keys.contains_key(&old_removed.variable_key));
+        assert_eq!(new.get_variable_by_key(old_removed.variable_key), None);
+    }
 }
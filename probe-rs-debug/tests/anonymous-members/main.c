@@ -0,0 +1,35 @@
+/* Structs with anonymous struct and union members, which are nested, see README.md. */
+
+#include <stdint.h>
+
+struct packet {
+    uint8_t kind;
+    union {
+        uint32_t word;
+        struct {
+            uint16_t low;
+            uint16_t high;
+        };
+    };
+    struct {
+        uint8_t flags;
+        union {
+            int8_t delta;
+            uint8_t raw;
+        };
+    };
+    uint8_t checksum;
+};
+
+struct packet PACKET = {
+    .kind = 1,
+    .word = 0x12345678,
+    .flags = 2,
+    .delta = -3,
+    .checksum = 4,
+};
+
+int _start(void)
+{
+    return PACKET.kind + PACKET.low + PACKET.flags + PACKET.raw + PACKET.checksum;
+}
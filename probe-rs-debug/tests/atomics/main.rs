@@ -0,0 +1,52 @@
+//! Atomics and volatile cells, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::cell::UnsafeCell;
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, AtomicI16, AtomicPtr, AtomicU32};
+
+/// The same layout as `vcell::VolatileCell`.
+pub struct VolatileCell<T> {
+    pub value: UnsafeCell<T>,
+}
+
+unsafe impl<T> Sync for VolatileCell<T> {}
+
+pub struct Registers {
+    pub status: VolatileCell<u32>,
+    pub control: VolatileCell<u8>,
+}
+
+pub static COUNTER: AtomicU32 = AtomicU32::new(42);
+
+pub static OFFSET: AtomicI16 = AtomicI16::new(-3);
+
+pub static READY: AtomicBool = AtomicBool::new(true);
+
+pub static TARGET: AtomicPtr<u32> = AtomicPtr::new(core::ptr::null_mut());
+
+pub static REGISTERS: Registers = Registers {
+    status: VolatileCell {
+        value: UnsafeCell::new(0x1234),
+    },
+    control: VolatileCell {
+        value: UnsafeCell::new(7),
+    },
+};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&COUNTER);
+    core::hint::black_box(&OFFSET);
+    core::hint::black_box(&READY);
+    core::hint::black_box(&TARGET);
+    core::hint::black_box(&REGISTERS);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,10 @@
+int int_value = -2;
+unsigned long long u64_value = 0x0123456789abcdef;
+float f32_value = 1.5f;
+double f64_value = 2.25;
+
+void _start(void)
+{
+    for (;;) {
+    }
+}
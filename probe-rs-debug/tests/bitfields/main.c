@@ -0,0 +1,33 @@
+/* Bitfields, like those of a register overlay, see README.md. */
+
+typedef unsigned int u32_t;
+
+struct control {
+    unsigned int enable : 1;
+    unsigned int mode : 3;
+    int offset : 5;
+    unsigned int prescaler : 20;
+    /* Does not fit in the rest of the first word, so it starts the second one. */
+    unsigned int channel : 6;
+    _Bool ready : 1;
+    int trim : 4;
+    u32_t flags : 2;
+    long long timestamp : 40;
+};
+
+struct control CONTROL = {
+    .enable = 1,
+    .mode = 5,
+    .offset = -3,
+    .prescaler = 0x12345,
+    .channel = 42,
+    .ready = 1,
+    .trim = -8,
+    .flags = 2,
+    .timestamp = -0x123456789,
+};
+
+int _start(void)
+{
+    return CONTROL.mode;
+}
@@ -0,0 +1,25 @@
+volatile int counter;
+
+static inline __attribute__((always_inline)) void bump(int amount)
+{
+    counter += amount;
+}
+
+void first(void)
+{
+    bump(1);
+}
+
+void second(void)
+{
+    bump(2);
+    bump(3);
+}
+
+void _start(void)
+{
+    first();
+    second();
+    for (;;) {
+    }
+}
@@ -0,0 +1,40 @@
+//! Byte buffers, which are shown with a preview of their bytes, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+/// A packet buffer, which starts with some text and a few bytes which are not printable.
+const fn packet() -> [u8; 64] {
+    let text = b"hello\x00\xffworld";
+    let mut packet = [0; 64];
+    let mut i = 0;
+    while i < text.len() {
+        packet[i] = text[i];
+        i += 1;
+    }
+    packet
+}
+
+pub static PACKET: [u8; 64] = packet();
+
+pub static SHORT: [u8; 5] = *b"hello";
+
+pub static BYTES: &[u8] = b"hello, world!!!!!!";
+
+pub static WORDS: [u16; 3] = [1, 2, 3];
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&PACKET);
+    core::hint::black_box(&SHORT);
+    core::hint::black_box(&BYTES);
+    core::hint::black_box(&WORDS);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,48 @@
+/* Variables of a C program, which are decoded without the conventions of Rust, see README.md. */
+
+#include <stddef.h>
+
+typedef enum { MODE_IDLE, MODE_RUN = 4 } mode_t;
+
+typedef struct {
+    const char *label;
+    char tag[8];
+    mode_t mode;
+} entry_t;
+
+entry_t ENTRIES[2] = {
+    { "first", "one", MODE_IDLE },
+    { "second", "two", MODE_RUN },
+};
+
+const char *GREETING = "Hello, C!";
+
+char NAME[16] = "probe-rs";
+
+char FULL[4] = { 'a', 'b', 'c', 'd' };
+
+const char *MISSING = NULL;
+
+/* Names which are special in Rust, but not in C. */
+struct PhantomData {
+    int marker;
+};
+
+struct Holder {
+    struct PhantomData phantom;
+    int __0;
+};
+
+struct Holder HOLDER = { { 1 }, 2 };
+
+struct None {
+    int value;
+};
+
+struct None NONE = { 3 };
+
+int _start(void)
+{
+    return ENTRIES[1].mode + GREETING[0] + NAME[0] + FULL[0] + (MISSING == NULL) + HOLDER.__0
+        + NONE.value;
+}
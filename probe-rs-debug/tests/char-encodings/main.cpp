@@ -0,0 +1,13 @@
+bool flag = true;
+char letter = 'A';
+char newline = '\n';
+unsigned char high_byte = 0xff;
+char32_t emoji = U'\U0001F600';
+// A surrogate, which is not a valid character.
+char32_t surrogate = 0xd800;
+
+extern "C" void _start(void)
+{
+    for (;;) {
+    }
+}
@@ -0,0 +1,69 @@
+; Equivalent of `clang -O0 -gdwarf-5 -S -emit-llvm main.c`, reduced to the
+; parts that are relevant for the debug information.
+source_filename = "main.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-unknown-linux-gnu"
+
+@counter = internal global i32 0, align 4, !dbg !0
+
+define dso_local i32 @add(i32 noundef %a, i32 noundef %b) #0 !dbg !15 {
+entry:
+  %a.addr = alloca i32, align 4
+  %b.addr = alloca i32, align 4
+  store i32 %a, i32* %a.addr, align 4
+  call void @llvm.dbg.declare(metadata i32* %a.addr, metadata !19, metadata !DIExpression()), !dbg !20
+  store i32 %b, i32* %b.addr, align 4
+  call void @llvm.dbg.declare(metadata i32* %b.addr, metadata !21, metadata !DIExpression()), !dbg !22
+  %0 = load i32, i32* %a.addr, align 4, !dbg !23
+  %1 = load i32, i32* %b.addr, align 4, !dbg !24
+  %add = add nsw i32 %0, %1, !dbg !25
+  ret i32 %add, !dbg !26
+}
+
+define dso_local i32 @main() #0 !dbg !27 {
+entry:
+  %call = call i32 @add(i32 noundef 1, i32 noundef 2), !dbg !30
+  store volatile i32 %call, i32* @counter, align 4, !dbg !31
+  %0 = load volatile i32, i32* @counter, align 4, !dbg !32
+  ret i32 %0, !dbg !33
+}
+
+declare void @llvm.dbg.declare(metadata, metadata, metadata) #1
+
+attributes #0 = { noinline nounwind optnone uwtable "frame-pointer"="all" }
+attributes #1 = { nofree nosync nounwind readnone speculatable willreturn }
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!9, !10, !11}
+!llvm.ident = !{!12}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "counter", scope: !2, file: !3, line: 1, type: !7, isLocal: true, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C11, file: !3, producer: "clang version 14.0.6", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !5, splitDebugInlining: false, nameTableKind: None)
+!3 = !DIFile(filename: "main.c", directory: "/tmp/checksums", checksumkind: CSK_MD5, checksum: "c30d0e3dac34749d23b616df65daea68")
+!4 = !{}
+!5 = !{!0}
+!7 = !DIDerivedType(tag: DW_TAG_volatile_type, baseType: !8)
+!8 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!9 = !{i32 7, !"Dwarf Version", i32 5}
+!10 = !{i32 2, !"Debug Info Version", i32 3}
+!11 = !{i32 1, !"wchar_size", i32 4}
+!12 = !{!"clang version 14.0.6"}
+!15 = distinct !DISubprogram(name: "add", scope: !3, file: !3, line: 3, type: !16, scopeLine: 3, flags: DIFlagPrototyped, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !4)
+!16 = !DISubroutineType(types: !17)
+!17 = !{!8, !8, !8}
+!19 = !DILocalVariable(name: "a", arg: 1, scope: !15, file: !3, line: 3, type: !8)
+!20 = !DILocation(line: 3, column: 13, scope: !15)
+!21 = !DILocalVariable(name: "b", arg: 2, scope: !15, file: !3, line: 3, type: !8)
+!22 = !DILocation(line: 3, column: 20, scope: !15)
+!23 = !DILocation(line: 4, column: 12, scope: !15)
+!24 = !DILocation(line: 4, column: 16, scope: !15)
+!25 = !DILocation(line: 4, column: 14, scope: !15)
+!26 = !DILocation(line: 4, column: 5, scope: !15)
+!27 = distinct !DISubprogram(name: "main", scope: !3, file: !3, line: 7, type: !28, scopeLine: 7, flags: DIFlagPrototyped, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !4)
+!28 = !DISubroutineType(types: !29)
+!29 = !{!8}
+!30 = !DILocation(line: 8, column: 15, scope: !27)
+!31 = !DILocation(line: 8, column: 13, scope: !27)
+!32 = !DILocation(line: 9, column: 12, scope: !27)
+!33 = !DILocation(line: 9, column: 5, scope: !27)
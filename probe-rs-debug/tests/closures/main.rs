@@ -0,0 +1,88 @@
+//! Closures and `async fn` state machines, see README.md.
+
+#![no_std]
+#![no_main]
+#![feature(type_alias_impl_trait)]
+
+use core::future::Future;
+use core::mem::MaybeUninit;
+use core::panic::PanicInfo;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub type Callback = impl Fn(u32) -> u32;
+
+pub type Check = impl Fn() -> bool;
+
+pub type Blink = impl Future<Output = u32>;
+
+/// Ready after it is polled once.
+pub struct Yield(bool);
+
+impl Future for Yield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+async fn blink(led: u8, times: u32) -> u32 {
+    let mut count = 0;
+    while count < times {
+        Yield(false).await;
+        count += 1;
+    }
+    Yield(false).await;
+    count + u32::from(led)
+}
+
+#[define_opaque(Callback)]
+fn callback(offset: u32, scale: &'static u32) -> Callback {
+    let limit = 100;
+    move |value| (value * *scale + offset).min(limit)
+}
+
+#[define_opaque(Check)]
+fn check(threshold: &'static u32) -> Check {
+    || *threshold > 1
+}
+
+#[define_opaque(Blink)]
+fn start_blink() -> Blink {
+    blink(7, 3)
+}
+
+static SCALE: u32 = 2;
+
+/// The tests write the closure and the state machine into memory themselves.
+pub static mut CALLBACK: MaybeUninit<Callback> = MaybeUninit::uninit();
+pub static mut CHECK: MaybeUninit<Check> = MaybeUninit::uninit();
+pub static mut BLINK: MaybeUninit<Blink> = MaybeUninit::uninit();
+
+#[unsafe(no_mangle)]
+pub extern "C" fn _start() -> ! {
+    unsafe {
+        (*(&raw mut CALLBACK)).write(callback(1, &SCALE));
+        (*(&raw mut CHECK)).write(check(&SCALE));
+        (*(&raw mut BLINK)).write(start_blink());
+    }
+    core::hint::black_box(&raw const CALLBACK);
+    core::hint::black_box(&raw const CHECK);
+    core::hint::black_box(&raw const BLINK);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+/// The prebuilt `core` of the target unwinds, so it refers to the personality function.
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_eh_personality() {}
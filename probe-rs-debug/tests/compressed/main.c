@@ -0,0 +1,29 @@
+struct point {
+    int x;
+    int y;
+};
+
+struct rectangle {
+    struct point top_left;
+    struct point bottom_right;
+};
+
+static int width(const struct rectangle *rectangle) {
+    return rectangle->bottom_right.x - rectangle->top_left.x;
+}
+
+static int height(const struct rectangle *rectangle) {
+    return rectangle->bottom_right.y - rectangle->top_left.y;
+}
+
+int area(const struct rectangle *rectangle) {
+    return width(rectangle) * height(rectangle);
+}
+
+void _start(void) {
+    struct rectangle rectangle = {{1, 2}, {4, 8}};
+    volatile int result = area(&rectangle);
+    (void)result;
+    for (;;) {
+    }
+}
@@ -0,0 +1,17 @@
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 64K
+  RAM : ORIGIN = 0x20000000, LENGTH = 16K
+}
+
+ENTRY(Reset);
+
+SECTIONS
+{
+  /* The initial stack pointer and the reset vector, so that no function is at address 0. */
+  .vector_table ORIGIN(FLASH) : { LONG(ORIGIN(RAM) + LENGTH(RAM)); LONG(Reset); } > FLASH
+  .text : { *(.text .text.*) } > FLASH
+  .rodata : { *(.rodata .rodata.*) } > FLASH
+  .data : { *(.data .data.*) } > RAM AT > FLASH
+  .bss (NOLOAD) : { *(.bss .bss.*) } > RAM
+}
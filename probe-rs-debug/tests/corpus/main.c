@@ -0,0 +1,34 @@
+/* The C part of the corpus, see README.md. */
+
+struct point {
+    int x;
+    short y;
+    unsigned int flags;
+};
+
+struct point origin = { -3, 7, 0x10 };
+
+unsigned int counters[3] = { 10, 20, 30 };
+
+static long long total;
+
+__attribute__((noinline)) static int scale(const struct point *point, int factor)
+{
+    int scaled = point->x * factor;
+    return scaled + point->y;
+}
+
+__attribute__((noinline)) static int accumulate(unsigned int index)
+{
+    int value = scale(&origin, (int)counters[index]);
+    total += value;
+    return value;
+}
+
+void Reset(void)
+{
+    unsigned int index = 1;
+    for (;;) {
+        accumulate(index);
+    }
+}
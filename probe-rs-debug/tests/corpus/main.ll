@@ -0,0 +1,147 @@
+; The IR of main.c, in the form `clang -O0 -g` emits it, see README.md.
+; `@DATALAYOUT@`, `@TRIPLE@` and `@DWARF_VERSION@` are set by the build.
+source_filename = "main.c"
+target datalayout = "@DATALAYOUT@"
+target triple = "@TRIPLE@"
+
+%struct.point = type { i32, i16, i32 }
+
+@origin = dso_local global %struct.point { i32 -3, i16 7, i32 16 }, align 4, !dbg !0
+@counters = dso_local global [3 x i32] [i32 10, i32 20, i32 30], align 4, !dbg !5
+@total = internal global i64 0, align 8, !dbg !10
+
+define internal i32 @scale(%struct.point* noundef %point, i32 noundef %factor) #0 !dbg !30 {
+entry:
+  %point.addr = alloca %struct.point*, align 4
+  %factor.addr = alloca i32, align 4
+  %scaled = alloca i32, align 4
+  store %struct.point* %point, %struct.point** %point.addr, align 4
+  call void @llvm.dbg.declare(metadata %struct.point** %point.addr, metadata !36, metadata !DIExpression()), !dbg !37
+  store i32 %factor, i32* %factor.addr, align 4
+  call void @llvm.dbg.declare(metadata i32* %factor.addr, metadata !38, metadata !DIExpression()), !dbg !39
+  call void @llvm.dbg.declare(metadata i32* %scaled, metadata !40, metadata !DIExpression()), !dbg !41
+  %0 = load %struct.point*, %struct.point** %point.addr, align 4, !dbg !42
+  %x = getelementptr inbounds %struct.point, %struct.point* %0, i32 0, i32 0, !dbg !43
+  %1 = load i32, i32* %x, align 4, !dbg !43
+  %2 = load i32, i32* %factor.addr, align 4, !dbg !44
+  %mul = mul nsw i32 %1, %2, !dbg !45
+  store i32 %mul, i32* %scaled, align 4, !dbg !41
+  %3 = load i32, i32* %scaled, align 4, !dbg !46
+  %4 = load %struct.point*, %struct.point** %point.addr, align 4, !dbg !47
+  %y = getelementptr inbounds %struct.point, %struct.point* %4, i32 0, i32 1, !dbg !48
+  %5 = load i16, i16* %y, align 4, !dbg !48
+  %conv = sext i16 %5 to i32, !dbg !47
+  %add = add nsw i32 %3, %conv, !dbg !49
+  ret i32 %add, !dbg !50
+}
+
+define internal i32 @accumulate(i32 noundef %index) #0 !dbg !60 {
+entry:
+  %index.addr = alloca i32, align 4
+  %value = alloca i32, align 4
+  store i32 %index, i32* %index.addr, align 4
+  call void @llvm.dbg.declare(metadata i32* %index.addr, metadata !63, metadata !DIExpression()), !dbg !64
+  call void @llvm.dbg.declare(metadata i32* %value, metadata !65, metadata !DIExpression()), !dbg !66
+  %0 = load i32, i32* %index.addr, align 4, !dbg !67
+  %arrayidx = getelementptr inbounds [3 x i32], [3 x i32]* @counters, i32 0, i32 %0, !dbg !68
+  %1 = load i32, i32* %arrayidx, align 4, !dbg !68
+  %call = call i32 @scale(%struct.point* noundef @origin, i32 noundef %1), !dbg !69
+  store i32 %call, i32* %value, align 4, !dbg !66
+  %2 = load i32, i32* %value, align 4, !dbg !70
+  %conv = sext i32 %2 to i64, !dbg !70
+  %3 = load i64, i64* @total, align 8, !dbg !71
+  %add = add nsw i64 %3, %conv, !dbg !71
+  store i64 %add, i64* @total, align 8, !dbg !71
+  %4 = load i32, i32* %value, align 4, !dbg !72
+  ret i32 %4, !dbg !73
+}
+
+define dso_local void @Reset() #0 !dbg !80 {
+entry:
+  %index = alloca i32, align 4
+  call void @llvm.dbg.declare(metadata i32* %index, metadata !83, metadata !DIExpression()), !dbg !84
+  store i32 1, i32* %index, align 4, !dbg !84
+  br label %for.cond, !dbg !87
+
+for.cond:
+  %0 = load i32, i32* %index, align 4, !dbg !85
+  %call = call i32 @accumulate(i32 noundef %0), !dbg !86
+  br label %for.cond, !dbg !87
+}
+
+declare void @llvm.dbg.declare(metadata, metadata, metadata) #1
+
+attributes #0 = { noinline nounwind optnone "frame-pointer"="all" "target-cpu"="cortex-m3" }
+attributes #1 = { nofree nosync nounwind readnone speculatable willreturn }
+
+!llvm.dbg.cu = !{!2}
+!llvm.module.flags = !{!20, !21, !22, !23}
+
+!0 = !DIGlobalVariableExpression(var: !1, expr: !DIExpression())
+!1 = distinct !DIGlobalVariable(name: "origin", scope: !2, file: !3, line: 9, type: !12, isLocal: false, isDefinition: true)
+!2 = distinct !DICompileUnit(language: DW_LANG_C99, file: !3, isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, globals: !4, splitDebugInlining: false, nameTableKind: None)
+!3 = !DIFile(filename: "main.c", directory: "/tmp/corpus")
+!4 = !{!0, !5, !10}
+!5 = !DIGlobalVariableExpression(var: !6, expr: !DIExpression())
+!6 = distinct !DIGlobalVariable(name: "counters", scope: !2, file: !3, line: 11, type: !7, isLocal: false, isDefinition: true)
+!7 = !DICompositeType(tag: DW_TAG_array_type, baseType: !8, size: 96, elements: !18)
+!8 = !DIBasicType(name: "unsigned int", size: 32, encoding: DW_ATE_unsigned)
+!10 = !DIGlobalVariableExpression(var: !11, expr: !DIExpression())
+!11 = distinct !DIGlobalVariable(name: "total", scope: !2, file: !3, line: 13, type: !19, isLocal: true, isDefinition: true)
+!12 = distinct !DICompositeType(tag: DW_TAG_structure_type, name: "point", file: !3, line: 3, size: 96, elements: !13)
+!13 = !{!14, !16, !17}
+!14 = !DIDerivedType(tag: DW_TAG_member, name: "x", scope: !12, file: !3, line: 4, baseType: !15, size: 32)
+!15 = !DIBasicType(name: "int", size: 32, encoding: DW_ATE_signed)
+!16 = !DIDerivedType(tag: DW_TAG_member, name: "y", scope: !12, file: !3, line: 5, baseType: !24, size: 16, offset: 32)
+!17 = !DIDerivedType(tag: DW_TAG_member, name: "flags", scope: !12, file: !3, line: 6, baseType: !8, size: 32, offset: 64)
+!18 = !{!25}
+!19 = !DIBasicType(name: "long long int", size: 64, encoding: DW_ATE_signed)
+!20 = !{i32 7, !"Dwarf Version", i32 @DWARF_VERSION@}
+!21 = !{i32 2, !"Debug Info Version", i32 3}
+!22 = !{i32 1, !"wchar_size", i32 4}
+!23 = !{i32 1, !"min_enum_size", i32 4}
+!24 = !DIBasicType(name: "short int", size: 16, encoding: DW_ATE_signed)
+!25 = !DISubrange(count: 3)
+!30 = distinct !DISubprogram(name: "scale", scope: !3, file: !3, line: 15, type: !31, scopeLine: 16, flags: DIFlagPrototyped, spFlags: DISPFlagLocalToUnit | DISPFlagDefinition, unit: !2, retainedNodes: !35)
+!31 = !DISubroutineType(types: !32)
+!32 = !{!15, !33, !15}
+!33 = !DIDerivedType(tag: DW_TAG_pointer_type, baseType: !34, size: 32)
+!34 = !DIDerivedType(tag: DW_TAG_const_type, baseType: !12)
+!35 = !{}
+!36 = !DILocalVariable(name: "point", arg: 1, scope: !30, file: !3, line: 15, type: !33)
+!37 = !DILocation(line: 15, column: 64, scope: !30)
+!38 = !DILocalVariable(name: "factor", arg: 2, scope: !30, file: !3, line: 15, type: !15)
+!39 = !DILocation(line: 15, column: 75, scope: !30)
+!40 = !DILocalVariable(name: "scaled", scope: !30, file: !3, line: 17, type: !15)
+!41 = !DILocation(line: 17, column: 9, scope: !30)
+!42 = !DILocation(line: 17, column: 18, scope: !30)
+!43 = !DILocation(line: 17, column: 25, scope: !30)
+!44 = !DILocation(line: 17, column: 29, scope: !30)
+!45 = !DILocation(line: 17, column: 27, scope: !30)
+!46 = !DILocation(line: 18, column: 12, scope: !30)
+!47 = !DILocation(line: 18, column: 21, scope: !30)
+!48 = !DILocation(line: 18, column: 28, scope: !30)
+!49 = !DILocation(line: 18, column: 19, scope: !30)
+!50 = !DILocation(line: 18, column: 5, scope: !30)
+!60 = distinct !DISubprogram(name: "accumulate", scope: !3, file: !3, line: 21, type: !61, scopeLine: 22, flags: DIFlagPrototyped, spFlags: DISPFlagLocalToUnit | DISPFlagDefinition, unit: !2, retainedNodes: !35)
+!61 = !DISubroutineType(types: !62)
+!62 = !{!15, !8}
+!63 = !DILocalVariable(name: "index", arg: 1, scope: !60, file: !3, line: 21, type: !8)
+!64 = !DILocation(line: 21, column: 62, scope: !60)
+!65 = !DILocalVariable(name: "value", scope: !60, file: !3, line: 23, type: !15)
+!66 = !DILocation(line: 23, column: 9, scope: !60)
+!67 = !DILocation(line: 23, column: 46, scope: !60)
+!68 = !DILocation(line: 23, column: 37, scope: !60)
+!69 = !DILocation(line: 23, column: 17, scope: !60)
+!70 = !DILocation(line: 24, column: 14, scope: !60)
+!71 = !DILocation(line: 24, column: 11, scope: !60)
+!72 = !DILocation(line: 25, column: 12, scope: !60)
+!73 = !DILocation(line: 25, column: 5, scope: !60)
+!80 = distinct !DISubprogram(name: "Reset", scope: !3, file: !3, line: 28, type: !81, scopeLine: 29, flags: DIFlagPrototyped, spFlags: DISPFlagDefinition, unit: !2, retainedNodes: !35)
+!81 = !DISubroutineType(types: !82)
+!82 = !{null}
+!83 = !DILocalVariable(name: "index", scope: !80, file: !3, line: 30, type: !8)
+!84 = !DILocation(line: 30, column: 18, scope: !80)
+!85 = !DILocation(line: 32, column: 20, scope: !80)
+!86 = !DILocation(line: 32, column: 9, scope: !80)
+!87 = !DILocation(line: 31, column: 5, scope: !80)
@@ -0,0 +1,70 @@
+//! A small Cortex-M program for the tests that run against the memory image of the binary,
+//! see `src/test_support.rs`.
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+#[derive(Clone, Copy)]
+pub enum Mode {
+    Idle,
+    Running(u32),
+    Stopped { code: i16 },
+}
+
+pub struct State {
+    pub count: u32,
+    pub enabled: bool,
+    pub mode: Mode,
+    pub history: [u8; 4],
+    pub name: &'static str,
+}
+
+pub static mut STATE: State = State {
+    count: 3,
+    enabled: true,
+    mode: Mode::Running(42),
+    history: [1, 2, 3, 4],
+    name: "corpus",
+};
+
+pub static mut TICKS: u64 = 0;
+
+pub static LIMITS: [i32; 3] = [-1, 0, 1];
+
+pub static STOPPED: Mode = Mode::Stopped { code: -7 };
+
+#[inline(never)]
+fn inner(value: u32) -> u32 {
+    let state = &raw mut STATE;
+    unsafe {
+        (*state).count = (*state).count.wrapping_add(value);
+        (*state).count
+    }
+}
+
+#[inline(never)]
+fn outer(value: u32) -> u32 {
+    let doubled = value * 2;
+    inner(doubled) + 1
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    loop {
+        let limits = core::hint::black_box(&LIMITS);
+        let result = outer(core::hint::black_box(1));
+        unsafe {
+            TICKS += u64::from(result) + limits.len() as u64;
+        }
+        if let Mode::Idle = *core::hint::black_box(&STOPPED) {
+            break;
+        }
+    }
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
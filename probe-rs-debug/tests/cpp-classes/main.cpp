@@ -0,0 +1,30 @@
+// Classes with base classes, virtual functions, member functions and a static member, see README.md.
+
+class Shape {
+public:
+    virtual int area() const { return 0; }
+    int id = 1;
+};
+
+class Named {
+public:
+    const char *name() const { return label; }
+    const char *label = "box";
+};
+
+class Box : public Shape, public Named {
+public:
+    int area() const override { return width * height; }
+    int width = 3;
+    int height = 4;
+    static int count;
+};
+
+int Box::count = 2;
+
+Box BOX;
+
+extern "C" int _start()
+{
+    return BOX.area() + BOX.name()[0] + Box::count;
+}
@@ -0,0 +1,353 @@
+//! Enums with variant discriminants wider than a byte, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+#[derive(Clone, Copy)]
+#[repr(u16)]
+pub enum Wide {
+    Low(u8) = 0x0001,
+    High(u32) = 0x0101,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i16)]
+pub enum Signed {
+    Negative(u8) = -2,
+    Positive(u8) = 0x00fe,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i64)]
+pub enum Large {
+    Min(u8) = i64::MIN,
+    Max(u16) = i64::MAX,
+}
+
+#[derive(Clone, Copy)]
+pub enum Many {
+    V0(u8),
+    V1(u8),
+    V2(u8),
+    V3(u8),
+    V4(u8),
+    V5(u8),
+    V6(u8),
+    V7(u8),
+    V8(u8),
+    V9(u8),
+    V10(u8),
+    V11(u8),
+    V12(u8),
+    V13(u8),
+    V14(u8),
+    V15(u8),
+    V16(u8),
+    V17(u8),
+    V18(u8),
+    V19(u8),
+    V20(u8),
+    V21(u8),
+    V22(u8),
+    V23(u8),
+    V24(u8),
+    V25(u8),
+    V26(u8),
+    V27(u8),
+    V28(u8),
+    V29(u8),
+    V30(u8),
+    V31(u8),
+    V32(u8),
+    V33(u8),
+    V34(u8),
+    V35(u8),
+    V36(u8),
+    V37(u8),
+    V38(u8),
+    V39(u8),
+    V40(u8),
+    V41(u8),
+    V42(u8),
+    V43(u8),
+    V44(u8),
+    V45(u8),
+    V46(u8),
+    V47(u8),
+    V48(u8),
+    V49(u8),
+    V50(u8),
+    V51(u8),
+    V52(u8),
+    V53(u8),
+    V54(u8),
+    V55(u8),
+    V56(u8),
+    V57(u8),
+    V58(u8),
+    V59(u8),
+    V60(u8),
+    V61(u8),
+    V62(u8),
+    V63(u8),
+    V64(u8),
+    V65(u8),
+    V66(u8),
+    V67(u8),
+    V68(u8),
+    V69(u8),
+    V70(u8),
+    V71(u8),
+    V72(u8),
+    V73(u8),
+    V74(u8),
+    V75(u8),
+    V76(u8),
+    V77(u8),
+    V78(u8),
+    V79(u8),
+    V80(u8),
+    V81(u8),
+    V82(u8),
+    V83(u8),
+    V84(u8),
+    V85(u8),
+    V86(u8),
+    V87(u8),
+    V88(u8),
+    V89(u8),
+    V90(u8),
+    V91(u8),
+    V92(u8),
+    V93(u8),
+    V94(u8),
+    V95(u8),
+    V96(u8),
+    V97(u8),
+    V98(u8),
+    V99(u8),
+    V100(u8),
+    V101(u8),
+    V102(u8),
+    V103(u8),
+    V104(u8),
+    V105(u8),
+    V106(u8),
+    V107(u8),
+    V108(u8),
+    V109(u8),
+    V110(u8),
+    V111(u8),
+    V112(u8),
+    V113(u8),
+    V114(u8),
+    V115(u8),
+    V116(u8),
+    V117(u8),
+    V118(u8),
+    V119(u8),
+    V120(u8),
+    V121(u8),
+    V122(u8),
+    V123(u8),
+    V124(u8),
+    V125(u8),
+    V126(u8),
+    V127(u8),
+    V128(u8),
+    V129(u8),
+    V130(u8),
+    V131(u8),
+    V132(u8),
+    V133(u8),
+    V134(u8),
+    V135(u8),
+    V136(u8),
+    V137(u8),
+    V138(u8),
+    V139(u8),
+    V140(u8),
+    V141(u8),
+    V142(u8),
+    V143(u8),
+    V144(u8),
+    V145(u8),
+    V146(u8),
+    V147(u8),
+    V148(u8),
+    V149(u8),
+    V150(u8),
+    V151(u8),
+    V152(u8),
+    V153(u8),
+    V154(u8),
+    V155(u8),
+    V156(u8),
+    V157(u8),
+    V158(u8),
+    V159(u8),
+    V160(u8),
+    V161(u8),
+    V162(u8),
+    V163(u8),
+    V164(u8),
+    V165(u8),
+    V166(u8),
+    V167(u8),
+    V168(u8),
+    V169(u8),
+    V170(u8),
+    V171(u8),
+    V172(u8),
+    V173(u8),
+    V174(u8),
+    V175(u8),
+    V176(u8),
+    V177(u8),
+    V178(u8),
+    V179(u8),
+    V180(u8),
+    V181(u8),
+    V182(u8),
+    V183(u8),
+    V184(u8),
+    V185(u8),
+    V186(u8),
+    V187(u8),
+    V188(u8),
+    V189(u8),
+    V190(u8),
+    V191(u8),
+    V192(u8),
+    V193(u8),
+    V194(u8),
+    V195(u8),
+    V196(u8),
+    V197(u8),
+    V198(u8),
+    V199(u8),
+    V200(u8),
+    V201(u8),
+    V202(u8),
+    V203(u8),
+    V204(u8),
+    V205(u8),
+    V206(u8),
+    V207(u8),
+    V208(u8),
+    V209(u8),
+    V210(u8),
+    V211(u8),
+    V212(u8),
+    V213(u8),
+    V214(u8),
+    V215(u8),
+    V216(u8),
+    V217(u8),
+    V218(u8),
+    V219(u8),
+    V220(u8),
+    V221(u8),
+    V222(u8),
+    V223(u8),
+    V224(u8),
+    V225(u8),
+    V226(u8),
+    V227(u8),
+    V228(u8),
+    V229(u8),
+    V230(u8),
+    V231(u8),
+    V232(u8),
+    V233(u8),
+    V234(u8),
+    V235(u8),
+    V236(u8),
+    V237(u8),
+    V238(u8),
+    V239(u8),
+    V240(u8),
+    V241(u8),
+    V242(u8),
+    V243(u8),
+    V244(u8),
+    V245(u8),
+    V246(u8),
+    V247(u8),
+    V248(u8),
+    V249(u8),
+    V250(u8),
+    V251(u8),
+    V252(u8),
+    V253(u8),
+    V254(u8),
+    V255(u8),
+    V256(u8),
+    V257(u8),
+    V258(u8),
+    V259(u8),
+    V260(u8),
+    V261(u8),
+    V262(u8),
+    V263(u8),
+    V264(u8),
+    V265(u8),
+    V266(u8),
+    V267(u8),
+    V268(u8),
+    V269(u8),
+    V270(u8),
+    V271(u8),
+    V272(u8),
+    V273(u8),
+    V274(u8),
+    V275(u8),
+    V276(u8),
+    V277(u8),
+    V278(u8),
+    V279(u8),
+    V280(u8),
+    V281(u8),
+    V282(u8),
+    V283(u8),
+    V284(u8),
+    V285(u8),
+    V286(u8),
+    V287(u8),
+    V288(u8),
+    V289(u8),
+    V290(u8),
+    V291(u8),
+    V292(u8),
+    V293(u8),
+    V294(u8),
+    V295(u8),
+    V296(u8),
+    V297(u8),
+    V298(u8),
+    V299(u8),
+}
+
+pub static mut WIDE: Wide = Wide::High(0x1234_5678);
+
+pub static mut SIGNED: Signed = Signed::Negative(7);
+
+pub static mut LARGE: Large = Large::Max(0x1234);
+
+pub static mut MANY: Many = Many::V299(42);
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const WIDE);
+    core::hint::black_box(&raw const SIGNED);
+    core::hint::black_box(&raw const LARGE);
+    core::hint::black_box(&raw const MANY);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,7 @@
+#include "shared.h"
+
+static int local = 2;
+
+int read_a(void) {
+    return shared + local;
+}
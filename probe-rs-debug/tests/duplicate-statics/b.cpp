@@ -0,0 +1,9 @@
+#include "shared.h"
+
+static int local = 3;
+
+extern "C" void _start(void) {
+    for (;;) {
+        shared += read_a() + local;
+    }
+}
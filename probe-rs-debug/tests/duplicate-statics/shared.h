@@ -0,0 +1,3 @@
+inline int shared = 1;
+
+int read_a(void);
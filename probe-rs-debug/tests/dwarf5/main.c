@@ -0,0 +1,10 @@
+static volatile int counter;
+
+int add(int a, int b) {
+    return a + b;
+}
+
+int main(void) {
+    counter = add(1, 2);
+    return counter;
+}
@@ -0,0 +1,14 @@
+/* C enums, which are as large as an `int`, see README.md. */
+
+enum level { LOW = -1, MEDIUM = 0, HIGH = 70000 };
+
+enum color { RED, GREEN = 0x1234 };
+
+enum level LEVEL = LOW;
+
+enum color COLOR = GREEN;
+
+int _start(void)
+{
+    return LEVEL + COLOR;
+}
@@ -0,0 +1,56 @@
+//! C-like enums of different sizes, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+#[derive(Clone, Copy)]
+#[repr(u8)]
+pub enum Small {
+    First = 1,
+    Second = 2,
+}
+
+#[derive(Clone, Copy)]
+#[repr(u16)]
+pub enum Wide {
+    Low = 1,
+    High = 0x1234,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i32)]
+pub enum Signed {
+    Negative = -2,
+    Positive = 70000,
+}
+
+#[derive(Clone, Copy)]
+#[repr(u64)]
+pub enum Large {
+    Zero = 0,
+    Max = u64::MAX,
+}
+
+pub static mut SMALL: Small = Small::Second;
+
+pub static mut WIDE: Wide = Wide::High;
+
+pub static mut SIGNED: Signed = Signed::Negative;
+
+pub static mut LARGE: Large = Large::Max;
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const SMALL);
+    core::hint::black_box(&raw const WIDE);
+    core::hint::black_box(&raw const SIGNED);
+    core::hint::black_box(&raw const LARGE);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,16 @@
+#include <math.h>
+
+_Float16 half_values[4] = {1.5, -0x1p-24, INFINITY, 65504};
+float float_values[4] = {0.1f, -2.5f, NAN, -INFINITY};
+double double_values[4] = {0.1, 1.0 / 3.0, NAN, INFINITY};
+
+struct point {
+    float x;
+    double y;
+} point = {1.25f, -3.75};
+
+void _start(void)
+{
+    for (;;) {
+    }
+}
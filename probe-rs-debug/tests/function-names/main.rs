@@ -0,0 +1,40 @@
+#![no_std]
+#![no_main]
+
+mod spi {
+    pub struct Spi {
+        pub sent: u32,
+    }
+
+    impl Spi {
+        #[inline(never)]
+        pub fn transfer(&mut self, byte: u8) -> u8 {
+            self.sent += 1;
+            byte ^ 0xff
+        }
+    }
+
+    #[inline(never)]
+    pub fn transfer<T: Into<u32>>(value: T) -> u32 {
+        value.into().rotate_left(3)
+    }
+}
+
+#[inline(never)]
+fn transfer_all(spi: &mut spi::Spi) -> u32 {
+    let byte = core::hint::black_box(2u8);
+    spi.transfer(byte) as u32 + spi::transfer(byte) + spi::transfer(u16::from(byte) << 8)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn _start() -> ! {
+    let mut spi = spi::Spi { sent: 0 };
+    let result = transfer_all(&mut spi);
+    unsafe { core::ptr::write_volatile(&raw mut spi.sent, result) };
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,36 @@
+/* A struct of callbacks, which point to functions, nowhere, and outside of any function, see
+ * README.md. */
+
+#include <stdint.h>
+
+struct callbacks {
+    void (*on_rx)(uint8_t byte);
+    int (*on_error)(int code);
+    void (*on_idle)(void);
+    void (*on_reset)(void);
+};
+
+static volatile uint8_t LAST_BYTE;
+
+void on_rx(uint8_t byte)
+{
+    LAST_BYTE = byte;
+}
+
+static int on_error(int code)
+{
+    return -code;
+}
+
+struct callbacks CALLBACKS = {
+    .on_rx = on_rx,
+    .on_error = on_error,
+    .on_idle = 0,
+    .on_reset = (void (*)(void))&LAST_BYTE,
+};
+
+int _start(void)
+{
+    CALLBACKS.on_rx(1);
+    return CALLBACKS.on_error(2);
+}
@@ -0,0 +1,15 @@
+static int next_sample(int step)
+{
+    static int count = 5;
+    int total = count + step;
+
+    count = total;
+    return total;
+}
+
+void _start(void)
+{
+    for (;;) {
+        next_sample(2);
+    }
+}
@@ -0,0 +1,28 @@
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+#[inline(never)]
+fn tick(step: u32) -> u32 {
+    static COUNTER: AtomicU32 = AtomicU32::new(7);
+
+    let previous = COUNTER.fetch_add(step, Ordering::Relaxed);
+    previous + step
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn _start() -> ! {
+    loop {
+        tick(1);
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rust_eh_personality() {}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
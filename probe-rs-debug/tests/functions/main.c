@@ -0,0 +1,23 @@
+volatile int sink;
+
+__attribute__((noinline, noreturn, cold)) void halt(void) {
+    for (;;) {
+    }
+}
+
+static inline __attribute__((always_inline)) int square(int x) {
+    return x * x;
+}
+
+__attribute__((noinline)) int checked(int x) {
+    if (x < 0) {
+        sink = x;
+        halt();
+    }
+    return square(x);
+}
+
+void _start(void) {
+    sink = square(sink) + checked(sink);
+    halt();
+}
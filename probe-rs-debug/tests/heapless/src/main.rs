@@ -0,0 +1,49 @@
+//! `heapless` and `alloc` collections, see README.md.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+
+/// Nothing is allocated, the tests write the heap values into memory themselves.
+struct NoAllocator;
+
+unsafe impl GlobalAlloc for NoAllocator {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoAllocator = NoAllocator;
+
+pub static mut NUMBERS: heapless::Vec<u16, 4> = heapless::Vec::new();
+
+pub static mut NAME: heapless::String<8> = heapless::String::new();
+
+pub static mut DEQUE: heapless::Deque<u8, 4> = heapless::Deque::new();
+
+pub static mut QUEUE: heapless::spsc::Queue<u8, 4> = heapless::spsc::Queue::new();
+
+pub static mut RING: VecDeque<u16> = VecDeque::new();
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const NUMBERS);
+    core::hint::black_box(&raw const NAME);
+    core::hint::black_box(&raw const DEQUE);
+    core::hint::black_box(&raw const QUEUE);
+    core::hint::black_box(&raw const RING);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,24 @@
+extern int sink(int value);
+extern void log_error(int code) __attribute__((cold));
+
+static inline int report(int value)
+{
+    sink(-value);
+    return sink(value * 3);
+}
+
+int check(int value)
+{
+    if (value < 0) {
+        log_error(value);
+        value = report(value);
+    }
+    return sink(value + 1);
+}
+
+void _start(void)
+{
+    for (int i = 0;; i++) {
+        check(i);
+    }
+}
@@ -0,0 +1,10 @@
+int sink(int value)
+{
+    __asm__ volatile("" : "+r"(value));
+    return value;
+}
+
+__attribute__((cold)) void log_error(int code)
+{
+    sink(code);
+}
@@ -0,0 +1,28 @@
+__attribute__((noinline)) int sink(int value)
+{
+    __asm__ volatile("" : "+r"(value));
+    return value;
+}
+
+static inline int deref(const int *pointer)
+{
+    return sink(*pointer);
+}
+
+int compute(int base)
+{
+    int answer = 42;
+    double scale = 2.5;
+    int total = deref(&answer);
+    total += sink((int)scale);
+    scale = 4.0;
+    total += sink(base * (int)scale);
+    return total;
+}
+
+void _start(void)
+{
+    for (;;) {
+        compute(40);
+    }
+}
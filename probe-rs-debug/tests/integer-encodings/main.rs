@@ -0,0 +1,48 @@
+//! Integers at their boundary values, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+pub struct Members {
+    pub small: i8,
+    pub large: i64,
+}
+
+pub static I8_VALUES: [i8; 2] = [i8::MIN, -1];
+
+pub static I16_VALUES: [i16; 2] = [i16::MIN, -1];
+
+pub static I32_VALUES: [i32; 2] = [i32::MIN, -1];
+
+pub static I64_VALUES: [i64; 2] = [i64::MIN, -1];
+
+pub static I128_VALUES: [i128; 2] = [i128::MIN, -1];
+
+pub static ISIZE_VALUES: [isize; 2] = [isize::MIN, -1];
+
+pub static U128_VALUES: [u128; 2] = [u128::MAX, 1 << 64];
+
+pub static MEMBERS: Members = Members {
+    small: -2,
+    large: -3,
+};
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&I8_VALUES);
+    core::hint::black_box(&I16_VALUES);
+    core::hint::black_box(&I32_VALUES);
+    core::hint::black_box(&I64_VALUES);
+    core::hint::black_box(&I128_VALUES);
+    core::hint::black_box(&ISIZE_VALUES);
+    core::hint::black_box(&U128_VALUES);
+    core::hint::black_box(&MEMBERS);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
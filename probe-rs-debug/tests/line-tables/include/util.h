@@ -0,0 +1,3 @@
+static int square(int value) {
+    return value * value;
+}
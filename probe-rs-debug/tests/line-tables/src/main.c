@@ -0,0 +1,9 @@
+#include "util.h"
+
+volatile int result;
+
+void _start(void) {
+    for (;;) {
+        result = square(result);
+    }
+}
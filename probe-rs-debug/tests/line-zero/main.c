@@ -0,0 +1,9 @@
+int counter;
+
+void _start(void) {
+    counter = 1;
+    counter *= 3;
+    counter += 2;
+    for (;;) {
+    }
+}
@@ -0,0 +1,67 @@
+"""Create the malformed variants of `../source-files/main.elf`."""
+
+import random
+import struct
+from pathlib import Path
+
+SOURCE = Path(__file__).parent / "../source-files/main.elf"
+
+
+def sections(data):
+    """Map the section names of an ELF64 file to their (offset, size) in the file."""
+    (shoff,) = struct.unpack_from("<Q", data, 0x28)
+    shentsize, shnum, shstrndx = struct.unpack_from("<HHH", data, 0x3A)
+    headers = [
+        struct.unpack_from("<IIQQQQ", data, shoff + index * shentsize)
+        for index in range(shnum)
+    ]
+    strtab_offset = headers[shstrndx][4]
+
+    result = {}
+    for name_offset, _, _, _, offset, size in headers:
+        end = data.index(b"\0", strtab_offset + name_offset)
+        result[data[strtab_offset + name_offset : end].decode()] = (offset, size)
+    return result
+
+
+def patch(name, section, offset, replacement):
+    data = bytearray(SOURCE.read_bytes())
+    start, size = sections(data)[section]
+    assert offset + len(replacement) <= size
+    data[start + offset : start + offset + len(replacement)] = replacement
+    Path(name).write_bytes(data)
+
+
+def fuzz(name, seed, count):
+    data = bytearray(SOURCE.read_bytes())
+    rng = random.Random(seed)
+    ranges = [
+        sections(data)[section]
+        for section in (".debug_info", ".debug_abbrev", ".debug_line")
+    ]
+    for _ in range(count):
+        start, size = rng.choice(ranges)
+        data[start + rng.randrange(size)] = rng.randrange(256)
+    Path(name).write_bytes(data)
+
+
+# The root DIE of the first unit is a null entry.
+patch("empty-unit.elf", ".debug_info", 0x0C, b"\x00")
+# The length of the first unit extends beyond the end of the section.
+patch("unit-length.elf", ".debug_info", 0x00, struct.pack("<I", 0x7FFF_FFF0))
+# The root DIE of the first unit uses an abbreviation code that does not exist.
+patch("unknown-abbreviation.elf", ".debug_info", 0x0C, b"\x7f")
+# The first attribute of the first abbreviation uses an invalid form.
+patch("invalid-form.elf", ".debug_abbrev", 0x04, b"\x7e")
+# The line program of the first unit has an unsupported version.
+patch("line-program-version.elf", ".debug_line", 0x04, struct.pack("<H", 99))
+# The CIE uses a CFA expression, and a value expression for the return address.
+patch(
+    "cfa-expression.elf",
+    ".debug_frame",
+    0x0D,
+    bytes([0x0F, 0x02, 0x77, 0x00, 0x16, 0x10, 0x02, 0x77, 0x08, 0x00]),
+)
+
+for seed in range(1, 9):
+    fuzz(f"fuzz-{seed}.elf", seed, 16)
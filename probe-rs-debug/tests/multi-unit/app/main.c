@@ -0,0 +1,5 @@
+int checked(int value) {
+    value = value * 3;
+    value = value + 7;
+    return value ^ 5;
+}
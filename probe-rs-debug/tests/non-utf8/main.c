@@ -0,0 +1,11 @@
+volatile int counter;
+
+void increment(int step) {
+    counter += step;
+}
+
+void _start(void) {
+    for (;;) {
+        increment(2);
+    }
+}
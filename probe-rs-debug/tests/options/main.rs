@@ -0,0 +1,28 @@
+//! Rust `Option` and `Result` layouts, see README.md.
+
+#![no_std]
+#![no_main]
+
+use core::num::NonZeroU32;
+use core::panic::PanicInfo;
+
+pub static mut REFERENCE: Option<&u32> = None;
+pub static mut NON_ZERO: Option<NonZeroU32> = None;
+pub static mut NUMBER: Option<u32> = Some(7);
+pub static mut RESULT: Result<u16, bool> = Err(true);
+pub static mut BOOL: Option<bool> = None;
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const REFERENCE);
+    core::hint::black_box(&raw const NON_ZERO);
+    core::hint::black_box(&raw const NUMBER);
+    core::hint::black_box(&raw const RESULT);
+    core::hint::black_box(&raw const BOOL);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
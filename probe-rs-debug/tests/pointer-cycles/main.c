@@ -0,0 +1,18 @@
+/* A doubly linked list of two nodes, which point to each other, see README.md. */
+
+struct node {
+    int value;
+    struct node *next;
+    struct node *prev;
+};
+
+extern struct node first;
+
+struct node second = { 2, &first, &first };
+
+struct node first = { 1, &second, &second };
+
+int _start(void)
+{
+    return first.next->value;
+}
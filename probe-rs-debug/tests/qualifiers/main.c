@@ -0,0 +1,24 @@
+/* Variables with stacked const, volatile and restrict qualifiers, see README.md. */
+
+#include <stdint.h>
+
+typedef volatile uint32_t reg_t;
+
+volatile uint32_t STATUS = 0x80000001;
+
+const volatile int32_t OFFSET = -5;
+
+reg_t CONTROL = 7;
+
+volatile uint32_t REGS[4] = { 1, 2, 3, 4 };
+
+typedef uint16_t pair_t[2];
+
+const volatile pair_t PAIR = { 10, 20 };
+
+volatile uint32_t *restrict const STATUS_PTR = &STATUS;
+
+int _start(void)
+{
+    return STATUS + OFFSET + CONTROL + REGS[0] + PAIR[0] + *STATUS_PTR;
+}
@@ -0,0 +1,14 @@
+// The reference member is not decoded, so its memory is shown as raw bytes.
+struct Holder {
+    int &value;
+    unsigned long tag;
+};
+
+int target = 0x12345678;
+
+Holder HOLDER = {target, 0xabcd};
+
+extern "C" int _start()
+{
+    return HOLDER.value + HOLDER.tag;
+}
@@ -0,0 +1,43 @@
+//! Rust vectors and slices, see README.md.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+
+/// Nothing is allocated, the tests write the heap buffers into memory themselves.
+struct NoAllocator;
+
+unsafe impl GlobalAlloc for NoAllocator {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoAllocator = NoAllocator;
+
+pub static mut NUMBERS: Vec<u16> = Vec::new();
+
+pub static SLICE: &[u16] = &[1, 2, 3];
+
+pub static EMPTY: &[u16] = &[];
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const NUMBERS);
+    core::hint::black_box(&SLICE);
+    core::hint::black_box(&EMPTY);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
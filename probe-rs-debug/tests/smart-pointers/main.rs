@@ -0,0 +1,64 @@
+//! Rust smart pointers and cells, see README.md.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::{Cell, RefCell};
+use core::panic::PanicInfo;
+
+/// Nothing is allocated, the tests write the heap values into memory themselves.
+struct NoAllocator;
+
+unsafe impl GlobalAlloc for NoAllocator {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoAllocator = NoAllocator;
+
+pub struct Point {
+    pub x: i16,
+    pub y: i16,
+}
+
+pub static mut BOXED: Option<Box<Point>> = None;
+
+pub static mut BOXED_SLICE: Option<Box<[u16]>> = None;
+
+pub static mut SHARED: Option<Rc<u32>> = None;
+
+pub static mut ATOMIC: Option<Arc<u32>> = None;
+
+pub static mut BORROWED: Cow<'static, str> = Cow::Borrowed("cow");
+
+pub static mut CELL: Cell<u32> = Cell::new(3);
+
+pub static mut REF_CELL: RefCell<u16> = RefCell::new(9);
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const BOXED);
+    core::hint::black_box(&raw const BOXED_SLICE);
+    core::hint::black_box(&raw const SHARED);
+    core::hint::black_box(&raw const ATOMIC);
+    core::hint::black_box(&raw const BORROWED);
+    core::hint::black_box(&raw const CELL);
+    core::hint::black_box(&raw const REF_CELL);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
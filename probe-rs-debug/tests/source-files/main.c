@@ -0,0 +1,10 @@
+#include "util.h"
+
+int result;
+
+void _start(void) {
+    struct point p = { 3, -4 };
+    result = manhattan(p);
+    for (;;) {
+    }
+}
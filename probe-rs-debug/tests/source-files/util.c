@@ -0,0 +1,5 @@
+#include "util.h"
+
+int manhattan(struct point p) {
+    return (p.x < 0 ? -p.x : p.x) + (p.y < 0 ? -p.y : p.y);
+}
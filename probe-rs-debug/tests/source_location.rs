@@ -91,7 +91,8 @@ fn source_location() {
                 line: Some(*line),
                 column: Some(*col),
                 path: path.clone(),
-                address: Some(*addr)
+                address: Some(*addr),
+                line_fallback: false,
             }),
             di.get_source_location(*addr)
         );
@@ -124,3 +125,27 @@ fn regression_pr2324() {
 
     assert_eq!(addr.address, 0x2e4);
 }
+
+#[test]
+fn dwarf5_source_location() {
+    let di = DebugInfo::from_file("tests/dwarf5/main.elf").unwrap();
+
+    // DWARF 5 line programs use file index 0 for the primary source file.
+    let path = UnixPathBuf::from("/tmp/dwarf5/main.c").to_typed_path_buf();
+
+    assert_eq!(
+        Some(SourceLocation {
+            line: Some(4),
+            column: Some(ColumnType::Column(12)),
+            path: path.clone(),
+            address: Some(0x40100a),
+            line_fallback: false,
+        }),
+        di.get_source_location(0x40100a)
+    );
+
+    let breakpoint = di
+        .get_breakpoint_location(path.to_path(), 8, Some(15))
+        .expect("Failed to find breakpoint location.");
+    assert_eq!(breakpoint.address, 0x401024);
+}
@@ -0,0 +1,29 @@
+namespace sensors {
+
+class Counter {
+public:
+    int increment(int step);
+    static int instances;
+
+private:
+    int count;
+};
+
+int Counter::instances = 1;
+
+int Counter::increment(int step)
+{
+    count += step;
+    return count;
+}
+
+} // namespace sensors
+
+sensors::Counter counter;
+
+extern "C" void _start(void)
+{
+    for (;;) {
+        counter.increment(sensors::Counter::instances);
+    }
+}
@@ -0,0 +1,24 @@
+typedef unsigned short counter_t;
+
+struct config {
+    int mode;
+    char name[6];
+};
+
+namespace state {
+int current = 1;
+counter_t counters[4][2];
+
+namespace {
+int ticks = 5;
+}
+}  // namespace state
+
+config settings = {2, "test"};
+__thread int per_thread = 3;
+
+extern "C" void _start(void) {
+    for (;;) {
+        state::current += state::counters[1][1] + settings.mode + per_thread + state::ticks;
+    }
+}
@@ -0,0 +1,163 @@
+# Strings described with DW_TAG_string_type, like the CHARACTER variables of Fortran.
+
+	.text
+	.globl	_start
+_start:
+	ret
+.Ltext_end:
+
+	.data
+	.globl	FIXED
+FIXED:
+	.ascii	"PROBE-RS"
+	.globl	NAME
+NAME:
+	.ascii	"hello world!"
+	.globl	NAME_LENGTH
+	.p2align 2
+NAME_LENGTH:
+	.long	5
+	.globl	LABEL
+LABEL:
+	.ascii	"debugger"
+	.globl	LABEL_LENGTH
+	.p2align 2
+LABEL_LENGTH:
+	.long	3
+
+	.section	.debug_abbrev,"",@progbits
+.Labbrev:
+	# The compilation unit.
+	.uleb128 1
+	.uleb128 0x11	# DW_TAG_compile_unit
+	.byte	1	# DW_CHILDREN_yes
+	.uleb128 0x25	# DW_AT_producer
+	.uleb128 0x08	# DW_FORM_string
+	.uleb128 0x13	# DW_AT_language
+	.uleb128 0x0b	# DW_FORM_data1
+	.uleb128 0x03	# DW_AT_name
+	.uleb128 0x08	# DW_FORM_string
+	.uleb128 0x1b	# DW_AT_comp_dir
+	.uleb128 0x08	# DW_FORM_string
+	.uleb128 0x11	# DW_AT_low_pc
+	.uleb128 0x01	# DW_FORM_addr
+	.uleb128 0x12	# DW_AT_high_pc
+	.uleb128 0x07	# DW_FORM_data8
+	.byte	0, 0
+	# A base type.
+	.uleb128 2
+	.uleb128 0x24	# DW_TAG_base_type
+	.byte	0	# DW_CHILDREN_no
+	.uleb128 0x03	# DW_AT_name
+	.uleb128 0x08	# DW_FORM_string
+	.uleb128 0x0b	# DW_AT_byte_size
+	.uleb128 0x0b	# DW_FORM_data1
+	.uleb128 0x3e	# DW_AT_encoding
+	.uleb128 0x0b	# DW_FORM_data1
+	.byte	0, 0
+	# A string with a fixed length.
+	.uleb128 3
+	.uleb128 0x12	# DW_TAG_string_type
+	.byte	0	# DW_CHILDREN_no
+	.uleb128 0x0b	# DW_AT_byte_size
+	.uleb128 0x0b	# DW_FORM_data1
+	.byte	0, 0
+	# A string whose length is stored at the location of an expression.
+	.uleb128 4
+	.uleb128 0x12	# DW_TAG_string_type
+	.byte	0	# DW_CHILDREN_no
+	.uleb128 0x19	# DW_AT_string_length
+	.uleb128 0x18	# DW_FORM_exprloc
+	.uleb128 0x0b	# DW_AT_byte_size
+	.uleb128 0x0b	# DW_FORM_data1
+	.byte	0, 0
+	# A string whose length is the value of a variable.
+	.uleb128 5
+	.uleb128 0x12	# DW_TAG_string_type
+	.byte	0	# DW_CHILDREN_no
+	.uleb128 0x19	# DW_AT_string_length
+	.uleb128 0x13	# DW_FORM_ref4
+	.uleb128 0x0b	# DW_AT_byte_size
+	.uleb128 0x0b	# DW_FORM_data1
+	.byte	0, 0
+	# A static variable.
+	.uleb128 6
+	.uleb128 0x34	# DW_TAG_variable
+	.byte	0	# DW_CHILDREN_no
+	.uleb128 0x03	# DW_AT_name
+	.uleb128 0x08	# DW_FORM_string
+	.uleb128 0x49	# DW_AT_type
+	.uleb128 0x13	# DW_FORM_ref4
+	.uleb128 0x3f	# DW_AT_external
+	.uleb128 0x19	# DW_FORM_flag_present
+	.uleb128 0x02	# DW_AT_location
+	.uleb128 0x18	# DW_FORM_exprloc
+	.byte	0, 0
+	.byte	0
+
+	.section	.debug_info,"",@progbits
+.Lcu:
+	.long	.Lcu_end - .Lcu_version
+.Lcu_version:
+	.value	4
+	.long	.Labbrev
+	.byte	8
+	.uleb128 1
+	.string	"hand written"
+	.byte	0x0e	# DW_LANG_Fortran95
+	.string	"main.f95"
+	.string	"/tmp/string-types"
+	.quad	_start
+	.quad	.Ltext_end - _start
+.Lint:
+	.uleb128 2
+	.string	"integer(kind=4)"
+	.byte	4
+	.byte	0x05	# DW_ATE_signed
+.Lfixed:
+	.uleb128 3
+	.byte	8
+.Lname:
+	.uleb128 4
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	NAME_LENGTH
+	.byte	4
+.Llabel:
+	.uleb128 5
+	.long	.Llabel_length - .Lcu
+	.byte	4
+
+	.uleb128 6
+	.string	"FIXED"
+	.long	.Lfixed - .Lcu
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	FIXED
+	.uleb128 6
+	.string	"NAME"
+	.long	.Lname - .Lcu
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	NAME
+	.uleb128 6
+	.string	"NAME_LENGTH"
+	.long	.Lint - .Lcu
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	NAME_LENGTH
+	.uleb128 6
+	.string	"LABEL"
+	.long	.Llabel - .Lcu
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	LABEL
+.Llabel_length:
+	.uleb128 6
+	.string	"LABEL_LENGTH"
+	.long	.Lint - .Lcu
+	.uleb128 9
+	.byte	0x03	# DW_OP_addr
+	.quad	LABEL_LENGTH
+	.byte	0
+.Lcu_end:
@@ -0,0 +1,43 @@
+//! Rust strings, see README.md.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+
+/// Nothing is allocated, the tests write the heap buffers into memory themselves.
+struct NoAllocator;
+
+unsafe impl GlobalAlloc for NoAllocator {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+        core::ptr::null_mut()
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoAllocator = NoAllocator;
+
+pub static mut GREETING: String = String::new();
+
+pub static EMPTY: String = String::new();
+
+pub static TEXT: &str = "text";
+
+#[unsafe(no_mangle)]
+pub extern "C" fn Reset() -> ! {
+    core::hint::black_box(&raw const GREETING);
+    core::hint::black_box(&EMPTY);
+    core::hint::black_box(&TEXT);
+    loop {}
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,84 @@
+# The debug information of main.c, after the types are moved to the supplementary file `main.sup`.
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	.uleb128 1		# DW_TAG_compile_unit
+	.uleb128 0x11
+	.byte 1
+	.uleb128 0x25		# DW_AT_producer, DW_FORM_string
+	.uleb128 0x08
+	.uleb128 0x13		# DW_AT_language, DW_FORM_data1
+	.uleb128 0x0b
+	.uleb128 0x03		# DW_AT_name, DW_FORM_string
+	.uleb128 0x08
+	.uleb128 0x1b		# DW_AT_comp_dir, DW_FORM_string
+	.uleb128 0x08
+	.byte 0, 0
+	.uleb128 2		# DW_TAG_imported_unit
+	.uleb128 0x3d
+	.byte 0
+	.uleb128 0x18		# DW_AT_import, DW_FORM_ref_sup4
+	.uleb128 0x1c
+	.byte 0, 0
+	.uleb128 3		# DW_TAG_variable
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03		# DW_AT_name, DW_FORM_strp_sup
+	.uleb128 0x1d
+	.uleb128 0x49		# DW_AT_type, DW_FORM_ref_sup4
+	.uleb128 0x1c
+	.uleb128 0x3f		# DW_AT_external, DW_FORM_flag_present
+	.uleb128 0x19
+	.uleb128 0x02		# DW_AT_location, DW_FORM_exprloc
+	.uleb128 0x18
+	.byte 0, 0
+	.uleb128 4		# DW_TAG_variable
+	.uleb128 0x34
+	.byte 0
+	.uleb128 0x03		# DW_AT_name, DW_FORM_string
+	.uleb128 0x08
+	.uleb128 0x49		# DW_AT_type, DW_FORM_ref_sup4
+	.uleb128 0x1c
+	.uleb128 0x3f		# DW_AT_external, DW_FORM_flag_present
+	.uleb128 0x19
+	.uleb128 0x02		# DW_AT_location, DW_FORM_exprloc
+	.uleb128 0x18
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+	.long .Lcu_end - .Lcu_start
+.Lcu_start:
+	.short 5		# DWARF version
+	.byte 1			# DW_UT_compile
+	.byte 8			# Address size
+	.long .Labbrev
+	.uleb128 1		# DW_TAG_compile_unit
+	.asciz "hand-written"
+	.byte 0x1d		# DW_LANG_C11
+	.asciz "main.c"
+	.asciz "/tmp/supplementary"
+	.uleb128 2		# DW_TAG_imported_unit
+	.long 0xc		# The partial unit in main.sup
+	.uleb128 3		# DW_TAG_variable
+	.long 0xe		# "origin" in main.sup
+	.long 0xe		# struct point in main.sup
+	.uleb128 9
+	.byte 0x03		# DW_OP_addr
+	.quad origin
+	.uleb128 4		# DW_TAG_variable
+	.asciz "count"
+	.long 0x29		# int in main.sup
+	.uleb128 9
+	.byte 0x03		# DW_OP_addr
+	.quad count
+	.byte 0
+.Lcu_end:
+
+	.section .debug_sup,"",@progbits
+	.short 5		# Version
+	.byte 0			# is_supplementary
+	.asciz "main.sup"
+	.uleb128 0		# No checksum
+
+	.section .note.GNU-stack,"",@progbits
@@ -0,0 +1,13 @@
+struct point {
+    int x;
+    int y;
+};
+
+struct point origin = {1, 2};
+int count = 3;
+
+void _start(void) {
+    for (;;) {
+        count += origin.x;
+    }
+}
@@ -0,0 +1,88 @@
+# The supplementary file, with the types that are shared with main.elf.
+
+	.section .debug_abbrev,"",@progbits
+.Labbrev:
+	.uleb128 1		# DW_TAG_partial_unit
+	.uleb128 0x3c
+	.byte 1
+	.uleb128 0x13		# DW_AT_language, DW_FORM_data1
+	.uleb128 0x0b
+	.byte 0, 0
+	.uleb128 2		# DW_TAG_structure_type
+	.uleb128 0x13
+	.byte 1
+	.uleb128 0x03		# DW_AT_name, DW_FORM_strp
+	.uleb128 0x0e
+	.uleb128 0x0b		# DW_AT_byte_size, DW_FORM_data1
+	.uleb128 0x0b
+	.byte 0, 0
+	.uleb128 3		# DW_TAG_member
+	.uleb128 0x0d
+	.byte 0
+	.uleb128 0x03		# DW_AT_name, DW_FORM_strp
+	.uleb128 0x0e
+	.uleb128 0x49		# DW_AT_type, DW_FORM_ref4
+	.uleb128 0x13
+	.uleb128 0x38		# DW_AT_data_member_location, DW_FORM_data1
+	.uleb128 0x0b
+	.byte 0, 0
+	.uleb128 4		# DW_TAG_base_type
+	.uleb128 0x24
+	.byte 0
+	.uleb128 0x03		# DW_AT_name, DW_FORM_strp
+	.uleb128 0x0e
+	.uleb128 0x3e		# DW_AT_encoding, DW_FORM_data1
+	.uleb128 0x0b
+	.uleb128 0x0b		# DW_AT_byte_size, DW_FORM_data1
+	.uleb128 0x0b
+	.byte 0, 0
+	.byte 0
+
+	.section .debug_info,"",@progbits
+	.long .Lpu_end - .Lpu_start
+.Lpu_start:
+	.short 5		# DWARF version
+	.byte 3			# DW_UT_partial
+	.byte 8			# Address size
+	.long .Labbrev
+.Lpartial_unit:			# 0xc
+	.uleb128 1		# DW_TAG_partial_unit
+	.byte 0x1d		# DW_LANG_C11
+.Lpoint:			# 0xe
+	.uleb128 2		# DW_TAG_structure_type
+	.long .Lstr_point
+	.byte 8
+	.uleb128 3		# DW_TAG_member
+	.long .Lstr_x
+	.long .Lint - .Lpu_start + 4
+	.byte 0
+	.uleb128 3		# DW_TAG_member
+	.long .Lstr_y
+	.long .Lint - .Lpu_start + 4
+	.byte 4
+	.byte 0
+.Lint:				# 0x29
+	.uleb128 4		# DW_TAG_base_type
+	.long .Lstr_int
+	.byte 0x05		# DW_ATE_signed
+	.byte 4
+	.byte 0
+.Lpu_end:
+
+	.section .debug_str,"",@progbits
+.Lstr_int:
+	.asciz "int"
+.Lstr_point:
+	.asciz "point"
+.Lstr_x:
+	.asciz "x"
+.Lstr_y:
+	.asciz "y"
+.Lstr_origin:
+	.asciz "origin"
+
+	.section .debug_sup,"",@progbits
+	.short 5		# Version
+	.byte 1			# is_supplementary
+	.asciz ""
+	.uleb128 0		# No checksum
@@ -0,0 +1,28 @@
+/* Typedefs of integers, structs, pointers and arrays, some of them chained, see README.md. */
+
+#include <stdint.h>
+
+typedef uint32_t reg_t;
+typedef reg_t status_t;
+
+typedef struct {
+    status_t status;
+    reg_t control;
+} device_t;
+
+typedef device_t *device_ptr_t;
+
+typedef uint8_t mac_t[6];
+
+status_t STATUS = 42;
+
+device_t DEVICE = { .status = 0x80000001, .control = 7 };
+
+device_ptr_t DEVICE_PTR = &DEVICE;
+
+mac_t MAC = { 0x02, 0x00, 0x5e, 0x10, 0x00, 0x01 };
+
+int _start(void)
+{
+    return DEVICE_PTR->control + MAC[0] + STATUS;
+}
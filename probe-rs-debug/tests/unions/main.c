@@ -0,0 +1,19 @@
+/* A union whose arms overlap the same four bytes, see README.md. */
+
+#include <stdint.h>
+
+union word {
+    uint32_t word;
+    uint8_t bytes[4];
+    struct halves {
+        uint16_t low;
+        uint16_t high;
+    } halves;
+};
+
+union word WORD = { .word = 0x12345678 };
+
+int _start(void)
+{
+    return WORD.bytes[0];
+}
@@ -0,0 +1,17 @@
+volatile int sink;
+
+__attribute__((noinline)) int work(int input)
+{
+    int first = input * 3;
+    sink = first;
+    int second = first + sink;
+    sink = second;
+    return second;
+}
+
+void _start(void)
+{
+    sink = work(7);
+    for (;;) {
+    }
+}
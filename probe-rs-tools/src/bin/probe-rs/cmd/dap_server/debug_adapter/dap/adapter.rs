@@ -4,7 +4,7 @@ use super::{
     repl_commands_helpers::{build_expanded_commands, command_completions},
     request_helpers::{
         disassemble_target_memory, get_dap_source, get_svd_variable_reference,
-        get_variable_reference, set_instruction_breakpoint,
+        get_variable_reference, md5_checksum, set_instruction_breakpoint,
     },
 };
 use crate::cmd::dap_server::{
@@ -341,6 +341,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                                             frame_base: stack_frame.frame_base,
                                             canonical_frame_address: stack_frame
                                                 .canonical_frame_address,
+                                            auto_deref_depth: None,
                                         },
                                     )?;
                             }
@@ -357,6 +358,8 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         }
 
                         if let (Some(variable), Some(variable_cache)) = (variable, variable_cache) {
+                            variable_cache
+                                .set_value_format(value_format(arguments.format.as_ref()));
                             let (
                                 variables_reference,
                                 named_child_variables_cnt,
@@ -388,6 +391,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                                     registers: &registers,
                                     frame_base: top_frame.frame_base,
                                     canonical_frame_address: top_frame.canonical_frame_address,
+                                    auto_deref_depth: None,
                                 };
                                 #[allow(
                                     clippy::expect_used,
@@ -432,6 +436,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                                     registers: &registers,
                                     frame_base: top_frame.frame_base,
                                     canonical_frame_address: top_frame.canonical_frame_address,
+                                    auto_deref_depth: None,
                                 };
                                 #[allow(
                                     clippy::expect_used,
@@ -460,6 +465,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
 
                         static_variable.extract_value(&mut target_core.core, static_cache);
                         static_cache.update_variable(static_variable)?;
+                        static_cache.set_value_format(value_format(arguments.format.as_ref()));
 
                         let (
                             variables_reference,
@@ -583,6 +589,9 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         // - The `StackFrame.id` for register variables - we will warn the user that updating these are not yet supported.
         // - The `Variable.parent_key` for a local or static variable - If these are base data types, we will attempt to update their value, otherwise we will warn the user that updating complex / structure variables are not yet supported.
         let parent_key: ObjectRef = arguments.variables_reference.into();
+        let parent_key = target_core
+            .translate_variable_key(parent_key)
+            .unwrap_or(parent_key);
         let new_value = &arguments.value;
 
         // TODO: Check for, and prevent SVD Peripheral/Register/Field values from being updated, until such time as we can do it safely.
@@ -854,6 +863,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                 Ok(VerifiedBreakpoint {
                     address,
                     source_location,
+                    checksum,
                 }) => Breakpoint {
                     column: source_location.column.map(|col| match col {
                         ColumnType::LeftEdge => 0_i64,
@@ -866,7 +876,10 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                     message: Some(format!(
                         "Source breakpoint at memory address: {address:#010X}"
                     )),
-                    source: Some(args.source.clone()),
+                    source: Some(Source {
+                        checksums: checksum.map(|checksum| vec![md5_checksum(checksum)]),
+                        ..args.source.clone()
+                    }),
                     instruction_reference: Some(format!("{address:#010X}")),
                     offset: None,
                     verified: true,
@@ -1128,6 +1141,9 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         };
 
         let frame_id: ObjectRef = arguments.frame_id.into();
+        let frame_id = target_core
+            .translate_variable_key(frame_id)
+            .unwrap_or(frame_id);
 
         tracing::trace!("Getting scopes for frame {:?}", frame_id);
 
@@ -1268,6 +1284,14 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         target_core: &mut CoreHandle<'_>,
         request: &Request,
     ) -> Result<()> {
+        let arguments: VariablesArguments = get_arguments(self, request)?;
+
+        // The client may still hold the references from before the core was last halted.
+        let variable_ref: ObjectRef = arguments.variables_reference.into();
+        let variable_ref = target_core
+            .translate_variable_key(variable_ref)
+            .unwrap_or(variable_ref);
+
         let Some(ref debug_info) = target_core.core_data.debug_info else {
             return self.send_response::<()>(
                 request,
@@ -1277,10 +1301,6 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
             );
         };
 
-        let arguments: VariablesArguments = get_arguments(self, request)?;
-
-        let variable_ref: ObjectRef = arguments.variables_reference.into();
-
         // First we check the SVD VariableCache, we do this first because it is the lowest computational overhead.
         if let Some(svd_cache) = target_core
             .core_data
@@ -1352,6 +1372,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                     registers: &registers,
                     frame_base: top_level_frame.frame_base,
                     canonical_frame_address: top_level_frame.canonical_frame_address,
+                    auto_deref_depth: None,
                 });
             }
         }
@@ -1367,6 +1388,7 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         registers: &stack_frame.registers,
                         frame_base: stack_frame.frame_base,
                         canonical_frame_address: stack_frame.canonical_frame_address,
+                        auto_deref_depth: None,
                     });
                     break;
                 }
@@ -1405,15 +1427,26 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
         // During the initial stack unwind operation, if encounter [Variable]'s with [VariableNodeType::is_deferred()], they will not be auto-expanded and included in the variable cache.
         // TODO: Use the DAP "Invalidated" event to refresh the variables for this stackframe. It will allow the UI to see updated compound values for pointer variables based on the newly resolved children.
         if let Some(variable_cache) = variable_cache {
+            // The DAP spec says that all children are returned if `count` is missing or 0.
+            let start = arguments.start.unwrap_or(0).max(0) as usize;
+            let count = match arguments.count {
+                Some(count) if count > 0 => count as usize,
+                _ => usize::MAX,
+            };
+
             if let Some(parent_variable) = parent_variable.as_mut()
                 && parent_variable.variable_node_type.is_deferred()
-                && !variable_cache.has_children(parent_variable)
+                && (parent_variable.variable_node_type.has_deferred_members()
+                    || !variable_cache.has_children(parent_variable))
             {
                 if let Some(frame_info) = frame_info {
-                    debug_info.cache_deferred_variables(
+                    // Only the requested page of the members of a long array is resolved.
+                    debug_info.cache_deferred_variables_paged(
                         variable_cache,
                         &mut target_core.core,
                         parent_variable,
+                        start,
+                        count,
                         frame_info,
                     )?;
                 } else {
@@ -1424,8 +1457,11 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                 }
             }
 
+            variable_cache.set_value_format(value_format(arguments.format.as_ref()));
+
             let dap_variables: Vec<Variable> = variable_cache
-                .get_children(variable_ref)
+                // Only return the requested page of children, e.g. for large arrays.
+                .get_children_paged(variable_ref, start, count)
                 // Filter out requested children, then map them as DAP variables
                 .filter(|variable| match &arguments.filter {
                     Some(filter) => match filter.as_str() {
@@ -1455,7 +1491,13 @@ impl<P: ProtocolAdapter> DebugAdapter<P> {
                         memory_reference: Some(variable.memory_location.to_string()),
                         indexed_variables: Some(indexed_child_variables_cnt),
                         named_variables: Some(named_child_variables_cnt),
-                        presentation_hint: None,
+                        // Distinguish the statics of a function from its other local variables.
+                        presentation_hint: variable.is_static.then(|| VariablePresentationHint {
+                            attributes: Some(vec!["static".to_string()]),
+                            kind: None,
+                            lazy: None,
+                            visibility: None,
+                        }),
                         type_: Some(variable.type_name()),
                         value: variable.to_string(variable_cache),
                         variables_reference: variables_reference.into(),
@@ -1917,3 +1959,12 @@ pub fn get_arguments<T: DeserializeOwned, P: ProtocolAdapter>(
         }
     }
 }
+
+/// The format for the values of variables, as requested with the DAP `format` argument.
+fn value_format(format: Option<&ValueFormat>) -> probe_rs_debug::ValueFormat {
+    if format.and_then(|format| format.hex) == Some(true) {
+        probe_rs_debug::ValueFormat::Hex
+    } else {
+        probe_rs_debug::ValueFormat::Auto
+    }
+}
@@ -1,6 +1,6 @@
 use crate::cmd::dap_server::{
     DebuggerError,
-    debug_adapter::dap::dap_types::{DisassembledInstruction, Source},
+    debug_adapter::dap::dap_types::{Checksum, ChecksumAlgorithm, DisassembledInstruction, Source},
     peripherals::svd_cache::{SvdVariableCache, Variable},
     server::{core_data::CoreHandle, session_data::BreakpointType},
 };
@@ -430,6 +430,14 @@ fn get_capstone_le(
     Ok(cs)
 }
 
+/// Create the DAP [`Checksum`] for the MD5 checksum of a source file, as recorded in the debug information.
+pub(crate) fn md5_checksum(checksum: [u8; 16]) -> Checksum {
+    Checksum {
+        algorithm: ChecksumAlgorithm::Md5,
+        checksum: checksum.iter().map(|byte| format!("{byte:02x}")).collect(),
+    }
+}
+
 /// A helper function to create a [`Source`] struct from a [`SourceLocation`]
 pub(crate) fn get_dap_source(source_location: &SourceLocation) -> Option<Source> {
     let file_path = source_location.path.to_path();
@@ -523,11 +531,17 @@ pub(crate) fn get_variable_reference(
 
     let mut named_child_variables_cnt = 0;
     let mut indexed_child_variables_cnt = 0;
-    for child_variable in cache.get_children(parent_variable.variable_key()) {
-        if child_variable.is_indexed() {
-            indexed_child_variables_cnt += 1;
-        } else {
-            named_child_variables_cnt += 1;
+    if parent_variable.variable_node_type.has_deferred_members() {
+        // The members of long arrays and sequences are only resolved a page at a time, when the
+        // client requests them.
+        indexed_child_variables_cnt = cache.child_count(parent_variable.variable_key()) as i64;
+    } else {
+        for child_variable in cache.get_children(parent_variable.variable_key()) {
+            if child_variable.is_indexed() {
+                indexed_child_variables_cnt += 1;
+            } else {
+                named_child_variables_cnt += 1;
+            }
         }
     }
 
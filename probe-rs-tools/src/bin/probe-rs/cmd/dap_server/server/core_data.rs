@@ -30,7 +30,8 @@ use probe_rs::{Architecture, BreakpointCause, BreakpointError, Error, MemoryInte
 use probe_rs::{Core, CoreStatus, HaltReason, rtt::ScanRegion};
 use probe_rs_debug::VerifiedBreakpoint;
 use probe_rs_debug::{
-    ColumnType, ObjectRef, VariableCache, debug_info::DebugInfo, stack_frame::StackFrameInfo,
+    BreakpointOptions, ColumnType, ObjectRef, VariableCache, debug_info::DebugInfo,
+    stack_frame::StackFrameInfo,
 };
 use time::UtcOffset;
 use typed_path::TypedPath;
@@ -52,6 +53,9 @@ pub struct CoreData {
     pub static_variables: Option<VariableCache>,
     pub core_peripherals: Option<SvdCache>,
     pub stack_frames: Vec<probe_rs_debug::stack_frame::StackFrame>,
+    /// The stack frames before the core was last halted, to translate the references which the
+    /// client still holds, see [`CoreHandle::translate_variable_key`].
+    pub previous_stack_frames: Vec<probe_rs_debug::stack_frame::StackFrame>,
     pub breakpoints: Vec<session_data::ActiveBreakpoint>,
     pub rtt_scan_ranges: ScanRegion,
     pub rtt_connection: Option<debug_rtt::RttConnection>,
@@ -188,6 +192,70 @@ impl CoreHandle<'_> {
             .find(|stack_frame| stack_frame.id == id)
     }
 
+    /// Translate the key of a stack frame or a local variable from before the core was last
+    /// halted to the key of the same frame or variable in the current stack frames, see
+    /// [`VariableCache::translate_keys`].
+    ///
+    /// The frames are matched by their function and their canonical frame address, so the frames
+    /// of a recursive function are not confused with each other. Deferred variables on the path
+    /// to the variable are resolved as needed. A key which has no match in the current frames is
+    /// not translated, so it is rejected instead of resolving to an unrelated variable.
+    pub(crate) fn translate_variable_key(&mut self, stale_key: ObjectRef) -> Option<ObjectRef> {
+        let previous_frame = self.core_data.previous_stack_frames.iter().find(|frame| {
+            frame.id == stale_key
+                || frame
+                    .local_variables
+                    .as_ref()
+                    .is_some_and(|cache| cache.get_variable_ref(stale_key).is_some())
+        })?;
+        let frame = self.core_data.stack_frames.iter_mut().find(|frame| {
+            // Without a canonical frame address, the frames of a recursive function can't be told
+            // apart.
+            previous_frame.canonical_frame_address.is_some()
+                && frame.canonical_frame_address == previous_frame.canonical_frame_address
+                && frame.function_name == previous_frame.function_name
+        })?;
+        if previous_frame.id == stale_key {
+            return Some(frame.id);
+        }
+
+        let debug_info = self.core_data.debug_info.as_ref()?;
+        let previous_cache = previous_frame.local_variables.as_ref()?;
+        let frame_info = StackFrameInfo {
+            registers: &frame.registers,
+            frame_base: frame.frame_base,
+            canonical_frame_address: frame.canonical_frame_address,
+            auto_deref_depth: None,
+        };
+        let cache = frame.local_variables.as_mut()?;
+        loop {
+            let keys = previous_cache.translate_keys(cache);
+            if let Some(key) = keys.get(&stale_key) {
+                return Some(*key);
+            }
+
+            // The children of a deferred variable are only matched once they are resolved, like
+            // they were in the previous frame.
+            let mut ancestor_key = previous_cache.get_variable_ref(stale_key)?.parent_key;
+            let ancestor_key = loop {
+                if let Some(key) = keys.get(&ancestor_key) {
+                    break *key;
+                }
+                ancestor_key = previous_cache.get_variable_ref(ancestor_key)?.parent_key;
+            };
+            let mut ancestor = cache.get_variable_by_key(ancestor_key)?;
+            if !ancestor.variable_node_type.is_deferred() || cache.has_children(&ancestor) {
+                return None;
+            }
+            debug_info
+                .cache_deferred_variables(cache, &mut self.core, &mut ancestor, frame_info)
+                .ok()?;
+            if !cache.has_children(&ancestor) {
+                return None;
+            }
+        }
+    }
+
     /// Confirm RTT initialization on the target, and use the RTT channel configurations to initialize the output windows on the DAP Client.
     pub fn attach_to_rtt<P: ProtocolAdapter>(
         &mut self,
@@ -420,11 +488,14 @@ impl CoreHandle<'_> {
         let VerifiedBreakpoint {
                  address,
                  source_location,
+                 checksum,
              } = debug_info
-            .get_breakpoint_location(
+            .get_breakpoint_location_with_options(
                 source_path,
                 requested_breakpoint_line,
                 requested_breakpoint_column,
+                // Breakpoints on lines without code move to the next line with code, like in other debuggers.
+                BreakpointOptions::default().with_next_line_window(20),
             )
             .map_err(|debug_error|
                 DebuggerError::Other(anyhow!("Cannot set breakpoint here. Try reducing compile time-, and link time-, optimization in your build configuration, or choose a different source location: {debug_error}")))?;
@@ -438,6 +509,7 @@ impl CoreHandle<'_> {
         Ok(VerifiedBreakpoint {
             address,
             source_location,
+            checksum,
         })
     }
 
@@ -504,6 +576,7 @@ impl CoreHandle<'_> {
                     registers: &self.core_data.stack_frames[0].registers,
                     frame_base: self.core_data.stack_frames[0].frame_base,
                     canonical_frame_address: self.core_data.stack_frames[0].canonical_frame_address,
+                    auto_deref_depth: None,
                 },
             );
             all_discrete_memory_ranges.append(&mut static_variables.get_discrete_memory_ranges());
@@ -526,6 +599,7 @@ impl CoreHandle<'_> {
                             registers: &frame.registers,
                             frame_base: frame.frame_base,
                             canonical_frame_address: frame.canonical_frame_address,
+                            auto_deref_depth: None,
                         },
                     );
                     all_discrete_memory_ranges
@@ -220,6 +220,7 @@ impl SessionData {
                 static_variables: None,
                 core_peripherals: None,
                 stack_frames: vec![],
+                previous_stack_frames: vec![],
                 breakpoints: vec![],
                 rtt_scan_ranges: ScanRegion::Ranges(vec![]),
                 rtt_connection: None,
@@ -469,13 +470,16 @@ impl SessionData {
                         Some(debug_info.create_static_scope_cache());
                 }
 
-                target_core.core_data.stack_frames = debug_info.unwind(
+                let stack_frames = debug_info.unwind(
                     &mut target_core.core,
                     initial_registers,
                     exception_interface.as_ref(),
                     instruction_set,
                     500, // TODO: we should be able to unwind incrementally as the user requests more frames on the UI
                 )?;
+                // The client may still refer to the variables of the previous frames.
+                target_core.core_data.previous_stack_frames =
+                    std::mem::replace(&mut target_core.core_data.stack_frames, stack_frames);
             }
         }
         Ok(suggest_delay_required)
@@ -3,7 +3,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use probe_rs::Session;
-use probe_rs_debug::DebugInfo;
+use probe_rs_debug::{DebugInfo, DebugInfoOptions};
 
 use anyhow::Context;
 use object::{Object, ObjectSymbol};
@@ -132,7 +132,14 @@ pub(super) fn callstack_profile(
     let sampling_interval = Duration::from_nanos((1e9 / callstack_profile_args.rate) as u64);
 
     let object_bytes = std::fs::read(executable_location)?;
-    let debug_info = DebugInfo::from_raw(&object_bytes)?;
+    // Frame pointer based unwinding does not need debug information.
+    let debug_info = DebugInfo::from_raw_with_options(
+        &object_bytes,
+        &DebugInfoOptions {
+            allow_missing_debug_info: true,
+            ..Default::default()
+        },
+    )?;
     let object = object::File::parse(object_bytes.as_slice())?;
     let entry_address_range = get_entry_point_address_range(&object)?;
 
@@ -80,8 +80,10 @@ impl MockMemory {
         self.add_range(address, bytes);
     }
 
-    fn missing_range(&self, start: u64, end: u64) -> ! {
-        panic!("No entry for range {start:#010x} - {end:#010x}");
+    /// The error of an access to memory which was not added, like the error of a read from memory
+    /// which does not exist on the target.
+    fn missing_range(&self, start: u64, end: u64) -> crate::Error {
+        crate::Error::Other(format!("No entry for range {start:#010x} - {end:#010x}"))
     }
 }
 
@@ -90,8 +92,17 @@ impl MemoryInterface for MockMemory {
         false
     }
 
-    fn read_word_64(&mut self, _address: u64) -> Result<u64, crate::Error> {
-        todo!()
+    // The mock memory can be read byte by byte, reads are not widened to whole words, which would
+    // run past the end of the ranges which were added.
+    fn read(&mut self, address: u64, data: &mut [u8]) -> Result<(), crate::Error> {
+        self.read_8(address, data)
+    }
+
+    fn read_word_64(&mut self, address: u64) -> Result<u64, crate::Error> {
+        let mut bytes = [0u8; 8];
+        self.read_8(address, &mut bytes)?;
+
+        Ok(u64::from_le_bytes(bytes))
     }
 
     fn read_word_32(&mut self, address: u64) -> Result<u32, crate::Error> {
@@ -101,16 +112,30 @@ impl MemoryInterface for MockMemory {
         Ok(u32::from_le_bytes(bytes))
     }
 
-    fn read_word_8(&mut self, _address: u64) -> Result<u8, crate::Error> {
-        todo!()
+    fn read_word_8(&mut self, address: u64) -> Result<u8, crate::Error> {
+        let mut bytes = [0u8; 1];
+        self.read_8(address, &mut bytes)?;
+
+        Ok(bytes[0])
     }
 
-    fn read_word_16(&mut self, _address: u64) -> Result<u16, crate::Error> {
-        todo!()
+    fn read_word_16(&mut self, address: u64) -> Result<u16, crate::Error> {
+        let mut bytes = [0u8; 2];
+        self.read_8(address, &mut bytes)?;
+
+        Ok(u16::from_le_bytes(bytes))
     }
 
-    fn read_64(&mut self, _address: u64, _data: &mut [u64]) -> Result<(), crate::Error> {
-        todo!()
+    fn read_64(&mut self, address: u64, data: &mut [u64]) -> Result<(), crate::Error> {
+        let mut buff = vec![0u8; data.len() * 8];
+
+        self.read_8(address, &mut buff)?;
+
+        for (i, chunk) in buff.chunks_exact(8).enumerate() {
+            data[i] = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Ok(())
     }
 
     fn read_32(&mut self, address: u64, data: &mut [u32]) -> Result<(), crate::Error> {
@@ -125,8 +150,16 @@ impl MemoryInterface for MockMemory {
         Ok(())
     }
 
-    fn read_16(&mut self, _address: u64, _data: &mut [u16]) -> Result<(), crate::Error> {
-        todo!()
+    fn read_16(&mut self, address: u64, data: &mut [u16]) -> Result<(), crate::Error> {
+        let mut buff = vec![0u8; data.len() * 2];
+
+        self.read_8(address, &mut buff)?;
+
+        for (i, chunk) in buff.chunks_exact(2).enumerate() {
+            data[i] = u16::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Ok(())
     }
 
     fn read_8(&mut self, address: u64, data: &mut [u8]) -> Result<(), crate::Error> {
@@ -139,7 +172,7 @@ impl MemoryInterface for MockMemory {
 
                 &self.values[index].1
             }
-            Err(0) => self.missing_range(address, address + data.len() as u64),
+            Err(0) => return Err(self.missing_range(address, address + data.len() as u64)),
             Err(index) => {
                 let previous_entry = &self.values[index - 1];
 
@@ -152,7 +185,7 @@ impl MemoryInterface for MockMemory {
 
                 if offset >= previous_entry.1.len() as u64 {
                     // The requested range is not covered by the previous entry
-                    self.missing_range(address, address + data.len() as u64)
+                    return Err(self.missing_range(address, address + data.len() as u64));
                 }
 
                 &previous_entry.1[offset as usize..]
@@ -204,8 +237,31 @@ impl MemoryInterface for MockMemory {
         todo!()
     }
 
-    fn write_8(&mut self, _address: u64, _data: &[u8]) -> Result<(), crate::Error> {
-        todo!()
+    fn write_8(&mut self, address: u64, data: &[u8]) -> Result<(), crate::Error> {
+        let index = match self
+            .values
+            .binary_search_by_key(&address, |(addr, _data)| *addr)
+        {
+            Ok(index) => index,
+            Err(0) => return Err(self.missing_range(address, address + data.len() as u64)),
+            Err(index) => index - 1,
+        };
+
+        let offset = address - self.values[index].0;
+        if offset >= self.values[index].1.len() as u64 {
+            // The requested range is not covered by the previous entry
+            return Err(self.missing_range(address, address + data.len() as u64));
+        }
+
+        let stored_data = &mut self.values[index].1[offset as usize..];
+        let length = stored_data.len().min(data.len());
+        stored_data[..length].copy_from_slice(&data[..length]);
+
+        if length < data.len() {
+            self.write_8(address + length as u64, &data[length..])
+        } else {
+            Ok(())
+        }
     }
 
     fn flush(&mut self) -> Result<(), crate::Error> {
@@ -232,3 +288,18 @@ fn mock_memory_read() {
         assert_eq!(actual, *expected);
     }
 }
+
+#[test]
+fn mock_memory_write() {
+    let mut mock_memory = MockMemory::new();
+
+    mock_memory.add_range(0x1000, vec![0; 4]);
+    mock_memory.add_range(0x1004, vec![0; 4]);
+
+    // The write spans both ranges.
+    mock_memory.write_8(0x1002, &[1, 2, 3, 4]).unwrap();
+
+    let mut data = [0u8; 8];
+    mock_memory.read_8(0x1000, &mut data).unwrap();
+    assert_eq!(data, [0, 0, 1, 2, 3, 4, 0, 0]);
+}